@@ -0,0 +1,63 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "ArbitraryInPlace")?;
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__arbitrary));
+    let where_clause = generics.make_where_clause();
+    for f in &fields {
+        let ty = &f.field.ty;
+        where_clause.predicates.push(if f.attrs.in_place {
+            parse_quote!(#ty: #krate::ArbitraryInPlace<'__arbitrary>)
+        } else {
+            parse_quote!(#ty: #krate::arbitrary::Arbitrary<'__arbitrary>)
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let inits = fields.iter().map(|f| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        if f.attrs.in_place {
+            quote! {
+                <#ty as #krate::ArbitraryInPlace<'__arbitrary>>::arbitrary_in_place(
+                    // only the next field is initialized through the slot
+                    #krate::project_uninit_mut!(unsafe { guard.slot() } => #member),
+                    u,
+                )?;
+            }
+        } else {
+            quote! {
+                let value = <#ty as #krate::arbitrary::Arbitrary<'__arbitrary>>::arbitrary(u)?;
+                // only the next field is initialized through the slot
+                #krate::partial_init!(unsafe { guard.slot() } => #member = value);
+            }
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::ArbitraryInPlace<'__arbitrary> for #name #ty_generics
+        #where_clause
+        {
+            fn arbitrary_in_place<'__slot>(
+                slot: &'__slot mut ::core::mem::MaybeUninit<Self>,
+                u: &mut #krate::arbitrary::Unstructured<'__arbitrary>,
+            ) -> #krate::arbitrary::Result<&'__slot mut Self> {
+                let mut guard = #krate::utils::PrefixGuard::new(slot);
+                #(
+                    #inits
+                    unsafe { guard.push() };
+                )*
+                ::core::result::Result::Ok(unsafe { guard.finish() })
+            }
+        }
+    })
+}
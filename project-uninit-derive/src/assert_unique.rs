@@ -0,0 +1,99 @@
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+
+/// A field path passed to `__assert_unique!`, split into its `.`-separated segments.
+struct FieldPath {
+    segments: Vec<TokenStream>,
+    keys: Vec<String>,
+    // the spans of the first and last tokens, so errors cover the whole path
+    first: Span,
+    last: Span,
+}
+
+impl FieldPath {
+    fn new(tokens: TokenStream) -> Self {
+        let mut segments = vec![TokenStream::new()];
+        let mut spans = None;
+        for tt in tokens {
+            let span = tt.span();
+            spans.get_or_insert((span, span)).1 = span;
+            match tt {
+                TokenTree::Punct(p) if p.as_char() == '.' => segments.push(TokenStream::new()),
+                tt => segments.last_mut().unwrap().extend(Some(tt)),
+            }
+        }
+        let keys = segments.iter().map(ToString::to_string).collect();
+        let (first, last) = spans.unwrap_or_else(|| (Span::call_site(), Span::call_site()));
+        Self {
+            segments,
+            keys,
+            first,
+            last,
+        }
+    }
+
+    // a `compile_error!` spanning the path, so the error points at it and not at the whole
+    // macro invocation
+    fn error(&self, message: TokenStream) -> TokenStream {
+        let start = quote_spanned!(self.first=> ::core::compile_error);
+        let end = quote_spanned!(self.last=> !{ #message });
+        quote!(#start #end;)
+    }
+
+    // the path as a string literal, with the same formatting as `stringify!`
+    fn display(&self, head: &TokenStream) -> TokenStream {
+        let segments = &self.segments;
+        quote!(::core::concat!(::core::stringify!(#head), #(".", ::core::stringify!(#segments)),*))
+    }
+}
+
+/// `__assert_unique!($head, [ [$path...] ... ])`: fail if a field path is used more than once,
+/// or along with one of its parents.
+///
+/// The paths are sorted so each only needs to be compared with its neighbour: a path sorts
+/// directly before its duplicates, and before its descendants.
+pub fn expand(input: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let paths = match tokens.pop() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => g.stream(),
+        _ => return quote!(::core::compile_error!("expected a list of field paths")),
+    };
+    // remove the comma after the head expression
+    tokens.pop();
+    let head: TokenStream = tokens.into_iter().collect();
+
+    let paths: Vec<FieldPath> = paths
+        .into_iter()
+        .filter_map(|tt| match tt {
+            TokenTree::Group(g) => Some(FieldPath::new(g.stream())),
+            _ => None,
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..paths.len()).collect();
+    order.sort_by(|&a, &b| paths[a].keys.cmp(&paths[b].keys).then(a.cmp(&b)));
+
+    let errors = order.windows(2).filter_map(|pair| {
+        let (parent, child) = (&paths[pair[0]], &paths[pair[1]]);
+        if !child.keys.starts_with(&parent.keys) {
+            return None;
+        }
+        // point at whichever of the two paths comes later in the invocation
+        let at = &paths[pair[0].max(pair[1])];
+        let child_str = child.display(&head);
+        Some(if child.keys.len() == parent.keys.len() {
+            at.error(quote! {
+                ::core::concat!("Cannot mutably borrow '", #child_str, "' more than once at a time")
+            })
+        } else {
+            let parent_str = parent.display(&head);
+            at.error(quote! {
+                ::core::concat!(
+                    "Cannot mutably borrow '", #child_str, "' and its parent '", #parent_str,
+                    "' at the same time.",
+                )
+            })
+        })
+    });
+
+    quote!({ #(#errors)* })
+}
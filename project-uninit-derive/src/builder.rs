@@ -0,0 +1,151 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, DeriveInput, GenericParam, Generics, Ident, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "InitBuilder")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("{}Init", name);
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    // the setters of tuple struct fields are named by index, like `set_0`
+    let bases: Vec<_> = fields
+        .iter()
+        .map(|f| match &f.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        })
+        .collect();
+
+    let states: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__S{}", i))
+        .collect();
+    let args: Vec<_> = input
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+        })
+        .collect();
+
+    // the generics of the builder with the state of each field for which `free` returns true
+    // left as a parameter
+    let generics = |free: &dyn Fn(usize) -> bool, defaults: bool| {
+        let mut generics: Generics = input.generics.clone();
+        for (i, state) in states.iter().enumerate() {
+            if !free(i) {
+                continue;
+            }
+            generics.params.push(if defaults {
+                syn::parse_quote!(#state: #krate::FieldState = #krate::Unset)
+            } else {
+                syn::parse_quote!(#state: #krate::FieldState)
+            });
+        }
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#name #ty_generics: #krate::UninitFields));
+        generics
+    };
+    let builder_ty = |state: &dyn Fn(usize, &Ident) -> TokenStream| {
+        let states = states.iter().enumerate().map(|(i, s)| state(i, s));
+        quote!(#builder<#(#args,)* #(#states),*>)
+    };
+
+    let def_generics = generics(&|_| true, true);
+    let def_where = &def_generics.where_clause;
+    let all_generics = generics(&|_| true, false);
+    let (all_impl, _, all_where) = all_generics.split_for_impl();
+    let fixed_generics = generics(&|_| false, false);
+    let (fixed_impl, _, fixed_where) = fixed_generics.split_for_impl();
+    let all_free = builder_ty(&|_, s| quote!(#s));
+    let all_unset = builder_ty(&|_, _| quote!(#krate::Unset));
+    let all_set = builder_ty(&|_, _| quote!(#krate::Set));
+
+    let setters = fields.iter().zip(&bases).enumerate().map(|(i, (f, base))| {
+        let member = &f.member;
+        let field_vis = &f.field.vis;
+        let ty = &f.field.ty;
+        let set = format_ident!("set_{}", base);
+        let doc = format!("Initialize `{}`.", base);
+        let generics = generics(&|j| j != i, false);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let before = builder_ty(&|j, s| if j == i { quote!(#krate::Unset) } else { quote!(#s) });
+        let after = builder_ty(&|j, s| if j == i { quote!(#krate::Set) } else { quote!(#s) });
+        quote! {
+            impl #impl_generics #before #where_clause {
+                #[doc = #doc]
+                #field_vis fn #set(self, value: #ty) -> #after {
+                    let mut slot = unsafe { ::core::ptr::read(&::core::mem::ManuallyDrop::new(self).__slot) };
+                    let target = unsafe { slot.slot() };
+                    #krate::partial_init!(target => #member = value);
+                    #builder { __slot: unsafe { slot.into_state() } }
+                }
+            }
+        }
+    });
+
+    let doc = format!(
+        "A builder for [`{}`] that tracks which fields are initialized in its type parameters, \
+         generated by `#[derive(InitBuilder)]`.",
+        name
+    );
+    let finish_doc = format!("Get the [`{}`] once every field is initialized.", name);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #builder #def_generics #def_where {
+            __slot: #krate::utils::BuilderSlot<#name #ty_generics, (#(#states,)*)>,
+        }
+
+        impl #fixed_impl #all_unset #fixed_where {
+            /// Start with every field uninitialized.
+            #vis fn new() -> Self {
+                Self {
+                    __slot: unsafe { #krate::utils::BuilderSlot::new() },
+                }
+            }
+        }
+
+        impl #fixed_impl ::core::default::Default for #all_unset #fixed_where {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl #fixed_impl #all_set #fixed_where {
+            #[doc = #finish_doc]
+            #vis fn finish(self) -> #name #ty_generics {
+                unsafe { ::core::ptr::read(&::core::mem::ManuallyDrop::new(self).__slot).assume_init() }
+            }
+        }
+
+        #(#setters)*
+
+        impl #all_impl ::core::ops::Drop for #all_free #all_where {
+            fn drop(&mut self) {
+                unsafe {
+                    self.__slot
+                        .drop_fields(&[#(<#states as #krate::FieldState>::SET),*])
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "DeserializeUninit")?;
+    let name = &input.ident;
+    // the fields are tracked with an `InitTracker`
+    if fields.len() > 64 {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(DeserializeUninit)] supports structs with at most 64 fields",
+        ));
+    }
+    let name_str = name.to_string();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__de));
+    let where_clause = generics.make_where_clause();
+    for f in &fields {
+        let ty = &f.field.ty;
+        where_clause.predicates.push(if f.attrs.in_place {
+            parse_quote!(#ty: #krate::DeserializeUninit<'__de>)
+        } else {
+            parse_quote!(#ty: #krate::serde::Deserialize<'__de>)
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    // tuple structs have no field names and are deserialized from sequences
+    let field_names = fields.iter().filter_map(|f| match &f.member {
+        Member::Named(ident) => Some(ident.to_string().trim_start_matches("r#").to_owned()),
+        Member::Unnamed(_) => None,
+    });
+
+    let arms = fields.iter().enumerate().map(|(i, f)| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        if f.attrs.in_place {
+            quote! {
+                #i => {
+                    <#ty as #krate::DeserializeUninit<'__de>>::deserialize_uninit(
+                        #krate::project_uninit_mut!(slot => #member),
+                        deserializer,
+                    )?;
+                }
+            }
+        } else {
+            quote! {
+                #i => {
+                    let value = <#ty as #krate::serde::Deserialize<'__de>>::deserialize(deserializer)?;
+                    #krate::partial_init!(slot => #member = value);
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::utils::DeserializeFields<'__de> for #name #ty_generics
+        #where_clause
+        {
+            const NAME: &'static str = #name_str;
+            const FIELD_NAMES: &'static [&'static str] = &[#(#field_names),*];
+
+            #[allow(unused_variables, unreachable_code)]
+            fn deserialize_field<__D: #krate::serde::Deserializer<'__de>>(
+                tracker: &mut #krate::InitTracker<'_, Self>,
+                index: usize,
+                deserializer: __D,
+            ) -> ::core::result::Result<(), __D::Error> {
+                let slot = unsafe { tracker.slot_mut() };
+                match index {
+                    #(#arms)*
+                    _ => ::core::unreachable!(),
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        unsafe impl #impl_generics #krate::DeserializeUninit<'__de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize_uninit<'__slot, __D: #krate::serde::Deserializer<'__de>>(
+                slot: &'__slot mut ::core::mem::MaybeUninit<Self>,
+                deserializer: __D,
+            ) -> ::core::result::Result<&'__slot mut Self, __D::Error> {
+                #krate::utils::deserialize_struct(slot, deserializer)
+            }
+        }
+    })
+}
@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "ExportFields")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let ptrs = format_ident!("{}FieldPtrs", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics.params;
+    let doc = format!(
+        "Pointers to the fields of [`{}`], returned by `ExportFields::field_ptrs`.",
+        name
+    );
+
+    // exported fields are public, and the rest keep their own visibility
+    let ptr_fields = fields.iter().map(|f| {
+        let ty = &f.field.ty;
+        let field_vis = &f.field.vis;
+        let field_vis = if f.attrs.export {
+            quote!(pub)
+        } else {
+            quote!(#field_vis)
+        };
+        match &f.member {
+            Member::Named(ident) => quote!(#field_vis #ident: *mut #ty),
+            Member::Unnamed(_) => quote!(#field_vis *mut #ty),
+        }
+    });
+    let ptr_values = fields.iter().map(|f| {
+        let member = &f.member;
+        let value = quote!(::core::ptr::addr_of_mut!((*this).#member));
+        match member {
+            Member::Named(ident) => quote!(#ident: #value),
+            Member::Unnamed(_) => value,
+        }
+    });
+    let marker = quote!(::core::marker::PhantomData<*mut #name #ty_generics>);
+
+    let (definition, construct) = match fields.first().map(|f| &f.member) {
+        Some(Member::Unnamed(_)) => (
+            quote! {
+                #vis struct #ptrs<#generics>(#(#ptr_fields,)* #marker) #where_clause;
+            },
+            quote!(#ptrs(#(#ptr_values,)* ::core::marker::PhantomData)),
+        ),
+        _ => (
+            quote! {
+                #vis struct #ptrs<#generics> #where_clause {
+                    #(#ptr_fields,)*
+                    __marker: #marker,
+                }
+            },
+            quote!(#ptrs { #(#ptr_values,)* __marker: ::core::marker::PhantomData }),
+        ),
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[allow(dead_code)]
+        #definition
+
+        unsafe impl #impl_generics #krate::ExportFields for #name #ty_generics #where_clause {
+            type Ptrs = #ptrs #ty_generics;
+
+            unsafe fn field_ptrs(this: *mut Self) -> Self::Ptrs {
+                #construct
+            }
+        }
+    })
+}
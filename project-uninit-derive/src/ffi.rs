@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "FfiFields")?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            repr_c |= meta.path.is_ident("C");
+            // skip the arguments of `align(N)` and `packed(N)`
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<syn::LitInt>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if !repr_c {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(FfiFields)] requires #[repr(C)]",
+        ));
+    }
+    if fields.len() > 64 {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(FfiFields)] supports structs with at most 64 fields",
+        ));
+    }
+
+    let version = fields.iter().map(|f| f.attrs.since).max().unwrap_or(0);
+    let entries = fields.iter().map(|f| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        let field_name = f.name();
+        let since = f.attrs.since;
+        quote! {
+            #krate::FfiField::new(
+                #field_name,
+                ::core::mem::offset_of!(Self, #member),
+                ::core::mem::size_of::<#ty>(),
+                #since,
+            )
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::FfiFields for #name #ty_generics #where_clause {
+            const LAYOUT: #krate::FfiLayout = #krate::FfiLayout::new(
+                #version,
+                ::core::mem::size_of::<Self>(),
+                ::core::mem::align_of::<Self>(),
+                &[#(#entries),*],
+            );
+        }
+    })
+}
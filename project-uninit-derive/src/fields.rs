@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Field, LitInt, Member, Result};
+
+/// A field of the struct a derive macro is applied to.
+pub struct StructField<'a> {
+    pub member: Member,
+    pub field: &'a Field,
+    pub attrs: FieldAttrs,
+}
+
+/// Options set on a field with `#[uninit(...)]`.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// `#[uninit(in_place)]`: initialize this field in place rather than by value.
+    pub in_place: bool,
+    /// `#[uninit(since = N)]`: the layout version in which this field was added.
+    pub since: u32,
+    /// `#[uninit(export)]`: allow this field to be projected wherever the struct is visible.
+    pub export: bool,
+    /// `#[uninit(pin)]`: this field is structurally pinned.
+    pub pin: bool,
+}
+
+impl FieldAttrs {
+    fn parse(field: &Field) -> Result<Self> {
+        let mut attrs = Self::default();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("uninit")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("in_place") {
+                    attrs.in_place = true;
+                    Ok(())
+                } else if meta.path.is_ident("export") {
+                    attrs.export = true;
+                    Ok(())
+                } else if meta.path.is_ident("pin") {
+                    attrs.pin = true;
+                    Ok(())
+                } else if meta.path.is_ident("since") {
+                    attrs.since = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown uninit attribute"))
+                }
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+impl StructField<'_> {
+    /// The name of the field as a string, or its index for tuple structs.
+    pub fn name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+}
+
+/// Get the fields of the struct, or fail if the input is not a struct.
+pub fn struct_fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<StructField<'a>>> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("#[derive({})] can only be used on structs", derive),
+            ))
+        }
+    };
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            Ok(StructField {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                },
+                field,
+                attrs: FieldAttrs::parse(field)?,
+            })
+        })
+        .collect()
+}
+
+/// The path to the `project_uninit` crate in generated code.
+pub fn crate_path() -> TokenStream {
+    quote!(::project_uninit)
+}
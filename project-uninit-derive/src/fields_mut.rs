@@ -0,0 +1,64 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::quote;
+
+/// Split a field path into its `=>`-separated segments.
+fn segments(path: TokenStream) -> Vec<TokenTree> {
+    path.into_iter()
+        .filter(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == '=' || p.as_char() == '>'))
+        .collect()
+}
+
+/// Whether a segment is an array index or a `*`, which `__field_mut!` only gets a pointer through.
+fn is_unborrowed(segment: &TokenTree) -> bool {
+    match segment {
+        TokenTree::Group(g) => g.delimiter() == Delimiter::Bracket,
+        TokenTree::Punct(p) => p.as_char() == '*',
+        _ => false,
+    }
+}
+
+/// `__fields_mut!($crate, ($base) [$path...] ...)`: borrow the place for every field path
+/// mutably at once, to check in an `if false` block that they can be accessed together.
+///
+/// Paths with array indices or `*` segments only get a pointer from `__field_mut!`, since the
+/// borrow checker can't tell elements or fields of the same wrapper apart, so the part of each
+/// of those paths before its first index or `*` is borrowed as well, once for every distinct
+/// prefix. Elements of the same array share a prefix, but fields of the same union don't, so
+/// projecting more than one field of a union is rejected by the borrow checker even when the
+/// paths go on to index or continue into them.
+pub fn expand(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let krate: TokenStream = tokens
+        .by_ref()
+        .take_while(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+        .collect();
+    let base = match tokens.next() {
+        Some(base) => base,
+        None => return quote!(::core::compile_error!("expected a base place")),
+    };
+
+    let paths: Vec<Vec<TokenTree>> = tokens
+        .filter_map(|tt| match tt {
+            TokenTree::Group(g) => Some(segments(g.stream())),
+            _ => None,
+        })
+        .collect();
+
+    let mut keys = Vec::new();
+    let prefixes = paths.iter().filter_map(|path| {
+        let index = path.iter().position(is_unborrowed)?;
+        let prefix = &path[..index];
+        let key = prefix.iter().map(ToString::to_string).collect::<Vec<_>>();
+        if prefix.is_empty() || keys.contains(&key) {
+            return None;
+        }
+        keys.push(key);
+        Some(quote!(&mut #krate::__field_place!(mut #base #(#prefix)=>*)))
+    });
+    let prefixes: Vec<TokenStream> = prefixes.collect();
+    let fields = paths
+        .iter()
+        .map(|path| quote!(#krate::__field_mut!(#base #(#path)=>*)));
+
+    quote!((#(#fields,)* #(#prefixes,)*))
+}
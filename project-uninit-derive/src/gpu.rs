@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, DeriveInput, LitInt, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+#[derive(Default)]
+struct GpuAttrs {
+    offset: Option<usize>,
+    align: Option<usize>,
+}
+
+impl GpuAttrs {
+    fn parse(attrs: &[Attribute], allow_offset: bool) -> Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("gpu")) {
+            attr.parse_nested_meta(|meta| {
+                if allow_offset && meta.path.is_ident("offset") {
+                    out.offset = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("align") {
+                    let lit = meta.value()?.parse::<LitInt>()?;
+                    let align: usize = lit.base10_parse()?;
+                    if !align.is_power_of_two() {
+                        return Err(syn::Error::new_spanned(lit, "alignment must be a power of two"));
+                    }
+                    out.align = Some(align);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown gpu attribute"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "GpuLayout")?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let struct_align = match GpuAttrs::parse(&input.attrs, false)?.align {
+        Some(align) => quote!(::core::option::Option::Some(#align)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    let mut specs = Vec::with_capacity(fields.len());
+    for f in &fields {
+        let attrs = GpuAttrs::parse(&f.field.attrs, true)?;
+        let ty = &f.field.ty;
+        let field_name = f.name();
+        let offset = match attrs.offset {
+            Some(offset) => quote!(::core::option::Option::Some(#offset)),
+            None => quote!(::core::option::Option::None),
+        };
+        let align = match attrs.align {
+            Some(align) => quote!(#align),
+            None => quote!(::core::mem::align_of::<#ty>()),
+        };
+        specs.push(quote! {
+            (#field_name, #offset, #align, ::core::mem::size_of::<#ty>())
+        });
+    }
+    let layout = quote!(#krate::utils::gpu_layout([#(#specs),*], #struct_align));
+
+    let writes = fields.iter().enumerate().map(|(index, f)| {
+        let member = &f.member;
+        quote! {
+            #krate::utils::write_unaligned(dst.add(Self::FIELDS[#index].offset()), &self.#member);
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::GpuLayout for #name #ty_generics #where_clause {
+            const SIZE: usize = #layout.1;
+            const FIELDS: &'static [#krate::GpuField] = &#layout.0;
+
+            unsafe fn write_to(&self, dst: *mut u8) {
+                #(#writes)*
+            }
+        }
+    })
+}
@@ -0,0 +1,199 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_quote, DeriveInput, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+use crate::project_uninit::is_packed;
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "PartialGuard")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let guard = format_ident!("{}PartialGuard", name);
+    if is_packed(input)? {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(PartialGuard)] can't be used on #[repr(packed)] structs",
+        ));
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__guard));
+    let (impl_generics, guard_ty_generics, where_clause) = generics.split_for_impl();
+    let count = fields.len();
+
+    let accessors = fields.iter().enumerate().map(|(index, f)| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        let field_vis = &f.field.vis;
+        let base = match member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(i) => i.index.to_string(),
+        };
+        let set = format_ident!("set_{}", base);
+        let get = format_ident!("get_{}", base);
+        let get_mut = format_ident!("get_{}_mut", base);
+        let take = format_ident!("take_{}", base);
+        let uninit = format_ident!("uninit_{}", base);
+        let mark = format_ident!("mark_{}_init", base);
+        let doc_set = format!(
+            "Initialize `{}`, dropping the previous value if there was one.",
+            base
+        );
+        let doc_get = format!("A reference to `{}`, if it is initialized.", base);
+        let doc_take = format!(
+            "Move `{}` out if it is initialized, leaving it uninitialized.",
+            base
+        );
+        let doc_uninit = format!(
+            "Drop `{}` if it is initialized, and get a reference to the uninitialized field, to \
+             initialize it in place before calling [`{}`](Self::{}).",
+            base, mark, mark
+        );
+        let doc_mark = format!(
+            "**Unsafe:** Mark `{}` as initialized, so it is dropped with the guard.\n\n\
+             ## Safety\n\
+             The field must be initialized, e.g. through [`{}`](Self::{}).",
+            base, uninit, uninit
+        );
+        quote! {
+            #[doc = #doc_set]
+            #field_vis fn #set(&mut self, value: #ty) -> &mut #ty {
+                self.#uninit();
+                unsafe {
+                    self.__slot.init_field(#index, |slot| {
+                        #krate::partial_init!(slot => #member = value)
+                    })
+                }
+            }
+
+            #[doc = #doc_get]
+            #field_vis fn #get(&self) -> ::core::option::Option<&#ty> {
+                if !self.__slot.is_init(#index) {
+                    return ::core::option::Option::None;
+                }
+                let field = #krate::project_uninit!(self.__slot.slot() => #member);
+                ::core::option::Option::Some(unsafe { field.assume_init_ref() })
+            }
+
+            #[doc = #doc_get]
+            #field_vis fn #get_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                if !self.__slot.is_init(#index) {
+                    return ::core::option::Option::None;
+                }
+                let slot = unsafe { self.__slot.slot_mut() };
+                let field = #krate::project_uninit_mut!(slot => #member);
+                ::core::option::Option::Some(unsafe { field.assume_init_mut() })
+            }
+
+            #[doc = #doc_take]
+            #field_vis fn #take(&mut self) -> ::core::option::Option<#ty> {
+                if !self.__slot.clear(#index) {
+                    return ::core::option::Option::None;
+                }
+                let ptr = unsafe { self.__slot.slot_mut() }.as_mut_ptr();
+                ::core::option::Option::Some(unsafe {
+                    #krate::utils::take_field(::core::ptr::addr_of_mut!((*ptr).#member))
+                })
+            }
+
+            #[doc = #doc_uninit]
+            #field_vis fn #uninit(&mut self) -> &mut ::core::mem::MaybeUninit<#ty> {
+                // cleared first, so a panicking `drop` can't drop the field twice
+                if self.__slot.clear(#index) {
+                    let ptr = unsafe { self.__slot.slot_mut() }.as_mut_ptr();
+                    unsafe { #krate::utils::drop_field(::core::ptr::addr_of_mut!((*ptr).#member)) };
+                }
+                let slot = unsafe { self.__slot.slot_mut() };
+                #krate::project_uninit_mut!(slot => #member)
+            }
+
+            #[doc = #doc_mark]
+            #field_vis unsafe fn #mark(&mut self) {
+                #[allow(unused_unsafe)]
+                unsafe {
+                    self.__slot.mark_init(#index)
+                }
+            }
+        }
+    });
+    let drops = fields.iter().enumerate().map(|(index, f)| {
+        let member = &f.member;
+        quote! {
+            if self.__slot.clear(#index) {
+                let ptr = unsafe { self.__slot.slot_mut() }.as_mut_ptr();
+                unsafe { #krate::utils::drop_field(::core::ptr::addr_of_mut!((*ptr).#member)) };
+            }
+        }
+    });
+
+    let guard_name = guard.to_string();
+    let debug_fields = fields.iter().enumerate().map(|(index, f)| {
+        let field_name = f.name();
+        quote!(.field(#field_name, &self.__slot.is_init(#index)))
+    });
+
+    let doc = format!(
+        "A guard for a partially initialized [`{}`] that drops the fields it initialized if it is \
+         dropped before it is finished, generated by `#[derive(PartialGuard)]`.",
+        name
+    );
+    let new_doc = format!(
+        "Guard `slot`, with every field of the [`{}`] uninitialized.",
+        name
+    );
+    let finish_doc = format!(
+        "Get a reference to the [`{}`] if every field is initialized. Otherwise, return the \
+         guard unchanged.",
+        name
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #guard #generics #where_clause {
+            __slot: #krate::utils::GuardSlot<'__guard, #name #ty_generics, #count>,
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #guard #guard_ty_generics #where_clause {
+            #[doc = #new_doc]
+            #vis fn new(slot: &'__guard mut ::core::mem::MaybeUninit<#name #ty_generics>) -> Self {
+                Self { __slot: #krate::utils::GuardSlot::new(slot) }
+            }
+
+            /// Whether every field is initialized.
+            #vis fn is_complete(&self) -> bool {
+                self.__slot.is_complete()
+            }
+
+            #[doc = #finish_doc]
+            #vis fn finish(self) -> ::core::result::Result<&'__guard mut #name #ty_generics, Self> {
+                if !self.is_complete() {
+                    return ::core::result::Result::Err(self);
+                }
+                let this = ::core::mem::ManuallyDrop::new(self);
+                let slot = unsafe { ::core::ptr::read(&this.__slot) };
+                ::core::result::Result::Ok(slot.into_init())
+            }
+
+            #(#accessors)*
+        }
+
+        // shows whether each field is initialized
+        impl #impl_generics ::core::fmt::Debug for #guard #guard_ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(#guard_name)
+                    #(#debug_fields)*
+                    .finish()
+            }
+        }
+
+        impl #impl_generics ::core::ops::Drop for #guard #guard_ty_generics #where_clause {
+            fn drop(&mut self) {
+                #(#drops)*
+            }
+        }
+    })
+}
@@ -0,0 +1,117 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Expr, FnArg, Ident, ImplItemFn, Member, Result, ReturnType, Stmt, Type};
+
+use crate::fields::crate_path;
+
+pub fn expand(input: ImplItemFn) -> Result<TokenStream> {
+    let in_place = lower_constructor(&input)?;
+    Ok(quote!(#input #in_place))
+}
+
+/// Generate `<name>_in_place` from a constructor that ends with a `Self { .. }` literal, writing
+/// each field of the literal into the slot instead.
+fn lower_constructor(input: &ImplItemFn) -> Result<ImplItemFn> {
+    let sig = &input.sig;
+    if let Some(FnArg::Receiver(receiver)) = sig.inputs.first() {
+        return Err(syn::Error::new_spanned(
+            receiver,
+            "#[init_in_place] can only be used on constructors, which don't take `self`",
+        ));
+    }
+    if !matches!(&sig.output, ReturnType::Type(_, ty) if is_self(ty)) {
+        return Err(syn::Error::new(
+            sig.output.span(),
+            "#[init_in_place] can only be used on functions that return `Self`",
+        ));
+    }
+
+    let mut stmts = input.block.stmts.clone();
+    let literal = match stmts.pop() {
+        Some(Stmt::Expr(expr, None)) => expr,
+        _ => return Err(missing_literal(input)),
+    };
+    let krate = crate_path();
+    // only visible to the tokens generated here, so the body can still use its own `slot`
+    let slot = Ident::new("slot", Span::mixed_site());
+
+    let init = match strip_parens(&literal) {
+        Expr::Struct(lit) if lit.qself.is_none() && lit.path.is_ident("Self") => {
+            let fields = lit.fields.iter().map(|field| {
+                let member = &field.member;
+                let expr = &field.expr;
+                quote!(#member: (#expr))
+            });
+            match &lit.rest {
+                Some(rest) => quote!(#krate::partial_init!(#slot => { #(#fields,)* ..#rest })),
+                None => quote!({
+                    #krate::partial_init!(#slot => { #(#fields,)* });
+                    // the literal names every field, or `new` wouldn't compile
+                    unsafe { #slot.assume_init_mut() }
+                }),
+            }
+        }
+        Expr::Call(call) if matches!(&*call.func, Expr::Path(p) if p.path.is_ident("Self")) => {
+            let fields = call.args.iter().enumerate().map(|(i, expr)| {
+                let member = Member::from(i);
+                quote!(#member: (#expr))
+            });
+            quote!({
+                #krate::partial_init!(#slot => { #(#fields,)* });
+                // the call passes every field, or `new` wouldn't compile
+                unsafe { #slot.assume_init_mut() }
+            })
+        }
+        _ => return Err(missing_literal(input)),
+    };
+
+    let mut sig = sig.clone();
+    sig.constness = None;
+    sig.ident = format_ident!("{}_in_place", sig.ident);
+    sig.generics.params.insert(0, parse_quote!('__slot));
+    sig.inputs.insert(
+        0,
+        parse_quote!(#slot: &'__slot mut ::core::mem::MaybeUninit<Self>),
+    );
+    sig.output = parse_quote!(-> &'__slot mut Self);
+
+    let attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc"));
+    let vis = &input.vis;
+    let doc = format!(
+        " Like [`Self::{}`], but initializes the value in place in `slot`.",
+        input.sig.ident
+    );
+    Ok(parse_quote! {
+        #(#attrs)*
+        #[doc = #doc]
+        #[allow(clippy::needless_lifetimes)]
+        #vis #sig {
+            #(#stmts)*
+            #init
+        }
+    })
+}
+
+fn missing_literal(input: &ImplItemFn) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.sig.ident,
+        "#[init_in_place] needs the function body to end with a `Self { .. }` or `Self(..)` \
+         expression",
+    )
+}
+
+fn strip_parens(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(p) => strip_parens(&p.expr),
+        Expr::Group(g) => strip_parens(&g.expr),
+        expr => expr,
+    }
+}
+
+fn is_self(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("Self"))
+}
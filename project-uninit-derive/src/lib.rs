@@ -0,0 +1,198 @@
+//! Derive macros for [`project-uninit`](https://docs.rs/project-uninit).
+//!
+//! These are re-exported by `project-uninit` when its `derive` feature is enabled and should be
+//! used from there.
+extern crate proc_macro;
+
+mod arbitrary;
+mod assert_unique;
+mod builder;
+mod de;
+mod export;
+mod ffi;
+mod fields;
+mod fields_mut;
+mod gpu;
+mod guard;
+mod init_in_place;
+mod mirror;
+mod project_uninit;
+mod soa;
+mod tlv;
+mod uninit_impl;
+mod variant;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ImplItemFn, ItemImpl};
+
+/// Implement `UninitFields` for a struct, and `UninitView` unless it is `#[repr(packed)]`, and
+/// define a `const fn offset_of_<field>()` returning the offset of each field.
+///
+/// Fields marked `#[uninit(pin)]` are structurally pinned, and `UninitPinView` is implemented to
+/// project them from a pinned slot.
+#[proc_macro_derive(ProjectUninit, attributes(uninit))]
+pub fn derive_project_uninit(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    project_uninit::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `DecodeTlv` for a struct that also derives `ProjectUninit`.
+#[proc_macro_derive(DecodeTlv, attributes(tlv))]
+pub fn derive_decode_tlv(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    tlv::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `ArbitraryInPlace` for a struct that also derives `ProjectUninit`.
+#[proc_macro_derive(ArbitraryInPlace, attributes(uninit))]
+pub fn derive_arbitrary_in_place(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    arbitrary::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `DeserializeUninit` for a struct that also derives `ProjectUninit`.
+#[proc_macro_derive(DeserializeUninit, attributes(uninit))]
+pub fn derive_deserialize_uninit(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    de::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate a builder for a struct that also derives `ProjectUninit`, which tracks which fields
+/// have been initialized in its type parameters and can only finish once all of them are.
+#[proc_macro_derive(InitBuilder)]
+pub fn derive_init_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    builder::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `ExportFields` for a struct, exporting its fields with the same visibility as
+/// the fields themselves, or publicly with `#[uninit(export)]`.
+#[proc_macro_derive(ExportFields, attributes(uninit))]
+pub fn derive_export_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    export::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `FfiFields` for a `#[repr(C)]` struct, with field versions set by
+/// `#[uninit(since = N)]`.
+#[proc_macro_derive(FfiFields, attributes(uninit))]
+pub fn derive_ffi_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ffi::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `GpuLayout` for a struct, with field placement controlled by `#[gpu(...)]`.
+#[proc_macro_derive(GpuLayout, attributes(gpu))]
+pub fn derive_gpu_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    gpu::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate struct-of-arrays storage for a struct and implement `UninitSoA` for it.
+#[proc_macro_derive(UninitSoA)]
+pub fn derive_uninit_soa(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    soa::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `UninitEnum` for an enum with an integer repr such as `#[repr(u8)]`.
+#[proc_macro_derive(UninitEnum)]
+pub fn derive_uninit_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    variant::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate a `FooUninitMirror` struct for a `#[repr(C)]` or `#[repr(transparent)]` struct with
+/// each field wrapped in `MaybeUninit`, and implement `UninitMirror` for it.
+#[proc_macro_derive(UninitMirror)]
+pub fn derive_uninit_mirror(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    mirror::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate a `FooPartialGuard` for a struct, which drops the fields it initialized if it is
+/// dropped before every field is initialized.
+#[proc_macro_derive(PartialGuard)]
+pub fn derive_partial_guard(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    guard::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Lower methods of an inherent impl block that take `self: &mut MaybeUninit<Self>` or
+/// `self: &MaybeUninit<Self>` into associated functions, so they can initialize `self` with
+/// `project-uninit`'s macros.
+#[proc_macro_attribute]
+pub fn uninit_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    if !args.is_empty() {
+        return syn::Error::new_spanned(args, "#[uninit_impl] doesn't take any arguments")
+            .into_compile_error()
+            .into();
+    }
+    let input = parse_macro_input!(input as ItemImpl);
+    uninit_impl::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate an in-place version of a constructor that ends with a `Self { .. }` literal, which
+/// takes a `&mut MaybeUninit<Self>` and writes each field of the literal into it.
+#[proc_macro_attribute]
+pub fn init_in_place(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    if !args.is_empty() {
+        return syn::Error::new_spanned(args, "#[init_in_place] doesn't take any arguments")
+            .into_compile_error()
+            .into();
+    }
+    let input = parse_macro_input!(input as ImplItemFn);
+    init_in_place::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Check the field paths passed to `project-uninit`'s macros for duplicates and overlaps.
+///
+/// This replaces the `macro_rules!` implementation of `__assert_unique!` when the `derive`
+/// feature is enabled, since that compares every pair of paths and gets slow for wide structs.
+/// Its errors also point at the offending path instead of the whole macro invocation.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __assert_unique(input: TokenStream) -> TokenStream {
+    assert_unique::expand(input.into()).into()
+}
+
+/// Borrow the places for the field paths passed to `project-uninit`'s macros mutably at once.
+///
+/// This replaces the `macro_rules!` implementation of `__fields_mut!` when the `derive` feature
+/// is enabled, and also rejects projecting more than one field of a union when the paths go on
+/// to index into arrays.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __fields_mut(input: TokenStream) -> TokenStream {
+    fields_mut::expand(input.into()).into()
+}
@@ -0,0 +1,113 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "UninitMirror")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let mirror = format_ident!("{}UninitMirror", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics.params;
+
+    // the mirror only has the same layout if the struct's layout is defined by its fields
+    let mut defined_layout = false;
+    let reprs: Vec<_> = input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("repr"))
+        .collect();
+    for attr in &reprs {
+        attr.parse_nested_meta(|meta| {
+            defined_layout |= meta.path.is_ident("C") || meta.path.is_ident("transparent");
+            // skip the arguments of `align(N)` and `packed(N)`
+            if meta.input.peek(syn::token::Paren) {
+                let args;
+                syn::parenthesized!(args in meta.input);
+                args.parse::<TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if !defined_layout {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(UninitMirror)] requires #[repr(C)] or #[repr(transparent)]",
+        ));
+    }
+
+    let doc = format!(
+        "[`{}`] with each field wrapped in `MaybeUninit`, with the same layout.",
+        name
+    );
+    let mirror_fields = fields.iter().map(|f| {
+        let ty = &f.field.ty;
+        let field_vis = &f.field.vis;
+        match &f.member {
+            Member::Named(ident) => quote!(#field_vis #ident: ::core::mem::MaybeUninit<#ty>),
+            Member::Unnamed(_) => quote!(#field_vis ::core::mem::MaybeUninit<#ty>),
+        }
+    });
+    let definition = match fields.first().map(|f| &f.member) {
+        Some(Member::Unnamed(_)) => quote! {
+            #vis struct #mirror<#generics>(#(#mirror_fields,)*) #where_clause;
+        },
+        _ => quote! {
+            #vis struct #mirror<#generics> #where_clause {
+                #(#mirror_fields,)*
+            }
+        },
+    };
+    let uninit_fields = fields.iter().map(|f| {
+        let member = &f.member;
+        quote!(#member: ::core::mem::MaybeUninit::uninit())
+    });
+    let doc_uninit = format!("A [`{}`] with every field uninitialized.", name);
+    let doc_as_uninit = format!("View the fields as a possibly uninitialized [`{}`].", name);
+    let doc_assume_init = format!(
+        "**Unsafe:** Convert the fields to a [`{}`], assuming they are initialized.\n\n\
+         ## Safety\n\
+         Every field must be initialized.",
+        name
+    );
+
+    let uninit_ty = quote!(::core::mem::MaybeUninit<#name #ty_generics>);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #(#reprs)*
+        #[allow(dead_code)]
+        #definition
+
+        #[allow(dead_code)]
+        impl #impl_generics #mirror #ty_generics #where_clause {
+            #[doc = #doc_uninit]
+            #vis const fn uninit() -> Self {
+                Self { #(#uninit_fields,)* }
+            }
+
+            #[doc = #doc_as_uninit]
+            #vis fn as_uninit(&self) -> &#uninit_ty {
+                // the mirror has the same layout as the struct
+                unsafe { &*(self as *const Self as *const #uninit_ty) }
+            }
+
+            #[doc = #doc_as_uninit]
+            #vis fn as_uninit_mut(&mut self) -> &mut #uninit_ty {
+                unsafe { &mut *(self as *mut Self as *mut #uninit_ty) }
+            }
+
+            #[doc = #doc_assume_init]
+            #vis unsafe fn assume_init(self) -> #name #ty_generics {
+                ::core::mem::transmute_copy(&::core::mem::ManuallyDrop::new(self))
+            }
+        }
+
+        unsafe impl #impl_generics #krate::UninitMirror for #name #ty_generics #where_clause {
+            type Mirror = #mirror #ty_generics;
+        }
+    })
+}
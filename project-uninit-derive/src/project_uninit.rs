@@ -0,0 +1,370 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Lifetime, Member, Result};
+
+use crate::fields::{crate_path, struct_fields, StructField};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "ProjectUninit")?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // field names are unique and keep their case, so these are too
+    let offset_fns: Vec<_> = fields
+        .iter()
+        .map(|f| match &f.member {
+            Member::Named(ident) => format_ident!("offset_of_{}", ident.unraw()),
+            Member::Unnamed(index) => format_ident!("offset_of_{}", index.index),
+        })
+        .collect();
+
+    // `FIELDS` takes its offsets from the functions, so they always agree
+    let infos = fields.iter().zip(&offset_fns).map(|(f, offset_fn)| {
+        let ty = &f.field.ty;
+        let field_name = f.name();
+        quote!(#krate::FieldInfo::new::<#ty>(#field_name, Self::#offset_fn()))
+    });
+
+    let offsets = fields.iter().zip(&offset_fns).map(|(f, offset_fn)| {
+        let member = &f.member;
+        let field_vis = &f.field.vis;
+        let doc = format!(
+            "Offset of `{}` in bytes from the start of the struct.",
+            f.name()
+        );
+        quote! {
+            #[doc = #doc]
+            #[allow(non_snake_case)]
+            #field_vis const fn #offset_fn() -> usize {
+                ::core::mem::offset_of!(Self, #member)
+            }
+        }
+    });
+
+    // references to the fields of packed structs may be unaligned
+    let packed = is_packed(input)?;
+    let views = if packed {
+        quote!()
+    } else {
+        expand_views(input, &fields)
+    };
+    let pin_view = match fields.iter().find(|f| f.attrs.pin) {
+        Some(f) if packed => {
+            return Err(syn::Error::new_spanned(
+                f.field,
+                "fields of #[repr(packed)] structs can't be pinned",
+            ))
+        }
+        Some(_) => expand_pin_view(input, &fields),
+        None => quote!(),
+    };
+
+    let all_fields = expand_all_fields(input, &fields);
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::UninitFields for #name #ty_generics #where_clause {
+            const FIELDS: &'static [#krate::FieldInfo] = &[#(#infos),*];
+        }
+
+        #all_fields
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#offsets)*
+        }
+
+        #views
+
+        #pin_view
+    })
+}
+
+// Implement `AllFields`, so `init_all!` and `box_emplace!` can check that every field is
+// initialized while type checking, by marking each field they're given as set in a tuple.
+fn expand_all_fields(input: &DeriveInput, fields: &[StructField]) -> TokenStream {
+    let krate = crate_path();
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let states: Vec<_> = (0..fields.len()).map(|i| format_ident!("S{}", i)).collect();
+    let markers = fields.iter().enumerate().map(|(i, f)| {
+        let marker = quote!(__Field<#i>);
+        match &f.member {
+            Member::Named(ident) => quote!(pub #ident: #marker),
+            Member::Unnamed(_) => quote!(pub #marker),
+        }
+    });
+    let tuple = matches!(fields.first().map(|f| &f.member), Some(Member::Unnamed(_)));
+    let fields_def = if tuple {
+        quote!(pub struct __Fields(#(#markers),*);)
+    } else {
+        quote!(pub struct __Fields { #(#markers),* })
+    };
+    let set_impls = (0..fields.len()).map(|i| {
+        let out = states.iter().enumerate().map(|(j, s)| {
+            if i == j {
+                quote!(#krate::utils::Set)
+            } else {
+                quote!(#s)
+            }
+        });
+        quote! {
+            impl<#(#states),*> #krate::utils::SetField<(#(#states,)*)> for __Field<#i> {
+                type Out = (#(#out,)*);
+            }
+        }
+    });
+    let unset = states.iter().map(|_| quote!(#krate::utils::Unset));
+    let set = states.iter().map(|_| quote!(#krate::utils::Set));
+
+    quote! {
+        #[allow(non_camel_case_types, dead_code)]
+        const _: () = {
+            #fields_def
+            pub struct __Field<const I: usize>;
+
+            #(#set_impls)*
+
+            impl #impl_generics #krate::utils::AllFields for #name #ty_generics #where_clause {
+                type Fields = __Fields;
+                type Unset = (#(#unset,)*);
+                type Complete = (#(#set,)*);
+            }
+        };
+    }
+}
+
+pub fn is_packed(input: &DeriveInput) -> Result<bool> {
+    let mut packed = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            packed |= meta.path.is_ident("packed");
+            // skip the arguments of `align(N)` and `packed(N)`
+            if meta.input.peek(syn::token::Paren) {
+                let args;
+                syn::parenthesized!(args in meta.input);
+                args.parse::<TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(packed)
+}
+
+// Generate `FooUninitRef<'a>` and `FooUninitMut<'a>` and implement `UninitView`.
+fn expand_views(input: &DeriveInput, fields: &[StructField]) -> TokenStream {
+    let krate = crate_path();
+    let name = &input.ident;
+    let vis = &input.vis;
+    let ref_name = format_ident!("{}UninitRef", name);
+    let mut_name = format_ident!("{}UninitMut", name);
+    let lt = Lifetime::new("'__uninit", proc_macro2::Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let params = &input.generics.params;
+    let ref_doc = format!(
+        "References to the possibly uninitialized fields of a [`{}`], returned by `uninit_fields`.",
+        name
+    );
+    let mut_doc = format!(
+        "Mutable references to the possibly uninitialized fields of a [`{}`], returned by \
+         `uninit_fields_mut`.",
+        name
+    );
+
+    let view_fields = |mutability: TokenStream| {
+        fields
+            .iter()
+            .map(|f| {
+                let field_vis = &f.field.vis;
+                let ty = &f.field.ty;
+                let ty = quote!(&#lt #mutability ::core::mem::MaybeUninit<#ty>);
+                match &f.member {
+                    Member::Named(ident) => quote!(#field_vis #ident: #ty),
+                    Member::Unnamed(_) => quote!(#field_vis #ty),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let view_values = |cast: TokenStream| {
+        fields
+            .iter()
+            .map(|f| {
+                let member = &f.member;
+                let ty = &f.field.ty;
+                let value = quote! {
+                    unsafe { #cast(::core::ptr::addr_of_mut!((*ptr).#member) as *mut ::core::mem::MaybeUninit<#ty>) }
+                };
+                match member {
+                    Member::Named(ident) => quote!(#ident: #value),
+                    Member::Unnamed(_) => value,
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let ref_fields = view_fields(quote!());
+    let mut_fields = view_fields(quote!(mut));
+    let ref_values = view_values(quote!(&*));
+    let mut_values = view_values(quote!(&mut *));
+    let ref_marker = quote!(::core::marker::PhantomData<&#lt #name #ty_generics>);
+    let mut_marker = quote!(::core::marker::PhantomData<&#lt mut #name #ty_generics>);
+
+    let tuple = matches!(fields.first().map(|f| &f.member), Some(Member::Unnamed(_)));
+    let (ref_def, mut_def, ref_new, mut_new) = if tuple {
+        (
+            quote!(#vis struct #ref_name<#lt, #params>(#(#ref_fields,)* #ref_marker) #where_clause;),
+            quote!(#vis struct #mut_name<#lt, #params>(#(#mut_fields,)* #mut_marker) #where_clause;),
+            quote!(#ref_name(#(#ref_values,)* ::core::marker::PhantomData)),
+            quote!(#mut_name(#(#mut_values,)* ::core::marker::PhantomData)),
+        )
+    } else {
+        (
+            quote!(#vis struct #ref_name<#lt, #params> #where_clause { #(#ref_fields,)* __marker: #ref_marker }),
+            quote!(#vis struct #mut_name<#lt, #params> #where_clause { #(#mut_fields,)* __marker: #mut_marker }),
+            quote!(#ref_name { #(#ref_values,)* __marker: ::core::marker::PhantomData }),
+            quote!(#mut_name { #(#mut_values,)* __marker: ::core::marker::PhantomData }),
+        )
+    };
+
+    let mut view_generics = input.generics.clone();
+    view_generics.params.insert(0, syn::parse_quote!(#lt));
+    let (_, view_ty_generics, _) = view_generics.split_for_impl();
+
+    quote! {
+        #[doc = #ref_doc]
+        #[allow(dead_code)]
+        #ref_def
+
+        #[doc = #mut_doc]
+        #[allow(dead_code)]
+        #mut_def
+
+        impl #impl_generics #krate::UninitView for #name #ty_generics #where_clause {
+            type Ref<#lt> = #ref_name #view_ty_generics where Self: #lt;
+            type Mut<#lt> = #mut_name #view_ty_generics where Self: #lt;
+
+            fn view(slot: &::core::mem::MaybeUninit<Self>) -> Self::Ref<'_> {
+                let ptr = slot.as_ptr() as *mut Self;
+                #ref_new
+            }
+
+            fn view_mut(slot: &mut ::core::mem::MaybeUninit<Self>) -> Self::Mut<'_> {
+                let ptr = slot.as_mut_ptr();
+                #mut_new
+            }
+        }
+    }
+}
+
+// Generate `FooUninitPin<'a>`, implement `UninitPinView`, and make sure `Foo` can't move out of its
+// pinned fields.
+fn expand_pin_view(input: &DeriveInput, fields: &[StructField]) -> TokenStream {
+    let krate = crate_path();
+    let name = &input.ident;
+    let vis = &input.vis;
+    let pin_name = format_ident!("{}UninitPin", name);
+    let lt = Lifetime::new("'__uninit", proc_macro2::Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let params = &input.generics.params;
+    let doc = format!(
+        "References to the possibly uninitialized fields of a pinned [`{}`], returned by \
+         `uninit_fields_pin`.",
+        name
+    );
+
+    let pin_fields = fields.iter().map(|f| {
+        let field_vis = &f.field.vis;
+        let ty = &f.field.ty;
+        let ty = if f.attrs.pin {
+            quote!(::core::pin::Pin<&#lt mut ::core::mem::MaybeUninit<#ty>>)
+        } else {
+            quote!(&#lt mut ::core::mem::MaybeUninit<#ty>)
+        };
+        match &f.member {
+            Member::Named(ident) => quote!(#field_vis #ident: #ty),
+            Member::Unnamed(_) => quote!(#field_vis #ty),
+        }
+    });
+    let pin_values = fields.iter().map(|f| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        let field = quote! {
+            &mut *(::core::ptr::addr_of_mut!((*ptr).#member) as *mut ::core::mem::MaybeUninit<#ty>)
+        };
+        let value = if f.attrs.pin {
+            quote!(unsafe { ::core::pin::Pin::new_unchecked(#field) })
+        } else {
+            quote!(unsafe { #field })
+        };
+        match member {
+            Member::Named(ident) => quote!(#ident: #value),
+            Member::Unnamed(_) => value,
+        }
+    });
+    let marker = quote!(::core::marker::PhantomData<&#lt mut #name #ty_generics>);
+
+    let tuple = matches!(fields.first().map(|f| &f.member), Some(Member::Unnamed(_)));
+    let (pin_def, pin_new) = if tuple {
+        (
+            quote!(#vis struct #pin_name<#lt, #params>(#(#pin_fields,)* #marker) #where_clause;),
+            quote!(#pin_name(#(#pin_values,)* ::core::marker::PhantomData)),
+        )
+    } else {
+        (
+            quote!(#vis struct #pin_name<#lt, #params> #where_clause { #(#pin_fields,)* __marker: #marker }),
+            quote!(#pin_name { #(#pin_values,)* __marker: ::core::marker::PhantomData }),
+        )
+    };
+
+    let mut view_generics = input.generics.clone();
+    view_generics.params.insert(0, syn::parse_quote!(#lt));
+    let (unpin_impl_generics, view_ty_generics, _) = view_generics.split_for_impl();
+
+    let pinned_members = fields
+        .iter()
+        .filter(|f| f.attrs.pin)
+        .map(|f| match &f.member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(index) => format_ident!("_{}", index.index),
+        });
+    let pinned_types = fields.iter().filter(|f| f.attrs.pin).map(|f| &f.field.ty);
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    quote! {
+        #[doc = #doc]
+        #[allow(dead_code)]
+        #pin_def
+
+        unsafe impl #impl_generics #krate::UninitPinView for #name #ty_generics #where_clause {
+            type Pin<#lt> = #pin_name #view_ty_generics where Self: #lt;
+
+            fn view_pin(slot: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<Self>>) -> Self::Pin<'_> {
+                // the pinned fields are pinned again, and the others are never pinned
+                let ptr = unsafe { slot.get_unchecked_mut() }.as_mut_ptr();
+                #pin_new
+            }
+        }
+
+        #[allow(non_camel_case_types, dead_code)]
+        const _: () = {
+            // `Self` is only `Unpin` if every pinned field is
+            pub struct __PinnedFields<#lt, #params> #where_clause {
+                __generics: #krate::utils::AlwaysUnpin<#lt, #name #ty_generics>,
+                #(#pinned_members: #pinned_types,)*
+            }
+
+            impl #unpin_impl_generics ::core::marker::Unpin for #name #ty_generics
+            where
+                __PinnedFields #view_ty_generics: ::core::marker::Unpin,
+                #predicates
+            {
+            }
+
+            // `drop` could move out of a pinned field, so `Self` can't implement `Drop`
+            trait MustNotImplDrop {}
+            #[allow(drop_bounds)]
+            impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+            impl #impl_generics MustNotImplDrop for #name #ty_generics #where_clause {}
+        };
+    }
+}
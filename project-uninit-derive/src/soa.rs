@@ -0,0 +1,181 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, DeriveInput, Index, Member, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "UninitSoA")?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let soa = format_ident!("{}SoA", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // the name used in the accessors of each field, which is its index for tuple structs, like
+    // `set_0`
+    let members: Vec<_> = fields.iter().map(|f| &f.member).collect();
+    let bases: Vec<_> = members
+        .iter()
+        .map(|member| match member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        })
+        .collect();
+    if bases.len() > 64 {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(UninitSoA)] supports structs with at most 64 fields",
+        ));
+    }
+    let types: Vec<_> = fields.iter().map(|f| &f.field.ty).collect();
+    // the index of each column in the tuple of columns, and in the row tracker
+    let indices: Vec<_> = (0..bases.len()).map(Index::from).collect();
+    let columns: Vec<_> = (0..bases.len()).collect();
+    let column_count = bases.len();
+
+    let accessors = indices.iter().zip(&bases).zip(&types).enumerate();
+    let accessors = accessors.map(|(column, ((index, base), ty))| {
+        let set = format_ident!("set_{}", base);
+        let get = format_ident!("get_{}", base);
+        let get_mut = format_ident!("get_{}_mut", base);
+        let clear = format_ident!("clear_{}", base);
+        let column_fn = format_ident!("column_{}", base);
+        let doc_set = format!(
+            "Initialize `{}` in `row`, dropping the previous value if there was one.",
+            base
+        );
+        let doc_get = format!("A reference to `{}` in `row`, if it is initialized.", base);
+        let doc_clear = format!("Drop `{}` in `row` if it is initialized.", base);
+        let doc_column = format!("The `{}` column.", base);
+        quote! {
+            #[doc = #doc_set]
+            #vis fn #set(&mut self, row: usize, value: #ty) -> &mut #ty {
+                unsafe {
+                    let (columns, rows) = self.__columns.parts();
+                    rows.write(&mut columns.#index, row, #column, value)
+                }
+            }
+
+            #[doc = #doc_get]
+            #vis fn #get(&self, row: usize) -> ::core::option::Option<&#ty> {
+                let rows = self.__columns.rows();
+                unsafe { rows.get(&self.__columns.columns().#index, row, #column) }
+            }
+
+            #[doc = #doc_get]
+            #vis fn #get_mut(&mut self, row: usize) -> ::core::option::Option<&mut #ty> {
+                unsafe {
+                    let (columns, rows) = self.__columns.parts();
+                    rows.get_mut(&mut columns.#index, row, #column)
+                }
+            }
+
+            #[doc = #doc_clear]
+            #vis fn #clear(&mut self, row: usize) {
+                unsafe {
+                    let (columns, rows) = self.__columns.parts();
+                    rows.clear(&mut columns.#index, row, #column)
+                }
+            }
+
+            #[doc = #doc_column]
+            #vis fn #column_fn(&self) -> &[::core::mem::MaybeUninit<#ty>] {
+                &self.__columns.columns().#index
+            }
+        }
+    });
+
+    // the fields are read out of a `ManuallyDrop` rather than destructured so that this also
+    // compiles for structs that implement `Drop`
+    let init_row_sets = members.iter().zip(&bases).map(|(member, base)| {
+        let set = format_ident!("set_{}", base);
+        quote!(self.#set(row, unsafe { ::core::ptr::read(&value.#member) });)
+    });
+
+    let doc = format!(
+        "Struct-of-arrays storage for [`{}`], generated by `#[derive(UninitSoA)]`.",
+        name
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #soa #impl_generics #where_clause {
+            __columns: #krate::utils::SoaColumns<(
+                #(#krate::utils::Box<[::core::mem::MaybeUninit<#types>]>,)*
+            )>,
+        }
+
+        impl #impl_generics #soa #ty_generics #where_clause {
+            /// Create storage for `len` rows with every cell uninitialized.
+            #vis fn new(len: usize) -> Self {
+                let columns = (#(#krate::utils::new_column::<#types>(len),)*);
+                Self {
+                    __columns: unsafe {
+                        #krate::utils::SoaColumns::new(columns, len, #column_count)
+                    },
+                }
+            }
+
+            /// The number of rows.
+            #vis fn len(&self) -> usize {
+                self.__columns.rows().len()
+            }
+
+            /// Whether there are no rows.
+            #vis fn is_empty(&self) -> bool {
+                self.__columns.rows().is_empty()
+            }
+
+            /// Which cells are initialized.
+            #vis fn rows(&self) -> &#krate::RowTracker {
+                self.__columns.rows()
+            }
+
+            #(#accessors)*
+
+            /// Move each field of `value` into its column in `row`, dropping any previous values.
+            ///
+            /// If the struct implements `Drop`, that implementation is not called for `value`.
+            #vis fn init_row(&mut self, row: usize, value: #name #ty_generics) {
+                let value = ::core::mem::ManuallyDrop::new(value);
+                #(#init_row_sets)*
+            }
+
+            /// Move the values out of `row` if every field is initialized.
+            #vis fn take_row(&mut self, row: usize) -> ::core::option::Option<#name #ty_generics> {
+                if !self.__columns.rows().is_row_complete(row) {
+                    return ::core::option::Option::None;
+                }
+                unsafe {
+                    let (columns, rows) = self.__columns.parts();
+                    ::core::option::Option::Some(#name {
+                        #(#members: rows.take(&mut columns.#indices, row, #columns),)*
+                    })
+                }
+            }
+
+            /// Drop every initialized field in `row`.
+            #vis fn clear_row(&mut self, row: usize) {
+                let (columns, rows) = unsafe { self.__columns.parts() };
+                #(unsafe { rows.clear(&mut columns.#indices, row, #columns) };)*
+            }
+        }
+
+        impl #impl_generics ::core::ops::Drop for #soa #ty_generics #where_clause {
+            fn drop(&mut self) {
+                for row in 0..self.len() {
+                    self.clear_row(row);
+                }
+            }
+        }
+
+        impl #impl_generics #krate::UninitSoA for #name #ty_generics #where_clause {
+            type SoA = #soa #ty_generics;
+
+            fn soa(len: usize) -> Self::SoA {
+                #soa::new(len)
+            }
+        }
+    })
+}
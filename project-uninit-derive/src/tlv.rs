@@ -0,0 +1,82 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitInt, Result};
+
+use crate::fields::{crate_path, struct_fields};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let fields = struct_fields(input, "DecodeTlv")?;
+    let name = &input.ident;
+    // the fields are tracked with an `InitTracker`
+    if fields.len() > 64 {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[derive(DecodeTlv)] supports structs with at most 64 fields",
+        ));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut tags = Vec::with_capacity(fields.len());
+    for (index, f) in fields.iter().enumerate() {
+        let mut tag = None;
+        for attr in f.field.attrs.iter().filter(|a| a.path().is_ident("tlv")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    tag = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u16>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown tlv attribute"))
+                }
+            })?;
+        }
+        let tag = tag.unwrap_or(index as u16);
+        if let Some((_, other)) = tags.iter().find(|(t, _)| *t == tag) {
+            return Err(syn::Error::new_spanned(
+                &f.member,
+                format!("tag {} is already used by field `{}`", tag, other),
+            ));
+        }
+        tags.push((tag, f.name()));
+    }
+
+    let tag_arms = tags.iter().enumerate().map(|(index, (tag, _))| {
+        quote!(#tag => ::core::option::Option::Some(#index),)
+    });
+
+    let decode_arms = fields.iter().enumerate().map(|(index, f)| {
+        let member = &f.member;
+        let ty = &f.field.ty;
+        let field_name = f.name();
+        quote! {
+            #index => {
+                let value = <#ty as #krate::TlvValue>::decode_tlv(value)
+                    .ok_or(#krate::TlvError::InvalidValue { field: #field_name })?;
+                #krate::partial_init!(slot => #member = value);
+            }
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #impl_generics #krate::DecodeTlv for #name #ty_generics #where_clause {
+            fn field_for_tag(tag: u16) -> ::core::option::Option<usize> {
+                match tag {
+                    #(#tag_arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn decode_field(
+                slot: &mut ::core::mem::MaybeUninit<Self>,
+                index: usize,
+                value: &[u8],
+            ) -> ::core::result::Result<(), #krate::TlvError> {
+                match index {
+                    #(#decode_arms)*
+                    _ => ::core::panic!("field index out of range"),
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
@@ -0,0 +1,203 @@
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_quote, Expr, FnArg, Ident, ImplItem, ImplItemFn, Item, ItemImpl, Lifetime, Macro, Member,
+    ParenthesizedGenericArguments, Result, ReturnType, Type, TypeBareFn, TypeReference,
+};
+
+use crate::fields::crate_path;
+
+pub fn expand(mut input: ItemImpl) -> Result<TokenStream> {
+    if let Some((_, path, _)) = &input.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "#[uninit_impl] can only be used on inherent impl blocks",
+        ));
+    }
+    for item in &mut input.items {
+        if let ImplItem::Fn(method) = item {
+            lower_method(method);
+        }
+    }
+    Ok(quote!(#input))
+}
+
+/// Turn a method with a `self: &mut MaybeUninit<Self>` or `self: &MaybeUninit<Self>` receiver
+/// into an associated function taking the same reference as its first argument.
+fn lower_method(method: &mut ImplItemFn) {
+    let receiver = match method.sig.inputs.first_mut() {
+        Some(FnArg::Receiver(receiver)) if is_uninit_ref(&receiver.ty) => receiver,
+        _ => return,
+    };
+    let mut ty = (*receiver.ty).clone();
+    let mutable = matches!(&ty, Type::Reference(r) if r.mutability.is_some());
+
+    // elided lifetimes in the return type borrow from `self` in a method, but an associated
+    // function needs them to be named
+    if let (Type::Reference(r), ReturnType::Type(_, output)) = (&mut ty, &mut method.sig.output) {
+        let lifetime = r
+            .lifetime
+            .clone()
+            .unwrap_or_else(|| Lifetime::new("'__self", Span::call_site()));
+        let mut elided = NameElided {
+            lifetime: lifetime.clone(),
+            found: false,
+        };
+        elided.visit_type_mut(output);
+        if elided.found && r.lifetime.is_none() {
+            r.lifetime = Some(lifetime.clone());
+            method
+                .sig
+                .generics
+                .params
+                .insert(0, parse_quote!(#lifetime));
+        }
+    }
+
+    // the argument is only visible to the tokens the macro generates, so it can't shadow or be
+    // shadowed by the method's own variables
+    let this = Ident::new("this", Span::mixed_site());
+    let arg: FnArg = parse_quote!(#this: #ty);
+    *method.sig.inputs.first_mut().unwrap() = arg;
+
+    LowerSelf {
+        this,
+        krate: crate_path(),
+        mutable,
+    }
+    .visit_block_mut(&mut method.block);
+}
+
+/// Gives elided lifetimes of references and `'_` in a type the lifetime of `self`.
+struct NameElided {
+    lifetime: Lifetime,
+    found: bool,
+}
+
+impl VisitMut for NameElided {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = Some(self.lifetime.clone());
+            self.found = true;
+        }
+        visit_mut::visit_type_reference_mut(self, ty);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime.clone();
+            self.found = true;
+        }
+    }
+
+    // lifetimes elided in `fn(&T) -> &U` and `Fn(&T) -> &U` belong to the function type
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _args: &mut ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Whether the type is a reference to `MaybeUninit<Self>`.
+fn is_uninit_ref(ty: &Type) -> bool {
+    let elem = match ty {
+        Type::Reference(r) => &*r.elem,
+        _ => return false,
+    };
+    let segment = match elem {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+        _ => None,
+    };
+    match segment {
+        Some(segment) => {
+            segment.ident == "MaybeUninit"
+                && matches!(
+                    &segment.arguments,
+                    syn::PathArguments::AngleBracketed(args)
+                        if args.args.len() == 1
+                            && matches!(
+                                args.args.first(),
+                                Some(syn::GenericArgument::Type(Type::Path(p)))
+                                    if p.path.is_ident("Self")
+                            )
+                )
+        }
+        None => false,
+    }
+}
+
+/// Replaces `self` in a method body with the lowered argument, and assignments to `self.field`
+/// with `partial_init!`.
+struct LowerSelf {
+    this: Ident,
+    krate: TokenStream,
+    mutable: bool,
+}
+
+impl LowerSelf {
+    /// The fields of `self.a.b`, or `None` if the expression isn't a field of `self`.
+    fn self_fields(expr: &Expr) -> Option<Vec<&Member>> {
+        match expr {
+            Expr::Field(field) => {
+                let mut path = match &*field.base {
+                    Expr::Path(p) if p.qself.is_none() && p.path.is_ident("self") => Vec::new(),
+                    base => Self::self_fields(base)?,
+                };
+                path.push(&field.member);
+                Some(path)
+            }
+            Expr::Paren(p) => Self::self_fields(&p.expr),
+            _ => None,
+        }
+    }
+
+    fn replace_tokens(&self, tokens: TokenStream) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|tt| match tt {
+                TokenTree::Ident(ident) if ident == "self" => TokenTree::Ident(self.this.clone()),
+                TokenTree::Group(group) => {
+                    let mut new =
+                        Group::new(group.delimiter(), self.replace_tokens(group.stream()));
+                    new.set_span(group.span());
+                    TokenTree::Group(new)
+                }
+                tt => tt,
+            })
+            .collect()
+    }
+}
+
+impl VisitMut for LowerSelf {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Assign(assign) = expr {
+            if self.mutable && Self::self_fields(&assign.left).is_some() {
+                self.visit_expr_mut(&mut assign.right);
+                let fields = Self::self_fields(&assign.left).unwrap().into_iter();
+                let (this, krate, right) = (&self.this, &self.krate, &assign.right);
+                *expr = parse_quote!({
+                    #krate::partial_init!(#this => #(#fields)=>* = #right);
+                });
+                return;
+            }
+        }
+        if let Expr::Path(p) = expr {
+            if p.qself.is_none() && p.path.is_ident("self") {
+                let this = &self.this;
+                *expr = parse_quote!(#this);
+                return;
+            }
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        let tokens = core::mem::take(&mut mac.tokens);
+        mac.tokens = self.replace_tokens(tokens);
+    }
+
+    // `self` means something else inside nested items
+    fn visit_item_mut(&mut self, _item: &mut Item) {}
+}
@@ -0,0 +1,207 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Fields, Ident, Member, Result};
+
+use crate::fields::crate_path;
+
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+// The integer type from `#[repr(Int)]`, which gives each variant the layout of a `#[repr(C)]`
+// struct starting with the tag.
+fn int_repr(input: &DeriveInput) -> Result<Ident> {
+    let mut int = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("packed") {
+                return Err(meta.error(
+                    "#[derive(UninitEnum)] requires an integer repr without `C` or `packed`",
+                ));
+            }
+            if let Some(ident) = meta.path.get_ident() {
+                if INT_REPRS.iter().any(|int| ident == int) {
+                    int = Some(ident.clone());
+                }
+            }
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    int.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(UninitEnum)] requires an integer repr such as #[repr(u8)]",
+        )
+    })
+}
+
+/// `init_variant_<name>`, with the name of the variant in snake case, e.g. `init_variant_http_get`
+/// for `HTTPGet`.
+fn constructor_name(variant: &Ident) -> Ident {
+    let chars: Vec<char> = variant.unraw().to_string().chars().collect();
+    let mut name = String::from("init_variant_");
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            // a word starts after a lowercase letter or digit, or at the last capital of an acronym
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                name.push('_');
+            }
+        }
+        name.extend(c.to_lowercase());
+    }
+    Ident::new(&name, variant.span())
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_path();
+    let name = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) if !data.variants.is_empty() => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[derive(UninitEnum)] can only be used on enums with at least one variant",
+            ))
+        }
+    };
+    let int = int_repr(input)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics.params;
+    let marker = quote!(::core::marker::PhantomData<*mut #name #ty_generics>);
+
+    let mut items = Vec::with_capacity(data.variants.len());
+    let mut variant_fields = Vec::with_capacity(data.variants.len());
+    let mut variant_values = Vec::with_capacity(data.variants.len());
+    let mut constructors = Vec::with_capacity(data.variants.len());
+    let mut constructor_names: Vec<(Ident, &Ident)> = Vec::with_capacity(data.variants.len());
+    // only visible to the tokens generated here, so it can't collide with a field's name
+    let slot = Ident::new("slot", Span::mixed_site());
+    let vis = &input.vis;
+    // implicit discriminants count up from the last explicit one
+    let mut base = quote!(0);
+    let mut offset = 0usize;
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let layout = format_ident!("__Layout_{}", ident);
+        let ptrs = format_ident!("__Ptrs_{}", ident);
+        if let Some((_, discriminant)) = &variant.discriminant {
+            base = quote!((#discriminant));
+            offset = 0;
+        }
+        let tag = quote!(#base + #offset as #int);
+        offset += 1;
+
+        let layout_idents: Vec<_> = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| f.ident.clone().unwrap_or_else(|| format_ident!("_{}", i)))
+            .collect();
+        let types: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+        let values = layout_idents
+            .iter()
+            .map(|f| quote!(::core::ptr::addr_of_mut!((*layout).#f)));
+        let (ptrs_def, ptrs_value) = match &variant.fields {
+            Fields::Unnamed(_) => (
+                quote!(pub struct #ptrs<#generics>(#(pub *mut #types,)* pub #marker) #where_clause;),
+                quote!(#ptrs(#(#values,)* ::core::marker::PhantomData)),
+            ),
+            _ => (
+                quote!(pub struct #ptrs<#generics> #where_clause {
+                    #(pub #layout_idents: *mut #types,)*
+                    pub __marker: #marker,
+                }),
+                quote!(#ptrs { #(#layout_idents: #values,)* __marker: ::core::marker::PhantomData }),
+            ),
+        };
+
+        let members = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match &f.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::from(i),
+            });
+        let fn_name = constructor_name(ident);
+        if let Some((_, other)) = constructor_names.iter().find(|(n, _)| *n == fn_name) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "variants `{}` and `{}` would both be initialized by `{}`",
+                    other, ident, fn_name
+                ),
+            ));
+        }
+        constructor_names.push((fn_name.clone(), ident));
+        let doc = format!(
+            "Initialize `slot` in place as [`{}::{}`](Self::{}), returning a mutable reference to \
+             it. A previous value in the slot is not dropped.",
+            name, ident, ident
+        );
+        constructors.push(quote! {
+            #[doc = #doc]
+            #vis fn #fn_name<'__slot>(
+                #slot: &'__slot mut ::core::mem::MaybeUninit<Self>,
+                #(#layout_idents: #types),*
+            ) -> &'__slot mut Self {
+                #krate::init_variant!(#slot => Self::#ident { #(#members: #layout_idents),* })
+            }
+        });
+
+        items.push(quote! {
+            #[repr(C)]
+            pub struct #layout<#generics> #where_clause {
+                __tag: #int,
+                #(#layout_idents: #types,)*
+                __marker: #marker,
+            }
+
+            #ptrs_def
+        });
+        variant_fields.push(quote! {
+            pub #ident: #krate::utils::VariantPtrs<#ptrs #ty_generics, #int>
+        });
+        variant_values.push(quote! {
+            #ident: {
+                let layout = this as *mut #layout #ty_generics;
+                #krate::utils::VariantPtrs {
+                    fields: #ptrs_value,
+                    tag: ::core::ptr::addr_of_mut!((*layout).__tag),
+                    value: #tag,
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        const _: () = {
+            #(#items)*
+
+            pub struct __Variants<#generics> #where_clause {
+                #(#variant_fields,)*
+            }
+
+            unsafe impl #impl_generics #krate::UninitEnum for #name #ty_generics #where_clause {
+                type Variants = __Variants #ty_generics;
+
+                unsafe fn variant_ptrs(this: *mut Self) -> Self::Variants {
+                    __Variants { #(#variant_values,)* }
+                }
+            }
+        };
+
+        #[allow(dead_code, clippy::needless_lifetimes, clippy::too_many_arguments)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#constructors)*
+        }
+    })
+}
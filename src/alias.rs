@@ -0,0 +1,184 @@
+/// Define a reusable alias for a field path.
+///
+/// `field_path!(NAME = a => b => 0)` defines a macro named `NAME` that stands for the path
+/// `a => b => 0`. The alias is used by writing `@NAME` at the start of a field path in
+/// [`project_uninit!`](crate::project_uninit), [`project_uninit_mut!`](crate::project_uninit_mut)
+/// and [`partial_init!`](crate::partial_init), optionally followed by more fields:
+/// `@NAME => c`.
+/// Aliases are expanded before the fields are checked, so using an alias and the path it stands
+/// for in the same invocation is rejected like any other duplicate field.
+///
+/// Like any `macro_rules!` macro, the alias can only be used after it is defined, including in
+/// child modules declared after it.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{field_path, partial_init, project_uninit};
+///
+/// struct Inner { value1: u8, value2: (i32, bool) }
+/// struct Outer { inner: Inner, id: u32 }
+///
+/// field_path!(INNER_VALUE = inner => value2);
+///
+/// let mut x = MaybeUninit::<Outer>::uninit();
+/// partial_init!(x => { id: 1, inner => value1: 2, @INNER_VALUE => 0: 3 });
+/// partial_init!(x => @INNER_VALUE => 1 = true);
+///
+/// let value = project_uninit!(x => @INNER_VALUE);
+/// assert_eq!(unsafe { value.assume_init() }, (3, true));
+/// ```
+#[macro_export]
+macro_rules! field_path {
+    ($(#[$attr:meta])* $name:ident = $($props:tt)=>+) => {
+        $crate::__field_path_def!(($) $(#[$attr])* $name [$($props)=>+]);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __field_path_def {
+    (($d:tt) $(#[$attr:meta])* $name:ident [$($path:tt)*]) => {
+        $(#[$attr])*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            (@resolve $d($d state:tt)*) => {
+                $crate::__resolve_paths!(@alias [$($path)*] $d($d state)*)
+            };
+        }
+    };
+}
+
+// Replace each `@ALIAS` at the start of a comma-separated entry with the path the alias stands
+// for, and each group like `inner => { a: 1, b: 2 }` with its entries prefixed by the path before
+// it, then call `$mac!($($prefix)* { entries })`, or `$mac!(@rest $($prefix)* { entries } rest)`
+// if the entries end with `..rest`.
+// If there aren't any aliases or groups, the entries were invalid to begin with, so this reports
+// an error rather than calling `$mac!` with the same entries again.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __resolve_paths {
+    // write each entry of a group to the finished entries with the group's path in front of it,
+    // one entry at a time; `$stack` holds the rest of each enclosing group, and finally the rest
+    // of the entries after the outermost group
+    (@group $state:tt [$($done:tt)*] [$($path:tt)*] $stack:tt
+        $($props:tt)=>+ : | $prev:pat_param | => $val:expr $(=> let $name:pat_param)?
+        $(, $($group:tt)*)?) => {
+        $crate::__resolve_paths!(@group $state
+            [$($done)* $($path)* => $($props)=>+ : | $prev | => $val $(=> let $name)? ,]
+            [$($path)*] $stack
+            $($($group)*)?)
+    };
+    (@group $state:tt [$($done:tt)*] [$($path:tt)*] $stack:tt
+        $($props:tt)=>+ : | $prev:tt : $ty:ty | => $val:expr $(=> let $name:pat_param)?
+        $(, $($group:tt)*)?) => {
+        $crate::__resolve_paths!(@group $state
+            [$($done)* $($path)* => $($props)=>+ : | $prev: $ty | => $val $(=> let $name)? ,]
+            [$($path)*] $stack
+            $($($group)*)?)
+    };
+    (@group $state:tt [$($done:tt)*] [$($path:tt)*] $stack:tt
+        $($props:tt)=>+ : $val:expr $(=> let $name:pat_param)? $(, $($group:tt)*)?) => {
+        $crate::__resolve_paths!(@group $state
+            [$($done)* $($path)* => $($props)=>+ : $val $(=> let $name)? ,] [$($path)*] $stack
+            $($($group)*)?)
+    };
+    // an entry without a value must end with a nested group
+    (@group $state:tt $done:tt $path:tt [$($stack:tt)*]
+        $($props:tt)=>+ $(, $($group:tt)*)?) => {
+        $crate::__resolve_paths!(@nested $state $done $path
+            [($path ($($($group)*)?)) $($stack)*] [] $($props)=>+)
+    };
+    // the end of a group
+    (@group ($mac:ident $prefix:tt $found:tt) $done:tt $path:tt [(@main ($($rest:tt)*))]) => {
+        $crate::__resolve_paths!(($mac $prefix (found)) $done [] $($rest)*)
+    };
+    (@group $state:tt $done:tt $path:tt [($outer:tt ($($group:tt)*)) $($stack:tt)*]) => {
+        $crate::__resolve_paths!(@group $state $done $outer [$($stack)*] $($group)*)
+    };
+    (@nested $state:tt $done:tt [$($path:tt)*] $stack:tt [$($cur:tt)*] => {$($group:tt)*}) => {
+        $crate::__resolve_paths!(@group $state $done [$($path)* => $($cur)*] $stack $($group)*)
+    };
+    (@nested $state:tt $done:tt $path:tt $stack:tt [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__resolve_paths!(@nested $state $done $path $stack [$($cur)* $next] $($rest)*)
+    };
+    (@nested $state:tt $done:tt $path:tt $stack:tt [$($cur:tt)*]) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a value or a group of fields after `",
+            ::core::stringify!($($cur)*),
+            "`",
+        ))
+    };
+    (@alias [$($path:tt)*] ($mac:ident ($($prefix:tt)*) $found:tt) [$($done:tt)*] [] $($rest:tt)*) => {
+        $crate::__resolve_paths!(($mac ($($prefix)*) (found)) [$($done)*] [$($path)*] $($rest)*)
+    };
+    (($mac:ident ($($prefix:tt)*) $found:tt) [$($done:tt)*] [] .. $($rest:tt)*) => {
+        $crate::$mac!(@rest $($prefix)* { $($done)* } $($rest)*)
+    };
+    ($state:tt [$($done:tt)*] [] @ $alias:ident $($rest:tt)*) => {
+        $alias!(@resolve $state [$($done)*] [] $($rest)*)
+    };
+    ($state:tt $done:tt [$($cur:tt)+] => {$($group:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__resolve_paths!(@group $state $done [$($cur)+] [(@main ($($($rest)*)?))]
+            $($group)*)
+    };
+    ($state:tt [$($done:tt)*] [$($cur:tt)*] , $($rest:tt)*) => {
+        $crate::__resolve_paths!($state [$($done)* $($cur)* ,] [] $($rest)*)
+    };
+    ($state:tt [$($done:tt)*] [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__resolve_paths!($state [$($done)*] [$($cur)* $next] $($rest)*)
+    };
+    (($mac:ident ($($prefix:tt)*) (found)) [$($done:tt)*] [$($cur:tt)*]) => {
+        $crate::$mac!($($prefix)* { $($done)* $($cur)* })
+    };
+    (($mac:ident ($($prefix:tt)*) ()) [$($done:tt)*] [$($cur:tt)*]) => {
+        ::core::compile_error!(
+            "expected field paths separated by `=>`, or aliases defined with `field_path!`"
+        )
+    };
+}
+
+///```compile_fail
+/// use project_uninit::{field_path, project_uninit_mut};
+/// struct Foo { a: (u8, u8) }
+/// field_path!(FIRST = a => 0);
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (first, again) = project_uninit_mut!(x => { @FIRST, a => 0 });
+///```
+fn _alias_and_path_to_same_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::{field_path, partial_init};
+/// struct Foo { a: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// partial_init!(x => @MISSING = 1);
+///```
+fn _undefined_alias_fails() {}
+
+// Collect the field paths of the entries of a `partial_init!` body, then call
+// `$mac!($($before)* [$([paths])*] $($after)*)`, or `$other_mac!($($other)*)` if the body has
+// entries other than field paths with values, like groups, aliases or `..rest`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __entry_paths {
+    ($then:tt $other:tt [$($done:tt)*]
+        $($props:tt)=>+ : | $prev:pat_param | => $val:expr $(=> let $name:pat_param)?
+        $(, $($rest:tt)*)?) => {
+        $crate::__entry_paths!($then $other [$($done)* [$($props)=>+]] $($($rest)*)?)
+    };
+    ($then:tt $other:tt [$($done:tt)*]
+        $($props:tt)=>+ : | $prev:tt : $ty:ty | => $val:expr $(=> let $name:pat_param)?
+        $(, $($rest:tt)*)?) => {
+        $crate::__entry_paths!($then $other [$($done)* [$($props)=>+]] $($($rest)*)?)
+    };
+    ($then:tt $other:tt [$($done:tt)*]
+        $($props:tt)=>+ : $val:expr $(=> let $name:pat_param)? $(, $($rest:tt)*)?) => {
+        $crate::__entry_paths!($then $other [$($done)* [$($props)=>+]] $($($rest)*)?)
+    };
+    (($mac:ident ($($before:tt)*) ($($after:tt)*)) $other:tt $done:tt) => {
+        $crate::$mac!($($before)* $done $($after)*)
+    };
+    ($then:tt ($other_mac:ident $($other:tt)*) $done:tt $($entries:tt)*) => {
+        $crate::$other_mac!($($other)*)
+    };
+}
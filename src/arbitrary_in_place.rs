@@ -0,0 +1,72 @@
+use core::mem::MaybeUninit;
+
+use ::arbitrary::{Result, Unstructured};
+
+use crate::array::PrefixDropGuard;
+
+/// A type that can be generated from fuzzer input directly inside a `MaybeUninit<_>` slot.
+///
+/// This can be derived with `#[derive(ArbitraryInPlace)]` for structs that also derive
+/// `ProjectUninit`. Each field is generated with [`Arbitrary`](::arbitrary::Arbitrary), in
+/// declaration order, unless it is
+/// marked `#[uninit(in_place)]`, in which case it is generated in place with `ArbitraryInPlace`.
+/// If generating a field fails, the fields that were already initialized are dropped.
+///
+/// ## Safety
+/// When `arbitrary_in_place` returns `Ok`, `slot` must be fully initialized and the returned
+/// reference must point to it.
+pub unsafe trait ArbitraryInPlace<'a>: Sized {
+    /// Initialize `slot` from the fuzzer input.
+    fn arbitrary_in_place<'s>(
+        slot: &'s mut MaybeUninit<Self>,
+        u: &mut Unstructured<'a>,
+    ) -> Result<&'s mut Self>;
+}
+
+unsafe impl<'a, T: ArbitraryInPlace<'a>, const N: usize> ArbitraryInPlace<'a> for [T; N] {
+    fn arbitrary_in_place<'s>(
+        slot: &'s mut MaybeUninit<Self>,
+        u: &mut Unstructured<'a>,
+    ) -> Result<&'s mut Self> {
+        let ptr = slot.as_mut_ptr() as *mut T;
+        let mut guard = PrefixDropGuard { ptr, len: 0 };
+        while guard.len < N {
+            let elem = unsafe { &mut *(ptr.add(guard.len) as *mut MaybeUninit<T>) };
+            T::arbitrary_in_place(elem, u)?;
+            guard.len += 1;
+        }
+        core::mem::forget(guard);
+        Ok(unsafe { &mut *slot.as_mut_ptr() })
+    }
+}
+
+/// Generate a value of type `T` from fuzzer input directly inside `slot`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::arbitrary::Unstructured;
+/// use project_uninit::{arbitrary_in_place, ArbitraryInPlace, ProjectUninit};
+///
+/// #[derive(ProjectUninit, ArbitraryInPlace)]
+/// struct Request {
+///     id: u32,
+///     flags: [bool; 4],
+///     #[uninit(in_place)]
+///     body: Body,
+/// }
+///
+/// #[derive(ProjectUninit, ArbitraryInPlace)]
+/// struct Body { len: u16, data: [u8; 1024] }
+///
+/// let mut slot = Box::new(MaybeUninit::<Request>::uninit());
+/// let mut u = Unstructured::new(&[1, 2, 3, 4, 5, 6, 7, 8]);
+/// let request = arbitrary_in_place(&mut slot, &mut u).unwrap();
+/// assert_eq!(request.id, 0x04030201);
+/// ```
+pub fn arbitrary_in_place<'a, 's, T: ArbitraryInPlace<'a>>(
+    slot: &'s mut MaybeUninit<T>,
+    u: &mut Unstructured<'a>,
+) -> Result<&'s mut T> {
+    T::arbitrary_in_place(slot, u)
+}
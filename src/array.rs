@@ -0,0 +1,93 @@
+use core::mem::{size_of, MaybeUninit};
+
+// Drops the first `len` elements starting at `ptr` unless forgotten.
+pub(crate) struct PrefixDropGuard<T> {
+    pub(crate) ptr: *mut T,
+    pub(crate) len: usize,
+}
+
+impl<T> Drop for PrefixDropGuard<T> {
+    fn drop(&mut self) {
+        unsafe { core::ptr::slice_from_raw_parts_mut(self.ptr, self.len).drop_in_place() };
+        crate::poison::deinit(self.ptr as *mut u8, self.len * size_of::<T>());
+    }
+}
+
+// Initialize `len` consecutive elements starting at `ptr` with `f(index)`, dropping the elements
+// written so far if `f` panics.
+pub(crate) unsafe fn init_elements<T>(ptr: *mut T, len: usize, mut f: impl FnMut(usize) -> T) {
+    let mut guard = PrefixDropGuard { ptr, len: 0 };
+    while guard.len < len {
+        ptr.add(guard.len).write(f(guard.len));
+        guard.len += 1;
+    }
+    core::mem::forget(guard);
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_init(ptr as *const u8, len * size_of::<T>());
+}
+
+/// Initialize an array in place, element by element, with `f(index)`.
+///
+/// Unlike building the array with [`core::array::from_fn`] and moving it into place, this never
+/// holds the whole array on the stack, so it can fill large arrays inside a `Box` or a struct.
+/// If `f` panics, the elements initialized so far are dropped.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::init_array;
+///
+/// let mut table = Box::new(MaybeUninit::<[u64; 1 << 16]>::uninit());
+/// let table = init_array(&mut table, |i| (i as u64) * (i as u64));
+/// assert_eq!(table[300], 90000);
+/// ```
+pub fn init_array<T, const N: usize>(
+    slot: &mut MaybeUninit<[T; N]>,
+    f: impl FnMut(usize) -> T,
+) -> &mut [T; N] {
+    unsafe {
+        init_elements(slot.as_mut_ptr() as *mut T, N, f);
+        &mut *slot.as_mut_ptr()
+    }
+}
+
+/// Initialize an array wrapped in `MaybeUninit`, or an array field of a struct wrapped in
+/// `MaybeUninit`, element by element, with a closure of the element's index.
+///
+/// This is [`init_array`] for anything that borrows as a `MaybeUninit<[T; N]>`, like a `Box` or
+/// a `Vec` element, or for a field given by a path as in
+/// [`project_uninit_mut!`](crate::project_uninit_mut). If the closure panics, the elements
+/// initialized so far are dropped before unwinding, and the array is left uninitialized.
+///
+/// Returns a `&mut [T; N]` to the initialized array.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_array_with, partial_init};
+///
+/// struct Mixer { channels: [Vec<f32>; 8], gain: f32 }
+///
+/// let mut mixer = Box::new(MaybeUninit::<Mixer>::uninit());
+/// let channels = init_array_with!(mixer => channels, |i| vec![0.0; 64 * (i + 1)]);
+/// assert_eq!(channels[7].len(), 512);
+/// partial_init!(mixer => gain = 1.0);
+///
+/// let mut squares = MaybeUninit::<[u32; 16]>::uninit();
+/// assert_eq!(init_array_with!(squares, |i| (i * i) as u32)[15], 225);
+/// ```
+#[macro_export]
+macro_rules! init_array_with {
+    // initialize an array field
+    ($expr:expr => $($props:tt)=>+, |$i:pat_param| $body:expr $(,)?) => {
+        $crate::init_array($crate::project_uninit_mut!($expr => $($props)=>+), |$i| $body)
+    };
+
+    // initialize the whole array
+    ($expr:expr, |$i:pat_param| $body:expr $(,)?) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        $crate::init_array(_ref, |$i| $body)
+    }};
+}
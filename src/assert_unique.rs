@@ -1,5 +1,5 @@
 
-// Assert that $a is not a prefix to any of the token sequences in $b
+// Assert that $a is not a prefix to any of the token sequences in $b, other than itself
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __assert_not_from_self {
@@ -9,60 +9,54 @@ macro_rules! __assert_not_from_self {
         [$($b:tt)*],
     // $d should be the '$' symbol
     $d:tt) => {{
-        // define a macro that errors when the given tokens start with $a:
+        // define a macro that counts the given tokens if they equal $a, and errors if they
+        // start with $a:
         macro_rules! __fail_if_starts_with {
             // $a and $b are equal:
-            ([$($a)+]) => {
-                compile_error!(concat!(
-                    "Cannot mutably borrow '",
-                    stringify!($head),
-                    concat!(".", $(stringify!($a)),+),
-                    "' more than once at a time",
-                ));
-            };
+            ([$($a)+]) => { 1 };
             // $a is a prefix to $b
-            ([$($a)+$d($d else:tt)+]) => {
-                compile_error!(concat!(
+            ([$($a)+$d($d else:tt)+]) => {{
+                ::core::compile_error!(::core::concat!(
                     "Cannot mutably borrow '",
-                    stringify!($head),
-                    concat!(".", $(stringify!($a)),+),
-                    concat!($d(stringify!($d else)),+),
-                    "' and and its parent '",
-                    stringify!($head),
-                    concat!(".", $(stringify!($a)),+),
+                    ::core::stringify!($head),
+                    ::core::concat!(".", $(::core::stringify!($a)),+),
+                    ::core::concat!($d(::core::stringify!($d else)),+),
+                    "' and its parent '",
+                    ::core::stringify!($head),
+                    ::core::concat!(".", $(::core::stringify!($a)),+),
                     "' at the same time.",
                 ));
-            };
-            ($d else:tt) => {};
+                0
+            }};
+            ($d else:tt) => { 0 };
         }
-        // run this macro on every string in $b
-        $(__fail_if_starts_with!($b);)*
+        // run this macro on every string in $b, which includes $a itself once
+        const _: () = if 0 $(+ __fail_if_starts_with!($b))* > 1 {
+            ::core::panic!(::core::concat!(
+                "Cannot mutably borrow '",
+                ::core::stringify!($head),
+                ::core::concat!(".", $(::core::stringify!($a)),+),
+                "' more than once at a time",
+            ));
+        };
     }};
 }
 
+// This compares every pair of paths, so with the `derive` feature it's replaced by a proc macro
+// that sorts them instead, and points its errors at the offending path.
+#[cfg(not(feature = "derive"))]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __assert_unique {
-    // __assert_unique!(@inner ... ) tests each field path against every other field used in the macro
-    // invocation.
+    // test each field path against every field path used in the macro invocation, including
+    // itself.
     // $head is the expression for the MaybeUninit that we're projecting into
-    // current represents the field path we're currently testing against the others
-    // [$prev...] are the field paths that come before
-    // [$next $rest...] are the field paths that come after
-    (@inner $head:expr, $current:tt, [$($prev:tt)*], [$next:tt $($rest:tt)*] ) => {
-        // test $current against every path in { $prev, $next, $rest }
-        $crate::__assert_not_from_self!($head, $current, [$($prev)* $next $($rest)*], $);
-        // move $current to $prev, $next to $current
-        $crate::__assert_unique!(@inner $head, $next, [$($prev)* $current], [$($rest)*]);
-    };
-    // $current is the last field path we need to check, so only test against prev and don't recurse
-    (@inner $head:expr, $current:tt, [$($prev:tt)*], [] ) => {
-        $crate::__assert_not_from_self!($head, $current, [$($prev)*], $);
+    // $all is the list of every field path
+    // This doesn't recurse, so it doesn't add to the macro recursion depth for wide structs.
+    (@each $head:expr, [$($path:tt)*], $all:tt) => {
+        $( $crate::__assert_not_from_self!($head, $path, $all, $); )*
     };
-    // there is more than one field path; recurse into (@inner...)
-    ($head:expr, [$first:tt $($rest:tt)+]) => {
-        $crate::__assert_unique!(@inner $head, $first, [], [$($rest)*]);
+    ($head:expr, [$($path:tt)*]) => {
+        $crate::__assert_unique!(@each $head, [$($path)*], [$($path)*]);
     };
-    // there is at most one field path
-    ($head:expr, [$($first:tt)?]) => { /* no problem */ };
 }
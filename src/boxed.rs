@@ -0,0 +1,529 @@
+use alloc::alloc::{alloc, handle_alloc_error, Layout};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::{Arc, Weak};
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+/// A smart pointer that owns a heap allocation of a `MaybeUninit<T>`.
+///
+/// This is implemented for `Box<MaybeUninit<T>>`, `Rc<MaybeUninit<T>>` and
+/// `Arc<MaybeUninit<T>>`, so code can be generic over the pointer type. The `new_uninit` and
+/// `assume_init` methods of each pointer type are inherent methods, with no trait to abstract
+/// over them.
+///
+/// A `Box<MaybeUninit<_>>` can be passed directly to the macros in this crate. An `Rc` or
+/// `Arc` can be passed as [`slot_mut`](UninitAlloc::slot_mut), which panics if the allocation
+/// is shared.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use std::rc::Rc;
+/// use project_uninit::{partial_init, UninitAlloc};
+///
+/// struct Config { name: &'static str, retries: u32 }
+///
+/// let mut config = Rc::<MaybeUninit<Config>>::uninit();
+/// partial_init!(config.slot_mut() => { name: "db", retries: 3 });
+/// let config: Rc<Config> = unsafe { UninitAlloc::assume_init(config) };
+/// assert_eq!(config.retries, 3);
+/// ```
+pub trait UninitAlloc<T>: Sized {
+    /// The pointer to the initialized value.
+    type Init;
+
+    /// Allocate an uninitialized `T` on the heap, without building it on the stack first.
+    fn uninit() -> Self;
+
+    /// Get a mutable reference to the uninitialized value.
+    ///
+    /// ## Panics
+    /// If the allocation is shared with another `Rc` or `Arc`.
+    fn slot_mut(&mut self) -> &mut MaybeUninit<T>;
+
+    /// Convert to a pointer to the initialized value.
+    ///
+    /// ## Safety
+    /// The value must be fully initialized.
+    unsafe fn assume_init(self) -> Self::Init;
+}
+
+impl<T> UninitAlloc<T> for Box<MaybeUninit<T>> {
+    type Init = Box<T>;
+
+    fn uninit() -> Self {
+        boxed_uninit()
+    }
+
+    fn slot_mut(&mut self) -> &mut MaybeUninit<T> {
+        self
+    }
+
+    unsafe fn assume_init(self) -> Box<T> {
+        Box::from_raw(Box::into_raw(self) as *mut T)
+    }
+}
+
+impl<T> UninitAlloc<T> for Rc<MaybeUninit<T>> {
+    type Init = Rc<T>;
+
+    fn uninit() -> Self {
+        rc_uninit()
+    }
+
+    fn slot_mut(&mut self) -> &mut MaybeUninit<T> {
+        Rc::get_mut(self).expect("the uninitialized value is shared with another Rc")
+    }
+
+    unsafe fn assume_init(self) -> Rc<T> {
+        Rc::<MaybeUninit<T>>::assume_init(self)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> UninitAlloc<T> for Arc<MaybeUninit<T>> {
+    type Init = Arc<T>;
+
+    fn uninit() -> Self {
+        arc_uninit()
+    }
+
+    fn slot_mut(&mut self) -> &mut MaybeUninit<T> {
+        Arc::get_mut(self).expect("the uninitialized value is shared with another Arc")
+    }
+
+    unsafe fn assume_init(self) -> Arc<T> {
+        Arc::<MaybeUninit<T>>::assume_init(self)
+    }
+}
+
+/// Allocate an uninitialized `T` in a `Box`, without building it on the stack first.
+pub fn boxed_uninit<T>() -> Box<MaybeUninit<T>> {
+    let layout = Layout::new::<T>();
+    if layout.size() == 0 {
+        // not `Box::new`, which reserves stack space for a `T` in debug builds even when this
+        // branch isn't taken
+        return unsafe { Box::from_raw(NonNull::dangling().as_ptr()) };
+    }
+    let ptr = unsafe { alloc(layout) } as *mut MaybeUninit<T>;
+    let mut slot = match NonNull::new(ptr) {
+        Some(ptr) => unsafe { Box::from_raw(ptr.as_ptr()) },
+        None => handle_alloc_error(layout),
+    };
+    crate::poison::poison_new(&mut slot);
+    slot
+}
+
+/// Construct a value in a `Box` by initializing it in place with a closure, without building it
+/// on the stack first.
+///
+/// This works like [`init_with`](crate::init_with): `init` is given the uninitialized heap
+/// slot, and must return a reference to that same slot, initialized. If `init` panics, the
+/// allocation is freed, and the fields it initialized are leaked.
+///
+/// ## Panics
+/// If `init` returns a reference to something other than the slot it was given.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{boxed_init_with, tracked_init, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Frame { pixels: Vec<u32>, width: usize, height: usize }
+///
+/// let frame = boxed_init_with::<Frame>(|slot| {
+///     let mut tracker = InitTracker::new(slot);
+///     tracked_init!(tracker => { width: 640, height: 480 });
+///     tracked_init!(tracker => pixels = vec![0; 640 * 480]);
+///     tracker.finish().ok().unwrap()
+/// });
+/// assert_eq!(frame.pixels.len(), frame.width * frame.height);
+/// # }
+/// ```
+pub fn boxed_init_with<T>(
+    init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+) -> Box<T> {
+    let mut slot = boxed_uninit();
+    crate::init_with::init_slot(&mut slot, init);
+    unsafe { UninitAlloc::assume_init(slot) }
+}
+
+/// Allocate an uninitialized `T` in an `Rc`, without building it on the stack first.
+pub fn rc_uninit<T>() -> Rc<MaybeUninit<T>> {
+    let mut slot = Rc::new_uninit();
+    // a fresh `Rc` is never shared
+    if let Some(slot) = Rc::get_mut(&mut slot) {
+        crate::poison::poison_new(slot);
+    }
+    slot
+}
+
+/// Allocate an uninitialized `T` in an `Arc`, without building it on the stack first.
+#[cfg(target_has_atomic = "ptr")]
+pub fn arc_uninit<T>() -> Arc<MaybeUninit<T>> {
+    let mut slot = Arc::new_uninit();
+    // a fresh `Arc` is never shared
+    if let Some(slot) = Arc::get_mut(&mut slot) {
+        crate::poison::poison_new(slot);
+    }
+    slot
+}
+
+/// Construct a value in an `Arc` that can hold `Weak` pointers to itself, by initializing it in
+/// place with a closure, as with [`Arc::new_cyclic`].
+///
+/// `init` is given a `Weak` pointer to the allocation, which can be cloned into the value, and
+/// the uninitialized slot, and must return a reference to that same slot, initialized, as with
+/// [`init_with`](crate::init_with). The `Weak` pointer can't be upgraded until this returns.
+/// If `init` panics, the fields it initialized are leaked, and the allocation is freed once no
+/// `Weak` pointer to it is left.
+///
+/// The slot is in the allocation itself, but `Arc::new_cyclic` then copies the value onto
+/// itself, through the stack, so this isn't suited to structs too large for the stack; use
+/// [`arc_uninit`] for those, if they don't need to point to themselves.
+///
+/// This relies on how the standard library currently implements `Arc::new_cyclic`: the
+/// allocation already exists, at the address of the `Weak` pointer, while the closure runs, and
+/// nothing else reads or writes the value until the closure's result is written to it. The
+/// standard library doesn't document this, so it's covered by this crate's tests.
+///
+/// ## Panics
+/// If `init` returns a reference to something other than the slot it was given.
+///
+/// ## Example
+/// ```
+/// use std::sync::{Arc, Mutex, Weak};
+/// use project_uninit::{arc_emplace_cyclic, partial_init};
+///
+/// struct Node { name: String, parent: Option<Weak<Node>>, children: Mutex<Vec<Arc<Node>>> }
+///
+/// let root = arc_emplace_cyclic(|this: &Weak<Node>, slot| {
+///     let child = Arc::new(Node {
+///         name: "leaf".into(),
+///         parent: Some(this.clone()),
+///         children: Mutex::new(Vec::new()),
+///     });
+///     // the parent can't be reached through `this` until it's initialized
+///     assert!(this.upgrade().is_none());
+///     partial_init!(slot => {
+///         name: "root".into(),
+///         parent: None,
+///         children: Mutex::new(vec![child]),
+///     });
+///     unsafe { slot.assume_init_mut() }
+/// });
+///
+/// let children = root.children.lock().unwrap();
+/// let parent = children[0].parent.as_ref().unwrap().upgrade().unwrap();
+/// assert!(Arc::ptr_eq(&parent, &root));
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+pub fn arc_emplace_cyclic<T>(
+    init: impl for<'s> FnOnce(&Weak<T>, &'s mut MaybeUninit<T>) -> &'s mut T,
+) -> Arc<T> {
+    let arc = Arc::<MaybeUninit<T>>::new_cyclic(|weak| {
+        // `MaybeUninit<T>` has the same layout as `T`
+        let this = unsafe { Weak::from_raw(Weak::into_raw(weak.clone()) as *const T) };
+        // the allocation exists while `weak` does, though it can't be upgraded yet
+        let slot = unsafe { &mut *(weak.as_ptr() as *mut MaybeUninit<T>) };
+        crate::poison::poison_new(slot);
+        crate::init_with::init_slot(slot, |slot| init(&this, slot));
+        // `new_cyclic` writes back whatever is returned, so return the value as it is
+        unsafe { core::ptr::read(slot) }
+    });
+    unsafe { UninitAlloc::assume_init(arc) }
+}
+
+/// Allocate a struct on the heap in a `Box`, `Rc` or `Arc` and initialize its fields in place.
+///
+/// Every field of the struct must be given a value, which is checked at compile time, so the
+/// struct is never built on the stack as a whole and then moved to the heap, as it is with
+/// `Box::new`, which can overflow the stack for large structs.
+/// The fields are written in the order they are given, as with
+/// [`partial_init!`](crate::partial_init). If a value panics, the allocation is freed without
+/// dropping any field.
+///
+/// ## Example
+/// ```
+/// use std::sync::Arc;
+/// use project_uninit::alloc_init;
+///
+/// struct Frame { id: u32, pixels: [u8; 4096] }
+///
+/// let frame: Box<Frame> = alloc_init!(Box, Frame => { id: 1, pixels: [0; 4096] });
+/// assert_eq!(frame.pixels.len(), 4096);
+///
+/// let shared: Arc<Frame> = alloc_init!(Arc, Frame => { id: 2, pixels: [0xff; 4096] });
+/// assert_eq!(shared.pixels[0], 0xff);
+/// ```
+#[macro_export]
+macro_rules! alloc_init {
+    ($($ptr:ident)::+, $($ty:ident)::+ => {$($field:ident : $val:expr),* $(,)?}) => {{
+        let mut slot: $($ptr)::+<::core::mem::MaybeUninit<_>> = $crate::UninitAlloc::uninit();
+        if false {
+            // this will never be executed
+            // it's only to infer the type of the slot and assert that every field is given a value
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
+            {
+                *$crate::UninitAlloc::slot_mut(&mut slot) = ::core::mem::MaybeUninit::new($($ty)::+ {
+                    $($field: ::core::unreachable!()),*
+                });
+            }
+        }
+        $crate::partial_init!($crate::UninitAlloc::slot_mut(&mut slot) => { $($field: $val),* });
+        #[allow(unused_unsafe)]
+        unsafe {
+            $crate::UninitAlloc::assume_init(slot)
+        }
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::alloc_init;
+/// struct Node { kind: u8, span: (u32, u32) }
+/// let _ = alloc_init!(Box, Node => { kind: 1 });
+///```
+fn _alloc_init_missing_field_fails() {}
+
+/// Construct a struct in a `Box` by initializing its fields in place with a block of statements,
+/// and return the box once every field is known to be initialized.
+///
+/// The block is given the uninitialized heap slot as `&mut MaybeUninit<T>`, so the struct is
+/// never built on the stack, in debug builds either. Only each value given to a field is, so a
+/// large array field should be written with [`init_array_with!`](crate::init_array_with).
+/// The type of the struct must be known before the block, so it's given either as the type of
+/// the slot, or before the block: `box_emplace!(Config, |slot| { ... })`.
+///
+/// The struct must derive [`ProjectUninit`](crate::ProjectUninit). A field counts as initialized when it's the whole path given to a
+/// [`partial_init!`](crate::partial_init) or [`init_array_with!`](crate::init_array_with) on the
+/// slot in a statement at the top level of the block, since those always run before the block
+/// ends. Every field must be counted, which is checked at compile time, so a field that is
+/// only written in a loop or a branch, or through a nested path, must be written again at the
+/// top level. The block can't `return` early. The counted statements may name the macros as
+/// `partial_init!` or `project_uninit::partial_init!`, and always expand to the ones from this
+/// crate.
+///
+/// If the block panics, the allocation is freed, and the fields it initialized are leaked.
+///
+/// ## Panics
+/// If a counted statement is given something other than the slot.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{box_emplace, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Config { name: String, limits: [u64; 1 << 18], verbose: bool }
+///
+/// let config = box_emplace!(Config, |slot| {
+///     partial_init!(slot => { name: "server".into(), verbose: false });
+///     init_array_with!(slot => limits, |i| i as u64 * 2);
+/// });
+/// assert_eq!(config.limits[1000], 2000);
+/// // without the `init_array_with!` statement this doesn't compile
+/// # }
+/// ```
+#[macro_export]
+macro_rules! box_emplace {
+    (|$slot:ident $(: $ty:ty)?| { $($body:tt)* }) => {
+        $crate::utils::box_emplace(|$slot $(: $ty)?| {
+            let ptr: *const ::core::mem::MaybeUninit<_> = &*$slot;
+            $crate::box_emplace!(@stmt ($slot ptr) [] [] $($body)*)
+        })
+    };
+    ($ty:ty, |$slot:ident| { $($body:tt)* }) => {
+        $crate::box_emplace!(|$slot: &mut ::core::mem::MaybeUninit<$ty>| { $($body)* })
+    };
+
+    // at the start of a statement
+    (@stmt ($slot:ident $ptr:ident) [$($names:tt)*] [$($out:tt)*]
+        $($(::)? project_uninit ::)? partial_init ! ( $s:ident => $($args:tt)* )
+        $(; $($rest:tt)*)?
+    ) => {
+        $crate::box_emplace!(@partial_init ($slot $ptr) [$($names)*] [$($out)*
+            $crate::partial_init!($crate::utils::emplace_slot($s, $ptr) => $($args)*);
+        ] ($($args)*) $($($rest)*)?)
+    };
+    (@stmt ($slot:ident $ptr:ident) [$($names:tt)*] [$($out:tt)*]
+        $($(::)? project_uninit ::)? init_array_with ! ( $s:ident => $($args:tt)* )
+        $(; $($rest:tt)*)?
+    ) => {
+        $crate::box_emplace!(@init_array_with ($slot $ptr) [$($names)*] [$($out)*
+            $crate::init_array_with!($crate::utils::emplace_slot($s, $ptr) => $($args)*);
+        ] ($($args)*) $($($rest)*)?)
+    };
+    (@stmt $vars:tt $names:tt $out:tt $($rest:tt)*) => {
+        $crate::box_emplace!(@skip $vars $names $out $($rest)*)
+    };
+
+    // get the paths initialized by a counted statement
+    (@partial_init $vars:tt [$($names:tt)*] $out:tt
+        ($($mode:ident)? {$($body:tt)*}) $($rest:tt)*
+    ) => {
+        $crate::__entry_paths!((box_emplace (@stmt $vars) ($out $($rest)*))
+            (box_emplace @stmt $vars [$($names)*] $out $($rest)*) [$($names)*] $($body)*)
+    };
+    (@partial_init $vars:tt [$($names:tt)*] $out:tt
+        (pub $($props:tt)=>+ = $val:expr) $($rest:tt)*
+    ) => {
+        $crate::box_emplace!(@stmt $vars [$($names)* [$($props)=>+]] $out $($rest)*)
+    };
+    (@partial_init $vars:tt [$($names:tt)*] $out:tt
+        (strict $($props:tt)=>+ = $val:expr) $($rest:tt)*
+    ) => {
+        $crate::box_emplace!(@stmt $vars [$($names)* [$($props)=>+]] $out $($rest)*)
+    };
+    (@partial_init $vars:tt [$($names:tt)*] $out:tt
+        ($($props:tt)=>+ = $val:expr) $($rest:tt)*
+    ) => {
+        $crate::box_emplace!(@stmt $vars [$($names)* [$($props)=>+]] $out $($rest)*)
+    };
+    (@init_array_with $vars:tt [$($names:tt)*] $out:tt
+        ($($props:tt)=>+ , $($init:tt)*) $($rest:tt)*
+    ) => {
+        $crate::box_emplace!(@stmt $vars [$($names)* [$($props)=>+]] $out $($rest)*)
+    };
+    // other forms, like struct update, aren't counted
+    (@$mac:ident $vars:tt $names:tt $out:tt ($($args:tt)*) $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names $out $($rest)*)
+    };
+
+    // skip to the end of the statement, a few tokens at a time to keep the recursion shallow
+    // a statement ends after `;`, or may end after a block, as with loops
+    (@skip $vars:tt $names:tt [$($out:tt)*] ; $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* ;] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] {$($block:tt)*} $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* {$($block)*}] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt ; $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a ;] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt {$($block:tt)*} $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a {$($block)*}] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt $b:tt ; $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a $b ;] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt $b:tt {$($block:tt)*} $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a $b {$($block)*}] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt $b:tt $c:tt ; $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a $b $c ;] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt $b:tt $c:tt {$($block:tt)*} $($rest:tt)*) => {
+        $crate::box_emplace!(@stmt $vars $names [$($out)* $a $b $c {$($block)*}] $($rest)*)
+    };
+    (@skip $vars:tt $names:tt [$($out:tt)*] $a:tt $b:tt $c:tt $d:tt $($rest:tt)+) => {
+        $crate::box_emplace!(@skip $vars $names [$($out)* $a $b $c $d] $($rest)+)
+    };
+
+    // the end of the block
+    (@skip ($slot:ident $ptr:ident) [$($names:tt)*] [$($out:tt)*] $($last:tt)*) => {{
+        // assert that every field is initialized by a counted statement
+        $crate::utils::check_all_fields($slot, |fields, state| {
+            $( $crate::box_emplace!(@set fields state $names); )*
+            state
+        });
+
+        #[allow(clippy::unused_unit)]
+        let _ = { $($out)* $($last)* };
+        // only reached once every counted statement has run
+        #[allow(unreachable_code, unused_unsafe)]
+        unsafe {
+            $crate::utils::EmplaceProof::new()
+        }
+    }};
+
+    // only whole fields are counted
+    (@set $fields:ident $state:ident [$field:tt]) => {
+        let $state = $crate::utils::set_field($state, $fields.$field);
+    };
+    (@set $fields:ident $state:ident [$($props:tt)*]) => {};
+}
+
+///```compile_fail
+/// use project_uninit::{box_emplace, partial_init, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Node { kind: u8, span: (u32, u32) }
+/// let _ = box_emplace!(Node, |slot| {
+///     partial_init!(slot => kind = 1);
+///     if true {
+///         partial_init!(slot => span = (0, 1));
+///     }
+/// });
+///```
+fn _box_emplace_missing_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::{box_emplace, partial_init, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Node { kind: u8, span: (u32, u32) }
+/// let _ = box_emplace!(Node, |slot| {
+///     partial_init!(slot => { kind: 1, span => 0: 0 });
+///     partial_init!(slot => span => 1 = 1);
+/// });
+///```
+fn _box_emplace_nested_field_fails() {}
+
+///```compile_fail,E0308
+/// use project_uninit::{box_emplace, partial_init, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Node { kind: u8, span: (u32, u32) }
+/// // checked while type checking, so even if this is never called
+/// fn _node() -> Box<Node> {
+///     box_emplace!(Node, |slot| {
+///         partial_init!(slot => kind = 1);
+///     })
+/// }
+///```
+#[cfg(feature = "derive")]
+fn _box_emplace_missing_field_in_unused_fn_fails() {}
+
+///```compile_fail
+/// use project_uninit::{box_emplace, partial_init, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Node { kind: u8 }
+/// let _ = box_emplace!(Node, |slot| {
+///     return;
+///     partial_init!(slot => kind = 1);
+/// });
+///```
+fn _box_emplace_return_fails() {}
+
+/// Proof that the block given to [`box_emplace!`] reached its end.
+#[doc(hidden)]
+pub struct EmplaceProof(());
+
+impl EmplaceProof {
+    /// ## Safety
+    /// Every field of the slot must be initialized.
+    pub unsafe fn new() -> Self {
+        Self(())
+    }
+}
+
+#[doc(hidden)]
+pub fn box_emplace<T>(init: impl FnOnce(&mut MaybeUninit<T>) -> EmplaceProof) -> Box<T> {
+    let mut slot = boxed_uninit();
+    init(&mut slot);
+    unsafe { UninitAlloc::assume_init(slot) }
+}
+
+/// Checks that a statement counted by [`box_emplace!`] is given the slot, and not another
+/// `MaybeUninit` that shadows its name.
+#[doc(hidden)]
+pub fn emplace_slot<T>(
+    slot: &mut MaybeUninit<T>,
+    ptr: *const MaybeUninit<T>,
+) -> &mut MaybeUninit<T> {
+    assert!(
+        core::ptr::eq(slot, ptr),
+        "box_emplace! statement was given something other than the slot"
+    );
+    slot
+}
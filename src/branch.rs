@@ -0,0 +1,155 @@
+/// Partially initialize a struct wrapped in `MaybeUninit` with a different set of values in each
+/// branch of a `match` or `if`, checking at compile time that every branch initializes the same
+/// fields.
+///
+/// Each branch is written as the body of a [`partial_init!`](crate::partial_init) invocation,
+/// and must give values to the same field paths, in any order, so that no branch forgets a field
+/// the others initialize. Since the fields are known to be initialized whichever branch runs,
+/// mutable references to them are returned, in the order the fields are given in the first
+/// branch.
+///
+/// The scrutinee of `match` and the conditions of `if` must be wrapped in parentheses, and an
+/// `if` must end with an `else` branch:
+/// `partial_init_branch!(x => if (cond) { a: 1, b: 2 } else { b: 3, a: 4 })`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::partial_init_branch;
+///
+/// enum Source { File(&'static str), Stdin, Inline(Vec<u8>) }
+/// struct Input { name: String, buffered: bool, data: Vec<u8> }
+///
+/// fn open(source: Source) -> Input {
+///     let mut input = MaybeUninit::<Input>::uninit();
+///     let (name, _, _) = partial_init_branch!(input => match (source) {
+///         Source::File(path) => { name: path.into(), buffered: true, data: Vec::new() },
+///         Source::Stdin => { name: "-".into(), buffered: false, data: Vec::new() },
+///         Source::Inline(bytes) => { data: bytes, name: "inline".into(), buffered: false },
+///     });
+///     name.make_ascii_uppercase();
+///     unsafe { input.assume_init() }
+/// }
+///
+/// assert_eq!(open(Source::File("a.txt")).name, "A.TXT");
+/// assert_eq!(open(Source::Inline(vec![1, 2])).data, [1, 2]);
+/// // a branch that leaves out `buffered` or initializes another field doesn't compile
+/// ```
+#[macro_export]
+macro_rules! partial_init_branch {
+    ($expr:expr => match ($scrutinee:expr) {
+        $($first_pat:pat_param)|+ $(if $first_guard:expr)? => $first:tt
+        $(, $($pat:pat_param)|+ $(if $guard:expr)? => {$($body:tt)*})* $(,)?
+    }) => {{
+        $($crate::partial_init_branch!(@check $first [$($body)*] $($pat)|+);)*
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        match $scrutinee {
+            $($first_pat)|+ $(if $first_guard)? => {
+                #[allow(clippy::let_unit_value)]
+                let _ = $crate::partial_init!(&mut *_ref => $first);
+            }
+            $($($pat)|+ $(if $guard)? => {
+                #[allow(clippy::let_unit_value)]
+                let _ = $crate::partial_init!(&mut *_ref => {$($body)*});
+            })*
+        }
+        $crate::partial_init_branch!(@refs _ref, $first)
+    }};
+
+    ($expr:expr => if ($first_cond:expr) $first:tt
+        $(else if ($cond:expr) {$($body:tt)*})*
+        else {$($last:tt)*}
+    ) => {{
+        $($crate::partial_init_branch!(@check $first [$($body)*] if $cond);)*
+        $crate::partial_init_branch!(@check $first [$($last)*] else);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        if $first_cond {
+            #[allow(clippy::let_unit_value)]
+            let _ = $crate::partial_init!(&mut *_ref => $first);
+        } $(else if $cond {
+            #[allow(clippy::let_unit_value)]
+            let _ = $crate::partial_init!(&mut *_ref => {$($body)*});
+        })* else {
+            #[allow(clippy::let_unit_value)]
+            let _ = $crate::partial_init!(&mut *_ref => {$($last)*});
+        }
+        $crate::partial_init_branch!(@refs _ref, $first)
+    }};
+
+    // assert that a branch initializes the same fields as the first branch
+    (@check {$($first:tt)*} [$($body:tt)*] $($branch:tt)*) => {
+        const _: () = if !$crate::utils::same_paths(
+            $crate::partial_init_branch!(@paths $($first)*),
+            $crate::partial_init_branch!(@paths $($body)*),
+        ) {
+            ::core::panic!(::core::concat!(
+                "the branch `",
+                ::core::stringify!($($branch)*),
+                "` doesn't initialize the same fields as the first branch",
+            ));
+        };
+    };
+    (@paths $($body:tt)*) => {
+        $crate::__entry_paths!((partial_init_branch (@path_strs) ()) (partial_init_branch @other)
+            [] $($body)*)
+    };
+    (@path_strs [$([$($props:tt)=>+])*]) => {
+        &[$($crate::__path_str!($($props)=>+)),*]
+    };
+    (@other) => {
+        ::core::compile_error!("each branch must give values to field paths, without groups")
+    };
+
+    // every branch initialized these fields
+    (@refs $ref:ident, {$($body:tt)*}) => {
+        $crate::__entry_paths!((partial_init_branch (@field_refs $ref,) ())
+            (partial_init_branch @other) [] $($body)*)
+    };
+    (@field_refs $ref:ident, [$([$($props:tt)=>+])*]) => {{
+        let ptr = $ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt($ref);
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ref = unsafe {
+                $crate::utils::deref_ptr_with_lt(
+                    ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                    lt,
+                )
+            };
+            prop_ref
+        },)*)
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::partial_init_branch;
+/// struct Foo { a: u8, b: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let fast = true;
+/// partial_init_branch!(x => if (fast) { a: 1, b: 2 } else { a: 3 });
+///```
+fn _branch_missing_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init_branch;
+/// struct Foo { a: u8, b: (u8, u8) }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// partial_init_branch!(x => match (3) {
+///     0 => { a: 1, b => 0: 2 },
+///     _ => { a: 1, b => 1: 2 },
+/// });
+///```
+fn _branch_different_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init_branch;
+/// struct Foo { a: u8, b: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let fast = true;
+/// partial_init_branch!(x => if (fast) { a: 1, b: 2 });
+///```
+fn _branch_requires_else() {}
@@ -0,0 +1,82 @@
+/// The state of a field of a builder generated by `#[derive(InitBuilder)]`: either [`Set`] or
+/// [`Unset`].
+///
+/// For a struct `Foo` that also derives `ProjectUninit`, `#[derive(InitBuilder)]` generates a
+/// `FooInit` builder with a type parameter for the state of each field. It has a `set_` method
+/// for each field, with the same visibility as the field, that can only be called while the
+/// field is unset, and a `finish` method that returns the struct once every field is set, so
+/// a struct can't be finished with fields missing. Fields that were set are dropped if the
+/// builder is dropped before it is finished. Fields of tuple structs are named by their index,
+/// as in `set_0`, and the builder of a unit struct can be finished right away.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{InitBuilder, ProjectUninit};
+///
+/// #[derive(ProjectUninit, InitBuilder)]
+/// struct Server { host: String, port: u16 }
+///
+/// let builder = ServerInit::new().set_port(8080);
+/// // `builder.finish()` doesn't compile yet, because `host` isn't set
+/// let server = builder.set_host("localhost".into()).finish();
+/// assert_eq!((server.host.as_str(), server.port), ("localhost", 8080));
+/// # }
+/// ```
+pub trait FieldState {
+    /// Whether the field has been initialized.
+    const SET: bool;
+}
+
+/// A field of a builder generated by `#[derive(InitBuilder)]` that has been initialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Set;
+
+/// A field of a builder generated by `#[derive(InitBuilder)]` that hasn't been initialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Unset;
+
+impl FieldState for Set {
+    const SET: bool = true;
+}
+
+impl FieldState for Unset {
+    const SET: bool = false;
+}
+
+///```compile_fail
+/// use project_uninit::{InitBuilder, ProjectUninit};
+/// #[derive(ProjectUninit, InitBuilder)]
+/// struct Foo { a: u32, b: u32 }
+/// let foo = FooInit::new().set_a(1).finish();
+///```
+fn _finish_with_unset_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::{InitBuilder, ProjectUninit};
+/// #[derive(ProjectUninit, InitBuilder)]
+/// struct Foo { a: u32 }
+/// let foo = FooInit::new().set_a(1).set_a(2).finish();
+///```
+fn _set_field_twice_fails() {}
+
+///```compile_fail
+/// mod inner {
+///     use project_uninit::{InitBuilder, ProjectUninit};
+///     #[derive(ProjectUninit, InitBuilder)]
+///     pub struct Foo { pub a: u32, b: u32 }
+/// }
+/// let foo = inner::FooInit::new().set_a(1).set_b(2).finish();
+///```
+fn _set_private_field_fails() {}
+
+///```compile_fail,E0308
+/// use project_uninit::{InitBuilder, ProjectUninit};
+/// #[derive(ProjectUninit, InitBuilder)]
+/// struct Foo { a: String }
+/// let mut unset = FooInit::new();
+/// let mut set = FooInit::new().set_a("a".into());
+/// // this would let `set` be finished with `a` uninitialized
+/// core::mem::swap(&mut unset.__slot, &mut set.__slot);
+///```
+fn _swap_slots_of_different_states_fails() {}
@@ -0,0 +1,54 @@
+/// Allocate a struct in a [`bumpalo::Bump`](::bumpalo::Bump) arena and initialize its fields in
+/// place, returning a mutable reference to it that lives as long as the arena.
+///
+/// Every field of the struct must be given a value, which is checked at compile time, so the
+/// value is never built on the stack and copied into the arena.
+/// The fields are written in the order they are given, as with
+/// [`partial_init!`](crate::partial_init).
+/// Like values allocated with `Bump::alloc`, the struct is never dropped by the arena.
+///
+/// ## Example
+/// ```
+/// use project_uninit::alloc_init_in;
+/// use project_uninit::bumpalo::Bump;
+///
+/// struct Node<'a> { kind: u8, span: (u32, u32), children: &'a [&'a Node<'a>] }
+///
+/// let bump = Bump::new();
+/// let leaf = alloc_init_in!(&bump, Node => { kind: 1, span: (0, 3), children: &[] });
+/// let root = alloc_init_in!(&bump, Node => {
+///     kind: 0,
+///     span: (0, 10),
+///     children: bump.alloc_slice_copy(&[&*leaf]),
+/// });
+/// assert_eq!(root.children[0].span, (0, 3));
+/// ```
+#[macro_export]
+macro_rules! alloc_init_in {
+    ($bump:expr, $($ty:ident)::+ => {$($field:ident : $val:expr),* $(,)?}) => {{
+        let slot = $crate::utils::alloc_uninit_in($bump);
+        if false {
+            // this will never be executed
+            // it's only to infer the type of the slot and assert that every field is given a value
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
+            {
+                *slot = ::core::mem::MaybeUninit::new($($ty)::+ {
+                    $($field: ::core::unreachable!()),*
+                });
+            }
+        }
+        $crate::partial_init!(slot => { $($field: $val),* });
+        #[allow(unused_unsafe)]
+        unsafe {
+            slot.assume_init_mut()
+        }
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::alloc_init_in;
+/// struct Node { kind: u8, span: (u32, u32) }
+/// let bump = project_uninit::bumpalo::Bump::new();
+/// let _ = alloc_init_in!(&bump, Node => { kind: 1 });
+///```
+fn _alloc_init_in_missing_field_fails() {}
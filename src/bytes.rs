@@ -0,0 +1,147 @@
+use core::fmt;
+use core::mem::{size_of, MaybeUninit};
+use core::ptr;
+
+#[cfg(feature = "bytemuck")]
+use ::bytemuck::{NoUninit, Pod};
+#[cfg(feature = "zerocopy")]
+use ::zerocopy::{FromBytes, Immutable, IntoBytes};
+
+/// An error from initializing a value from a byte slice of the wrong length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteLenError {
+    /// The size of the value in bytes.
+    pub expected: usize,
+    /// The length of the byte slice.
+    pub found: usize,
+}
+
+impl fmt::Display for ByteLenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} bytes to initialize the value, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+// Copy `bytes` into `slot`, which must be valid for every bit pattern.
+unsafe fn copy_from_bytes<'a, T>(
+    slot: &'a mut MaybeUninit<T>,
+    bytes: &[u8],
+) -> Result<&'a mut T, ByteLenError> {
+    if bytes.len() != size_of::<T>() {
+        return Err(ByteLenError {
+            expected: size_of::<T>(),
+            found: bytes.len(),
+        });
+    }
+    let dst = slot.as_mut_ptr() as *mut u8;
+    ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_init(dst, bytes.len());
+    Ok(slot.assume_init_mut())
+}
+
+/// Initialize a [`Pod`] value, or a field projected with
+/// [`project_uninit_mut!`](crate::project_uninit_mut), by copying it from a byte slice of the
+/// same size, which doesn't need to be aligned.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::bytemuck::{Pod, Zeroable};
+/// use project_uninit::{init_pod, partial_init, pod_bytes, project_uninit, project_uninit_mut};
+///
+/// #[derive(Clone, Copy, Pod, Zeroable)]
+/// #[repr(C)]
+/// struct Header { magic: [u8; 4], len: u32 }
+///
+/// struct Message { header: Header, body: Vec<u8> }
+///
+/// let input = [b'M', b'S', b'G', b'1', 3, 0, 0, 0, 10, 20, 30];
+/// let mut msg = MaybeUninit::<Message>::uninit();
+/// let header = init_pod(project_uninit_mut!(msg => header), &input[..8]).unwrap();
+/// let body = input[8..][..header.len as usize].to_vec();
+/// partial_init!(msg => body = body);
+///
+/// let magic = unsafe { pod_bytes(project_uninit!(msg => header => magic)) };
+/// assert_eq!(magic, b"MSG1");
+/// ```
+#[cfg(feature = "bytemuck")]
+pub fn init_pod<'a, T: Pod>(
+    slot: &'a mut MaybeUninit<T>,
+    bytes: &[u8],
+) -> Result<&'a mut T, ByteLenError> {
+    unsafe { copy_from_bytes(slot, bytes) }
+}
+
+/// View an initialized value, or a field projected with
+/// [`project_uninit!`](crate::project_uninit), as bytes.
+///
+/// ## Safety
+/// The value must be initialized.
+#[cfg(feature = "bytemuck")]
+pub unsafe fn pod_bytes<T: NoUninit>(slot: &MaybeUninit<T>) -> &[u8] {
+    ::bytemuck::bytes_of(slot.assume_init_ref())
+}
+
+/// Initialize a [`FromBytes`] value, or a field projected with
+/// [`project_uninit_mut!`](crate::project_uninit_mut), by copying it from a byte slice of the
+/// same size, which doesn't need to be aligned.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::zerocopy::{FromBytes, Immutable, IntoBytes};
+/// use project_uninit::{init_from_bytes, partial_init, project_uninit, project_uninit_mut, value_bytes};
+///
+/// #[derive(FromBytes, IntoBytes, Immutable)]
+/// #[repr(C)]
+/// struct Header { magic: [u8; 4], len: u32 }
+///
+/// struct Message { header: Header, body: Vec<u8> }
+///
+/// let input = [b'M', b'S', b'G', b'1', 3, 0, 0, 0, 10, 20, 30];
+/// let mut msg = MaybeUninit::<Message>::uninit();
+/// let header = init_from_bytes(project_uninit_mut!(msg => header), &input[..8]).unwrap();
+/// let body = input[8..][..header.len as usize].to_vec();
+/// partial_init!(msg => body = body);
+///
+/// let magic = unsafe { value_bytes(project_uninit!(msg => header => magic)) };
+/// assert_eq!(magic, b"MSG1");
+/// ```
+#[cfg(feature = "zerocopy")]
+pub fn init_from_bytes<'a, T: FromBytes>(
+    slot: &'a mut MaybeUninit<T>,
+    bytes: &[u8],
+) -> Result<&'a mut T, ByteLenError> {
+    unsafe { copy_from_bytes(slot, bytes) }
+}
+
+/// View an initialized value, or a field projected with
+/// [`project_uninit!`](crate::project_uninit), as bytes.
+///
+/// ## Safety
+/// The value must be initialized.
+#[cfg(feature = "zerocopy")]
+pub unsafe fn value_bytes<T: IntoBytes + Immutable>(slot: &MaybeUninit<T>) -> &[u8] {
+    slot.assume_init_ref().as_bytes()
+}
+
+///```compile_fail
+/// use core::mem::MaybeUninit;
+/// let mut x = MaybeUninit::<&'static str>::uninit();
+/// let _ = project_uninit::init_pod(&mut x, &[0; 16]);
+///```
+#[cfg(feature = "bytemuck")]
+fn _init_pod_requires_pod() {}
+
+///```compile_fail
+/// use core::mem::MaybeUninit;
+/// let mut x = MaybeUninit::<bool>::uninit();
+/// let _ = project_uninit::init_from_bytes(&mut x, &[2]);
+///```
+#[cfg(feature = "zerocopy")]
+fn _init_from_bytes_requires_from_bytes() {}
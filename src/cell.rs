@@ -0,0 +1,302 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// Uninitialized storage that can be placed in a `static` and claimed exactly once, for
+/// reserving memory at compile time and initializing it in place at run time.
+///
+/// This is the usual embedded pattern of reserving RAM in `.bss` and initializing it at boot,
+/// without `static mut`: [`take`](UninitCell::take) hands out a `&'static mut MaybeUninit<T>` the
+/// first time it is called, and `None` after that, so the slot can be initialized with
+/// [`partial_init!`](crate::partial_init) or any other macro in this crate.
+/// [`init_with`](UninitCell::init_with) claims the slot and initializes it with a closure that
+/// returns the same proof of initialization as [`init_with`](crate::init_with).
+///
+/// The claim is a single atomic swap, so it is safe to race with interrupt handlers and other
+/// threads. The value is never dropped.
+///
+/// With the `poison` feature enabled in debug builds, every byte of the slot is overwritten with
+/// [`POISON_BYTE`](crate::POISON_BYTE) when it is claimed.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{partial_init, UninitCell};
+///
+/// struct Ring { buf: [u8; 4096], head: usize, tail: usize }
+///
+/// static RING: UninitCell<Ring> = UninitCell::new();
+///
+/// // at boot
+/// let slot = RING.take().unwrap();
+/// partial_init!(slot => { buf: [0; 4096], head: 0, tail: 0 });
+/// let ring: &'static mut Ring = unsafe { slot.assume_init_mut() };
+/// ring.buf[0] = 1;
+///
+/// // the slot can't be claimed again
+/// assert!(RING.take().is_none());
+/// ```
+pub struct UninitCell<T> {
+    slot: UnsafeCell<MaybeUninit<T>>,
+    taken: AtomicBool,
+}
+
+// the slot is only ever accessed through the one `&mut` handed out by `take`
+unsafe impl<T: Send> Sync for UninitCell<T> {}
+
+impl<T> UninitCell<T> {
+    /// Create an unclaimed cell with an uninitialized slot.
+    pub const fn new() -> Self {
+        Self {
+            slot: UnsafeCell::new(MaybeUninit::uninit()),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Claim the slot, returning a mutable reference to it the first time this is called, and
+    /// `None` every time after that.
+    // the slot is handed out at most once
+    #[allow(clippy::mut_from_ref)]
+    pub fn take(&self) -> Option<&mut MaybeUninit<T>> {
+        if self.taken.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let slot = unsafe { &mut *self.slot.get() };
+        crate::poison::poison_new(slot);
+        Some(slot)
+    }
+
+    /// Claim the slot and initialize it with `init`, which is given the uninitialized slot and
+    /// must return a reference to that same slot, initialized, as with
+    /// [`init_with`](crate::init_with).
+    ///
+    /// Returns `None` without calling `init` if the slot was already claimed. If `init` panics,
+    /// the slot stays claimed and the fields it initialized are leaked.
+    ///
+    /// ## Panics
+    /// If `init` returns a reference to something other than the slot it was given.
+    #[allow(clippy::mut_from_ref)]
+    pub fn init_with(
+        &self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> Option<&mut T> {
+        let slot = self.take()?;
+        crate::init_with::init_slot(slot, init);
+        Some(unsafe { slot.assume_init_mut() })
+    }
+
+    /// Whether the slot has been claimed.
+    pub fn is_taken(&self) -> bool {
+        self.taken.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Default for UninitCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for UninitCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitCell")
+            .field("taken", &self.is_taken())
+            .finish_non_exhaustive()
+    }
+}
+
+const UNINIT: u8 = 0;
+const CLAIMED: u8 = 1;
+const READY: u8 = 2;
+
+/// A slot that is initialized in place by one thread and then published to every other thread,
+/// which only see the value once it is fully initialized.
+///
+/// [`claim`](AtomicInitCell::claim) hands the uninitialized slot to one thread as a [`Publisher`],
+/// which dereferences to `MaybeUninit<T>` so it can be filled with
+/// [`partial_init!`](crate::partial_init) or any other macro in this crate.
+/// [`publish`](Publisher::publish) then marks the value as ready with a `Release` store, and
+/// [`get`](AtomicInitCell::get) only returns it after an `Acquire` load has seen that store, so
+/// every write made while initializing happens before any read through `get`.
+///
+/// Unlike [`UninitCell`], the value is dropped with the cell if it was published. If a
+/// `Publisher` is dropped without publishing, the slot can be claimed again and any fields it
+/// initialized are leaked.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{partial_init, AtomicInitCell};
+///
+/// struct Config { name: String, workers: usize }
+///
+/// static CONFIG: AtomicInitCell<Config> = AtomicInitCell::new();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let mut slot = CONFIG.claim().unwrap();
+///         partial_init!(slot => { name: "server".into(), workers: 4 });
+///         unsafe { slot.publish() };
+///     });
+///     s.spawn(|| {
+///         // either not published yet, or fully initialized
+///         if let Some(config) = CONFIG.get() {
+///             assert_eq!(config.workers, 4);
+///         }
+///     });
+/// });
+/// assert_eq!(CONFIG.get().unwrap().name, "server");
+/// ```
+pub struct AtomicInitCell<T> {
+    slot: UnsafeCell<MaybeUninit<T>>,
+    state: AtomicU8,
+}
+
+// the value is only written by the thread holding the `Publisher`, and shared once published
+unsafe impl<T: Send> Send for AtomicInitCell<T> {}
+unsafe impl<T: Send + Sync> Sync for AtomicInitCell<T> {}
+
+impl<T> AtomicInitCell<T> {
+    /// Create an unclaimed cell with an uninitialized slot.
+    pub const fn new() -> Self {
+        Self {
+            slot: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(UNINIT),
+        }
+    }
+
+    /// Claim the slot for initialization, or return `None` if it is already claimed or
+    /// published.
+    pub fn claim(&self) -> Option<Publisher<'_, T>> {
+        self.state
+            .compare_exchange(UNINIT, CLAIMED, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        let slot = unsafe { &mut *self.slot.get() };
+        crate::poison::poison_new(slot);
+        Some(Publisher { cell: self, slot })
+    }
+
+    /// Claim the slot, initialize it with `init` and publish it, as with
+    /// [`init_with`](crate::init_with).
+    ///
+    /// Returns `None` without calling `init` if the slot is already claimed or published. If
+    /// `init` panics, the slot can be claimed again.
+    ///
+    /// ## Panics
+    /// If `init` returns a reference to something other than the slot it was given.
+    pub fn init_with(
+        &self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> Option<&T> {
+        let mut publisher = self.claim()?;
+        crate::init_with::init_slot(&mut publisher, init);
+        Some(unsafe { publisher.publish() })
+    }
+
+    /// Get the value if it has been published.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == READY {
+            Some(unsafe { (*self.slot.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the value mutably if it has been published.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if *self.state.get_mut() == READY {
+            Some(unsafe { self.slot.get_mut().assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Take the value out of the cell if it has been published.
+    pub fn into_inner(self) -> Option<T> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        if *this.state.get_mut() == READY {
+            Some(unsafe { this.slot.get_mut().assume_init_read() })
+        } else {
+            None
+        }
+    }
+
+    /// Whether the value has been published.
+    pub fn is_published(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == READY
+    }
+}
+
+impl<T> Default for AtomicInitCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for AtomicInitCell<T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.get_mut() {
+            unsafe { core::ptr::drop_in_place(value) };
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AtomicInitCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_tuple("AtomicInitCell");
+        match self.get() {
+            Some(value) => f.field(value),
+            None => f.field(&format_args!("<unpublished>")),
+        };
+        f.finish()
+    }
+}
+
+/// Exclusive access to the uninitialized slot of an [`AtomicInitCell`], returned by
+/// [`claim`](AtomicInitCell::claim).
+///
+/// Dereferences to `MaybeUninit<T>`. Dropping it without calling
+/// [`publish`](Publisher::publish) releases the claim.
+pub struct Publisher<'a, T> {
+    cell: &'a AtomicInitCell<T>,
+    slot: &'a mut MaybeUninit<T>,
+}
+
+impl<'a, T> Publisher<'a, T> {
+    /// Publish the value with a `Release` store, so that it is returned by
+    /// [`get`](AtomicInitCell::get) on every thread from then on.
+    ///
+    /// ## Safety
+    /// The value must be fully initialized.
+    pub unsafe fn publish(self) -> &'a T {
+        let this = core::mem::ManuallyDrop::new(self);
+        this.cell.state.store(READY, Ordering::Release);
+        (*this.cell.slot.get()).assume_init_ref()
+    }
+}
+
+impl<T> Deref for Publisher<'_, T> {
+    type Target = MaybeUninit<T>;
+
+    fn deref(&self) -> &MaybeUninit<T> {
+        self.slot
+    }
+}
+
+impl<T> DerefMut for Publisher<'_, T> {
+    fn deref_mut(&mut self) -> &mut MaybeUninit<T> {
+        self.slot
+    }
+}
+
+impl<T> Drop for Publisher<'_, T> {
+    fn drop(&mut self) {
+        self.cell.state.store(UNINIT, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for Publisher<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Publisher").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,130 @@
+use core::fmt;
+use core::ptr;
+
+use crate::{FieldBytes, FieldSink, InitTracker, UninitFields};
+
+/// An error from restoring a field with [`InitTracker::restore_field`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// The struct has no field with the given name.
+    UnknownField,
+    /// The bytes restored to the field at `index` have a different size than the field.
+    SizeMismatch {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField => f.write_str("no field with the checkpointed name"),
+            Self::SizeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field {} has size {}, but {} bytes were restored",
+                index, expected, found
+            ),
+        }
+    }
+}
+
+impl<T: UninitFields> InitTracker<'_, T> {
+    /// Write the name, offset and bytes of every field that has been marked as initialized to
+    /// `sink`, in declaration order, so initialization can later be resumed from where it left
+    /// off with [`restore_field`](InitTracker::restore_field).
+    ///
+    /// ## Safety
+    /// The initialized fields must not contain padding bytes. Their bytes are only meaningful to
+    /// restore if they hold plain data, without pointers or handles to resources that won't
+    /// survive the checkpoint.
+    ///
+    /// ## Example
+    /// ```
+    /// # #[cfg(feature = "derive")] {
+    /// use core::mem::MaybeUninit;
+    /// use project_uninit::{partial_init, FieldBytes, InitTracker, ProjectUninit};
+    ///
+    /// #[derive(ProjectUninit)]
+    /// struct Mesh { cells: u64, spacing: f64, seed: u32 }
+    ///
+    /// let mut saved = Vec::new();
+    /// {
+    ///     let mut slot = MaybeUninit::<Mesh>::uninit();
+    ///     let mut tracker = InitTracker::new(&mut slot);
+    ///     unsafe {
+    ///         partial_init!(tracker.slot_mut() => { cells: 1 << 20, spacing: 0.25 });
+    ///         tracker.set_init(0);
+    ///         tracker.set_init(1);
+    ///         let result: Result<(), ()> = tracker.checkpoint(&mut |f: FieldBytes| {
+    ///             Ok(saved.push((f.path, f.bytes.to_vec())))
+    ///         });
+    ///         result.unwrap();
+    ///     }
+    ///     // the process restarts here
+    /// }
+    ///
+    /// let mut slot = MaybeUninit::<Mesh>::uninit();
+    /// let mut tracker = InitTracker::new(&mut slot);
+    /// for (name, bytes) in &saved {
+    ///     unsafe { tracker.restore_field(name, bytes) }.unwrap();
+    /// }
+    /// assert_eq!(tracker.missing().next().unwrap().name(), "seed");
+    /// unsafe {
+    ///     partial_init!(tracker.slot_mut() => seed = 7);
+    ///     tracker.set_init(2);
+    /// }
+    /// let mesh = tracker.finish().unwrap();
+    /// assert_eq!((mesh.cells, mesh.spacing, mesh.seed), (1 << 20, 0.25, 7));
+    /// # }
+    /// ```
+    pub unsafe fn checkpoint<S: FieldSink + ?Sized>(&self, sink: &mut S) -> Result<(), S::Error> {
+        let base = self.slot().as_ptr() as *const u8;
+        for (i, field) in T::FIELDS.iter().enumerate() {
+            if self.is_init(i) {
+                sink.write_field(FieldBytes {
+                    path: field.name(),
+                    offset: field.offset(),
+                    bytes: core::slice::from_raw_parts(base.add(field.offset()), field.size()),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy `bytes` saved by [`checkpoint`](InitTracker::checkpoint) into the field named `name`
+    /// and mark it as initialized, dropping its previous value if it was already initialized.
+    ///
+    /// Returns the index of the field.
+    ///
+    /// ## Safety
+    /// `bytes` must be a valid value of the field's type, such as the bytes checkpointed from
+    /// the same field of the same type.
+    #[track_caller]
+    pub unsafe fn restore_field(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<usize, CheckpointError> {
+        let index = T::field_index(name).ok_or(CheckpointError::UnknownField)?;
+        let field = &T::FIELDS[index];
+        if bytes.len() != field.size() {
+            return Err(CheckpointError::SizeMismatch {
+                index,
+                expected: field.size(),
+                found: bytes.len(),
+            });
+        }
+        self.drop_field(index);
+        let dst = (self.slot_mut().as_mut_ptr() as *mut u8).add(field.offset());
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+        #[cfg(feature = "shadow")]
+        crate::shadow::mark_init(dst, bytes.len());
+        self.set_init(index);
+        Ok(index)
+    }
+}
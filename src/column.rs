@@ -0,0 +1,386 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{size_of, MaybeUninit};
+use core::ptr::NonNull;
+
+use crate::utils::Lifetime;
+use crate::{InitTracker, UninitFields};
+
+/// Storage that keeps values of a single type in fixed-stride slots, like a component column in
+/// the archetype table of an ECS.
+///
+/// Implement this for a storage type to construct values directly in its slots with
+/// [`emplace_row`].
+///
+/// ## Safety
+/// For every row that `emplace_row` is called with, [`slot_ptr`](ColumnStorage::slot_ptr) must
+/// return a pointer that is valid for writes of the value being constructed, aligned for it, and
+/// not aliased by any live reference.
+pub unsafe trait ColumnStorage {
+    /// A pointer to the slot for `row`.
+    fn slot_ptr(&mut self, row: usize) -> *mut u8;
+
+    /// Called when constructing a value in `row` failed or panicked, after the fields that were
+    /// initialized have been dropped, e.g. to return the row to a free list.
+    ///
+    /// Does nothing by default.
+    fn abandon_row(&mut self, row: usize) {
+        let _ = row;
+    }
+}
+
+/// A column described by a raw base pointer, a stride in bytes, and a number of rows.
+#[derive(Debug)]
+pub struct RawColumn {
+    base: *mut u8,
+    stride: usize,
+    len: usize,
+}
+
+impl RawColumn {
+    /// Describe a column of `len` slots, `stride` bytes apart, starting at `base`.
+    ///
+    /// ## Safety
+    /// Each of the `len` slots must be valid for writes and suitably aligned for the values
+    /// constructed in it, and must not be accessed through any other pointer while this column is
+    /// in use.
+    pub unsafe fn new(base: *mut u8, stride: usize, len: usize) -> Self {
+        Self { base, stride, len }
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The distance between slots in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+unsafe impl ColumnStorage for RawColumn {
+    fn slot_ptr(&mut self, row: usize) -> *mut u8 {
+        assert!(row < self.len, "row index out of range");
+        unsafe { self.base.add(row * self.stride) }
+    }
+}
+
+// Calls `abandon_row` unless construction completed.
+struct AbandonGuard<S: ColumnStorage + ?Sized> {
+    storage: *mut S,
+    row: usize,
+}
+
+impl<S: ColumnStorage + ?Sized> Drop for AbandonGuard<S> {
+    fn drop(&mut self) {
+        unsafe { (*self.storage).abandon_row(self.row) };
+    }
+}
+
+/// Construct a value in the slot for `row` of a column, one field at a time.
+///
+/// `init` initializes the fields through the [`InitTracker`] it is given, typically with
+/// [`partial_init!`](crate::partial_init) followed by [`InitTracker::set_init`].
+/// If every field is initialized when `init` returns, a reference to the value is returned.
+/// Otherwise, and if `init` panics, the fields that were initialized are dropped, the row is
+/// passed to [`ColumnStorage::abandon_row`], and `None` is returned or the panic continues.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{emplace_row, partial_init, ProjectUninit, RawColumn};
+///
+/// #[derive(ProjectUninit)]
+/// struct Velocity { x: f32, y: f32 }
+///
+/// let mut storage = [MaybeUninit::<Velocity>::uninit(), MaybeUninit::uninit()];
+/// let mut column = unsafe {
+///     RawColumn::new(storage.as_mut_ptr() as *mut u8, core::mem::size_of::<Velocity>(), 2)
+/// };
+///
+/// let v = emplace_row::<Velocity, _>(&mut column, 1, |t| unsafe {
+///     partial_init!(t.slot_mut() => { x: 1.0, y: -1.0 });
+///     t.set_init(0);
+///     t.set_init(1);
+/// })
+/// .unwrap();
+/// assert_eq!((v.x, v.y), (1.0, -1.0));
+/// # }
+/// ```
+pub fn emplace_row<'a, T, S>(
+    storage: &'a mut S,
+    row: usize,
+    init: impl FnOnce(&mut InitTracker<'a, T>),
+) -> Option<&'a mut T>
+where
+    T: UninitFields,
+    S: ColumnStorage + ?Sized,
+{
+    let ptr = storage.slot_ptr(row) as *mut MaybeUninit<T>;
+    debug_assert_eq!(
+        ptr.addr() % core::mem::align_of::<T>(),
+        0,
+        "column slot is not aligned"
+    );
+    let guard = AbandonGuard {
+        storage: storage as *mut S,
+        row,
+    };
+    let mut tracker = InitTracker::new(unsafe { &mut *ptr });
+    init(&mut tracker);
+    let value = tracker.finish().ok()?;
+    core::mem::forget(guard);
+    Some(value)
+}
+
+/// The same field of every element of a slice of `MaybeUninit<T>`, projected with
+/// [`project_column_mut!`](crate::project_column_mut), for initializing that field of every
+/// element in bulk.
+///
+/// Writing an element doesn't drop any value that was already there. If a closure passed to
+/// [`fill_with`](UninitColumn::fill_with) panics, the values written before it are leaked
+/// along with the rest of the elements.
+pub struct UninitColumn<'a, F> {
+    ptr: *mut F,
+    stride: usize,
+    len: usize,
+    _lt: PhantomData<&'a mut [MaybeUninit<F>]>,
+}
+
+// `UninitColumn` is equivalent to a slice of `&'a mut MaybeUninit<F>`
+unsafe impl<F: Send> Send for UninitColumn<'_, F> {}
+unsafe impl<F: Sync> Sync for UninitColumn<'_, F> {}
+
+impl<'a, F> UninitColumn<'a, F> {
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The distance between the field in consecutive elements in bytes, which is the size of the
+    /// element type.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    fn element(&mut self, index: usize) -> &mut MaybeUninit<F> {
+        unsafe { &mut *(self.ptr.byte_add(index * self.stride) as *mut MaybeUninit<F>) }
+    }
+
+    /// The field of the element at `index`, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut MaybeUninit<F>> {
+        if index < self.len {
+            Some(self.element(index))
+        } else {
+            None
+        }
+    }
+
+    /// Initialize the field of the element at `index`, returning a mutable reference to it.
+    ///
+    /// ## Panics
+    /// If `index` is out of bounds.
+    pub fn write(&mut self, index: usize, value: F) -> &mut F {
+        assert!(index < self.len, "column index out of range");
+        self.element(index).write(value)
+    }
+
+    /// Initialize the field of every element with the value returned by `f` for its index, in
+    /// order.
+    pub fn fill_with(&mut self, mut f: impl FnMut(usize) -> F) {
+        for index in 0..self.len {
+            self.element(index).write(f(index));
+        }
+    }
+
+    /// Initialize the field of every element with a clone of `value`.
+    pub fn fill(&mut self, value: F)
+    where
+        F: Clone,
+    {
+        self.fill_with(|_| value.clone());
+    }
+
+    /// Initialize the field of every element by copying the values in `src`.
+    ///
+    /// ## Panics
+    /// If `src` doesn't have the same length as the column.
+    pub fn copy_from_slice(&mut self, src: &[F])
+    where
+        F: Copy,
+    {
+        self.clone_from_slice(src);
+    }
+
+    /// Initialize the field of every element with a clone of the values in `src`.
+    ///
+    /// ## Panics
+    /// If `src` doesn't have the same length as the column.
+    pub fn clone_from_slice(&mut self, src: &[F])
+    where
+        F: Clone,
+    {
+        assert_eq!(
+            src.len(),
+            self.len,
+            "source slice length does not match column length"
+        );
+        self.fill_with(|index| src[index].clone());
+    }
+
+    /// An iterator over the field of each element.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut MaybeUninit<F>> + '_ {
+        let (ptr, stride) = (self.ptr, self.stride);
+        (0..self.len).map(move |index| unsafe {
+            &mut *(ptr.byte_add(index * stride) as *mut MaybeUninit<F>)
+        })
+    }
+}
+
+impl<F> fmt::Debug for UninitColumn<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitColumn")
+            .field("len", &self.len)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+/// Collections of `MaybeUninit<T>` elements that
+/// [`project_column_mut!`](crate::project_column_mut) can project columns from.
+#[doc(hidden)]
+pub trait UninitElements<T> {
+    fn uninit_elements_mut(&mut self) -> &mut [MaybeUninit<T>];
+}
+
+impl<T> UninitElements<T> for [MaybeUninit<T>] {
+    fn uninit_elements_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<T, const N: usize> UninitElements<T> for [MaybeUninit<T>; N] {
+    fn uninit_elements_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<T, const N: usize> UninitElements<T> for MaybeUninit<[T; N]> {
+    fn uninit_elements_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        // `MaybeUninit<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`
+        unsafe { &mut *(self.as_mut_ptr() as *mut [MaybeUninit<T>; N]) }
+    }
+}
+
+#[doc(hidden)]
+pub fn element_base<T>(elements: &mut [MaybeUninit<T>]) -> *mut T {
+    elements.as_mut_ptr() as *mut T
+}
+
+#[doc(hidden)]
+pub fn dangling_field<F>() -> *mut F {
+    NonNull::dangling().as_ptr()
+}
+
+#[doc(hidden)]
+pub unsafe fn uninit_column<'a, T, F>(
+    _base: *mut T,
+    field: *mut F,
+    len: usize,
+    _lt: Lifetime<'a>,
+) -> UninitColumn<'a, F> {
+    UninitColumn {
+        ptr: field,
+        stride: size_of::<T>(),
+        len,
+        _lt: PhantomData,
+    }
+}
+
+/// Obtain [`UninitColumn`]s over the same fields of every element of a slice of
+/// `MaybeUninit<T>`, to initialize each field of every element at once, column by column.
+///
+/// The target can be a slice, array or `Vec` of `MaybeUninit<T>`, or a `MaybeUninit<[T; N]>`.
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// statically ensures that the same field isn't projected more than once, so several columns can
+/// be initialized at the same time. Once every column is initialized, the elements are.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::project_column_mut;
+///
+/// struct Particle { pos: [f32; 2], vel: [f32; 2], id: u32 }
+///
+/// let positions = [[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]];
+/// let mut particles = MaybeUninit::<[Particle; 3]>::uninit();
+/// let (mut pos, mut vel, mut id) = project_column_mut!(particles => { pos, vel, id });
+/// pos.copy_from_slice(&positions);
+/// vel.fill([0.0; 2]);
+/// id.fill_with(|i| 100 + i as u32);
+///
+/// let particles = unsafe { particles.assume_init() };
+/// assert_eq!((particles[1].pos, particles[2].id), ([2.0, 3.0], 102));
+/// ```
+#[macro_export]
+macro_rules! project_column_mut {
+    // project multiple columns
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use $crate::utils::UninitElements;
+        let _ref = $expr.uninit_elements_mut();
+        let len = _ref.len();
+        let ptr = $crate::utils::element_base(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            // an empty slice may not point to an element to project the field from
+            let prop_ptr = if len == 0 {
+                $crate::utils::dangling_field()
+            } else {
+                #[allow(unused_unsafe)]
+                unsafe {
+                    ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+))
+                }
+            };
+            #[allow(unused_unsafe)]
+            let column = unsafe { $crate::utils::uninit_column(ptr, prop_ptr, len, lt) };
+            column
+        },)*)
+    }};
+
+    // project a single column
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_column_mut!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::project_column_mut;
+/// struct Foo { a: u32, b: u32 }
+/// let mut x = core::mem::MaybeUninit::<[Foo; 4]>::uninit();
+/// let (a, a2) = project_column_mut!(x => { a, a });
+///```
+fn _project_same_column_twice_fails() {}
@@ -0,0 +1,65 @@
+/// Define a `compile_fail` doc test asserting that the given code is rejected by the compiler.
+///
+/// This generates an empty private function whose documentation is a `compile_fail` code block
+/// containing the given tokens, which is the same scaffolding this crate uses to check that its
+/// macros reject duplicate fields and aliased borrows.
+/// Crates that wrap these macros can use it to check that their own APIs reject the same misuse.
+/// An error code may be given after the function name, making the test fail if compilation fails
+/// for a different reason.
+///
+/// Like any doc test, the code must `use` everything it refers to from the crate under test, and
+/// the test only runs when the invocation is in a library crate.
+///
+/// ## Example
+/// ```
+/// use project_uninit::compile_fail_doctest;
+///
+/// compile_fail_doctest!(_duplicate_field_fails, {
+///     use core::mem::MaybeUninit;
+///     use project_uninit::partial_init;
+///     struct Foo { a: i32, b: u32 }
+///     let mut x = MaybeUninit::<Foo>::uninit();
+///     let _ = partial_init!(x => { a: 1, a: 2 });
+/// });
+///
+/// compile_fail_doctest!(_aliased_borrow_fails, E0499, {
+///     use core::mem::MaybeUninit;
+///     use project_uninit::project_uninit_mut;
+///     struct Foo { a: i32, b: u32 }
+///     let mut x = MaybeUninit::<Foo>::uninit();
+///     let a1 = project_uninit_mut!(x => a);
+///     let a2 = project_uninit_mut!(x => a);
+///     drop(a1);
+/// });
+/// ```
+#[macro_export]
+macro_rules! compile_fail_doctest {
+    ($name:ident, $code:ident, { $($tokens:tt)* }) => {
+        #[doc = concat!("```compile_fail,", stringify!($code), "\n", stringify!($($tokens)*), "\n```")]
+        #[allow(dead_code)]
+        fn $name() {}
+    };
+    ($name:ident, { $($tokens:tt)* }) => {
+        #[doc = concat!("```compile_fail\n", stringify!($($tokens)*), "\n```")]
+        #[allow(dead_code)]
+        fn $name() {}
+    };
+}
+
+compile_fail_doctest!(_compile_fail_doctest_detects_duplicate_fields, {
+    use core::mem::MaybeUninit;
+    use project_uninit::project_uninit_mut;
+    struct Foo { a: i32, b: u32 }
+    let mut x = MaybeUninit::<Foo>::uninit();
+    let _ = project_uninit_mut!(x => { a, b, a });
+});
+
+compile_fail_doctest!(_compile_fail_doctest_detects_aliasing, E0499, {
+    use core::mem::MaybeUninit;
+    use project_uninit::partial_init;
+    struct Foo { a: i32, b: u32 }
+    let mut x = MaybeUninit::<Foo>::uninit();
+    let a = partial_init!(x => a = 1);
+    let b = partial_init!(x => b = 2);
+    *a += 1;
+});
@@ -0,0 +1,212 @@
+/// Obtain `&MaybeUninit<_>` references to fields of a struct wrapped in `MaybeUninit<_>`, in a
+/// `const` context.
+///
+/// This works like [`project_uninit!`](crate::project_uninit), except that traits can't be
+/// called in `const` code, so the struct is taken as a place of type `MaybeUninit<_>` rather than
+/// anything that implements `Borrow`. To project through a reference `r`, pass `*r`.
+/// Aliases and `pub`/`strict` paths aren't supported.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::const_project_uninit;
+///
+/// struct Pair { a: u32, b: u64 }
+///
+/// const fn a_offset(pair: &MaybeUninit<Pair>) -> usize {
+///     let a = const_project_uninit!(*pair => a);
+///     unsafe { (a as *const MaybeUninit<u32>).byte_offset_from(pair) as usize }
+/// }
+///
+/// const A: usize = a_offset(&MaybeUninit::uninit());
+/// assert_eq!(A, core::mem::offset_of!(Pair, a));
+/// ```
+#[macro_export]
+macro_rules! const_project_uninit {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        let _ref: &::core::mem::MaybeUninit<_> = &$expr;
+        let ptr = _ref.as_ptr();
+        let lt = $crate::utils::bind_ref_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::uninit_from_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::const_project_uninit!($expr => {$($props)=>+}).0
+    };
+}
+
+/// Obtain `&mut MaybeUninit<_>` references to fields of a struct wrapped in `MaybeUninit<_>`, in
+/// a `const` context.
+///
+/// This works like [`project_uninit_mut!`](crate::project_uninit_mut), and statically ensures
+/// that multiple references to the same value are not returned. As with
+/// [`const_project_uninit!`](crate::const_project_uninit), the struct is taken as a place of type
+/// `MaybeUninit<_>`, so pass `*r` to project through a reference `r`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::const_project_uninit_mut;
+///
+/// struct Range { start: u32, end: u32 }
+///
+/// const fn init_range(slot: &mut MaybeUninit<Range>, start: u32, len: u32) -> &mut Range {
+///     let (s, e) = const_project_uninit_mut!(*slot => { start, end });
+///     s.write(start);
+///     e.write(start + len);
+///     unsafe { slot.assume_init_mut() }
+/// }
+///
+/// const R: Range = {
+///     let mut slot = MaybeUninit::uninit();
+///     init_range(&mut slot, 4, 2);
+///     unsafe { slot.assume_init() }
+/// };
+/// assert_eq!((R.start, R.end), (4, 6));
+/// ```
+#[macro_export]
+macro_rules! const_project_uninit_mut {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        let _ref: &mut ::core::mem::MaybeUninit<_> = &mut $expr;
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::const_project_uninit_mut!($expr => {$($props)=>+}).0
+    };
+}
+
+/// Partially initialize a struct wrapped in `MaybeUninit<_>`, in a `const` context, such as the
+/// initializer of a `static`.
+///
+/// This works like the plain form of [`partial_init!`](crate::partial_init), writing each value
+/// in turn and returning a tuple of `&mut` references to the initialized fields. As with
+/// [`const_project_uninit_mut!`](crate::const_project_uninit_mut), the struct is taken as a place
+/// of type `MaybeUninit<_>`, so pass `*r` to initialize through a reference `r`.
+/// Closures, grouped paths, `=> let` bindings and `..rest` aren't supported, and init hooks
+/// registered with the `hooks` feature aren't called.
+///
+/// Calling `assume_init` on a value with a field that was never written is still undefined
+/// behavior, but const evaluation usually reports it as an error rather than compiling.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::const_partial_init;
+///
+/// struct Entry { name: &'static str, id: u32 }
+/// struct Table { entries: [Entry; 2], len: usize }
+///
+/// static TABLE: Table = {
+///     let mut table = MaybeUninit::<Table>::uninit();
+///     const_partial_init!(table => {
+///         entries => [0] => name: "zero",
+///         entries => [0] => id: 0,
+///         entries => [1]: Entry { name: "one", id: 1 },
+///     });
+///     let len = const_partial_init!(table => len = 2);
+///     *len -= 1;
+///     unsafe { table.assume_init() }
+/// };
+/// assert_eq!((TABLE.entries[0].name, TABLE.entries[1].id, TABLE.len), ("zero", 1, 1));
+/// ```
+#[macro_export]
+macro_rules! const_partial_init {
+    // initialize multiple fields
+    ($expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        let _ref: &mut ::core::mem::MaybeUninit<_> = &mut $expr;
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ::core::ptr::write(prop_ptr, $val);
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
+            }
+            prop_ref
+        },)*)
+    }};
+
+    // initialize a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::const_partial_init!($expr => { $($props)=>+: $val }).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::const_partial_init;
+/// struct Foo { a: u8, b: u8 }
+/// const FOO: Foo = {
+///     let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+///     const_partial_init!(x => { a: 1, a: 2, b: 3 });
+///     unsafe { x.assume_init() }
+/// };
+///```
+fn _const_init_same_field_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::const_partial_init;
+/// struct Foo { a: u8, b: u8 }
+/// const FOO: Foo = {
+///     let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+///     const_partial_init!(x => a = 1);
+///     unsafe { x.assume_init() }
+/// };
+///```
+fn _const_init_missing_field_fails() {}
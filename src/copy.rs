@@ -0,0 +1,188 @@
+/// Initialize fields of a struct wrapped in `MaybeUninit` by copying them from the same fields of
+/// an existing value, and return mutable references to them.
+///
+/// The source is given after `from`, and can be a value of the struct or a reference to one:
+/// `copy_from_init!(x => { a, b => 0 } from template)`. Only the given fields are read from it,
+/// and each of them must implement `Copy`, so the source is left untouched.
+/// The fields are written in the order they are given, as with
+/// [`partial_init!`](crate::partial_init), which accepts the same field paths.
+/// Use [`clone_from_init!`](crate::clone_from_init) for fields that aren't `Copy`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{copy_from_init, partial_init};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Style { color: [u8; 3], width: f32, dashed: bool, label: String }
+///
+/// let template = Style { color: [0, 0, 255], width: 2.0, dashed: false, label: "base".into() };
+///
+/// let mut style = MaybeUninit::<Style>::uninit();
+/// copy_from_init!(style => { color, width } from &template);
+/// partial_init!(style => { dashed: true, label: "highlight".into() });
+///
+/// let style = unsafe { style.assume_init() };
+/// assert_eq!(style.color, template.color);
+/// assert!(style.dashed);
+/// ```
+#[macro_export]
+macro_rules! copy_from_init {
+    // copy mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?} from $src:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let src = $crate::utils::source_of(_ref, &$src);
+        $crate::partial_init!(_ref => {$(
+            $($props)=>+ : $crate::utils::copy_field(&$crate::__field_place!((*src) $($props)=>+)),
+        )*})
+    }};
+
+    // copy a single field
+    ($expr:expr => $($props:tt)=>+ from $src:expr) => {
+        $crate::copy_from_init!($expr => {$($props)=>+} from $src).0
+    };
+}
+
+/// Initialize fields of a struct wrapped in `MaybeUninit` by cloning them from the same fields of
+/// an existing value, and return mutable references to them.
+///
+/// This accepts the same syntax as [`copy_from_init!`](crate::copy_from_init), and clones each
+/// field with [`Clone::clone`] instead of copying it, without cloning the rest of the source.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{clone_from_init, partial_init};
+///
+/// struct Request { host: String, headers: Vec<(String, String)>, path: String }
+///
+/// let template = Request {
+///     host: "example.com".into(),
+///     headers: vec![("accept".into(), "text/html".into())],
+///     path: "/".into(),
+/// };
+///
+/// let requests: Vec<Request> = ["/a", "/b"].iter().map(|path| {
+///     let mut request = MaybeUninit::<Request>::uninit();
+///     clone_from_init!(request => { host, headers } from template);
+///     partial_init!(request => path = path.to_string());
+///     unsafe { request.assume_init() }
+/// }).collect();
+/// assert_eq!(requests[1].host, "example.com");
+/// assert_eq!(requests[1].path, "/b");
+/// ```
+#[macro_export]
+macro_rules! clone_from_init {
+    // clone mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?} from $src:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let src = $crate::utils::source_of(_ref, &$src);
+        $crate::partial_init!(_ref => {$(
+            $($props)=>+ : ::core::clone::Clone::clone(
+                &$crate::__field_place!((*src) $($props)=>+)
+            ),
+        )*})
+    }};
+
+    // clone a single field
+    ($expr:expr => $($props:tt)=>+ from $src:expr) => {
+        $crate::clone_from_init!($expr => {$($props)=>+} from $src).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::copy_from_init;
+/// struct Foo { a: String, b: u32 }
+/// let template = Foo { a: String::new(), b: 1 };
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// copy_from_init!(x => a from template);
+///```
+fn _copy_from_init_requires_copy() {}
+
+///```compile_fail
+/// use project_uninit::clone_from_init;
+/// struct Foo { a: u32 }
+/// struct Bar { a: u32 }
+/// let template = Bar { a: 1 };
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// clone_from_init!(x => a from template);
+///```
+fn _clone_from_init_requires_same_type() {}
+
+/// **Unsafe:** Initialize fields of a struct wrapped in `MaybeUninit` by moving them out of the
+/// same fields of another `MaybeUninit` of the struct, and return mutable references to them.
+///
+/// Each field is copied directly from the source to the slot with
+/// [`ptr::copy_nonoverlapping`](core::ptr::copy_nonoverlapping), so even a huge field never passes
+/// through the stack, and becomes logically uninitialized in the source, as with
+/// [`read_field!`](crate::read_field).
+/// The source is given after `from`, and can be anything that borrows as a `MaybeUninit` of the
+/// same struct, like a `Box<MaybeUninit<T>>`.
+/// This accepts the same field paths as [`partial_init!`](crate::partial_init), and returns a
+/// single reference or a tuple of references.
+///
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized in the source, and must not be read or dropped
+/// through the source again until it is given a new value.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_array_with, move_init, partial_init};
+///
+/// struct Level { tiles: [[u16; 512]; 512], name: String }
+///
+/// let mut loaded = Box::new(MaybeUninit::<Level>::uninit());
+/// init_array_with!(loaded => tiles, |_| [7; 512]);
+/// partial_init!(loaded => name = "cave".into());
+///
+/// // move the tiles into the active level without a 512KiB copy on the stack
+/// let mut active = Box::new(MaybeUninit::<Level>::uninit());
+/// unsafe { move_init!(active => { tiles, name } from loaded) };
+/// let active = unsafe { active.assume_init() };
+/// assert_eq!(active.tiles[511][511], 7);
+/// assert_eq!(active.name, "cave");
+/// ```
+#[macro_export]
+macro_rules! move_init {
+    // move mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?} from $src:expr) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let _src: &mut ::core::mem::MaybeUninit<_> = $src.borrow_mut();
+        let src = $crate::utils::move_source(_ref, _src);
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            #[allow(unused_unsafe)]
+            let (prop_ptr, src_ptr) = unsafe { (
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*src) $($props)=>+)),
+            ) };
+            $crate::utils::move_field(src_ptr, prop_ptr);
+            $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+            $crate::utils::deref_ptr_with_lt(prop_ptr, lt)
+        },)*)
+    }};
+
+    // move a single field
+    ($expr:expr => $($props:tt)=>+ from $src:expr) => {
+        $crate::move_init!($expr => {$($props)=>+} from $src).0
+    };
+}
@@ -0,0 +1,169 @@
+//! Statistics about how tracked targets were initialized over the course of a program run.
+//!
+//! With the `coverage` feature enabled, every [`InitTracker`](crate::InitTracker) records which
+//! fields it marks as initialized, and whether it was finished or dropped while incomplete.
+//! The totals for each type can be retrieved with [`report`], e.g. at the end of a test run, to
+//! find fields that are never initialized and half-built values that are abandoned.
+//!
+//! Fields that are marked as initialized again without being dropped in between are counted as
+//! overwrites. These point at either leaked values, if the old value needed to be dropped, or
+//! wasted work in the initialization path.
+//!
+//! ## Example
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use core::mem::MaybeUninit;
+//! use project_uninit::{coverage, partial_init, InitTracker, ProjectUninit};
+//!
+//! #[derive(ProjectUninit)]
+//! struct Point { x: i32, y: i32 }
+//!
+//! let mut slot = MaybeUninit::<Point>::uninit();
+//! let mut tracker = InitTracker::new(&mut slot);
+//! unsafe {
+//!     partial_init!(tracker.slot_mut() => x = 1);
+//!     tracker.set_init(0);
+//! }
+//! drop(tracker);
+//!
+//! let report = coverage::report();
+//! let point = report.get(core::any::type_name::<Point>()).unwrap();
+//! assert_eq!(point.dropped_incomplete, 1);
+//! assert_eq!(point.never_initialized().collect::<Vec<_>>(), ["y"]);
+//! println!("{}", report);
+//! # }
+//! ```
+
+use core::fmt;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use crate::UninitFields;
+
+/// How many times a single field was marked as initialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldCoverage {
+    pub name: &'static str,
+    pub init_count: usize,
+    /// How many of those times the field was already marked as initialized.
+    pub overwrite_count: usize,
+}
+
+/// Initialization statistics for a single type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeCoverage {
+    /// The name of the type, as given by [`core::any::type_name`].
+    pub type_name: &'static str,
+    /// Statistics for each field, in declaration order.
+    pub fields: Vec<FieldCoverage>,
+    /// The number of trackers that were finished with every field initialized.
+    pub completed: usize,
+    /// The number of trackers that were dropped before every field was initialized.
+    pub dropped_incomplete: usize,
+}
+
+impl TypeCoverage {
+    /// The names of the fields that were never marked as initialized.
+    pub fn never_initialized(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .filter(|f| f.init_count == 0)
+            .map(|f| f.name)
+    }
+
+    /// The names of the fields that were overwritten at least once.
+    pub fn overwritten(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .filter(|f| f.overwrite_count != 0)
+            .map(|f| f.name)
+    }
+}
+
+/// A snapshot of the initialization statistics for every tracked type, returned by [`report`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub types: Vec<TypeCoverage>,
+}
+
+impl CoverageReport {
+    /// Get the statistics for the type with the given name.
+    pub fn get(&self, type_name: &str) -> Option<&TypeCoverage> {
+        self.types.iter().find(|t| t.type_name == type_name)
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ty in &self.types {
+            writeln!(
+                f,
+                "{}: {} completed, {} dropped incomplete",
+                ty.type_name, ty.completed, ty.dropped_incomplete,
+            )?;
+            for field in &ty.fields {
+                write!(f, "    {}: initialized {} times", field.name, field.init_count)?;
+                if field.overwrite_count != 0 {
+                    write!(f, " ({} overwrites)", field.overwrite_count)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+static TYPES: Mutex<Vec<TypeCoverage>> = Mutex::new(Vec::new());
+
+fn with_type<T: UninitFields>(f: impl FnOnce(&mut TypeCoverage)) {
+    let mut types = TYPES.lock().unwrap_or_else(|e| e.into_inner());
+    let type_name = core::any::type_name::<T>();
+    let index = match types.iter().position(|t| t.type_name == type_name) {
+        Some(index) => index,
+        None => {
+            types.push(TypeCoverage {
+                type_name,
+                fields: T::FIELDS
+                    .iter()
+                    .map(|f| FieldCoverage {
+                        name: f.name(),
+                        init_count: 0,
+                        overwrite_count: 0,
+                    })
+                    .collect(),
+                completed: 0,
+                dropped_incomplete: 0,
+            });
+            types.len() - 1
+        }
+    };
+    f(&mut types[index]);
+}
+
+pub(crate) fn record_field_init<T: UninitFields>(index: usize) {
+    with_type::<T>(|t| t.fields[index].init_count += 1);
+}
+
+pub(crate) fn record_field_overwrite<T: UninitFields>(index: usize) {
+    with_type::<T>(|t| t.fields[index].overwrite_count += 1);
+}
+
+pub(crate) fn record_completed<T: UninitFields>() {
+    with_type::<T>(|t| t.completed += 1);
+}
+
+pub(crate) fn record_dropped_incomplete<T: UninitFields>() {
+    with_type::<T>(|t| t.dropped_incomplete += 1);
+}
+
+/// Get the statistics recorded so far for every tracked type.
+pub fn report() -> CoverageReport {
+    CoverageReport {
+        types: TYPES.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
+/// Clear all recorded statistics.
+pub fn reset() {
+    TYPES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
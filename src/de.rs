@@ -0,0 +1,255 @@
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use ::serde::de::{
+    self, DeserializeSeed, Deserializer, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use ::serde::Deserialize;
+
+use crate::array::PrefixDropGuard;
+use crate::{InitTracker, UninitFields};
+
+/// A type that can be deserialized directly inside a `MaybeUninit<_>` slot.
+///
+/// This can be derived with `#[derive(DeserializeUninit)]` for structs that also derive
+/// `ProjectUninit`. Each field is deserialized with [`Deserialize`], unless it is marked
+/// `#[uninit(in_place)]`, in which case it is deserialized in place with `DeserializeUninit`.
+/// Structs with named fields can be deserialized from maps or sequences, like with
+/// `#[derive(Deserialize)]`, and tuple structs from sequences. Unknown fields are ignored.
+/// If deserializing a field fails, the fields that were already initialized are dropped.
+///
+/// Arrays are deserialized from sequences element by element, so arrays of any length are
+/// supported.
+///
+/// ## Safety
+/// When `deserialize_uninit` returns `Ok`, `slot` must be fully initialized and the returned
+/// reference must point to it.
+pub unsafe trait DeserializeUninit<'de>: Sized {
+    /// Initialize `slot` from `deserializer`.
+    fn deserialize_uninit<'s, D: Deserializer<'de>>(
+        slot: &'s mut MaybeUninit<Self>,
+        deserializer: D,
+    ) -> Result<&'s mut Self, D::Error>;
+}
+
+unsafe impl<'de, T: Deserialize<'de>, const N: usize> DeserializeUninit<'de> for [T; N] {
+    fn deserialize_uninit<'s, D: Deserializer<'de>>(
+        slot: &'s mut MaybeUninit<Self>,
+        deserializer: D,
+    ) -> Result<&'s mut Self, D::Error> {
+        struct ArrayVisitor<T, const N: usize>(*mut T);
+
+        impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+            type Value = ();
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length {}", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+                let mut guard = PrefixDropGuard {
+                    ptr: self.0,
+                    len: 0,
+                };
+                while guard.len < N {
+                    match seq.next_element()? {
+                        Some(elem) => unsafe { self.0.add(guard.len).write(elem) },
+                        None => return Err(A::Error::invalid_length(guard.len, &self)),
+                    }
+                    guard.len += 1;
+                }
+                core::mem::forget(guard);
+                #[cfg(feature = "shadow")]
+                crate::shadow::mark_init(self.0 as *const u8, N * core::mem::size_of::<T>());
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_tuple(N, ArrayVisitor::<T, N>(slot.as_mut_ptr() as *mut T))?;
+        Ok(unsafe { &mut *slot.as_mut_ptr() })
+    }
+}
+
+/// Deserialize a value of type `T` directly inside `slot`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{deserialize_uninit, DeserializeUninit, ProjectUninit};
+///
+/// #[derive(ProjectUninit, DeserializeUninit)]
+/// struct Config {
+///     name: String,
+///     #[uninit(in_place)]
+///     table: Table,
+/// }
+///
+/// #[derive(ProjectUninit, DeserializeUninit)]
+/// struct Table {
+///     len: usize,
+///     #[uninit(in_place)]
+///     entries: [u32; 1024],
+/// }
+///
+/// let json = format!(r#"{{ "name": "x", "table": {{ "len": 2, "entries": {:?} }} }}"#, [7; 1024]);
+/// let mut slot = Box::new(MaybeUninit::<Config>::uninit());
+/// let mut de = serde_json::Deserializer::from_str(&json);
+/// let config = deserialize_uninit(&mut slot, &mut de).unwrap();
+/// assert_eq!(config.name, "x");
+/// assert_eq!(config.table.entries[1023], 7);
+/// ```
+pub fn deserialize_uninit<'de, 's, T: DeserializeUninit<'de>, D: Deserializer<'de>>(
+    slot: &'s mut MaybeUninit<T>,
+    deserializer: D,
+) -> Result<&'s mut T, D::Error> {
+    T::deserialize_uninit(slot, deserializer)
+}
+
+/// A [`DeserializeSeed`] that deserializes a `T` into a slot, for use inside hand-written
+/// visitors.
+pub struct UninitSeed<'s, T>(pub &'s mut MaybeUninit<T>);
+
+impl<'de, 's, T: DeserializeUninit<'de>> DeserializeSeed<'de> for UninitSeed<'s, T> {
+    type Value = &'s mut T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<&'s mut T, D::Error> {
+        T::deserialize_uninit(self.0, deserializer)
+    }
+}
+
+/// Implemented by `#[derive(DeserializeUninit)]` to deserialize fields by index.
+///
+/// ## Safety
+/// When `deserialize_field` returns `Ok`, the field at `index` must be initialized.
+#[doc(hidden)]
+pub unsafe trait DeserializeFields<'de>: UninitFields {
+    const NAME: &'static str;
+    /// The names of the fields, or an empty slice for tuple structs.
+    const FIELD_NAMES: &'static [&'static str];
+
+    fn deserialize_field<D: Deserializer<'de>>(
+        tracker: &mut InitTracker<'_, Self>,
+        index: usize,
+        deserializer: D,
+    ) -> Result<(), D::Error>;
+}
+
+#[doc(hidden)]
+pub fn deserialize_struct<'de, 's, T: DeserializeFields<'de>, D: Deserializer<'de>>(
+    slot: &'s mut MaybeUninit<T>,
+    deserializer: D,
+) -> Result<&'s mut T, D::Error> {
+    let mut tracker = InitTracker::new(slot);
+    let visitor = StructVisitor {
+        tracker: &mut tracker,
+    };
+    if T::FIELD_NAMES.is_empty() && !T::FIELDS.is_empty() {
+        deserializer.deserialize_tuple_struct(T::NAME, T::FIELDS.len(), visitor)?;
+    } else {
+        deserializer.deserialize_struct(T::NAME, T::FIELD_NAMES, visitor)?;
+    }
+    tracker.finish().map_err(|tracker| {
+        let index = (0..T::FIELDS.len()).find(|&i| !tracker.is_init(i)).unwrap();
+        match T::FIELD_NAMES.get(index) {
+            Some(name) => D::Error::missing_field(name),
+            None => D::Error::invalid_length(index, &"a tuple struct with more fields"),
+        }
+    })
+}
+
+struct StructVisitor<'a, 's, T: UninitFields> {
+    tracker: &'a mut InitTracker<'s, T>,
+}
+
+impl<'de, T: DeserializeFields<'de>> Visitor<'de> for StructVisitor<'_, '_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "struct {}", T::NAME)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        for index in 0..T::FIELDS.len() {
+            let seed = FieldSeed {
+                tracker: &mut *self.tracker,
+                index,
+            };
+            if seq.next_element_seed(seed)?.is_none() {
+                return Err(A::Error::invalid_length(index, &self));
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key_seed(KeySeed::<T>(PhantomData))? {
+            match key {
+                Some(index) if self.tracker.is_init(index) => {
+                    return Err(A::Error::duplicate_field(T::FIELD_NAMES[index]));
+                }
+                Some(index) => {
+                    map.next_value_seed(FieldSeed {
+                        tracker: &mut *self.tracker,
+                        index,
+                    })?;
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct FieldSeed<'a, 's, T: UninitFields> {
+    tracker: &'a mut InitTracker<'s, T>,
+    index: usize,
+}
+
+impl<'de, T: DeserializeFields<'de>> DeserializeSeed<'de> for FieldSeed<'_, '_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        T::deserialize_field(self.tracker, self.index, deserializer)?;
+        // fields deserialized in place may leave their padding unwritten
+        #[cfg(feature = "shadow")]
+        {
+            let field = &T::FIELDS[self.index];
+            let base = self.tracker.slot().as_ptr() as *const u8;
+            crate::shadow::mark_init(unsafe { base.add(field.offset()) }, field.size());
+        }
+        unsafe { self.tracker.set_init(self.index) };
+        Ok(())
+    }
+}
+
+// Deserializes a field name into its index, or `None` for unknown fields.
+struct KeySeed<T>(PhantomData<T>);
+
+impl<'de, T: DeserializeFields<'de>> DeserializeSeed<'de> for KeySeed<T> {
+    type Value = Option<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<usize>, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de, T: DeserializeFields<'de>> Visitor<'de> for KeySeed<T> {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a field identifier")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Option<usize>, E> {
+        Ok(Some(v as usize).filter(|&i| i < T::FIELD_NAMES.len()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Option<usize>, E> {
+        Ok(T::FIELD_NAMES.iter().position(|&name| name == v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Option<usize>, E> {
+        Ok(T::FIELD_NAMES.iter().position(|&name| name.as_bytes() == v))
+    }
+}
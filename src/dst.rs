@@ -0,0 +1,182 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use crate::utils::Lifetime;
+
+/// A mutable reference to possibly uninitialized memory for a dynamically sized struct, such as
+/// one ending with a slice: `struct Packet { len: usize, data: [u8] }`.
+///
+/// `MaybeUninit<T>` requires `T: Sized`, so this takes its place for structs with an unsized tail
+/// field. It is created from a fat pointer, which carries the length of the tail, and its fields
+/// are projected with [`project_uninit_dst!`](crate::project_uninit_dst).
+pub struct UninitDst<'a, T: ?Sized> {
+    ptr: NonNull<T>,
+    _lt: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: ?Sized> UninitDst<'a, T> {
+    /// **Unsafe:** Wrap a pointer to possibly uninitialized memory for a `T`.
+    ///
+    /// For structs ending with a slice, the pointer is usually made by casting a slice pointer
+    /// with the length of the tail, like
+    /// `ptr::slice_from_raw_parts_mut(base, len) as *mut Packet`.
+    ///
+    /// ## Safety
+    /// `ptr` must be non-null, aligned for `T`, and valid for reads and writes of the size of `T`
+    /// given by its metadata, for all of `'a`, and no other pointer may access that memory
+    /// during `'a`.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            _lt: PhantomData,
+        }
+    }
+
+    /// Get a raw pointer to the struct, with the same metadata it was created with.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// **Unsafe:** Get a mutable reference to the struct, assuming it is initialized.
+    ///
+    /// ## Safety
+    /// Every field of the struct, including each element of its tail, must be initialized.
+    pub unsafe fn assume_init(self) -> &'a mut T {
+        &mut *self.ptr.as_ptr()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for UninitDst<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UninitDst").field(&self.ptr).finish()
+    }
+}
+
+/// A field of a dynamically sized struct, which may itself be unsized.
+///
+/// This maps each field type to the type of a possibly uninitialized reference to it:
+/// `MaybeUninit<F>` for sized fields, and a slice of `MaybeUninit` for slices and `str`.
+///
+/// ## Safety
+/// `uninit_ptr` must return the same pointer it is given, with the metadata of `Uninit` describing
+/// the same memory.
+pub unsafe trait UninitPlace {
+    /// The possibly uninitialized version of the field.
+    type Uninit: ?Sized;
+
+    #[doc(hidden)]
+    fn uninit_ptr(ptr: *mut Self) -> *mut Self::Uninit;
+}
+
+unsafe impl<T> UninitPlace for T {
+    type Uninit = MaybeUninit<T>;
+
+    fn uninit_ptr(ptr: *mut T) -> *mut MaybeUninit<T> {
+        ptr as *mut MaybeUninit<T>
+    }
+}
+
+unsafe impl<T> UninitPlace for [T] {
+    type Uninit = [MaybeUninit<T>];
+
+    fn uninit_ptr(ptr: *mut [T]) -> *mut [MaybeUninit<T>] {
+        ptr as *mut [MaybeUninit<T>]
+    }
+}
+
+unsafe impl UninitPlace for str {
+    type Uninit = [MaybeUninit<u8>];
+
+    fn uninit_ptr(ptr: *mut str) -> *mut [MaybeUninit<u8>] {
+        ptr as *mut [MaybeUninit<u8>]
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn uninit_place<'a, F: UninitPlace + ?Sized>(
+    ptr: *mut F,
+    _lt: Lifetime<'a>,
+) -> &'a mut F::Uninit {
+    &mut *F::uninit_ptr(ptr)
+}
+
+/// Obtain `&mut MaybeUninit<_>` references to the sized fields of a dynamically sized struct in
+/// an [`UninitDst`], and a `&mut [MaybeUninit<_>]` reference to its unsized tail.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// statically ensures that multiple references to the same value are not returned.
+/// A `str` tail is projected as `&mut [MaybeUninit<u8>]`, and must be valid UTF-8 once
+/// initialized.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::ptr;
+/// use project_uninit::{project_uninit_dst, UninitDst};
+///
+/// #[repr(C)]
+/// struct Packet { kind: u16, len: u16, data: [u8] }
+///
+/// // room for the header and 12 bytes of data, aligned for `Packet`
+/// let mut buf = [MaybeUninit::<u16>::uninit(); 8];
+/// let ptr = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, 12) as *mut Packet;
+/// let mut slot = unsafe { UninitDst::from_raw(ptr) };
+///
+/// let (kind, len, data) = project_uninit_dst!(slot => { kind, len, data });
+/// kind.write(1);
+/// len.write(data.len() as u16);
+/// for (i, byte) in data.iter_mut().enumerate() {
+///     byte.write(i as u8);
+/// }
+///
+/// let packet: &mut Packet = unsafe { slot.assume_init() };
+/// assert_eq!((packet.kind, packet.len, packet.data[11]), (1, 12, 11));
+/// ```
+#[macro_export]
+macro_rules! project_uninit_dst {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut $crate::UninitDst<'_, _> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        #[allow(unused_unsafe)]
+        let fields = unsafe {
+            ($(
+                $crate::utils::uninit_place(
+                    ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                    lt,
+                ),
+            )*)
+        };
+        fields
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_uninit_dst!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::{project_uninit_dst, UninitDst};
+/// struct Packet { len: usize, data: [u8] }
+/// fn f(mut slot: UninitDst<'_, Packet>) {
+///     let (data, data2) = project_uninit_dst!(slot => { data, data });
+/// }
+///```
+fn _project_dst_same_field_twice_fails() {}
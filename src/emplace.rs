@@ -0,0 +1,179 @@
+use core::alloc::Layout;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+/// An allocator that values can be constructed in place in, by initializing them with a closure
+/// as with [`init_with`](crate::init_with).
+///
+/// This gives one interface for in-place construction into any allocator: it is implemented for
+/// [`Heap`], which returns a `Box`, for [`bumpalo::Bump`](::bumpalo::Bump) with the `bumpalo`
+/// feature, and for [`FnArena`], which adapts any function that allocates memory for the
+/// lifetime of the arena. Other arenas can implement it directly.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, Emplace};
+///
+/// struct Frame { pixels: [[u8; 4]; 1024], index: u32 }
+///
+/// fn render<A: Emplace>(arena: &A, index: u32) -> A::Handle<'_, Frame> {
+///     arena.emplace_with(|slot: &mut MaybeUninit<Frame>| {
+///         partial_init!(slot => { pixels: [[0; 4]; 1024], index: index });
+///         // every field is initialized
+///         unsafe { slot.assume_init_mut() }
+///     })
+/// }
+///
+/// # #[cfg(feature = "alloc")] {
+/// let frame: Box<Frame> = render(&project_uninit::Heap, 3);
+/// assert_eq!(frame.index, 3);
+/// # }
+/// ```
+pub trait Emplace {
+    /// The owner of, or reference to, a value allocated with [`emplace_with`](Emplace::emplace_with).
+    type Handle<'a, T: 'a>
+    where
+        Self: 'a;
+
+    /// Allocate an uninitialized `T`, and initialize it in place with `init`, which is given the
+    /// uninitialized slot and must return a reference to that same slot, initialized.
+    ///
+    /// If `init` panics, the fields it initialized are leaked.
+    ///
+    /// ## Panics
+    /// If `init` returns a reference to something other than the slot it was given, or if the
+    /// allocation fails.
+    fn emplace_with<'a, T: 'a>(
+        &'a self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> Self::Handle<'a, T>;
+}
+
+/// Initialize the `T` at `ptr` with `init`, for implementations of [`Emplace`] for arenas.
+///
+/// ## Safety
+/// `ptr` must be valid for reads and writes of a `T` for `'a`, and not accessed other than
+/// through the returned reference in that time.
+unsafe fn emplace_at<'a, T>(
+    ptr: NonNull<T>,
+    init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+) -> &'a mut T {
+    let slot = &mut *(ptr.as_ptr() as *mut MaybeUninit<T>);
+    crate::poison::poison_new(slot);
+    crate::init_with::init_slot(slot, init);
+    slot.assume_init_mut()
+}
+
+/// The global heap, which [`Emplace`]s values in a `Box`, as with
+/// [`boxed_init_with`](crate::boxed_init_with).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Heap;
+
+#[cfg(feature = "alloc")]
+impl Emplace for Heap {
+    type Handle<'a, T: 'a> = alloc::boxed::Box<T>;
+
+    fn emplace_with<'a, T: 'a>(
+        &'a self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> alloc::boxed::Box<T> {
+        crate::boxed_init_with(init)
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl Emplace for ::bumpalo::Bump {
+    type Handle<'a, T: 'a> = &'a mut T;
+
+    /// The value is never dropped by the arena, as with `Bump::alloc`.
+    fn emplace_with<'a, T: 'a>(
+        &'a self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> &'a mut T {
+        let ptr = self.alloc_layout(Layout::new::<T>());
+        unsafe { emplace_at(ptr.cast(), init) }
+    }
+}
+
+/// An adapter that implements [`Emplace`] with a function that allocates memory for a
+/// [`Layout`], for arenas and allocators that don't implement it themselves.
+///
+/// Values are returned as `&mut T` references that borrow the adapter, and are never dropped.
+///
+/// ## Example
+/// ```
+/// use core::alloc::Layout;
+/// use core::cell::Cell;
+/// use core::mem::MaybeUninit;
+/// use core::ptr::NonNull;
+/// use project_uninit::{Emplace, FnArena};
+///
+/// // a fixed buffer handing out memory from its start
+/// #[repr(align(16))]
+/// struct Buffer([MaybeUninit<u8>; 256]);
+/// let mut buffer = Buffer([MaybeUninit::uninit(); 256]);
+/// let base = buffer.0.as_mut_ptr();
+/// let used = Cell::new(0usize);
+///
+/// let arena = unsafe {
+///     FnArena::new(|layout: Layout| {
+///         let start = used.get().next_multiple_of(layout.align());
+///         let end = start.checked_add(layout.size()).filter(|&end| end <= 256)?;
+///         used.set(end);
+///         NonNull::new(base.add(start) as *mut u8)
+///     })
+/// };
+/// let pair = arena.emplace_with(|slot| slot.write((1u64, 2u32)));
+/// let word = arena.emplace_with(|slot| slot.write(3u16));
+/// pair.1 += *word as u32;
+/// assert_eq!(*pair, (1, 5));
+/// assert_eq!(used.get(), 18);
+/// ```
+pub struct FnArena<F> {
+    alloc: F,
+}
+
+impl<F: Fn(Layout) -> Option<NonNull<u8>>> FnArena<F> {
+    /// Create an adapter that allocates memory with `alloc`, which returns `None` if the
+    /// allocation fails.
+    ///
+    /// ## Safety
+    /// The memory returned by `alloc` must fit the layout it is given, and must stay valid, and
+    /// not be accessed by anything else, for as long as the adapter is borrowed by the values
+    /// allocated in it.
+    pub unsafe fn new(alloc: F) -> Self {
+        Self { alloc }
+    }
+}
+
+impl<F: Fn(Layout) -> Option<NonNull<u8>>> Emplace for FnArena<F> {
+    type Handle<'a, T: 'a>
+        = &'a mut T
+    where
+        Self: 'a;
+
+    fn emplace_with<'a, T: 'a>(
+        &'a self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> &'a mut T {
+        let layout = Layout::new::<T>();
+        let ptr = match (self.alloc)(layout) {
+            Some(ptr) => ptr,
+            None => panic!(
+                "arena failed to allocate {} bytes aligned to {}",
+                layout.size(),
+                layout.align()
+            ),
+        };
+        unsafe { emplace_at(ptr.cast(), init) }
+    }
+}
+
+impl<F> fmt::Debug for FnArena<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnArena").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,111 @@
+/// A struct that exports pointers to its fields, so they can be projected by code that can't
+/// access the fields directly, usually implemented with `#[derive(ExportFields)]`.
+///
+/// The derive generates a `FooFieldPtrs` struct next to `Foo`, with a `*mut` pointer to each
+/// field. Each pointer has the same visibility as its field, unless the field is marked with
+/// `#[uninit(export)]`, in which case it is public. This lets the crate that defines a type
+/// decide which of its fields other crates may project and initialize, without making the
+/// fields themselves public.
+///
+/// Exported fields are projected by writing `pub` before the field paths in
+/// [`project_uninit!`](crate::project_uninit), [`project_uninit_mut!`](crate::project_uninit_mut)
+/// and [`partial_init!`](crate::partial_init). Every struct along a nested path must implement
+/// `ExportFields`.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, project_uninit};
+///
+/// mod net {
+///     use core::mem::MaybeUninit;
+///     use project_uninit::ExportFields;
+///
+///     #[derive(ExportFields)]
+///     pub struct Packet {
+///         pub len: u16,
+///         #[uninit(export)]
+///         payload: [u8; 64],
+///         checksum: u32,
+///     }
+///
+///     impl Packet {
+///         pub fn seal(slot: &mut MaybeUninit<Packet>) -> &mut Packet {
+///             let p = slot.as_mut_ptr();
+///             unsafe {
+///                 let sum = (*p).payload.iter().map(|&b| b as u32).sum();
+///                 (*p).checksum = sum;
+///                 slot.assume_init_mut()
+///             }
+///         }
+///
+///         pub fn checksum(&self) -> u32 {
+///             self.checksum
+///         }
+///     }
+/// }
+///
+/// let mut packet = MaybeUninit::<net::Packet>::uninit();
+/// partial_init!(packet => pub { len: 64, payload: [1; 64] });
+/// let len = project_uninit!(packet => pub len);
+/// assert_eq!(unsafe { len.assume_init() }, 64);
+/// assert_eq!(net::Packet::seal(&mut packet).checksum(), 64);
+/// # }
+/// ```
+///
+/// ## Safety
+/// `field_ptrs` must return pointers to the fields of the struct `this` points to, each in a
+/// field of `Ptrs` with the same name and type as the field it points to, wrapped in `*mut`.
+pub unsafe trait ExportFields {
+    /// A struct with a pointer to each field.
+    type Ptrs;
+
+    /// Get pointers to the fields of the struct at `this`, without reading it.
+    ///
+    /// ## Safety
+    /// `this` must point to an allocation that is large enough for `Self`, but it doesn't need
+    /// to be initialized.
+    unsafe fn field_ptrs(this: *mut Self) -> Self::Ptrs;
+}
+
+// Get a pointer to an exported field, following each field of the path through
+// `ExportFields::field_ptrs`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_ptr {
+    (@ ($ptr:expr) * $(=> $rest:tt)*) => {
+        $crate::__export_ptr!(@ ($crate::utils::wrapped_ptr($ptr)) $($rest)=>*)
+    };
+    (@ ($ptr:expr) $next:tt $(=> $rest:tt)*) => {
+        $crate::__export_ptr!(@ ($crate::utils::export_field_ptrs($ptr).$next) $($rest)=>*)
+    };
+    (@ ($ptr:expr)) => {
+        $ptr
+    };
+    ($ptr:expr, $($props:tt)=>+) => {
+        $crate::__export_ptr!(@ ($ptr) $($props)=>+)
+    };
+}
+
+///```compile_fail
+/// mod inner {
+///     use project_uninit::ExportFields;
+///     #[derive(ExportFields)]
+///     pub struct Foo { pub a: u32, b: u32 }
+/// }
+/// let mut x = core::mem::MaybeUninit::<inner::Foo>::uninit();
+/// project_uninit::partial_init!(x => pub b = 1);
+///```
+fn _export_private_field_fails() {}
+
+///```compile_fail
+/// mod inner {
+///     use project_uninit::ExportFields;
+///     #[derive(ExportFields)]
+///     pub struct Foo { pub a: u32, pub b: u32 }
+/// }
+/// let mut x = core::mem::MaybeUninit::<inner::Foo>::uninit();
+/// let (a, a2) = project_uninit::project_uninit_mut!(x => pub { a, a });
+///```
+fn _export_same_field_twice_fails() {}
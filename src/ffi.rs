@@ -0,0 +1,320 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ptr;
+
+/// A `#[repr(C)]` struct with a field table that can be passed across a dynamic library boundary,
+/// usually implemented with `#[derive(FfiFields)]`.
+///
+/// Each field records the layout version it was added in, set with `#[uninit(since = N)]`
+/// (0 by default). The version of the layout is the highest version of any field.
+///
+/// This is enabled by the `ffi` feature rather than an `abi_stable` feature, and doesn't depend on
+/// the `abi_stable` crate: a layout describes plain `#[repr(C)]` structs, including ones with
+/// `align(N)` or `packed(N)`, and `StableAbi` types are only supported if they are one of those.
+///
+/// ## Safety
+/// `LAYOUT` must describe every field of the struct with its correct offset and size, and its
+/// size and alignment must be those of the struct.
+pub unsafe trait FfiFields: Sized {
+    const LAYOUT: FfiLayout;
+}
+
+/// A field in an [`FfiLayout`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FfiField {
+    name: *const u8,
+    name_len: usize,
+    offset: usize,
+    size: usize,
+    since: u32,
+}
+
+impl FfiField {
+    /// Describe a field with the given name, offset and size in bytes, added in layout version
+    /// `since`.
+    pub const fn new(name: &'static str, offset: usize, size: usize, since: u32) -> Self {
+        Self {
+            name: name.as_ptr(),
+            name_len: name.len(),
+            offset,
+            size,
+            since,
+        }
+    }
+
+    /// The name of the field.
+    pub fn name(&self) -> &str {
+        unsafe {
+            core::str::from_utf8_unchecked(core::slice::from_raw_parts(self.name, self.name_len))
+        }
+    }
+
+    /// Offset of the field in bytes from the start of the struct.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Size of the field in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The layout version in which the field was added.
+    pub fn since(&self) -> u32 {
+        self.since
+    }
+}
+
+/// An FFI-stable description of a struct's fields, exported by the module that defines the
+/// struct.
+///
+/// A host that was built against a newer version of a struct can use the layout exported by an
+/// older plugin to initialize exactly the fields the plugin knows about, with [`FfiInit`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FfiLayout {
+    version: u32,
+    size: usize,
+    align: usize,
+    fields: *const FfiField,
+    field_count: usize,
+}
+
+impl FfiLayout {
+    /// Create a layout from a version, the size and alignment of the struct, and its fields.
+    pub const fn new(version: u32, size: usize, align: usize, fields: &'static [FfiField]) -> Self {
+        Self {
+            version,
+            size,
+            align,
+            fields: fields.as_ptr(),
+            field_count: fields.len(),
+        }
+    }
+
+    /// The version of the layout.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The size of the struct in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the struct in bytes.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The fields of the struct.
+    pub fn fields(&self) -> &[FfiField] {
+        unsafe { core::slice::from_raw_parts(self.fields, self.field_count) }
+    }
+
+    /// Find a field by name.
+    pub fn field(&self, name: &str) -> Option<(usize, &FfiField)> {
+        self.fields().iter().enumerate().find(|(_, f)| f.name() == name)
+    }
+
+    /// Check that every field of this layout that `other` also has, by name, is at the same
+    /// offset and has the same size in both.
+    ///
+    /// A host can use this to check the layout exported by a plugin against its own version of the
+    /// struct, before reading fields from it with their host types.
+    /// Errors refer to fields by their index in `self`.
+    pub fn check_compatible(&self, other: &FfiLayout) -> Result<(), FfiError> {
+        for (index, field) in self.fields().iter().enumerate() {
+            let (_, theirs) = match other.field(field.name()) {
+                Some(theirs) => theirs,
+                None => continue,
+            };
+            if theirs.offset != field.offset {
+                return Err(FfiError::OffsetMismatch {
+                    index,
+                    expected: field.offset,
+                    found: theirs.offset,
+                });
+            }
+            if theirs.size != field.size {
+                return Err(FfiError::SizeMismatch {
+                    index,
+                    expected: field.size,
+                    found: theirs.size,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error from [`FfiInit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FfiError {
+    /// The value written to the field at `index` has a different size than the field, or the
+    /// field has a different size in another layout.
+    SizeMismatch {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The field at `index` has a different offset in another layout.
+    OffsetMismatch {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The field at `index` was never initialized.
+    Missing { index: usize },
+}
+
+impl fmt::Display for FfiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SizeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field {} has size {}, but a value of size {} was written",
+                index, expected, found
+            ),
+            Self::OffsetMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field {} has offset {}, but another layout has it at offset {}",
+                index, expected, found
+            ),
+            Self::Missing { index } => write!(f, "field {} was not initialized", index),
+        }
+    }
+}
+
+/// Initializes a struct owned by another module, such as a dynamically loaded plugin, through a
+/// raw pointer and the [`FfiLayout`] that module exported.
+///
+/// Fields are written by name with unaligned copies and tracked, so [`finish`](FfiInit::finish)
+/// can check that every field in the layout was initialized. Writes to fields that the layout
+/// doesn't contain, because they were added in a newer version than the other module was built
+/// with, are skipped.
+/// Values are copied bytewise, so field types should be plain `#[repr(C)]` data.
+/// Supports layouts with at most 64 fields.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "ffi")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{FfiFields, FfiInit};
+///
+/// // the plugin's version of the struct
+/// #[derive(FfiFields)]
+/// #[repr(C)]
+/// struct Config { id: u32, scale: f32 }
+///
+/// let mut config = MaybeUninit::<Config>::uninit();
+/// let layout = &Config::LAYOUT;
+///
+/// // the host, which also knows about a field added in version 2
+/// let mut init = unsafe { FfiInit::new(config.as_mut_ptr() as *mut u8, layout) };
+/// assert_eq!(init.write("id", 7u32), Ok(true));
+/// assert_eq!(init.write("scale", 1.5f32), Ok(true));
+/// assert_eq!(init.write("threads", 4u32), Ok(false));
+/// init.finish().unwrap();
+///
+/// let config = unsafe { config.assume_init() };
+/// assert_eq!((config.id, config.scale), (7, 1.5));
+/// # }
+/// ```
+pub struct FfiInit<'a> {
+    base: *mut u8,
+    layout: &'a FfiLayout,
+    init: u64,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> FfiInit<'a> {
+    /// Track the initialization of the struct at `base`, described by `layout`.
+    ///
+    /// ## Safety
+    /// `base` must be valid for reads and writes of `layout.size()` bytes for `'a`, and `layout`
+    /// must correctly describe the fields of the struct.
+    pub unsafe fn new(base: *mut u8, layout: &'a FfiLayout) -> Self {
+        assert!(
+            layout.fields().len() <= 64,
+            "FfiInit supports layouts with at most 64 fields"
+        );
+        Self {
+            base,
+            layout,
+            init: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The layout of the struct.
+    pub fn layout(&self) -> &'a FfiLayout {
+        self.layout
+    }
+
+    /// Whether the field at `index` has been initialized.
+    pub fn is_init(&self, index: usize) -> bool {
+        self.init & (1 << index) != 0
+    }
+
+    /// Write `value` to the field named `name`.
+    ///
+    /// Returns `Ok(false)` without writing anything if the layout has no such field.
+    pub fn write<F: Copy>(&mut self, name: &str, value: F) -> Result<bool, FfiError> {
+        let (index, field) = match self.layout.field(name) {
+            Some(field) => field,
+            None => return Ok(false),
+        };
+        if field.size != size_of::<F>() {
+            return Err(FfiError::SizeMismatch {
+                index,
+                expected: field.size,
+                found: size_of::<F>(),
+            });
+        }
+        unsafe { ptr::write_unaligned(self.base.add(field.offset) as *mut F, value) };
+        self.init |= 1 << index;
+        Ok(true)
+    }
+
+    /// Read the field named `name`, if the layout has such a field and it has been initialized.
+    ///
+    /// ## Safety
+    /// `F` must be the type of the field.
+    pub unsafe fn read<F: Copy>(&self, name: &str) -> Option<F> {
+        let (index, field) = self.layout.field(name)?;
+        if !self.is_init(index) || field.size != size_of::<F>() {
+            return None;
+        }
+        Some(ptr::read_unaligned(self.base.add(field.offset) as *const F))
+    }
+
+    /// The fields that have not been initialized.
+    pub fn missing(&self) -> impl Iterator<Item = &'a FfiField> + '_ {
+        let layout = self.layout;
+        layout
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| !self.is_init(*i))
+            .map(|(_, f)| f)
+    }
+
+    /// Check that every field in the layout has been initialized.
+    pub fn finish(self) -> Result<(), FfiError> {
+        match (0..self.layout.fields().len()).find(|&i| !self.is_init(i)) {
+            Some(index) => Err(FfiError::Missing { index }),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+/// Describes a single field of a struct implementing [`UninitFields`].
+#[derive(Clone, Copy, Debug)]
+pub struct FieldInfo {
+    name: &'static str,
+    offset: usize,
+    size: usize,
+    drop: unsafe fn(*mut u8),
+}
+
+impl FieldInfo {
+    /// Describe a field of type `F` with the given name and byte offset.
+    pub const fn new<F>(name: &'static str, offset: usize) -> Self {
+        Self {
+            name,
+            offset,
+            size: mem::size_of::<F>(),
+            drop: drop_erased::<F>,
+        }
+    }
+
+    /// The name of the field, or its index for tuple structs.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Offset of the field in bytes from the start of the struct.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Size of the field in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Drop the field in place, given a pointer to the struct that contains it.
+    ///
+    /// ## Safety
+    /// `base` must point to a struct described by this field, and the field must be initialized.
+    pub unsafe fn drop_in_place(&self, base: *mut u8) {
+        let field = base.add(self.offset);
+        (self.drop)(field);
+        crate::poison::deinit(field, self.size);
+    }
+}
+
+unsafe fn drop_erased<F>(p: *mut u8) {
+    ptr::drop_in_place(p as *mut F)
+}
+
+/// Metadata describing every field of a struct, usually generated by
+/// `#[derive(ProjectUninit)]`.
+///
+/// ## Safety
+/// `FIELDS` must contain exactly one entry for each field of the struct, in declaration order,
+/// with the correct offset and type.
+pub unsafe trait UninitFields: Sized {
+    const FIELDS: &'static [FieldInfo];
+
+    /// Find the index of the field with the given name.
+    fn field_index(name: &str) -> Option<usize> {
+        Self::FIELDS.iter().position(|f| f.name == name)
+    }
+}
+
+/// The offset in bytes of `field` from `base`, computed from their addresses without exposing
+/// their provenance.
+///
+/// ## Panics
+/// If `field` is before `base`.
+pub fn field_offset<T: ?Sized, F: ?Sized>(base: *const T, field: *const F) -> usize {
+    field
+        .cast::<u8>()
+        .addr()
+        .checked_sub(base.cast::<u8>().addr())
+        .expect("the field must not be before the start of the struct")
+}
+
+/// **Unsafe:** Obtain a `&MaybeUninit<F>` reference to the field of `slot` at the given byte
+/// offset.
+///
+/// `#[derive(ProjectUninit)]` defines a `const fn offset_of_<field>()` for each field of a struct,
+/// with the same offsets as its [`UninitFields`] metadata, so offsets stored in tables, like
+/// serialization schemas or FFI descriptors, can be turned back into projections.
+///
+/// ## Safety
+/// `offset` must be the offset of a field of type `F` in `T`.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, project_by_offset, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Header { magic: u32, len: u16, flags: u16 }
+///
+/// let mut header = MaybeUninit::<Header>::uninit();
+/// partial_init!(header => len = 12);
+/// let len = unsafe { project_by_offset::<_, u16>(&header, Header::offset_of_len()) };
+/// assert_eq!(unsafe { len.assume_init() }, 12);
+/// # }
+/// ```
+pub unsafe fn project_by_offset<T, F>(slot: &MaybeUninit<T>, offset: usize) -> &MaybeUninit<F> {
+    debug_assert!(offset + mem::size_of::<F>() <= mem::size_of::<T>());
+    &*(slot.as_ptr().cast::<u8>().add(offset) as *const MaybeUninit<F>)
+}
+
+/// **Unsafe:** Obtain a `&mut MaybeUninit<F>` reference to the field of `slot` at the given byte
+/// offset.
+///
+/// See [`project_by_offset`].
+///
+/// ## Safety
+/// `offset` must be the offset of a field of type `F` in `T`.
+pub unsafe fn project_by_offset_mut<T, F>(
+    slot: &mut MaybeUninit<T>,
+    offset: usize,
+) -> &mut MaybeUninit<F> {
+    debug_assert!(offset + mem::size_of::<F>() <= mem::size_of::<T>());
+    &mut *(slot.as_mut_ptr().cast::<u8>().add(offset) as *mut MaybeUninit<F>)
+}
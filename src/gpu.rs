@@ -0,0 +1,215 @@
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ptr;
+
+/// A struct with an explicit byte layout for GPU buffers, usually implemented with
+/// `#[derive(GpuLayout)]`.
+///
+/// The GPU layout is independent of the Rust layout: each field is placed at the offset given
+/// by `#[gpu(offset = N)]`, or else after the previous field, rounded up to the field's
+/// alignment or to `#[gpu(align = N)]`. This is how std140/std430-style padding, such as 16-byte
+/// aligned `vec3`s, is declared.
+/// The size of the struct is the end of its last field rounded up to the largest field alignment,
+/// or to `#[gpu(align = N)]` on the struct.
+///
+/// ## Safety
+/// `SIZE` must be at least the end of every field in `FIELDS`, and `write_to` must write
+/// only within the first `SIZE` bytes of `dst`.
+pub unsafe trait GpuLayout: Copy {
+    /// The size of the struct in a GPU buffer, including padding.
+    const SIZE: usize;
+    /// The fields of the struct, in declaration order.
+    const FIELDS: &'static [GpuField];
+
+    /// Write every field to its offset from `dst`.
+    ///
+    /// ## Safety
+    /// `dst` must be valid for writes of `SIZE` bytes. It doesn't need to be aligned.
+    unsafe fn write_to(&self, dst: *mut u8);
+}
+
+/// Where a field of a [`GpuLayout`] struct is placed in a GPU buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GpuField {
+    name: &'static str,
+    offset: usize,
+    size: usize,
+}
+
+impl GpuField {
+    /// Describe a field with the given name, offset, and size in bytes.
+    pub const fn new(name: &'static str, offset: usize, size: usize) -> Self {
+        Self { name, offset, size }
+    }
+
+    /// The name of the field.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Offset of the field in bytes from the start of the struct in the buffer.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Size of the field in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// Writes [`GpuLayout`] values into mapped buffer memory through a raw pointer.
+///
+/// Every write is an unaligned copy through the pointer, so no reference to the mapping is ever
+/// created, which matters for memory that the GPU or driver may access concurrently. Padding
+/// bytes between fields are left untouched.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "gpu")] {
+/// use project_uninit::{GpuLayout, StagingWriter};
+///
+/// #[derive(GpuLayout, Clone, Copy)]
+/// struct Light {
+///     position: [f32; 3],
+///     #[gpu(align = 16)]
+///     color: [f32; 3],
+///     intensity: f32,
+/// }
+///
+/// assert_eq!(Light::SIZE, 32);
+///
+/// // normally a pointer into a mapped staging buffer
+/// let mut mapping = vec![0u8; 64];
+/// let mut writer = unsafe { StagingWriter::new(mapping.as_mut_ptr(), mapping.len()) };
+/// let light = Light { position: [1.0, 2.0, 3.0], color: [1.0; 3], intensity: 0.5 };
+/// writer.write_slice(0, &[light, light]);
+/// writer.write_field::<Light, f32>(32, "intensity", 2.0);
+///
+/// assert_eq!(mapping[16..20], 1.0f32.to_ne_bytes());
+/// assert_eq!(mapping[60..64], 2.0f32.to_ne_bytes());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StagingWriter<'a> {
+    ptr: *mut u8,
+    len: usize,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> StagingWriter<'a> {
+    /// Create a writer for `len` bytes starting at `ptr`.
+    ///
+    /// ## Safety
+    /// `ptr` must be valid for writes of `len` bytes for `'a`.
+    pub unsafe fn new(ptr: *mut u8, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of bytes the writer covers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the writer covers no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn check(&self, offset: usize, size: usize) {
+        assert!(
+            offset.checked_add(size).is_some_and(|end| end <= self.len),
+            "write of {} bytes at offset {} is out of bounds of a {} byte buffer",
+            size,
+            offset,
+            self.len,
+        );
+    }
+
+    /// Write every field of `value` at `offset` bytes into the buffer.
+    pub fn write<T: GpuLayout>(&mut self, offset: usize, value: &T) {
+        self.check(offset, T::SIZE);
+        unsafe { value.write_to(self.ptr.add(offset)) };
+    }
+
+    /// Write consecutive values starting at `offset` bytes into the buffer, `T::SIZE` bytes apart.
+    pub fn write_slice<T: GpuLayout>(&mut self, offset: usize, values: &[T]) {
+        self.check(offset, T::SIZE * values.len());
+        for (i, value) in values.iter().enumerate() {
+            unsafe { value.write_to(self.ptr.add(offset + i * T::SIZE)) };
+        }
+    }
+
+    /// Write a single field named `name` of a `T` placed at `offset` bytes into the buffer.
+    ///
+    /// Panics if `T` has no such field, or if its size doesn't match `F`.
+    pub fn write_field<T: GpuLayout, F: Copy>(&mut self, offset: usize, name: &str, value: F) {
+        let field = T::FIELDS
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no field named `{}`", name));
+        assert_eq!(field.size, size_of::<F>(), "size mismatch for field `{}`", name);
+        self.check(offset + field.offset, field.size);
+        unsafe { write_unaligned(self.ptr.add(offset + field.offset), &value) };
+    }
+}
+
+/// Copy the bytes of `value` to `dst`.
+///
+/// ## Safety
+/// `dst` must be valid for writes of `size_of::<F>()` bytes.
+#[inline(always)]
+pub unsafe fn write_unaligned<F>(dst: *mut u8, value: &F) {
+    ptr::copy_nonoverlapping(value as *const F as *const u8, dst, size_of::<F>());
+}
+
+const fn align_up(n: usize, align: usize) -> usize {
+    n.div_ceil(align) * align
+}
+
+/// Compute the offsets of fields given as `(name, offset, align, size)`, where an offset of
+/// `None` means the field follows the previous one, and the size of the struct.
+pub const fn gpu_layout<const N: usize>(
+    fields: [(&'static str, Option<usize>, usize, usize); N],
+    struct_align: Option<usize>,
+) -> ([GpuField; N], usize) {
+    let mut out = [GpuField::new("", 0, 0); N];
+    let mut end = 0;
+    let mut max_align = 1;
+    let mut i = 0;
+    while i < N {
+        let (name, offset, align, size) = fields[i];
+        let offset = match offset {
+            Some(offset) => {
+                if offset < end {
+                    panic!("GPU field offset overlaps the previous field");
+                }
+                offset
+            }
+            None => align_up(end, align),
+        };
+        if align > max_align {
+            max_align = align;
+        }
+        out[i] = GpuField::new(name, offset, size);
+        end = offset + size;
+        i += 1;
+    }
+    let struct_align = match struct_align {
+        Some(align) => align,
+        None => max_align,
+    };
+    (out, align_up(end, struct_align))
+}
+
+///```compile_fail
+/// use project_uninit::GpuLayout;
+/// #[derive(GpuLayout, Clone, Copy)]
+/// struct Overlapping { a: [f32; 4], #[gpu(offset = 8)] b: f32 }
+/// let _ = Overlapping::SIZE;
+///```
+fn _gpu_overlapping_offset_fails() {}
@@ -0,0 +1,155 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of, MaybeUninit};
+
+/// An error from validating a range of [`GuestMemory`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuestMemoryError {
+    /// `size` bytes at `offset` don't fit in a memory of `len` bytes.
+    OutOfBounds {
+        offset: usize,
+        size: usize,
+        len: usize,
+    },
+    /// The address at `offset` isn't a multiple of `align`.
+    Misaligned { offset: usize, align: usize },
+}
+
+impl fmt::Display for GuestMemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { offset, size, len } => write!(
+                f,
+                "{} bytes at offset {} are out of bounds of a {} byte guest memory",
+                size, offset, len
+            ),
+            Self::Misaligned { offset, align } => write!(
+                f,
+                "offset {} is not aligned to {} bytes in guest memory",
+                offset, align
+            ),
+        }
+    }
+}
+
+/// The linear memory of a WebAssembly guest, or any other byte range shared with code that
+/// reads it as structured data, seen from the host.
+///
+/// Ranges of the memory can be checked for bounds and alignment and then treated as
+/// `MaybeUninit<T>` slots, so values can be initialized field by field directly in the guest's
+/// memory with [`partial_init!`](crate::partial_init) and friends, without serializing them.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{partial_init, GuestMemory};
+///
+/// #[repr(C)]
+/// struct Request { id: u32, len: u32, flags: u32 }
+///
+/// // stands in for the guest's memory, e.g. from `Memory::data_ptr` and `Memory::data_size`
+/// let mut words = [0u32; 16];
+/// let mut memory = unsafe { GuestMemory::from_raw_parts(words.as_mut_ptr() as *mut u8, 64) };
+///
+/// let slot = unsafe { memory.slot::<Request>(8) }.unwrap();
+/// partial_init!(slot => { id: 1, len: 64, flags: 0 });
+/// assert!(unsafe { memory.slot::<Request>(62) }.is_err());
+/// assert!(unsafe { memory.slot::<Request>(6) }.is_err());
+/// assert_eq!(words[2..5], [1, 64, 0]);
+/// ```
+#[derive(Debug)]
+pub struct GuestMemory<'a> {
+    base: *mut u8,
+    len: usize,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> GuestMemory<'a> {
+    /// Borrow `memory` as guest memory.
+    pub fn new(memory: &'a mut [u8]) -> Self {
+        Self {
+            base: memory.as_mut_ptr(),
+            len: memory.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Treat `len` bytes starting at `base` as guest memory, e.g. a pointer and size from a
+    /// runtime's memory export.
+    ///
+    /// ## Safety
+    /// `base` must be valid for reads and writes of `len` bytes for `'a`, and the memory must not
+    /// be accessed through any other pointer while it is borrowed.
+    pub unsafe fn from_raw_parts(base: *mut u8, len: usize) -> Self {
+        Self {
+            base,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The size of the memory in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the memory is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check that `size` bytes at `offset` are in bounds and that the address at `offset` is a
+    /// multiple of `align`.
+    pub fn check(&self, offset: usize, size: usize, align: usize) -> Result<(), GuestMemoryError> {
+        if offset.checked_add(size).is_none_or(|end| end > self.len) {
+            return Err(GuestMemoryError::OutOfBounds {
+                offset,
+                size,
+                len: self.len,
+            });
+        }
+        if !self.base.addr().wrapping_add(offset).is_multiple_of(align) {
+            return Err(GuestMemoryError::Misaligned { offset, align });
+        }
+        Ok(())
+    }
+
+    /// Treat the `T` at `offset` bytes into the memory as an uninitialized slot, after checking
+    /// that it is in bounds and aligned.
+    ///
+    /// ## Safety
+    /// Writing a `T` leaves any padding bytes in it uninitialized, so if `T` has padding, those
+    /// bytes must not be read as bytes from the Rust side, e.g. through the slice the memory was
+    /// created from, until they have been overwritten.
+    /// The layout of `T` should be `#[repr(C)]` and match the guest's definition.
+    pub unsafe fn slot<T>(
+        &mut self,
+        offset: usize,
+    ) -> Result<&mut MaybeUninit<T>, GuestMemoryError> {
+        self.check(offset, size_of::<T>(), align_of::<T>())?;
+        Ok(&mut *(self.base.add(offset) as *mut MaybeUninit<T>))
+    }
+
+    /// Treat `count` consecutive `T`s at `offset` bytes into the memory as uninitialized slots,
+    /// after checking that they are in bounds and aligned.
+    ///
+    /// ## Safety
+    /// The same requirements as [`slot`](GuestMemory::slot) apply.
+    pub unsafe fn slots<T>(
+        &mut self,
+        offset: usize,
+        count: usize,
+    ) -> Result<&mut [MaybeUninit<T>], GuestMemoryError> {
+        let size = size_of::<T>()
+            .checked_mul(count)
+            .ok_or(GuestMemoryError::OutOfBounds {
+                offset,
+                size: usize::MAX,
+                len: self.len,
+            })?;
+        self.check(offset, size, align_of::<T>())?;
+        Ok(core::slice::from_raw_parts_mut(
+            self.base.add(offset) as *mut MaybeUninit<T>,
+            count,
+        ))
+    }
+}
@@ -0,0 +1,70 @@
+use core::{
+    any::type_name,
+    mem::{size_of, transmute},
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Describes a single field write performed by one of the init macros, such as
+/// [`partial_init!`](crate::partial_init).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitEvent {
+    /// The name of the struct containing the field, as given by [`core::any::type_name`].
+    pub type_name: &'static str,
+    /// The field path as written in the macro invocation, separated by `.` (e.g. `"inner.value2.0"`).
+    pub path: &'static str,
+    /// The size of the field in bytes.
+    pub size: usize,
+}
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Register a function to be called with an [`InitEvent`] every time one of the init macros
+/// writes a field, replacing any previously registered function.
+/// Pass `None` to remove the hook.
+///
+/// The hook is global and may be called from any thread.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use project_uninit::{partial_init, set_init_hook, InitEvent};
+///
+/// static BYTES_WRITTEN: AtomicUsize = AtomicUsize::new(0);
+///
+/// set_init_hook(Some(|event: InitEvent| {
+///     BYTES_WRITTEN.fetch_add(event.size, Ordering::Relaxed);
+/// }));
+///
+/// let mut x = MaybeUninit::<(u8, u64)>::uninit();
+/// partial_init!(x => { 0: 1, 1: 2 });
+///
+/// set_init_hook(None);
+/// assert_eq!(BYTES_WRITTEN.load(Ordering::Relaxed), 9);
+/// ```
+pub fn set_init_hook(hook: Option<fn(InitEvent)>) {
+    let ptr = match hook {
+        Some(hook) => hook as *mut (),
+        None => ptr::null_mut(),
+    };
+    HOOK.store(ptr, Ordering::Release);
+}
+
+#[inline]
+pub(crate) fn fire<T, F>(path: &'static str) {
+    fire_sized::<T>(path, size_of::<F>())
+}
+
+#[inline]
+pub(crate) fn fire_sized<T>(path: &'static str, size: usize) {
+    let ptr = HOOK.load(Ordering::Acquire);
+    if !ptr.is_null() {
+        let hook = unsafe { transmute::<*mut (), fn(InitEvent)>(ptr) };
+        hook(InitEvent {
+            type_name: type_name::<T>(),
+            path,
+            size,
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+use core::mem::size_of;
+use core::ptr;
+
+use crate::{FieldInfo, InitTracker, UninitFields};
+
+/// An error from loading or writing the byte image of a struct with an [`InitTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageError {
+    /// The image is `found` bytes long, but the struct is `expected` bytes.
+    SizeMismatch { expected: usize, found: usize },
+    /// The field at `index` is not initialized.
+    Incomplete { index: usize },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SizeMismatch { expected, found } => write!(
+                f,
+                "image is {} bytes, but the struct is {} bytes",
+                found, expected
+            ),
+            Self::Incomplete { index } => write!(f, "field {} is not initialized", index),
+        }
+    }
+}
+
+impl<T: UninitFields> InitTracker<'_, T> {
+    /// Load the byte image of a whole struct, such as a config block read from flash, and mark
+    /// each field whose bytes `valid` accepts as initialized.
+    ///
+    /// Fields that are already initialized are dropped first. Fields that `valid` rejects are left
+    /// uninitialized, to be given new values with [`partial_init!`](crate::partial_init) and
+    /// [`set_init`](InitTracker::set_init). Fields that were accepted can be patched in place
+    /// with [`reinit!`](crate::reinit).
+    ///
+    /// Returns an error if `image` isn't exactly the size of the struct.
+    ///
+    /// ## Safety
+    /// `valid` must only accept bytes that are a valid value of the field's type.
+    ///
+    /// ## Example
+    /// ```
+    /// # #[cfg(feature = "derive")] {
+    /// use core::mem::MaybeUninit;
+    /// use project_uninit::{partial_init, reinit, InitTracker, ProjectUninit};
+    ///
+    /// #[derive(ProjectUninit)]
+    /// #[repr(C)]
+    /// struct Config { baud: u32, retries: u16, mode: u8 }
+    ///
+    /// // the block as it was stored, with a byte of trailing padding
+    /// let mut flash = [0u8; 8];
+    /// flash[0..4].copy_from_slice(&9600u32.to_ne_bytes());
+    /// flash[4..6].copy_from_slice(&3u16.to_ne_bytes());
+    /// flash[6] = 9;
+    /// flash[7] = 0xff;
+    ///
+    /// let mut slot = MaybeUninit::<Config>::uninit();
+    /// let mut config = InitTracker::new(&mut slot);
+    /// // `mode` must be 0, 1, or 2
+    /// unsafe { config.load_image(&flash, |field, bytes| field.name() != "mode" || bytes[0] < 3) }
+    ///     .unwrap();
+    /// assert_eq!(config.missing().next().unwrap().name(), "mode");
+    ///
+    /// unsafe {
+    ///     partial_init!(config.slot_mut() => mode = 0);
+    ///     config.set_init(2);
+    ///     reinit!(config.slot_mut() => baud = 115200);
+    ///     config.write_image(&mut flash).unwrap();
+    /// }
+    /// assert_eq!(flash[0..4], 115200u32.to_ne_bytes());
+    /// assert_eq!(flash[6..8], [0, 0]);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub unsafe fn load_image(
+        &mut self,
+        image: &[u8],
+        mut valid: impl FnMut(&FieldInfo, &[u8]) -> bool,
+    ) -> Result<(), ImageError> {
+        check_size::<T>(image.len())?;
+        for (i, field) in T::FIELDS.iter().enumerate() {
+            self.drop_field(i);
+            let bytes = &image[field.offset()..field.offset() + field.size()];
+            if valid(field, bytes) {
+                let dst = (self.slot_mut().as_mut_ptr() as *mut u8).add(field.offset());
+                ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+                #[cfg(feature = "shadow")]
+                crate::shadow::mark_init(dst, bytes.len());
+                self.set_init(i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the byte image of the struct to `out`, with every byte that isn't part of a field
+    /// set to zero, e.g. to write a config block back to flash.
+    ///
+    /// Returns an error if `out` isn't exactly the size of the struct, or if any field isn't
+    /// initialized.
+    ///
+    /// ## Safety
+    /// The fields must not contain padding bytes themselves.
+    pub unsafe fn write_image(&self, out: &mut [u8]) -> Result<(), ImageError> {
+        check_size::<T>(out.len())?;
+        if let Some(index) = (0..T::FIELDS.len()).find(|&i| !self.is_init(i)) {
+            return Err(ImageError::Incomplete { index });
+        }
+        out.fill(0);
+        let base = self.slot().as_ptr() as *const u8;
+        for field in T::FIELDS {
+            ptr::copy_nonoverlapping(
+                base.add(field.offset()),
+                out.as_mut_ptr().add(field.offset()),
+                field.size(),
+            );
+        }
+        Ok(())
+    }
+}
+
+fn check_size<T>(len: usize) -> Result<(), ImageError> {
+    if len != size_of::<T>() {
+        return Err(ImageError::SizeMismatch {
+            expected: size_of::<T>(),
+            found: len,
+        });
+    }
+    Ok(())
+}
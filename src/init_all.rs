@@ -0,0 +1,77 @@
+/// Initialize every field of a struct wrapped in `MaybeUninit` and return the initialized value.
+///
+/// The struct must derive [`ProjectUninit`](crate::ProjectUninit), and every one of its fields
+/// must be given a value, which is checked at compile time using the field metadata it
+/// generates. Since the struct is known to be fully
+/// initialized, it's read out of the slot without `unsafe`, leaving the slot uninitialized again.
+///
+/// The fields are written in the order they are given, as with
+/// [`partial_init!`](crate::partial_init). Only top-level fields can be given, by name or, for
+/// tuple structs, by index.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_all, ProjectUninit};
+///
+/// #[derive(ProjectUninit, Debug, PartialEq)]
+/// struct Account { id: u64, owner: String, balance: i64 }
+///
+/// let mut slot = MaybeUninit::<Account>::uninit();
+/// let account = init_all!(slot => { owner: "alice".into(), id: 7, balance: 0 });
+/// assert_eq!(account, Account { id: 7, owner: "alice".into(), balance: 0 });
+/// // `init_all!(slot => { id: 7, balance: 0 })` doesn't compile
+/// # }
+/// ```
+#[macro_export]
+macro_rules! init_all {
+    ($expr:expr => {$($field:tt : $val:expr),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+
+        // assert that every field is given a value
+        $crate::utils::check_all_fields(_ref, |fields, state| {
+            $( let state = $crate::utils::set_field(state, fields.$field); )*
+            state
+        });
+
+        #[allow(clippy::unused_unit)]
+        let _ = $crate::partial_init!(_ref => { $($field: $val),* });
+        #[allow(unused_unsafe)]
+        unsafe {
+            _ref.assume_init_read()
+        }
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::{init_all, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Foo { a: u8, b: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let foo = init_all!(x => { a: 1 });
+///```
+fn _init_all_missing_field_fails() {}
+
+///```compile_fail,E0308
+/// use project_uninit::{init_all, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Foo(u8, u8);
+/// // checked while type checking, so even if this is never called
+/// fn _foo() -> Foo {
+///     let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+///     init_all!(x => { 1: 1 })
+/// }
+///```
+#[cfg(feature = "derive")]
+fn _init_all_missing_field_in_unused_fn_fails() {}
+
+///```compile_fail
+/// use project_uninit::init_all;
+/// struct Foo { a: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let foo = init_all!(x => { a: 1 });
+///```
+fn _init_all_requires_field_metadata() {}
@@ -0,0 +1,65 @@
+use core::mem::MaybeUninit;
+
+/// Construct a value by initializing it in place with a closure, without any macros at the call
+/// site.
+///
+/// `init` is given an uninitialized slot, and must return a reference to that same slot,
+/// initialized, as proof that every field was initialized. The references returned by this
+/// crate's macros and functions that finish initializing a whole value, like
+/// [`partial_init!`](crate::partial_init) with `..rest`,
+/// [`InitTracker::finish`](crate::InitTracker::finish), [`Out::write`](crate::Out::write) or
+/// [`UninitSplit::join`](crate::UninitSplit::join), serve as that proof.
+///
+/// If `init` panics, the fields it initialized are leaked.
+///
+/// ## Panics
+/// If `init` returns a reference to something other than the slot it was given.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{init_with, tracked_init, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Endpoint { host: String, port: u16 }
+///
+/// let endpoint = init_with::<Endpoint>(|slot| {
+///     let mut tracker = InitTracker::new(slot);
+///     tracked_init!(tracker => { host: "localhost".into(), port: 8080 });
+///     tracker.finish().ok().unwrap()
+/// });
+/// assert_eq!((endpoint.host.as_str(), endpoint.port), ("localhost", 8080));
+/// # }
+/// ```
+pub fn init_with<T>(init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T) -> T {
+    let mut slot = MaybeUninit::uninit();
+    crate::poison::poison_new(&mut slot);
+    init_slot(&mut slot, init);
+    unsafe { slot.assume_init() }
+}
+
+/// Initialize `slot` with `init`, checking that it returned a reference to `slot`.
+pub(crate) fn init_slot<T>(
+    slot: &mut MaybeUninit<T>,
+    init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+) {
+    let ptr = slot.as_mut_ptr();
+    #[cfg(feature = "shadow")]
+    let _watch = crate::shadow::watch(slot);
+    let value: *const T = init(slot);
+    assert!(
+        core::ptr::eq(value, ptr),
+        "initializer returned a reference to something other than its slot"
+    );
+}
+
+///```compile_fail,E0308
+/// use project_uninit::{init_with, partial_init};
+/// struct Point { x: i32, y: i32 }
+/// // without `unsafe`, an uninitialized slot can't be returned as the proof
+/// let _ = init_with::<Point>(|slot| {
+///     partial_init!(slot => x = 1);
+///     slot
+/// });
+///```
+fn _init_with_uninit_slot_fails() {}
@@ -0,0 +1,131 @@
+use core::fmt;
+
+/// The error produced by a fallible initializer when a failure is injected with
+/// `fail_at_write` (requires the `test-support` feature).
+///
+/// With the `test-support` feature, error types used with
+/// [`try_partial_init!`](crate::try_partial_init) must implement `From<InjectedFailure>`.
+/// Without it, this error is never produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InjectedFailure;
+
+impl fmt::Display for InjectedFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("injected failure")
+    }
+}
+
+impl From<InjectedFailure> for () {
+    fn from(_: InjectedFailure) {}
+}
+
+/// Count a fallible field write, and fail if a failure was injected for this write.
+///
+/// [`try_partial_init!`](crate::try_partial_init) calls this before each field it writes.
+/// Hand-written fallible initializers can call it before each of their writes so that tests can
+/// make them fail at any point with `fail_at_write`.
+/// Without the `test-support` feature, this always returns `Ok`.
+#[inline(always)]
+pub fn check_injected_failure() -> Result<(), InjectedFailure> {
+    #[cfg(feature = "test-support")]
+    if armed::tick() {
+        return Err(InjectedFailure);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "test-support")]
+mod armed {
+    use std::cell::Cell;
+
+    std::thread_local! {
+        // the number of fallible writes left before the injected failure
+        static REMAINING: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    pub(super) fn set(remaining: Option<usize>) {
+        REMAINING.with(|r| r.set(remaining));
+    }
+
+    pub(super) fn get() -> Option<usize> {
+        REMAINING.with(|r| r.get())
+    }
+
+    pub(super) fn tick() -> bool {
+        REMAINING.with(|r| match r.get() {
+            Some(0) => {
+                r.set(None);
+                true
+            }
+            Some(n) => {
+                r.set(Some(n - 1));
+                false
+            }
+            None => false,
+        })
+    }
+}
+
+/// Make the `n`th fallible field write on the current thread fail with [`InjectedFailure`],
+/// counting from zero.
+///
+/// Only writes checked with [`check_injected_failure`], such as those made by
+/// [`try_partial_init!`](crate::try_partial_init), are counted.
+/// The failure is injected once, after which writes succeed again.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{fail_at_write, try_partial_init, InjectedFailure};
+///
+/// struct Point { x: i32, y: i32 }
+///
+/// let mut p = MaybeUninit::<Point>::uninit();
+/// fail_at_write(1);
+/// let result = try_partial_init!(p => { x: Ok::<_, InjectedFailure>(1), y: Ok(2) });
+/// assert_eq!(result.unwrap_err(), InjectedFailure);
+/// ```
+#[cfg(feature = "test-support")]
+pub fn fail_at_write(n: usize) {
+    armed::set(Some(n));
+}
+
+/// Cancel a failure injected with [`fail_at_write`] that hasn't happened yet.
+#[cfg(feature = "test-support")]
+pub fn clear_injected_failure() {
+    armed::set(None);
+}
+
+/// Call `f` repeatedly, injecting a failure at the first fallible write, then at the second, and
+/// so on, until a call completes without reaching the injected failure.
+///
+/// Returns the number of calls in which a failure was injected.
+/// This exercises every error path of an initializer deterministically, e.g. to check that each
+/// one drops the fields initialized so far.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{exhaust_failure_points, try_partial_init, InjectedFailure};
+///
+/// struct Point { x: i32, y: i32 }
+///
+/// let failures = exhaust_failure_points(|| {
+///     let mut p = MaybeUninit::<Point>::uninit();
+///     let _ = try_partial_init!(p => { x: Ok::<_, InjectedFailure>(1), y: Ok(2) });
+/// });
+/// assert_eq!(failures, 2);
+/// ```
+#[cfg(feature = "test-support")]
+pub fn exhaust_failure_points(mut f: impl FnMut()) -> usize {
+    let mut n = 0;
+    loop {
+        fail_at_write(n);
+        f();
+        if armed::get().is_some() {
+            clear_injected_failure();
+            return n;
+        }
+        n += 1;
+    }
+}
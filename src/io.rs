@@ -0,0 +1,245 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr;
+use std::io::{self, Read};
+
+/// A possibly uninitialized byte buffer, such as a byte array field of a struct wrapped in
+/// `MaybeUninit`, that is filled from an [`io::Read`] source in place.
+///
+/// The buffer tracks how many bytes at its start have been filled by the reader, and how many
+/// have been initialized at all. `Read` can't be given uninitialized memory on stable Rust, so
+/// before the first read into each part of the buffer, that part is zeroed in place. Nothing is
+/// read into a temporary buffer and copied, and no byte is zeroed twice across reads.
+///
+/// Use [`read_exact_into!`](crate::read_exact_into) to fill a `[u8; N]` field in one step.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{project_uninit_mut, ReadBuf};
+///
+/// struct Message { len: usize, body: [MaybeUninit<u8>; 64] }
+///
+/// let mut input: &[u8] = b"hello";
+/// let mut message = MaybeUninit::<Message>::uninit();
+/// let (len, body) = project_uninit_mut!(message => { len, body });
+///
+/// // a `[MaybeUninit<u8>; N]` field is always initialized, even if its bytes aren't
+/// let body = unsafe { body.assume_init_mut() };
+/// let mut buf = ReadBuf::new(body);
+/// while buf.read_from(&mut input)? != 0 {}
+/// len.write(buf.len());
+///
+/// let message = unsafe { message.assume_init() };
+/// assert_eq!(message.len, 5);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ReadBuf<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    init: usize,
+}
+
+impl<'a> ReadBuf<'a> {
+    /// An empty buffer over `buf`, which may be uninitialized.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            buf,
+            filled: 0,
+            init: 0,
+        }
+    }
+
+    /// An empty buffer over a possibly uninitialized byte array, such as a field returned by
+    /// [`project_uninit_mut!`](crate::project_uninit_mut).
+    pub fn from_array<const N: usize>(array: &'a mut MaybeUninit<[u8; N]>) -> Self {
+        let ptr = array.as_mut_ptr() as *mut MaybeUninit<u8>;
+        Self::new(unsafe { core::slice::from_raw_parts_mut(ptr, N) })
+    }
+
+    /// The size of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The number of bytes filled by the reader.
+    pub fn len(&self) -> usize {
+        self.filled
+    }
+
+    /// Whether nothing has been read into the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// Whether the whole buffer has been filled.
+    pub fn is_full(&self) -> bool {
+        self.filled == self.buf.len()
+    }
+
+    /// The number of bytes that have been initialized, either by the reader or by zeroing them
+    /// before a read.
+    pub fn init_len(&self) -> usize {
+        self.init
+    }
+
+    /// The bytes filled by the reader.
+    pub fn filled(&self) -> &[u8] {
+        unsafe { &*(&self.buf[..self.filled] as *const [MaybeUninit<u8>] as *const [u8]) }
+    }
+
+    /// The bytes filled by the reader.
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        unsafe { &mut *(&mut self.buf[..self.filled] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// The bytes filled by the reader, for the rest of the buffer's lifetime.
+    pub fn into_filled(self) -> &'a mut [u8] {
+        unsafe { &mut *(&mut self.buf[..self.filled] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// The part of the buffer that hasn't been filled, initialized so it can be passed to
+    /// `Read`.
+    fn unfilled(&mut self) -> &mut [u8] {
+        let len = self.buf.len();
+        if self.init < len {
+            unsafe { ptr::write_bytes(self.buf[self.init..].as_mut_ptr(), 0, len - self.init) };
+            self.init = len;
+        }
+        unsafe { &mut *(&mut self.buf[self.filled..] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Call [`read`](Read::read) once to read into the rest of the buffer, returning the number
+    /// of bytes read, which is `0` at the end of the input or if the buffer is full.
+    pub fn read_from<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
+        if self.is_full() {
+            return Ok(0);
+        }
+        let n = reader.read(self.unfilled())?;
+        assert!(
+            n <= self.buf.len() - self.filled,
+            "`Read::read` returned more bytes than the buffer holds"
+        );
+        self.filled += n;
+        Ok(n)
+    }
+
+    /// Read until the buffer is full, like [`read_exact`](Read::read_exact).
+    ///
+    /// If the input ends first, an error of kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof)
+    /// is returned. On any error, the bytes read so far are still counted by
+    /// [`len`](Self::len).
+    pub fn read_exact_from<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<()> {
+        while !self.is_full() {
+            match self.read_from(reader) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill the whole buffer",
+                    ))
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ReadBuf<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBuf")
+            .field("filled", &self.filled)
+            .field("init", &self.init)
+            .field("capacity", &self.buf.len())
+            .finish()
+    }
+}
+
+/// Fill the possibly uninitialized array at `ptr` from `reader`, for [`read_exact_into!`].
+///
+/// ## Safety
+/// `ptr` must be valid for reads and writes of `[u8; N]`, and not accessed through anything else
+/// until this returns.
+#[doc(hidden)]
+pub unsafe fn read_exact_field<R: Read + ?Sized, const N: usize>(
+    ptr: *mut [u8; N],
+    reader: &mut R,
+) -> io::Result<()> {
+    ReadBuf::from_array(&mut *(ptr as *mut MaybeUninit<[u8; N]>)).read_exact_from(reader)
+}
+
+/// Fill a `[u8; N]` field of a struct wrapped in `MaybeUninit` from an [`io::Read`] source,
+/// and return a mutable reference to it.
+///
+/// The reader is given after a comma, as `&mut R`, and the field is filled in place with
+/// [`ReadBuf::read_exact_from`], so a header can be read straight into its fields without an
+/// intermediate buffer. If the input ends before the field is full, or the reader fails, the
+/// error is returned and the field must be treated as uninitialized.
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut).
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, read_exact_into};
+///
+/// #[repr(C)]
+/// struct Header { magic: [u8; 4], version: [u8; 2], flags: u16 }
+///
+/// let mut input: &[u8] = b"PUNI\x01\x02rest";
+/// let mut header = MaybeUninit::<Header>::uninit();
+/// let magic = read_exact_into!(header => magic, &mut input)?;
+/// assert_eq!(magic, b"PUNI");
+/// read_exact_into!(header => version, &mut input)?;
+/// partial_init!(header => flags = 0);
+///
+/// let header = unsafe { header.assume_init() };
+/// assert_eq!(header.version, [1, 2]);
+/// assert_eq!(input, b"rest");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[macro_export]
+macro_rules! read_exact_into {
+    ($expr:expr => $($props:tt)=>+, $reader:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the field
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__field_mut!((_x) $($props)=>+);
+            $crate::__assert_arrays!((_x) $($props)=>+);
+        }
+        #[allow(unused_unsafe)]
+        let prop_ptr = unsafe {
+            ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+))
+        };
+        let reader = $reader;
+        #[allow(unused_unsafe)]
+        let result = unsafe { $crate::utils::read_exact_field(prop_ptr, reader) };
+        match result {
+            ::core::result::Result::Ok(()) => {
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                #[allow(unused_unsafe)]
+                let prop_ref = unsafe { $crate::utils::deref_ptr_with_lt(prop_ptr, lt) };
+                ::core::result::Result::Ok(prop_ref)
+            }
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::read_exact_into;
+/// struct Foo { a: [u16; 4] }
+/// let mut input: &[u8] = &[0; 8];
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// read_exact_into!(x => a, &mut input).unwrap();
+///```
+fn _read_exact_into_requires_bytes() {}
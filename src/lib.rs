@@ -82,6 +82,19 @@
 //!     age: 24,
 //! });
 //! ```
+//! ## Provenance
+//!
+//! Every pointer this crate derives, whether to a field, an element or a byte offset, is derived
+//! from the pointer to the slot it was given with `addr_of!`, `addr_of_mut!` or pointer
+//! arithmetic, so it has the provenance of that slot. Pointers are never cast to or from
+//! integers: where an address is needed, such as to check alignment or compare spans, it is read
+//! with [`pointer::addr`](https://doc.rust-lang.org/std/primitive.pointer.html#method.addr),
+//! which doesn't expose the provenance of the pointer. This lets the crate be used under
+//! `-Zmiri-strict-provenance`, and the `strict-provenance` feature enforces it with the
+//! `fuzzy_provenance_casts` and `lossy_provenance_casts` lints on nightly.
+//! [`field_offset`] and [`project_by_offset`] convert between field pointers and offsets in the
+//! same way.
+//!
 //! ## Safety
 //!
 //! It's safe to mutably project multiple fields as long as they are distinct.
@@ -142,9 +155,341 @@
 //! }
 //! ```
 #![no_std]
+#![cfg_attr(
+    feature = "strict-provenance",
+    feature(strict_provenance_lints),
+    deny(fuzzy_provenance_casts, lossy_provenance_casts)
+)]
 
+#[cfg(any(
+    feature = "alloc",
+    feature = "nalgebra",
+    feature = "proptest",
+    feature = "pyo3",
+    feature = "soa",
+    feature = "test-support"
+))]
+extern crate alloc;
+#[cfg(any(
+    feature = "coverage",
+    feature = "mmap",
+    feature = "shadow",
+    feature = "std",
+    feature = "test-support"
+))]
+extern crate std;
+
+mod alias;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_in_place;
+mod array;
 mod assert_unique;
+#[cfg(feature = "alloc")]
+mod boxed;
+mod branch;
+mod builder;
+#[cfg(feature = "bumpalo")]
+mod bump;
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+mod bytes;
+#[cfg(target_has_atomic = "8")]
+mod cell;
+mod checkpoint;
+mod column;
+mod const_init;
+mod copy;
+#[cfg(feature = "serde")]
+mod de;
+mod dst;
+mod emplace;
+#[cfg(feature = "test-support")]
+mod compile_fail;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+mod export;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod fields;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod guest;
+#[cfg(feature = "hooks")]
+mod hooks;
+mod image;
+mod init_all;
+mod init_with;
+mod inject;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod mirror;
+#[cfg(all(feature = "mmap", unix))]
+pub mod mmap;
+mod option;
+mod out;
+mod page;
 mod partial_init;
+mod pin;
+mod poison;
 mod project;
+#[cfg(feature = "pyo3")]
+mod python;
+mod read;
+mod register;
+mod sanitize;
+mod serialize;
+mod secret;
+mod slice;
+#[cfg(feature = "alloc")]
+mod spare;
+mod split;
+#[cfg(feature = "shadow")]
+pub mod shadow;
+#[cfg(feature = "soa")]
+mod soa;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+#[cfg(feature = "tlv")]
+mod tlv;
+mod tracker;
+mod triple;
+mod union;
+mod variant;
+mod via;
+mod view;
+mod volatile;
+mod zero;
 #[doc(hidden)]
 pub mod utils;
+
+#[cfg(feature = "arbitrary")]
+pub use ::arbitrary;
+#[cfg(feature = "arbitrary")]
+pub use arbitrary_in_place::{arbitrary_in_place, ArbitraryInPlace};
+pub use array::init_array;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use boxed::{arc_emplace_cyclic, arc_uninit};
+#[cfg(feature = "alloc")]
+pub use boxed::{boxed_init_with, boxed_uninit, rc_uninit, UninitAlloc};
+pub use builder::{FieldState, Set, Unset};
+#[cfg(feature = "bumpalo")]
+pub use ::bumpalo;
+#[cfg(feature = "bytemuck")]
+pub use ::bytemuck;
+#[cfg(feature = "bytemuck")]
+pub use bytes::{init_pod, pod_bytes};
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+pub use bytes::ByteLenError;
+#[cfg(target_has_atomic = "8")]
+pub use cell::{AtomicInitCell, Publisher, UninitCell};
+pub use checkpoint::CheckpointError;
+pub use column::{emplace_row, ColumnStorage, RawColumn, UninitColumn};
+#[cfg(feature = "serde")]
+pub use de::{deserialize_uninit, DeserializeUninit, UninitSeed};
+pub use dst::{UninitDst, UninitPlace};
+#[cfg(feature = "alloc")]
+pub use emplace::Heap;
+pub use emplace::{Emplace, FnArena};
+pub use export::ExportFields;
+#[cfg(feature = "ffi")]
+pub use ffi::{FfiError, FfiField, FfiFields, FfiInit, FfiLayout};
+pub use fields::{
+    field_offset, project_by_offset, project_by_offset_mut, FieldInfo, UninitFields,
+};
+#[cfg(feature = "gpu")]
+pub use gpu::{GpuField, GpuLayout, StagingWriter};
+pub use guest::{GuestMemory, GuestMemoryError};
+#[cfg(feature = "hooks")]
+pub use hooks::{set_init_hook, InitEvent};
+pub use image::ImageError;
+pub use init_with::init_with;
+pub use inject::{check_injected_failure, InjectedFailure};
+#[cfg(feature = "test-support")]
+pub use inject::{clear_injected_failure, exhaust_failure_points, fail_at_write};
+#[cfg(feature = "std")]
+pub use io::ReadBuf;
+#[cfg(feature = "nalgebra")]
+pub use ::nalgebra;
+#[cfg(feature = "nalgebra")]
+pub use linalg::{boxed_matrix, init_matrix};
+pub use mirror::{UninitMirror, UninitMirrorExt};
+pub use out::Out;
+pub use page::{slot_from_bytes, PageError, PageView};
+pub use pin::{assume_init_pin, UninitPinView, UninitPinViewExt};
+pub use poison::{fill_poison, uninit_poisoned, POISON_BYTE};
+#[cfg(feature = "pyo3")]
+pub use ::pyo3;
+#[cfg(feature = "pyo3")]
+pub use python::py_new_in_place;
+pub use register::{Reg, RegisterBlock};
+pub use secret::{zeroize_uninit, SecretGuard};
+#[cfg(feature = "serde")]
+pub use ::serde;
+pub use serialize::{FieldBytes, FieldSink};
+#[cfg(feature = "alloc")]
+pub use spare::SpareInit;
+#[cfg(feature = "soa")]
+pub use soa::{RowTracker, UninitSoA};
+pub use split::{InitPart, JoinParts, UninitPart, UninitSplit};
+pub use tracker::{InitTracker, MissingFields};
+#[cfg(feature = "tlv")]
+pub use tlv::{decode_tlv, DecodeTlv, TlvDecoder, TlvError, TlvValue};
+pub use triple::{TripleBuffer, TripleReader, TripleWriter};
+pub use variant::UninitEnum;
+pub use via::ReturnCode;
+pub use view::{UninitView, UninitViewExt};
+#[cfg(feature = "zerocopy")]
+pub use ::zerocopy;
+#[cfg(feature = "zerocopy")]
+pub use bytes::{init_from_bytes, value_bytes};
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use project_uninit_derive::__assert_unique;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use project_uninit_derive::__fields_mut;
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::ProjectUninit;
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::ExportFields;
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::InitBuilder;
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::UninitEnum;
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::UninitMirror;
+/// Generate a drop guard for a partially initialized struct.
+///
+/// For a struct `Foo`, this generates `FooPartialGuard<'a>`, which wraps a
+/// `&'a mut MaybeUninit<Foo>` along with a flag for each field, and drops exactly the fields that
+/// are initialized if it is dropped, e.g. when an error is returned or a panic unwinds partway
+/// through initialization. Unlike [`InitTracker`], it doesn't need `#[derive(ProjectUninit)]`, and
+/// each field has its own methods, with the same visibility as the field:
+/// - `set_a(value)` initializes `a`, dropping its previous value if there was one.
+/// - `get_a()` and `get_a_mut()` return references to `a` if it is initialized.
+/// - `take_a()` moves `a` out if it is initialized.
+/// - `uninit_a()` returns a `&mut MaybeUninit<_>` to initialize `a` in place, after which the
+///   unsafe `mark_a_init()` marks it as initialized.
+///
+/// Fields of tuple structs are named by their index, as in `set_0`. `finish` returns a reference
+/// to the struct once every field is initialized. This can't be used on `#[repr(packed)]`
+/// structs. As with [`InitTracker::new`], `new` poisons the slot when the `poison` feature is
+/// enabled in debug builds, so fields must be initialized through the guard.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::PartialGuard;
+///
+/// #[derive(PartialGuard)]
+/// struct Request { method: String, headers: Vec<(String, String)>, body: Vec<u8> }
+///
+/// fn parse(slot: &mut MaybeUninit<Request>, input: &str) -> Result<(), String> {
+///     let mut guard = RequestPartialGuard::new(slot);
+///     let (head, body) = input.split_once("\n\n").ok_or("missing body")?;
+///     let mut lines = head.lines();
+///     guard.set_method(lines.next().ok_or("missing method")?.into());
+///     let headers = guard.set_headers(Vec::new());
+///     for line in lines {
+///         // returning here drops `method` and `headers`, but not the uninitialized `body`
+///         let (name, value) = line.split_once(": ").ok_or("invalid header")?;
+///         headers.push((name.into(), value.into()));
+///     }
+///     guard.set_body(body.as_bytes().to_vec());
+///     guard.finish().map_err(|_| "incomplete request")?;
+///     Ok(())
+/// }
+///
+/// let mut slot = MaybeUninit::uninit();
+/// assert_eq!(parse(&mut slot, "GET\nbroken\n\n"), Err("invalid header".into()));
+/// parse(&mut slot, "GET\nHost: example.com\n\nhello").unwrap();
+/// let request = unsafe { slot.assume_init() };
+/// assert_eq!(request.headers[0].1, "example.com");
+/// ```
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::PartialGuard;
+/// Methods of the impl block that take `self: &mut MaybeUninit<Self>` or
+/// `self: &MaybeUninit<Self>` are turned into associated functions taking that reference as their
+/// first argument, and are called like `Foo::init(&mut slot)`.
+///
+/// Inside these methods, `self` is the `MaybeUninit<Self>` reference, and can be passed to this
+/// crate's macros: `partial_init!(self => { a: 1, b: 2 })`. Assigning to a field of a
+/// `&mut MaybeUninit<Self>` with `self.a.b = value` is lowered to
+/// `partial_init!(self => a => b = value)`, which initializes the field without dropping its
+/// old value. Other methods of the impl block are left as they are.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{project_uninit, uninit_impl};
+///
+/// struct Buffer { data: Vec<u8>, len: usize, pos: (u32, u32) }
+///
+/// #[uninit_impl]
+/// impl Buffer {
+///     fn init(self: &mut MaybeUninit<Self>, data: Vec<u8>) -> &mut Self {
+///         self.len = data.len();
+///         self.data = data;
+///         self.pos.0 = 0;
+///         self.pos.1 = 0;
+///         unsafe { self.assume_init_mut() }
+///     }
+///
+///     fn len(self: &MaybeUninit<Self>) -> &MaybeUninit<usize> {
+///         project_uninit!(self => len)
+///     }
+/// }
+///
+/// let mut buffer = MaybeUninit::uninit();
+/// Buffer::init(&mut buffer, vec![1, 2, 3]);
+/// assert_eq!(unsafe { Buffer::len(&buffer).assume_init() }, 3);
+/// ```
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::uninit_impl;
+/// Generate an in-place version of a constructor, so big types don't need two constructors.
+///
+/// On an associated function `fn name(...) -> Self` whose body ends with a `Self { .. }` or
+/// `Self(..)` expression, this also generates
+/// `fn name_in_place(slot: &mut MaybeUninit<Self>, ...) -> &mut Self` with the same arguments,
+/// which runs the same statements and then writes each field of the expression into `slot` with
+/// [`partial_init!`](crate::partial_init), in the order they're written, instead of building the
+/// value on the stack. A `..rest` at the end of the literal is passed on to `partial_init!`.
+///
+/// The original constructor is kept as it is, and checks that the expression gives every field.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_in_place, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Frame { pixels: [u32; 4096], width: usize, height: usize }
+///
+/// impl Frame {
+///     #[init_in_place]
+///     fn new(width: usize, fill: u32) -> Self {
+///         let height = 4096 / width;
+///         Self { pixels: [fill; 4096], width, height }
+///     }
+/// }
+///
+/// let mut frame = Box::new(MaybeUninit::uninit());
+/// let frame = Frame::new_in_place(&mut frame, 64, 7);
+/// assert_eq!((frame.height, frame.pixels[4095]), (64, 7));
+/// ```
+#[cfg(feature = "derive")]
+pub use project_uninit_derive::init_in_place;
+#[cfg(feature = "tlv")]
+pub use project_uninit_derive::DecodeTlv;
+#[cfg(feature = "arbitrary")]
+pub use project_uninit_derive::ArbitraryInPlace;
+#[cfg(feature = "serde")]
+pub use project_uninit_derive::DeserializeUninit;
+#[cfg(feature = "soa")]
+pub use project_uninit_derive::UninitSoA;
+#[cfg(feature = "gpu")]
+pub use project_uninit_derive::GpuLayout;
+#[cfg(feature = "ffi")]
+pub use project_uninit_derive::FfiFields;
@@ -0,0 +1,54 @@
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+use ::nalgebra::{SMatrix, Scalar};
+
+use crate::array::init_elements;
+
+/// Initialize a statically sized matrix in place, element by element, with `f(row, column)`.
+///
+/// The elements are initialized in column-major order, the order they are stored in.
+/// The matrix is never held on the stack, so this works for matrices that are too large to
+/// construct with `SMatrix::from_fn` and then move into place.
+/// If `f` panics, the elements initialized so far are dropped.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::init_matrix;
+/// use project_uninit::nalgebra::Matrix3;
+///
+/// let mut m = MaybeUninit::<Matrix3<f64>>::uninit();
+/// let m = init_matrix(&mut m, |r, c| if r == c { 1.0 } else { 0.0 });
+/// assert_eq!(*m, Matrix3::identity());
+/// ```
+pub fn init_matrix<T: Scalar, const R: usize, const C: usize>(
+    slot: &mut MaybeUninit<SMatrix<T, R, C>>,
+    mut f: impl FnMut(usize, usize) -> T,
+) -> &mut SMatrix<T, R, C> {
+    let data = crate::project_uninit_mut!(slot => data => 0);
+    unsafe {
+        init_elements(data.as_mut_ptr() as *mut T, R * C, |i| f(i % R, i / R));
+        &mut *slot.as_mut_ptr()
+    }
+}
+
+/// Allocate a statically sized matrix on the heap and initialize it in place with
+/// `f(row, column)`, as with [`init_matrix`].
+///
+/// ## Example
+/// ```
+/// use project_uninit::boxed_matrix;
+/// use project_uninit::nalgebra::SMatrix;
+///
+/// // 2 MiB, which would overflow the stack of many threads if built there first
+/// let m: Box<SMatrix<f64, 512, 512>> = boxed_matrix(|r, c| (r * 512 + c) as f64);
+/// assert_eq!(m[(1, 2)], 514.0);
+/// ```
+pub fn boxed_matrix<T: Scalar, const R: usize, const C: usize>(
+    f: impl FnMut(usize, usize) -> T,
+) -> Box<SMatrix<T, R, C>> {
+    let mut slot = Box::<SMatrix<T, R, C>>::new_uninit();
+    init_matrix(&mut slot, f);
+    unsafe { slot.assume_init() }
+}
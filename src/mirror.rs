@@ -0,0 +1,70 @@
+use core::mem::MaybeUninit;
+
+/// A struct with a mirror type that has the same fields wrapped in `MaybeUninit`, usually
+/// implemented with `#[derive(UninitMirror)]`.
+///
+/// For a `#[repr(C)]` or `#[repr(transparent)]` struct `Foo`, the derive generates
+/// `FooUninitMirror` next to it, with the same `repr` and each field `f: F` replaced by
+/// `f: MaybeUninit<F>` with the same visibility. Since `MaybeUninit<F>` has the same size and
+/// alignment as `F`, the mirror has the same layout as `Foo`, so a `&mut MaybeUninit<Foo>` can be
+/// viewed as a `&mut FooUninitMirror` with [`as_mirror_mut`](UninitMirrorExt::as_mirror_mut), and
+/// back with the mirror's `as_uninit_mut` method. The uninitialized struct can then be
+/// initialized with plain field syntax, or destructured with a pattern, rather than with a macro.
+///
+/// The mirror also has a `const fn uninit()` constructor, and an unsafe `assume_init` method to
+/// convert it to `Foo` by value.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{UninitMirror, UninitMirrorExt};
+///
+/// #[derive(UninitMirror)]
+/// #[repr(C)]
+/// struct Header { magic: [u8; 4], len: u32, flags: u16 }
+///
+/// let mut slot = MaybeUninit::<Header>::uninit();
+/// let HeaderUninitMirror { magic, len, flags } = slot.as_mirror_mut();
+/// magic.write(*b"PUNI");
+/// len.write(12);
+/// flags.write(0);
+///
+/// let header = unsafe { slot.assume_init() };
+/// assert_eq!((&header.magic, header.len, header.flags), (b"PUNI", 12, 0));
+/// # }
+/// ```
+///
+/// ## Safety
+/// `Mirror` must have the same size, alignment and field offsets as `Self`, with each field of
+/// `Self` at the same offset as a field of `Mirror` of the same type wrapped in `MaybeUninit`.
+pub unsafe trait UninitMirror: Sized {
+    /// The struct with each field wrapped in `MaybeUninit`.
+    type Mirror;
+}
+
+/// Methods on `MaybeUninit` for viewing a struct implementing [`UninitMirror`] as its mirror.
+pub trait UninitMirrorExt<T: UninitMirror> {
+    /// View the possibly uninitialized struct as its mirror.
+    fn as_mirror(&self) -> &T::Mirror;
+
+    /// View the possibly uninitialized struct as its mirror, to initialize its fields.
+    fn as_mirror_mut(&mut self) -> &mut T::Mirror;
+}
+
+impl<T: UninitMirror> UninitMirrorExt<T> for MaybeUninit<T> {
+    fn as_mirror(&self) -> &T::Mirror {
+        unsafe { &*(self.as_ptr() as *const T::Mirror) }
+    }
+
+    fn as_mirror_mut(&mut self) -> &mut T::Mirror {
+        unsafe { &mut *(self.as_mut_ptr() as *mut T::Mirror) }
+    }
+}
+
+///```compile_fail
+/// use project_uninit::UninitMirror;
+/// #[derive(UninitMirror)]
+/// struct Foo { a: u8, b: u32 }
+///```
+fn _mirror_without_repr_fails() {}
@@ -0,0 +1,213 @@
+//! Uninitialized storage in anonymous memory mappings.
+//!
+//! [`MappedUninit`] owns a private anonymous mapping sized for a single `T` and dereferences to
+//! `MaybeUninit<T>`, so it can be filled with any of this crate's initializers, such as
+//! [`partial_init!`](crate::partial_init) or [`init_array`](crate::init_array). Unlike a `Box`,
+//! the mapping can be backed by huge pages and pre-faulted, which controls the page-fault and TLB
+//! behavior of large buffers together with their initialization.
+//!
+//! ## Example
+//! ```
+//! use project_uninit::init_array;
+//! use project_uninit::mmap::{HugePages, MapOptions, MappedUninit};
+//!
+//! let options = MapOptions {
+//!     huge_pages: HugePages::Transparent,
+//!     ..MapOptions::default()
+//! };
+//! let mut grid = MappedUninit::<[f64; 1 << 20]>::new(options).unwrap();
+//! init_array(&mut grid, |i| i as f64);
+//! let grid = unsafe { grid.assume_init() };
+//! assert_eq!(grid[1000], 1000.0);
+//! ```
+
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use std::io;
+
+/// Whether a mapping should be backed by huge pages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HugePages {
+    /// Use the default page size.
+    #[default]
+    None,
+    /// Ask the kernel to back the mapping with transparent huge pages (`MADV_HUGEPAGE`).
+    /// This is a hint, and is ignored on platforms that don't support it.
+    Transparent,
+    /// Map the region from the huge page pool (`MAP_HUGETLB`). Creating the mapping fails if the
+    /// pool doesn't have enough pages, or on platforms other than Linux.
+    Explicit,
+}
+
+/// Options for creating a [`MappedUninit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MapOptions {
+    /// Whether to back the mapping with huge pages.
+    pub huge_pages: HugePages,
+    /// Fault in every page when the mapping is created (`MAP_POPULATE`) rather than on first
+    /// write. Ignored on platforms other than Linux.
+    pub populate: bool,
+}
+
+/// A private anonymous mapping holding a possibly uninitialized `T`.
+///
+/// Dereferences to `MaybeUninit<T>`. Dropping it unmaps the memory without dropping the value.
+pub struct MappedUninit<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for MappedUninit<T> {}
+unsafe impl<T: Sync> Sync for MappedUninit<T> {}
+
+impl<T> MappedUninit<T> {
+    /// Map a region large enough for a `T`.
+    ///
+    /// The contents of a fresh anonymous mapping are zeroed by the kernel, but should still be
+    /// treated as uninitialized.
+    /// With [`HugePages::Explicit`], the region is rounded up to a whole number of huge pages.
+    pub fn new(options: MapOptions) -> io::Result<Self> {
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        assert!(
+            align_of::<T>() <= page,
+            "MappedUninit doesn't support types aligned to more than a page"
+        );
+        let mut len = size_of::<T>().max(1);
+        if options.huge_pages == HugePages::Explicit {
+            // `munmap` of a `MAP_HUGETLB` mapping fails unless its length is a multiple of the
+            // huge page size
+            len = len
+                .checked_next_multiple_of(huge_page_size())
+                .ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+        }
+        let ptr = unsafe { map(len, options)? };
+        crate::sanitize::mark_uninit(ptr.as_ptr(), len);
+        Ok(Self {
+            ptr: ptr.cast(),
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Treat the value as initialized.
+    ///
+    /// ## Safety
+    /// The value must be fully initialized.
+    pub unsafe fn assume_init(self) -> Mapped<T> {
+        Mapped {
+            inner: ManuallyDrop::new(self),
+        }
+    }
+}
+
+impl<T> Deref for MappedUninit<T> {
+    type Target = MaybeUninit<T>;
+
+    fn deref(&self) -> &MaybeUninit<T> {
+        unsafe { &*(self.ptr.as_ptr() as *const MaybeUninit<T>) }
+    }
+}
+
+impl<T> DerefMut for MappedUninit<T> {
+    fn deref_mut(&mut self) -> &mut MaybeUninit<T> {
+        unsafe { &mut *(self.ptr.as_ptr() as *mut MaybeUninit<T>) }
+    }
+}
+
+impl<T> Drop for MappedUninit<T> {
+    fn drop(&mut self) {
+        let result = unsafe { libc::munmap(self.ptr.as_ptr() as *mut libc::c_void, self.len) };
+        debug_assert_eq!(result, 0, "munmap failed: {}", io::Error::last_os_error());
+    }
+}
+
+/// An initialized `T` in an anonymous mapping, created by [`MappedUninit::assume_init`].
+///
+/// Dereferences to `T`. Dropping it drops the value and unmaps the memory.
+pub struct Mapped<T> {
+    inner: ManuallyDrop<MappedUninit<T>>,
+}
+
+impl<T> Deref for Mapped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.inner.assume_init_ref() }
+    }
+}
+
+impl<T> DerefMut for Mapped<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.inner.assume_init_mut() }
+    }
+}
+
+impl<T> Drop for Mapped<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.inner.assume_init_drop();
+            ManuallyDrop::drop(&mut self.inner);
+        }
+    }
+}
+
+/// The default huge page size, from `/proc/meminfo`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn huge_page_size() -> usize {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("Hugepagesize:"))
+        .and_then(|kb| kb.trim().strip_suffix("kB")?.trim().parse::<usize>().ok())
+        .map_or(2 << 20, |kb| kb << 10)
+}
+
+// explicit huge pages aren't supported, so this is never used to map anything
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn huge_page_size() -> usize {
+    1
+}
+
+unsafe fn map(len: usize, options: MapOptions) -> io::Result<NonNull<u8>> {
+    #[allow(unused_mut)]
+    let mut flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        if options.huge_pages == HugePages::Explicit {
+            flags |= libc::MAP_HUGETLB;
+        }
+        if options.populate {
+            flags |= libc::MAP_POPULATE;
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    if options.huge_pages == HugePages::Explicit {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "explicit huge pages are only supported on Linux",
+        ));
+    }
+
+    let ptr = libc::mmap(
+        core::ptr::null_mut(),
+        len,
+        libc::PROT_READ | libc::PROT_WRITE,
+        flags,
+        -1,
+        0,
+    );
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if options.huge_pages == HugePages::Transparent {
+        // only a hint, so failure is ignored
+        libc::madvise(ptr, len, libc::MADV_HUGEPAGE);
+    }
+
+    Ok(NonNull::new_unchecked(ptr as *mut u8))
+}
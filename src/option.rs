@@ -0,0 +1,108 @@
+use core::mem::MaybeUninit;
+
+/// Initialize an `Option` wrapped in `MaybeUninit` as `Some`, returning a mutable reference to
+/// its payload.
+///
+/// Unlike enums that implement [`UninitEnum`](crate::UninitEnum), `Option` and `Result` don't
+/// have a guaranteed layout, and the payload may share its bytes with the discriminant, so the
+/// payload can't be initialized through a projection before the variant is chosen.
+/// The value is written as a whole, usually directly into the slot, and a reference to the
+/// payload is returned without matching on the enum at the call site. A previous value in the
+/// slot is not dropped.
+///
+/// To build a large payload in place, initialize it in its own slot, for example with
+/// [`init_with`](crate::init_with), or make the slot's type an enum with a `#[repr(u8)]` and
+/// use [`init_variant!`](crate::init_variant).
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_err, init_ok, init_some};
+///
+/// let mut cache = MaybeUninit::<Option<Vec<u32>>>::uninit();
+/// init_some!(cache => vec![1, 2]).push(3);
+/// assert_eq!(unsafe { cache.assume_init() }, Some(vec![1, 2, 3]));
+///
+/// let mut result = MaybeUninit::<Result<u32, String>>::uninit();
+/// *init_ok!(result => 1) += 1;
+/// assert_eq!(unsafe { result.assume_init_ref() }, &Ok(2));
+/// init_err!(result => "failed".into()).push('!');
+/// assert_eq!(unsafe { result.assume_init() }, Err("failed!".into()));
+/// ```
+#[macro_export]
+macro_rules! init_some {
+    ($slot:expr => $val:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<::core::option::Option<_>> = $slot.borrow_mut();
+        $crate::utils::write_some(_ref, $val)
+    }};
+}
+
+/// Initialize a `Result` wrapped in `MaybeUninit` as `Ok`, returning a mutable reference to its
+/// payload.
+///
+/// See [`init_some!`](crate::init_some) for how the value is written, and an example.
+#[macro_export]
+macro_rules! init_ok {
+    ($slot:expr => $val:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<::core::result::Result<_, _>> = $slot.borrow_mut();
+        $crate::utils::write_ok(_ref, $val)
+    }};
+}
+
+/// Initialize a `Result` wrapped in `MaybeUninit` as `Err`, returning a mutable reference to its
+/// payload.
+///
+/// See [`init_some!`](crate::init_some) for how the value is written, and an example.
+#[macro_export]
+macro_rules! init_err {
+    ($slot:expr => $val:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<::core::result::Result<_, _>> = $slot.borrow_mut();
+        $crate::utils::write_err(_ref, $val)
+    }};
+}
+
+#[doc(hidden)]
+pub fn write_some<T>(slot: &mut MaybeUninit<Option<T>>, value: T) -> &mut T {
+    let ptr = slot.as_mut_ptr();
+    let option = slot.write(Some(value));
+    crate::utils::init_hook(ptr, ptr, "Some");
+    match option {
+        Some(value) => value,
+        None => unreachable!(),
+    }
+}
+
+#[doc(hidden)]
+pub fn write_ok<T, E>(slot: &mut MaybeUninit<Result<T, E>>, value: T) -> &mut T {
+    let ptr = slot.as_mut_ptr();
+    let result = slot.write(Ok(value));
+    crate::utils::init_hook(ptr, ptr, "Ok");
+    match result {
+        Ok(value) => value,
+        Err(_) => unreachable!(),
+    }
+}
+
+#[doc(hidden)]
+pub fn write_err<T, E>(slot: &mut MaybeUninit<Result<T, E>>, value: E) -> &mut E {
+    let ptr = slot.as_mut_ptr();
+    let result = slot.write(Err(value));
+    crate::utils::init_hook(ptr, ptr, "Err");
+    match result {
+        Err(value) => value,
+        Ok(_) => unreachable!(),
+    }
+}
+
+///```compile_fail
+/// use project_uninit::init_ok;
+/// let mut x = core::mem::MaybeUninit::<Option<u8>>::uninit();
+/// init_ok!(x => 1);
+///```
+fn _init_ok_on_option_fails() {}
@@ -0,0 +1,144 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// A write-only reference to a possibly uninitialized value.
+///
+/// Unlike `&mut MaybeUninit<T>`, an `Out` has no way to read the value, even unsafely, so the
+/// only thing code holding one can do is initialize it. Writing consumes the `Out` and returns
+/// a `&mut T` to the value it wrote.
+/// Writing doesn't drop any value that was already there.
+///
+/// Handles for fields of a struct in a `MaybeUninit` are obtained with
+/// [`project_out!`](crate::project_out).
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::Out;
+///
+/// fn fill_greeting(out: Out<'_, String>, name: &str) {
+///     out.write(format!("Hello, {}!", name));
+/// }
+///
+/// let mut greeting = MaybeUninit::uninit();
+/// fill_greeting(Out::new(&mut greeting), "Alice");
+/// assert_eq!(unsafe { greeting.assume_init() }, "Hello, Alice!");
+/// ```
+pub struct Out<'a, T> {
+    slot: &'a mut MaybeUninit<T>,
+}
+
+impl<'a, T> Out<'a, T> {
+    /// Create a write-only reference to `slot`.
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        Out { slot }
+    }
+
+    /// Initialize the value, returning a mutable reference to it.
+    pub fn write(self, value: T) -> &'a mut T {
+        self.slot.write(value)
+    }
+
+    /// Get a shorter-lived `Out` to the same value, so that it can be written more than once.
+    pub fn reborrow(&mut self) -> Out<'_, T> {
+        Out { slot: self.slot }
+    }
+
+    /// Get a raw pointer to the value, e.g. to initialize it in place through an FFI call.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.slot.as_mut_ptr()
+    }
+}
+
+impl<'a, T> From<&'a mut MaybeUninit<T>> for Out<'a, T> {
+    fn from(slot: &'a mut MaybeUninit<T>) -> Self {
+        Out::new(slot)
+    }
+}
+
+impl<T> fmt::Debug for Out<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Out").finish_non_exhaustive()
+    }
+}
+
+/// Obtain write-only [`Out`] references to fields of a struct wrapped in `MaybeUninit<_>`.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut) and
+/// statically ensures that multiple references to the same field are not returned.
+/// Unlike the `&mut MaybeUninit<_>` references returned by `project_uninit_mut!`, the fields
+/// can't be read through the returned references, even with `assume_init`, before they are
+/// written.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{project_out, Out};
+///
+/// struct Header { magic: [u8; 4], len: u32 }
+///
+/// fn write_magic(out: Out<'_, [u8; 4]>) {
+///     out.write(*b"PUIN");
+/// }
+///
+/// let mut header = MaybeUninit::<Header>::uninit();
+/// let (magic, len) = project_out!(header => { magic, len });
+/// write_magic(magic);
+/// *len.write(0) += 16;
+///
+/// let header = unsafe { header.assume_init() };
+/// assert_eq!((&header.magic, header.len), (b"PUIN", 16));
+/// ```
+#[macro_export]
+macro_rules! project_out {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::Out::new($crate::utils::uninit_from_mut_ptr(prop_ptr, lt));
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_out!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::project_out;
+/// struct Foo { a: u32 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let a = project_out!(x => a);
+/// let value = unsafe { a.assume_init() };
+///```
+fn _out_cannot_be_read() {}
+
+///```compile_fail
+/// use project_uninit::project_out;
+/// struct Foo { a: u32, b: u32 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (a, a2) = project_out!(x => { a, a });
+///```
+fn _project_out_same_field_twice_fails() {}
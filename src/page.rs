@@ -0,0 +1,254 @@
+use core::fmt;
+use core::mem::{align_of, size_of, MaybeUninit};
+
+/// An error from carving a typed value out of a [`PageView`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageError {
+    /// `size` bytes at `offset` don't fit before the end of the view at `end`.
+    OutOfBounds {
+        offset: usize,
+        size: usize,
+        end: usize,
+    },
+    /// `offset` isn't aligned to `align` bytes.
+    Misaligned { offset: usize, align: usize },
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { offset, size, end } => write!(
+                f,
+                "{} bytes at page offset {} extend past the end of the view at {}",
+                size, offset, end
+            ),
+            Self::Misaligned { offset, align } => write!(
+                f,
+                "page offset {} is not aligned to {} bytes",
+                offset, align
+            ),
+        }
+    }
+}
+
+/// A view of a range of an uninitialized page buffer, from which typed headers and entry slots
+/// can be carved out with bounds and alignment checks.
+///
+/// Carving consumes the view and returns the typed slot along with a view of the rest of the
+/// range, so every slot borrows a disjoint part of the page and they can all be initialized at
+/// the same time, e.g. with [`partial_init!`](crate::partial_init).
+/// Offsets in errors and from [`offset`](PageView::offset) are relative to the start of the page
+/// the view was created from.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, PageView};
+///
+/// #[repr(C)]
+/// struct Header { kind: u8, flags: u8, count: u16, free_end: u32 }
+///
+/// #[repr(C)]
+/// struct Slot { offset: u16, len: u16 }
+///
+/// #[repr(C, align(8))]
+/// struct Page(MaybeUninit<[u8; 4096]>);
+///
+/// let mut page = Page(MaybeUninit::uninit());
+/// let view = PageView::new(&mut page.0);
+/// let (header, rest) = view.split_first::<Header>().unwrap();
+/// let (slots, rest) = rest.split_first_n::<Slot>(1).unwrap();
+/// let (_free, entry) = rest.split_last_n::<u8>(5).unwrap();
+///
+/// partial_init!(header => { kind: 1, flags: 0, count: 1, free_end: 4091 });
+/// partial_init!(slots[0] => { offset: 4091, len: 5 });
+/// for (byte, value) in entry.iter_mut().zip(b"hello") {
+///     byte.write(*value);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PageView<'a> {
+    bytes: &'a mut [MaybeUninit<u8>],
+    offset: usize,
+}
+
+impl<'a> PageView<'a> {
+    /// View a whole page.
+    pub fn new<const N: usize>(page: &'a mut MaybeUninit<[u8; N]>) -> Self {
+        let bytes =
+            unsafe { &mut *(page as *mut MaybeUninit<[u8; N]> as *mut [MaybeUninit<u8>; N]) };
+        Self::from_slice(bytes)
+    }
+
+    /// View a page of any size.
+    pub fn from_slice(bytes: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    /// The offset of the start of the view from the start of the page.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The size of the view in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The bytes in the view.
+    pub fn bytes(&mut self) -> &mut [MaybeUninit<u8>] {
+        self.bytes
+    }
+
+    fn check<T>(&self, at: usize, count: usize) -> Result<(), PageError> {
+        let offset = self.offset + at;
+        let end = self.offset + self.bytes.len();
+        let size = size_of::<T>().checked_mul(count);
+        match size.and_then(|size| at.checked_add(size)) {
+            Some(at_end) if at_end <= self.bytes.len() => {}
+            _ => {
+                return Err(PageError::OutOfBounds {
+                    offset,
+                    size: size.unwrap_or(usize::MAX),
+                    end,
+                })
+            }
+        }
+        let align = align_of::<T>();
+        if !(self.bytes.as_ptr().addr() + at).is_multiple_of(align) {
+            return Err(PageError::Misaligned { offset, align });
+        }
+        Ok(())
+    }
+
+    /// Split the view in two at `mid` bytes from its start.
+    pub fn split_at(self, mid: usize) -> Result<(Self, Self), PageError> {
+        if mid > self.bytes.len() {
+            return Err(PageError::OutOfBounds {
+                offset: self.offset,
+                size: mid,
+                end: self.offset + self.bytes.len(),
+            });
+        }
+        let (front, back) = self.bytes.split_at_mut(mid);
+        Ok((
+            Self {
+                bytes: front,
+                offset: self.offset,
+            },
+            Self {
+                bytes: back,
+                offset: self.offset + mid,
+            },
+        ))
+    }
+
+    /// Skip ahead to the next offset aligned to `align` bytes.
+    pub fn align_to(self, align: usize) -> Result<Self, PageError> {
+        let addr = self.bytes.as_ptr().addr();
+        let padding = addr.next_multiple_of(align) - addr;
+        Ok(self.split_at(padding)?.1)
+    }
+
+    /// A slot for a `T` at `at` bytes from the start of the view.
+    pub fn get<T>(&mut self, at: usize) -> Result<&mut MaybeUninit<T>, PageError> {
+        self.check::<T>(at, 1)?;
+        Ok(unsafe { &mut *(self.bytes.as_mut_ptr().add(at) as *mut MaybeUninit<T>) })
+    }
+
+    /// Carve a slot for a `T` out of the start of the view, and return it with the rest of the
+    /// view.
+    pub fn split_first<T>(self) -> Result<(&'a mut MaybeUninit<T>, Self), PageError> {
+        let (slots, rest) = self.split_first_n::<T>(1)?;
+        Ok((&mut slots[0], rest))
+    }
+
+    /// Carve slots for `count` consecutive `T`s out of the start of the view, and return them
+    /// with the rest of the view.
+    pub fn split_first_n<T>(
+        self,
+        count: usize,
+    ) -> Result<(&'a mut [MaybeUninit<T>], Self), PageError> {
+        self.check::<T>(0, count)?;
+        let (front, rest) = self.split_at(size_of::<T>() * count)?;
+        Ok((front.into_slots(count), rest))
+    }
+
+    /// Carve a slot for a `T` out of the end of the view, and return it with the rest of the
+    /// view.
+    pub fn split_last<T>(self) -> Result<(Self, &'a mut MaybeUninit<T>), PageError> {
+        let (rest, slots) = self.split_last_n::<T>(1)?;
+        Ok((rest, &mut slots[0]))
+    }
+
+    /// Carve slots for `count` consecutive `T`s out of the end of the view, and return them with
+    /// the rest of the view.
+    ///
+    /// This is how entries are allocated from the end of a slotted page.
+    pub fn split_last_n<T>(
+        self,
+        count: usize,
+    ) -> Result<(Self, &'a mut [MaybeUninit<T>]), PageError> {
+        let size = size_of::<T>()
+            .checked_mul(count)
+            .filter(|&size| size <= self.bytes.len());
+        let at = match size {
+            Some(size) => self.bytes.len() - size,
+            None => {
+                return Err(PageError::OutOfBounds {
+                    offset: self.offset,
+                    size: size_of::<T>().saturating_mul(count),
+                    end: self.offset + self.bytes.len(),
+                })
+            }
+        };
+        self.check::<T>(at, count)?;
+        let (rest, back) = self.split_at(at)?;
+        Ok((rest, back.into_slots(count)))
+    }
+
+    // The view must have been checked for `count` `T`s at its start.
+    fn into_slots<T>(self, count: usize) -> &'a mut [MaybeUninit<T>] {
+        unsafe {
+            core::slice::from_raw_parts_mut(self.bytes.as_mut_ptr() as *mut MaybeUninit<T>, count)
+        }
+    }
+}
+
+/// Carve a slot for a `T` out of the start of an uninitialized byte buffer, such as a scratch
+/// buffer on the stack or in an arena, and return it with the rest of the buffer.
+///
+/// Returns an error if the buffer is too small for a `T`, or its start isn't aligned for one.
+/// Offsets in the error are relative to the start of `bytes`. Use [`PageView`] to carve more
+/// than one value, from either end, or at other offsets.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, slot_from_bytes, PageError};
+///
+/// struct Frame { id: u32, len: u16 }
+///
+/// #[repr(align(8))]
+/// struct Scratch([MaybeUninit<u8>; 64]);
+///
+/// let mut scratch = Scratch([MaybeUninit::uninit(); 64]);
+/// let (frame, rest) = slot_from_bytes::<Frame>(&mut scratch.0).unwrap();
+/// partial_init!(frame => { id: 1, len: 12 });
+/// assert_eq!(rest.len(), 64 - core::mem::size_of::<Frame>());
+///
+/// // one byte in, the buffer isn't aligned for a `u64`
+/// let err = slot_from_bytes::<u64>(&mut scratch.0[1..]).unwrap_err();
+/// assert_eq!(err, PageError::Misaligned { offset: 0, align: 8 });
+/// ```
+pub fn slot_from_bytes<T>(
+    bytes: &mut [MaybeUninit<u8>],
+) -> Result<(&mut MaybeUninit<T>, &mut [MaybeUninit<u8>]), PageError> {
+    let (slot, rest) = PageView::from_slice(bytes).split_first::<T>()?;
+    Ok((slot, rest.bytes))
+}
@@ -6,6 +6,44 @@
 /// and that multiple references to the same value are not returned.
 ///
 /// This must be used in an `unsafe` block or function when accessing fields of unions.
+/// Prefer [`partial_init_union!`](crate::partial_init_union), which marks the access as `unsafe`
+/// in the invocation itself.
+/// Fields exported with [`ExportFields`](crate::ExportFields) can be initialized where they aren't
+/// otherwise accessible by writing `pub` before the field paths: `partial_init!(x => pub { a: 1 })`.
+/// Writing `strict` before the field paths instead checks that each field is a field of the
+/// struct itself rather than of a `Deref` target, for types that implement
+/// [`UninitView`](crate::UninitView): `partial_init!(x => strict { a: 1 })`.
+/// Elements of arrays are initialized with integer literal indices, like
+/// `partial_init!(x => { buf => [0]: 1, buf => [1]: 2 })`.
+/// Fields that are themselves `MaybeUninit<_>` or `ManuallyDrop<_>` are continued into with a
+/// `*` segment, like `partial_init!(x => cache => * => value = 1)`.
+/// Paths defined with [`field_path!`](crate::field_path) are used by writing `@NAME` at the start
+/// of a field path: `partial_init!(x => { @NAME: 1, b: 2 })`.
+/// Fields under a common path can be grouped in braces after it, as in
+/// `partial_init!(x => inner => { a: 1, b => 0: 2 })` or
+/// `partial_init!(x => { id: 1, inner => { a: 2, b: 3 } })`, and groups can be nested.
+/// Groups are expanded into full paths before the fields are checked, so the same field in two
+/// groups is rejected like any other duplicate field. Only the fields of groups are initialized,
+/// and a mutable reference to each of them is returned.
+/// Instead of returning a tuple, the references can be bound to names in the fields themselves by
+/// ending a field with `=> let name`, as in `partial_init!(x => { a: 1 => let a, b: 2 });`. This
+/// expands to a `let` statement, so it can only be used as a statement, and fields without a
+/// name aren't bound.
+/// Like struct update syntax, ending the fields with `..rest` moves every other field from `rest`,
+/// a value of the whole struct such as `Default::default()`, and drops the fields of `rest` that
+/// were given, using the metadata from [`UninitFields`](crate::UninitFields). Only top-level
+/// fields can be given before `..rest`, and a `&mut` reference to the initialized struct is
+/// returned: `partial_init!(x => { a: 1, ..Default::default() })`.
+/// A value written as `|args| => value`, like `len: |(data,)| => data.len()`, is computed with a
+/// tuple of shared references to the fields given before it, which are already initialized. The
+/// references only live until the value is computed, so it can't borrow from them. A plain
+/// closure, like `callback: |x| x + 1`, is written to its field like any other value.
+/// When the type of the struct can't be inferred from its fields, as in generic code, it can be
+/// given after the slot with `as`: `partial_init!(x as MaybeUninit<Foo<T>> => a = 1)`. A slot
+/// written with more than one token, like `self.slot`, must then be put in parentheses.
+///
+/// Fields of `#[repr(packed)]` structs may be unaligned, so references to them can't be returned.
+/// Use [`partial_init_unaligned!`](crate::partial_init_unaligned) to initialize them instead.
 ///
 /// ## Syntax
 /// ```
@@ -49,6 +87,17 @@
 /// assert_eq!(*last, "Jones");
 /// assert_eq!(*age, 37);
 /// assert_eq!(*id1, 888);
+///
+/// // Group fields under a common path:
+/// let (first, last) = partial_init!(bob => name => { first: "Robert", last: "Jones" });
+/// let (age, id0, id1) = partial_init!(bob => { age: 38, id => { 0: 1, 1: 2 } });
+///
+/// // Bind the references to names instead of returning them:
+/// partial_init!(bob => {
+///     age: 39 => let age,
+///     id => { 0: 3, 1: 4 => let id1 },
+/// });
+/// assert_eq!((*age, *id1), (39, 4));
 /// ```
 ///
 ///
@@ -85,8 +134,319 @@
 ///     id: (123, 789),
 /// });
 /// ```
+///
+/// ## Fields computed from earlier fields
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::partial_init;
+///
+/// struct Packet { payload: Vec<u8>, len: usize, checksum: u8 }
+///
+/// let mut packet = MaybeUninit::<Packet>::uninit();
+/// partial_init!(packet => {
+///     payload: vec![1, 2, 3],
+///     len: |(payload,)| => payload.len(),
+///     checksum: |(payload, len)| => payload.iter().fold(*len as u8, |a, b| a ^ b),
+/// });
+/// let packet = unsafe { packet.assume_init() };
+/// assert_eq!((packet.len, packet.checksum), (3, 3 ^ 1 ^ 2 ^ 3));
+/// ```
+///
+/// ## Struct update
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, ProjectUninit};
+///
+/// #[derive(ProjectUninit, Default, PartialEq, Eq, Debug)]
+/// struct Options { name: &'static str, verbose: bool, retries: u32 }
+///
+/// let mut options = MaybeUninit::<Options>::uninit();
+/// let options: &mut Options = partial_init!(options => { name: "Foo", ..Default::default() });
+/// assert_eq!(*options, Options { name: "Foo", verbose: false, retries: 0 });
+/// # }
+/// ```
 #[macro_export]
 macro_rules! partial_init {
+    // give the type of the slot
+    ($head:tt as $ty:ty => $($rest:tt)*) => {
+        $crate::partial_init!({
+            #[allow(unused_imports)]
+            use ::core::borrow::BorrowMut;
+            let _ref: &mut $ty = $head.borrow_mut();
+            _ref
+        } => $($rest)*)
+    };
+
+    // intialize multiple exported fields
+    ($expr:expr => pub {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        ($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = $crate::__export_ptr!(ptr, $($props)=>+);
+                ::core::ptr::write(prop_ptr, $val);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
+            }
+            prop_ref
+        },)*)
+    }};
+
+    // initialize a single exported field
+    ($expr:expr => pub $($props:tt)=>+ = $val:expr) => {
+        $crate::partial_init!($expr => pub { $($props)=>+: $val }).0
+    };
+
+    // intialize multiple fields, checking that each is a field of the struct itself
+    ($expr:expr => strict {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields, and that they aren't
+            // reached through `Deref`
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+            #[allow(unused_unsafe)]
+            unsafe {
+                $( $crate::__strict_path!(ptr, $($props)=>+); )*
+            }
+        }
+        ($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ::core::ptr::write(prop_ptr, $val);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
+            }
+            prop_ref
+        },)*)
+    }};
+
+    // initialize a single field, checking that it is a field of the struct itself
+    ($expr:expr => strict $($props:tt)=>+ = $val:expr) => {
+        $crate::partial_init!($expr => strict { $($props)=>+: $val }).0
+    };
+
+    // intialize multiple fields
+    ($expr:expr => {$($body:tt)*}) => {
+        $crate::partial_init!(@collect ($expr) {$($body)*} () [] [] [] $($body)*)
+    };
+
+    // initialize a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::partial_init!($expr => { $($props)=>+: $val }).0
+    };
+
+    // initialize a single field through an alias
+    ($expr:expr => @ $alias:ident $(=> $props:tt)* = $val:expr) => {
+        $crate::partial_init!($expr => { @ $alias $(=> $props)*: $val }).0
+    };
+
+    // initialize multiple fields grouped under a common path
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::partial_init!($expr => { $($props)=>+ })
+    };
+
+    // initialize multiple fields with values that don't depend on each other
+    (@plain $expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ::core::ptr::write(prop_ptr, $val);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
+            }
+            prop_ref
+        },)*)
+    }};
+
+    // sort the fields into ones with plain values and ones initialized by closures, and collect
+    // the names each reference is bound to
+    (@collect ($expr:expr) $body:tt $found:tt [$($done:tt)*] [$($names:tt)*] [$($bound:tt)*]
+        $($props:tt)=>+ : | $prev:pat_param | => $val:expr $(=> let $name:pat_param)?
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::partial_init!(@collect ($expr) $body (found)
+            [$($done)* [$($props)=>+] (lazy ($prev) $val)]
+            [$($names)* ($($name)?)] [$($bound)* $(($name))?] $($($rest)*)?)
+    };
+    (@collect ($expr:expr) $body:tt $found:tt [$($done:tt)*] [$($names:tt)*] [$($bound:tt)*]
+        $($props:tt)=>+ : | $prev:tt : $ty:ty | => $val:expr $(=> let $name:pat_param)?
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::partial_init!(@collect ($expr) $body (found)
+            [$($done)* [$($props)=>+] (lazy ($prev: $ty) $val)]
+            [$($names)* ($($name)?)] [$($bound)* $(($name))?] $($($rest)*)?)
+    };
+    (@collect ($expr:expr) $body:tt $found:tt [$($done:tt)*] [$($names:tt)*] [$($bound:tt)*]
+        $($props:tt)=>+ : $val:expr $(=> let $name:pat_param)? $(, $($rest:tt)*)?
+    ) => {
+        $crate::partial_init!(@collect ($expr) $body $found
+            [$($done)* [$($props)=>+] (plain $val)]
+            [$($names)* ($($name)?)] [$($bound)* $(($name))?] $($($rest)*)?)
+    };
+    // bind the references to the given names
+    (@collect ($expr:expr) $body:tt $found:tt $done:tt [$(($($name:pat_param)?))*] [$($bound:tt)+]) => {
+        let ($($crate::partial_init!(@bind $($name)?),)*) =
+            $crate::partial_init!(@collect ($expr) $body $found $done [] []);
+    };
+    (@collect ($expr:expr) $body:tt () [$([$($props:tt)=>+] (plain $val:expr))*] $names:tt []) => {
+        $crate::partial_init!(@plain $expr => { $($($props)=>+: $val),* })
+    };
+    (@collect ($expr:expr) $body:tt (found) [$([$($props:tt)=>+] $value:tt)*] $names:tt []) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $crate::partial_init!(@write ptr, lt, [] $([$($props)=>+] $value)*)
+    }};
+    // aliases and `..rest`
+    (@collect ($expr:expr) {$($body:tt)*} $($other:tt)*) => {
+        $crate::__resolve_paths!((partial_init ($expr =>) ()) [] [] $($body)*)
+    };
+
+    (@bind $name:pat_param) => { $name };
+    (@bind) => { _ };
+
+    // write each field in order, passing references to the fields written so far to closures
+    (@write $ptr:ident, $lt:ident, [$($prev:ident)*] [$($props:tt)=>+] $value:tt $($rest:tt)*) => {{
+        #[allow(unused_unsafe)]
+        let field = unsafe {
+            ::core::ptr::addr_of_mut!($crate::__field_place!((*$ptr) $($props)=>+))
+        };
+        #[allow(unused_parens)]
+        let value = $crate::partial_init!(@value $lt, [$($prev)*] $value);
+        #[allow(unused_unsafe)]
+        unsafe {
+            ::core::ptr::write(field, value);
+            $crate::utils::init_hook($ptr, field, $crate::__path_str!($($props)=>+));
+        }
+        $crate::partial_init!(@write $ptr, $lt, [$($prev)* field] $($rest)*)
+    }};
+    (@write $ptr:ident, $lt:ident, [$($prev:ident)*]) => {
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ref = unsafe { $crate::utils::deref_ptr_with_lt($prev, $lt) };
+            prop_ref
+        },)*)
+    };
+    (@value $lt:ident, [$($prev:ident)*] (plain $val:expr)) => {
+        $val
+    };
+    (@value $lt:ident, [$($prev:ident)*] (lazy ($($arg:tt)*) $val:expr)) => {
+        $crate::utils::call_with($lt, |lt, _| {
+            #[allow(unused_unsafe)]
+            let $($arg)* = unsafe { ($($crate::utils::ref_ptr_with_lt($prev, lt),)*) };
+            $val
+        })
+    };
+
+    // write the given fields, then move every other field from `$rest`
+    (@rest $expr:expr => {$($field:tt : $val:expr),* $(,)?} $($rest:tt)+) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        #[allow(clippy::unused_unit)]
+        let _ = $crate::partial_init!(&mut *_ref => { $($field: $val),* });
+        let rest = $($rest)+;
+        #[allow(unused_unsafe)]
+        unsafe {
+            $crate::utils::init_rest(_ref, rest, &[$(::core::stringify!($field)),*])
+        }
+    }};
+}
+
+/// Partially initialize a struct wrapped in `MaybeUninit` with values that may fail to be
+/// produced.
+///
+/// This accepts the same syntax as [`partial_init!`], but each value is a `Result<_, E>`.
+/// If every value is `Ok`, the fields are written and `Ok` is returned with the same mutable
+/// references [`partial_init!`] would return.
+/// Otherwise, the first `Err` is returned, the values after it are not evaluated, and no field is
+/// written; the values produced before it are dropped.
+///
+/// With the `test-support` feature, `E` must also implement
+/// `From<`[`InjectedFailure`](crate::InjectedFailure)`>`, so that tests can make any write fail
+/// with `fail_at_write`.
+///
+/// This must be used in an `unsafe` block or function when accessing fields of unions.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::try_partial_init;
+///
+/// #[derive(PartialEq, Eq, Debug)]
+/// struct Config { port: u16, threads: u8 }
+///
+/// fn parse(port: &str, threads: &str) -> Result<Config, ()> {
+///     let mut config = MaybeUninit::<Config>::uninit();
+///     try_partial_init!(config => {
+///         port: port.parse().map_err(drop),
+///         threads: threads.parse().map_err(drop),
+///     })?;
+///     Ok(unsafe { config.assume_init() })
+/// }
+///
+/// assert_eq!(parse("8080", "4"), Ok(Config { port: 8080, threads: 4 }));
+/// assert_eq!(parse("8080", "many"), Err(()));
+///
+/// let mut config = MaybeUninit::<Config>::uninit();
+/// let port = try_partial_init!(config => port = Ok::<_, ()>(80)).unwrap();
+/// assert_eq!(*port, 80);
+/// ```
+#[macro_export]
+macro_rules! try_partial_init {
     // intialize multiple fields
     ($expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
         // generate an error message if a field is used more than once
@@ -102,26 +462,344 @@ macro_rules! partial_init {
             // it's only to assert that it is safe to access the fields
             #[allow(unused_unsafe)]
             let _x = unsafe { &mut *ptr };
-            let _y = ($(&mut _x.$($props).+,)*);
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $crate::try_partial_init!(@eval ptr, lt, [], $([$($props)=>+] ($val))*)
+    }};
+
+    // initialize a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        match $crate::try_partial_init!($expr => { $($props)=>+: $val }) {
+            ::core::result::Result::Ok(refs) => ::core::result::Result::Ok(refs.0),
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    };
+
+    // evaluate the values one at a time, stopping at the first error
+    (@eval $ptr:ident, $lt:ident, [$($done:tt)*], [$($props:tt)=>+] ($val:expr) $($rest:tt)*) => {
+        match $crate::utils::try_init_value($val) {
+            ::core::result::Result::Ok(value) => {
+                $crate::try_partial_init!(@eval $ptr, $lt, [$($done)* [value $($props)=>+]], $($rest)*)
+            }
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    };
+
+    // every value is available, so write them all
+    (@eval $ptr:ident, $lt:ident, [$([$value:ident $($props:tt)=>+])*], ) => {
+        ::core::result::Result::Ok(($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*$ptr) $($props)=>+));
+                ::core::ptr::write(prop_ptr, $value);
+                $crate::utils::init_hook($ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, $lt);
+            }
+            prop_ref
+        },)*))
+    };
+}
+
+/// **Unsafe:** Replace already initialized fields of a struct wrapped in `MaybeUninit`, dropping
+/// their old values.
+///
+/// This accepts the same syntax as [`partial_init!`] and returns the same mutable references.
+/// Each value is evaluated and swapped into its field before the old value is dropped, in the
+/// order the fields appear in the macro invocation, so the target stays fully initialized even
+/// if evaluating a value or dropping an old one panics.
+///
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized. With an [`InitTracker`](crate::InitTracker), these
+/// are the fields marked as initialized, which can be replaced through
+/// [`slot_mut`](crate::InitTracker::slot_mut).
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, reinit};
+///
+/// struct Settings { title: String, volume: u8, shaders: Vec<String> }
+///
+/// let mut live = MaybeUninit::<Settings>::uninit();
+/// partial_init!(live => {
+///     title: String::from("demo"),
+///     volume: 10,
+///     shaders: vec![String::from("blur")],
+/// });
+///
+/// // apply only the fields that changed on reload
+/// let new_volume = Some(7);
+/// let new_shaders = vec![String::from("bloom")];
+/// unsafe { reinit!(live => shaders = new_shaders) };
+/// if let Some(volume) = new_volume {
+///     unsafe { reinit!(live => volume = volume) };
+/// }
+///
+/// let live = unsafe { live.assume_init() };
+/// assert_eq!((live.title.as_str(), live.volume), ("demo", 7));
+/// assert_eq!(live.shaders, ["bloom"]);
+/// ```
+#[macro_export]
+macro_rules! reinit {
+    // replace multiple fields
+    ($expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            let value = $val;
+            $crate::utils::replace_field(ptr, prop_ptr, value, $crate::__path_str!($($props)=>+));
+            #[allow(unused_unsafe)]
+            let prop_ref = unsafe { $crate::utils::deref_ptr_with_lt(prop_ptr, lt) };
+            prop_ref
+        },)*)
+    }};
+
+    // replace a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::reinit!($expr => { $($props)=>+: $val }).0
+    };
+}
+
+/// **Unsafe:** Partially initialize a struct wrapped in `MaybeUninit`, feeding the bytes of each
+/// field into a [`Hasher`](core::hash::Hasher) as it is written.
+///
+/// This accepts the same syntax as [`partial_init!`], preceded by an expression for the hasher,
+/// and returns the same mutable references.
+/// The fields are hashed in the order they appear in the macro invocation, so the hasher ends up
+/// with a checksum of exactly the bytes that were initialized.
+///
+/// This must be used in an `unsafe` block or function.
+/// The types of the specified fields must not contain any padding bytes.
+///
+/// ## Example
+/// ```
+/// use core::hash::Hasher;
+/// use core::mem::MaybeUninit;
+/// use project_uninit::partial_init_hashed;
+///
+/// #[repr(C)]
+/// struct Entry { key: u64, len: u32, flags: u32 }
+///
+/// // FNV-1a
+/// struct Fnv(u64);
+/// impl Hasher for Fnv {
+///     fn write(&mut self, bytes: &[u8]) {
+///         for b in bytes {
+///             self.0 = (self.0 ^ *b as u64).wrapping_mul(0x100000001b3);
+///         }
+///     }
+///     fn finish(&self) -> u64 { self.0 }
+/// }
+///
+/// let mut entry = MaybeUninit::<Entry>::uninit();
+/// let mut hasher = Fnv(0xcbf29ce484222325);
+/// let (key, len) = unsafe { partial_init_hashed!(hasher, entry => { key: 42, len: 7 }) };
+/// assert_eq!((*key, *len), (42, 7));
+///
+/// let mut expected = Fnv(0xcbf29ce484222325);
+/// expected.write(&42u64.to_ne_bytes());
+/// expected.write(&7u32.to_ne_bytes());
+/// assert_eq!(hasher.finish(), expected.finish());
+/// ```
+#[macro_export]
+macro_rules! partial_init_hashed {
+    // intialize multiple fields
+    ($hasher:expr, $expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let hasher = &mut $hasher;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
         }
         ($({
             let prop_ref;
             #[allow(unused_unsafe)]
             unsafe {
-                let prop_ptr = ::core::ptr::addr_of_mut!((*ptr).$($props).+);
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
                 ::core::ptr::write(prop_ptr, $val);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
                 prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
             }
+            $crate::utils::hash_bytes_of(hasher, prop_ref);
             prop_ref
         },)*)
     }};
 
+    // initialize a single field
+    ($hasher:expr, $expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::partial_init_hashed!($hasher, $expr => { $($props)=>+: $val }).0
+    };
+}
+
+/// Partially initialize a struct wrapped in `MaybeUninit` whose fields may be unaligned, such as a
+/// `#[repr(packed)]` struct.
+///
+/// This accepts the same field paths as [`partial_init!`], but each value is written with
+/// [`write_unaligned`](core::ptr::write_unaligned), and raw pointers to the fields are returned
+/// instead of references, since a reference to an unaligned field is undefined behavior.
+/// Read the fields back with [`read_unaligned`](core::ptr::read_unaligned).
+/// Like [`partial_init!`], this statically ensures that the same field is not set multiple times
+/// in the same macro call.
+///
+/// This must be used in an `unsafe` block or function when a path continues into a field of a
+/// union, as with [`addr_of_mut!`](core::ptr::addr_of_mut). A union's own fields can be written
+/// without `unsafe`, since no reference to them is created.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::partial_init_unaligned;
+///
+/// #[repr(C, packed)]
+/// struct Header { tag: u8, len: u32, crc: [u16; 2] }
+///
+/// let mut header = MaybeUninit::<Header>::uninit();
+/// let len: *mut u32 = partial_init_unaligned!(header => len = 512);
+/// assert_eq!(unsafe { len.read_unaligned() }, 512);
+///
+/// partial_init_unaligned!(header => { tag: 1, crc => [0]: 0xabcd, crc => [1]: 0x1234 });
+/// let header = unsafe { header.assume_init() };
+/// assert_eq!({ header.crc }, [0xabcd, 0x1234]);
+/// ```
+#[macro_export]
+macro_rules! partial_init_unaligned {
+    // initialize multiple fields
+    ($expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to reach the fields, and that the indexed
+            // fields are arrays; the fields can't be borrowed, since they may be unaligned
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            $( let _ = ::core::ptr::addr_of_mut!($crate::__field_place!((*_x) $($props)=>+)); )*
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            let value = $val;
+            #[allow(unused_unsafe)]
+            unsafe {
+                ::core::ptr::write_unaligned(prop_ptr, value);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+            }
+            prop_ptr
+        },)*)
+    }};
+
     // initialize a single field
     ($expr:expr => $($props:tt)=>+ = $val:expr) => {
-        $crate::partial_init!($expr => { $($props)=>+: $val }).0
+        $crate::partial_init_unaligned!($expr => { $($props)=>+: $val }).0
     };
 }
 
+///```compile_fail,E0793
+/// use project_uninit::partial_init;
+/// use core::mem::MaybeUninit;
+/// #[repr(packed)]
+/// struct Foo { a: u8, b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = partial_init!(x => { a: 1, b: 6 });
+///```
+fn _partial_init_packed_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init;
+/// use core::mem::MaybeUninit;
+/// struct Foo<'a> { a: u32, b: &'a u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let (a, b) = partial_init!(x => { a: 1, b: |(a,)| => a });
+/// *a += 1;
+/// let _ = *b;
+///```
+fn _partial_init_lazy_value_borrows_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init_unaligned;
+/// use core::mem::MaybeUninit;
+/// #[repr(packed)]
+/// struct Foo { a: u8, b: (u32, u16) }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = partial_init_unaligned!(x => { a: 1, b: (6, 7), b => 0: 8 });
+///```
+fn _partial_init_unaligned_parent_and_child_fails() {}
+
+///```compile_fail,E0133
+/// use project_uninit::partial_init_unaligned;
+/// use core::mem::MaybeUninit;
+/// #[derive(Clone, Copy)]
+/// union Value { int: u32, pair: (u8, u16) }
+/// #[repr(packed)]
+/// struct Foo { tag: u8, value: Value }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = partial_init_unaligned!(x => value => pair => 1 = 2);
+///```
+fn _partial_init_unaligned_into_union_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::partial_init_hashed;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: i32, b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let mut hasher = std::collections::hash_map::DefaultHasher::new();
+/// let _ = partial_init_hashed!(hasher, x => { a: 1, b: 6 });
+///```
+fn _partial_init_hashed_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::try_partial_init;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: i32, b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = try_partial_init!(x => { a: Ok::<_, ()>(1), a: Ok(2) });
+///```
+fn _try_partial_init_multiple_per_macro_call_fails() {}
+
+///```compile_fail
+/// use project_uninit::reinit;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: i32, b: u32 }
+/// let mut x = MaybeUninit::new(Foo { a: 1, b: 2 });
+/// let _ = reinit!(x => { a: 1, b: 6 });
+///```
+fn _reinit_requires_unsafe() {}
+
 ///```compile_fail
 /// use project_uninit::partial_init;
 /// use core::mem::MaybeUninit;
@@ -131,6 +809,24 @@ macro_rules! partial_init {
 ///```
 fn _test_multiple_per_macro_call_fails() {}
 
+///```compile_fail
+/// use project_uninit::partial_init;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: (i32, u32), b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = partial_init!(x => { a => { 0: 1, 1: 2 }, b: 3, a => { 1: 4 } });
+///```
+fn _same_field_in_two_groups_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: (i32, u32), b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let _ = partial_init!(x => { a: (1, 2), a => { 1: 4 } });
+///```
+fn _group_under_initialized_parent_fails() {}
+
 ///```compile_fail
 /// use project_uninit::{partial_init, project_uninit};
 /// use core::mem::MaybeUninit;
@@ -153,3 +849,13 @@ fn _partial_init_with_existing_field_borrow_fails() {}
 /// *a = MaybeUninit::new(3);
 ///```
 fn _partial_init_with_existing_mut_field_borrow_fails() {}
+
+///```compile_fail
+/// use project_uninit::{partial_init, ProjectUninit};
+/// use core::mem::MaybeUninit;
+/// #[derive(ProjectUninit, Default)]
+/// struct Foo { a: (i32, i32), b: u32 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// partial_init!(x => { a => 0: 1, ..Default::default() });
+///```
+fn _partial_init_rest_with_nested_field_fails() {}
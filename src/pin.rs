@@ -0,0 +1,239 @@
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+
+/// A struct with a typed view of its possibly uninitialized fields through a pinned slot,
+/// generated by `#[derive(ProjectUninit)]` for structs with fields marked `#[uninit(pin)]`.
+///
+/// For a struct `Foo`, the derive generates `FooUninitPin<'a>` next to it, like the views of
+/// [`UninitView`](crate::UninitView), where each field marked `#[uninit(pin)]` is wrapped in
+/// `Pin<&'a mut MaybeUninit<_>>` and every other field in `&'a mut MaybeUninit<_>`.
+/// The marked fields are structurally pinned, so the derive makes sure that the struct can't
+/// move out of them: it implements `Unpin` for the struct only if the types of the pinned fields
+/// do, and the struct can't implement `Drop`, since `drop` takes `&mut self` even if the struct
+/// was pinned.
+///
+/// Once every field is initialized, [`assume_init_pin`] turns the pinned slot into a pinned
+/// reference to the struct.
+///
+/// ## Safety
+/// Each field returned pinned by `view_pin` must be structurally pinned, as above.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::marker::PhantomPinned;
+/// use core::mem::MaybeUninit;
+/// use core::ptr;
+/// use project_uninit::{assume_init_pin, ProjectUninit, UninitPinViewExt};
+///
+/// struct Links { prev: *const Links, next: *const Links, _pin: PhantomPinned }
+///
+/// #[derive(ProjectUninit)]
+/// struct Node { #[uninit(pin)] links: Links, value: u32 }
+///
+/// let mut slot = Box::pin(MaybeUninit::<Node>::uninit());
+/// let fields = slot.as_mut().uninit_fields_pin();
+/// fields.value.write(7);
+/// let this: *const Links = fields.links.as_ptr();
+/// unsafe {
+///     // `Links` is only ever used through the pinned projection
+///     let links = fields.links.get_unchecked_mut();
+///     links.write(Links { prev: this, next: this, _pin: PhantomPinned });
+/// }
+///
+/// let node = unsafe { assume_init_pin(slot.as_mut()) };
+/// assert_eq!(node.value, 7);
+/// assert!(ptr::eq(node.links.next, &node.links));
+/// # }
+/// ```
+pub unsafe trait UninitPinView: Sized {
+    /// A struct with a pinned `&mut MaybeUninit` reference to each pinned field, and an
+    /// unpinned one to each other field.
+    type Pin<'a>
+    where
+        Self: 'a;
+
+    /// Get pinned references to the pinned fields of `slot`, and mutable references to the
+    /// others.
+    fn view_pin(slot: Pin<&mut MaybeUninit<Self>>) -> Self::Pin<'_>;
+}
+
+/// Methods on pinned `MaybeUninit` slots for viewing the fields of a struct implementing
+/// [`UninitPinView`].
+pub trait UninitPinViewExt<'a, T: UninitPinView> {
+    /// Get references to every field at once, pinning the structurally pinned ones.
+    fn uninit_fields_pin(self) -> T::Pin<'a>;
+}
+
+impl<'a, T: UninitPinView> UninitPinViewExt<'a, T> for Pin<&'a mut MaybeUninit<T>> {
+    fn uninit_fields_pin(self) -> T::Pin<'a> {
+        T::view_pin(self)
+    }
+}
+
+/// **Unsafe:** Get a pinned reference to the value in a pinned slot.
+///
+/// ## Safety
+/// The value must be initialized. Since it is pinned, it must also be dropped in place before the
+/// slot's memory is reused or freed, e.g. with `drop_in_place`, as `MaybeUninit` never drops it.
+pub unsafe fn assume_init_pin<T>(slot: Pin<&mut MaybeUninit<T>>) -> Pin<&mut T> {
+    slot.map_unchecked_mut(|slot| slot.assume_init_mut())
+}
+
+/// Implements `Unpin` regardless of `T`, so that generated `Unpin` impls can mention every
+/// generic parameter of a struct without depending on whether the struct itself is `Unpin`.
+#[doc(hidden)]
+pub struct AlwaysUnpin<'a, T: ?Sized>(PhantomData<&'a ()>, PhantomData<T>);
+
+impl<T: ?Sized> Unpin for AlwaysUnpin<'_, T> {}
+
+/// Obtain pinned `Pin<&mut MaybeUninit<_>>` references to fields of a struct in a pinned
+/// `Pin<&mut MaybeUninit<_>>`, or `&mut MaybeUninit<_>` references to fields marked `unpin`.
+///
+/// This statically ensures that multiple references to the same value are not returned, like
+/// [`project_uninit_mut!`](crate::project_uninit_mut). The pinned slot is consumed, so pass
+/// `slot.as_mut()` to keep using it afterwards. A pinned field can be projected further with
+/// another call, e.g. to reach the `Unpin` fields of a pinned field.
+///
+/// **Unsafe:** Projecting a pinned field must be done in an `unsafe` block, since the struct must
+/// treat the field as structurally pinned: it must not move out of the field while the struct is
+/// pinned, e.g. in its `Drop` impl, and it must not implement `Unpin` unless the field does.
+/// Fields marked `unpin` can be projected outside of an `unsafe` block, but their types must
+/// implement `Unpin`.
+///
+/// ## Example
+/// ```
+/// use core::marker::PhantomPinned;
+/// use core::mem::MaybeUninit;
+/// use core::pin::Pin;
+/// use core::ptr;
+/// use project_uninit::project_uninit_pin;
+///
+/// struct Links { prev: *const Links, next: *const Links, _pin: PhantomPinned }
+/// struct Node { links: Links, value: u32 }
+///
+/// let mut slot = Box::pin(MaybeUninit::<Node>::uninit());
+///
+/// // `Node` doesn't move out of `links`, so it can be projected as pinned
+/// let (links, value) = unsafe { project_uninit_pin!(slot.as_mut() => { links, unpin value }) };
+/// value.write(7);
+/// let this: *const Links = links.as_ptr();
+/// let (prev, next) = project_uninit_pin!(links => { unpin prev, unpin next });
+/// prev.write(this);
+/// next.write(this);
+///
+/// let node: Pin<&mut Node> = unsafe { slot.as_mut().map_unchecked_mut(|s| s.assume_init_mut()) };
+/// assert_eq!(node.value, 7);
+/// assert!(ptr::eq(node.links.next, &node.links));
+/// ```
+#[macro_export]
+macro_rules! project_uninit_pin {
+    // sort the field paths into pinned and unpinned ones
+    (@parse $expr:expr; [$($done:tt)*] unpin $($props:tt)=>+ $(, $($rest:tt)*)?) => {
+        $crate::project_uninit_pin!(@parse $expr; [$($done)* (unpin $($props)=>+)] $($($rest)*)?)
+    };
+    (@parse $expr:expr; [$($done:tt)*] $($props:tt)=>+ $(, $($rest:tt)*)?) => {
+        $crate::project_uninit_pin!(@parse $expr; [$($done)* (pin $($props)=>+)] $($($rest)*)?)
+    };
+    (@parse $expr:expr; [$(($mode:ident $($props:tt)=>+))*]) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        let _pin: ::core::pin::Pin<&mut ::core::mem::MaybeUninit<_>> = $expr;
+        // the fields are never moved, and are only returned unpinned if they are `Unpin`
+        #[allow(unused_unsafe)]
+        let _ref = unsafe { ::core::pin::Pin::get_unchecked_mut(_pin) };
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
+            }
+            $crate::project_uninit_pin!(@$mode ret)
+        },)*)
+    }};
+    // pinning a projected field is up to the caller, so this is outside of an `unsafe` block
+    (@pin $field:ident) => {
+        $crate::utils::pin_field($field)
+    };
+    (@unpin $field:ident) => {
+        $crate::utils::unpin_field($field)
+    };
+
+    // project mutliple fields
+    ($expr:expr => {$($paths:tt)*}) => {
+        $crate::project_uninit_pin!(@parse $expr; [] $($paths)*)
+    };
+
+    // project a single field
+    ($expr:expr => $($path:tt)+) => {
+        $crate::project_uninit_pin!($expr => {$($path)+}).0
+    };
+}
+
+///```compile_fail
+/// use core::marker::PhantomPinned;
+/// use project_uninit::project_uninit_pin;
+/// struct Foo { a: u32, b: PhantomPinned }
+/// let mut x = Box::pin(core::mem::MaybeUninit::<Foo>::uninit());
+/// let b = project_uninit_pin!(x.as_mut() => unpin b);
+///```
+fn _project_pin_unpin_field_must_be_unpin() {}
+
+///```compile_fail
+/// use core::marker::PhantomPinned;
+/// use project_uninit::project_uninit_pin;
+/// struct Foo { a: u32, b: PhantomPinned }
+/// let mut x = Box::pin(core::mem::MaybeUninit::<Foo>::uninit());
+/// let b = project_uninit_pin!(x.as_mut() => b);
+///```
+fn _project_pin_pinned_field_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_pin;
+/// struct Foo { a: u32, b: u32 }
+/// let mut x = Box::pin(core::mem::MaybeUninit::<Foo>::uninit());
+/// let (a, a2) = project_uninit_pin!(x.as_mut() => { unpin a, unpin a });
+///```
+fn _project_pin_same_field_twice_fails() {}
+
+///```compile_fail
+/// use core::marker::PhantomPinned;
+/// use project_uninit::ProjectUninit;
+/// #[derive(ProjectUninit)]
+/// struct Foo { #[uninit(pin)] a: PhantomPinned, b: u32 }
+/// fn assert_unpin<T: Unpin>() {}
+/// assert_unpin::<Foo>();
+///```
+fn _pinned_field_must_be_unpin_for_struct_to_be_unpin() {}
+
+///```compile_fail
+/// use project_uninit::ProjectUninit;
+/// #[derive(ProjectUninit)]
+/// struct Foo { #[uninit(pin)] a: u32 }
+/// impl Drop for Foo {
+///     fn drop(&mut self) {}
+/// }
+///```
+fn _pinned_struct_implements_drop_fails() {}
+
+///```compile_fail
+/// use core::marker::PhantomPinned;
+/// use project_uninit::ProjectUninit;
+/// #[derive(ProjectUninit)]
+/// struct Foo { #[uninit(pin)] a: PhantomPinned }
+/// impl Unpin for Foo {}
+///```
+fn _pinned_struct_implements_unpin_fails() {}
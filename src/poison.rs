@@ -0,0 +1,68 @@
+use core::{mem::MaybeUninit, ptr};
+
+use crate::sanitize;
+
+/// The byte pattern written by [`uninit_poisoned`] and [`fill_poison`].
+///
+/// With the `poison` feature enabled in debug builds, this crate also writes it over every slot
+/// it creates or starts tracking, such as those of [`InitTracker::new`](crate::InitTracker::new),
+/// [`init_with`](crate::init_with), `boxed_uninit` and generated builders and partial guards, and
+/// over every field it drops, so reading a field that isn't initialized is obvious in tests and
+/// core dumps.
+pub const POISON_BYTE: u8 = 0xa5;
+
+/// Create a `MaybeUninit<T>` whose bytes are all [`POISON_BYTE`].
+///
+/// Reading a field before it is initialized then produces an obviously bogus value
+/// (e.g. `0xa5a5a5a5`) instead of plausible stale data.
+/// The storage is still considered uninitialized by memory checkers enabled with the `msan` or
+/// `valgrind` features.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{partial_init, uninit_poisoned};
+///
+/// let mut pair = uninit_poisoned::<(u32, u32)>();
+/// partial_init!(pair => 0 = 1);
+///
+/// // oops, forgot to initialize the second field
+/// let pair = unsafe { pair.assume_init() };
+/// assert_eq!(pair, (1, 0xa5a5a5a5));
+/// ```
+pub fn uninit_poisoned<T>() -> MaybeUninit<T> {
+    let mut slot = MaybeUninit::uninit();
+    fill_poison(&mut slot);
+    slot
+}
+
+/// Overwrite every byte of `slot` with [`POISON_BYTE`].
+///
+/// Any value previously stored in the slot is not dropped.
+pub fn fill_poison<T>(slot: &mut MaybeUninit<T>) {
+    let ptr = slot.as_mut_ptr() as *mut u8;
+    unsafe { ptr::write_bytes(ptr, POISON_BYTE, core::mem::size_of::<T>()) };
+    sanitize::mark_uninit(ptr, core::mem::size_of::<T>());
+}
+
+// Called when this crate creates a slot, or starts tracking one, before any field is initialized.
+// With the `poison` feature enabled in debug builds, the slot is filled with `POISON_BYTE`, so
+// reading a field that was never initialized gives an obviously bogus value.
+#[doc(hidden)]
+#[allow(unused_variables)]
+pub fn poison_new<T>(slot: &mut MaybeUninit<T>) {
+    #[cfg(all(feature = "poison", debug_assertions))]
+    fill_poison(slot);
+}
+
+// Called after `len` bytes at `ptr` have been dropped in place by this crate.
+// With the `poison` feature enabled in debug builds, the bytes are filled with `POISON_BYTE`.
+#[allow(unused_variables)]
+pub(crate) fn deinit(ptr: *mut u8, len: usize) {
+    #[cfg(all(feature = "poison", debug_assertions))]
+    unsafe {
+        ptr::write_bytes(ptr, POISON_BYTE, len)
+    };
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_uninit(ptr, len);
+    sanitize::mark_uninit(ptr, len);
+}
@@ -1,6 +1,27 @@
 /// Obtain `&MaybeUninit<_>` references to fields of a struct wrapped in `MaybeUninit<_>`.
 ///
 /// This must be used in an `unsafe` block or function when accessing fields of unions.
+/// Prefer [`project_union!`](crate::project_union), which marks the access as `unsafe` in the
+/// invocation itself.
+/// Fields exported with [`ExportFields`](crate::ExportFields) can be projected where they aren't
+/// otherwise accessible by writing `pub` before the field paths: `project_uninit!(x => pub { a, b })`.
+/// Writing `strict` before the field paths instead checks that each field is a field of the
+/// struct itself rather than of a `Deref` target, for types that implement
+/// [`UninitView`](crate::UninitView).
+/// Elements of arrays are projected with integer literal indices, which are checked against the
+/// length of the array at compile time: `project_uninit!(x => buf => [3])`.
+/// Fields that are themselves `MaybeUninit<_>` or `ManuallyDrop<_>` are continued into with a
+/// `*` segment: `project_uninit!(x => cache => * => value)` projects `value` of a
+/// `MaybeUninit<Inner>` field. This goes through a pointer cast rather than `Deref`, so it can
+/// also be used in `strict` mode.
+/// Paths defined with [`field_path!`](crate::field_path) are used by writing `@NAME` at the start
+/// of a field path: `project_uninit!(x => @NAME => a)`.
+/// Fields named with raw identifiers are written the same way as in the struct:
+/// `project_uninit!(x => r#type)`. Names like `ptr` or `lt` used by the macros' own bindings don't
+/// collide with fields or variables of the same name.
+/// When the type of the struct can't be inferred from the field paths, as in generic code, it can
+/// be given after the slot with `as`: `project_uninit!(x as MaybeUninit<Foo<T>> => a)`.
+/// A slot written with more than one token, like `self.slot`, must then be put in parentheses.
 ///
 /// ## Syntax
 /// ```
@@ -70,6 +91,78 @@
 ///
 #[macro_export]
 macro_rules! project_uninit {
+    // give the type of the slot
+    ($head:tt as $ty:ty => $($rest:tt)*) => {
+        $crate::project_uninit!({
+            #[allow(unused_imports)]
+            use ::core::borrow::Borrow;
+            let _ref: &$ty = $head.borrow();
+            _ref
+        } => $($rest)*)
+    };
+
+    // project mutliple exported fields
+    ($expr:expr => pub {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &::core::mem::MaybeUninit<_> = $expr.borrow();
+        let ptr = $crate::utils::as_mut_ptr(::core::mem::MaybeUninit::as_ptr(_ref));
+        let lt = $crate::utils::bind_ref_lt(_ref);
+
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = $crate::__export_ptr!(ptr, $($props)=>+);
+                ret = $crate::utils::uninit_from_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single exported field
+    ($expr:expr => pub $($props:tt)=>+) => {
+        $crate::project_uninit!($expr => pub {$($props)=>+}).0
+    };
+
+    // project mutliple fields, checking that each is a field of the struct itself
+    ($expr:expr => strict {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &::core::mem::MaybeUninit<_> = $expr.borrow();
+        let ptr = ::core::mem::MaybeUninit::as_ptr(_ref);
+        let lt = $crate::utils::bind_ref_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields, and that they aren't
+            // reached through `Deref`
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+            #[allow(unused_unsafe)]
+            unsafe {
+                $( $crate::__strict_path!(ptr, $($props)=>+); )*
+            }
+        }
+
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::uninit_from_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single field, checking that it is a field of the struct itself
+    ($expr:expr => strict $($props:tt)=>+) => {
+        $crate::project_uninit!($expr => strict {$($props)=>+}).0
+    };
+
     // project mutliple fields
     ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
         #[allow(unused_imports)]
@@ -83,24 +176,35 @@ macro_rules! project_uninit {
             // it's only to assert that it is safe to access the fields
             #[allow(unused_unsafe)]
             let _x = unsafe { &*ptr };
-            let _y = ($(&_x.$($props).+,)*);
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
         }
 
         ($({
             let ret;
             #[allow(unused_unsafe)]
             unsafe {
-                let prop_ptr = ::core::ptr::addr_of!((*ptr).$($props).+);
+                let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+));
                 ret = $crate::utils::uninit_from_ptr(prop_ptr, lt);
             }
             ret
         },)*)
     }};
 
+    // project multiple fields, some of them through aliases
+    ($expr:expr => {$($body:tt)*}) => {
+        $crate::__resolve_paths!((project_uninit ($expr =>) ()) [] [] $($body)*)
+    };
+
     // project a single field
     ($expr:expr => $($props:tt)=>+) => {
         $crate::project_uninit!($expr => {$($props)=>+}).0
     };
+
+    // project a single field through an alias
+    ($expr:expr => @ $alias:ident $(=> $props:tt)*) => {
+        $crate::project_uninit!($expr => {@ $alias $(=> $props)*}).0
+    };
 }
 
 /// Obtain `&mut MaybeUninit<_>` references to fields of a struct wrapped in `MaybeUninit<_>`.
@@ -108,6 +212,25 @@ macro_rules! project_uninit {
 /// This statically ensures that multiple references to the same value are not returned.
 ///
 /// This must be used in an `unsafe` block or function when accessing fields of unions.
+/// Prefer [`project_union_mut!`](crate::project_union_mut), which marks the access as `unsafe`
+/// in the invocation itself.
+/// Fields exported with [`ExportFields`](crate::ExportFields) can be projected where they aren't
+/// otherwise accessible by writing `pub` before the field paths: `project_uninit_mut!(x => pub a)`.
+/// Writing `strict` before the field paths instead checks that each field is a field of the
+/// struct itself rather than of a `Deref` target, for types that implement
+/// [`UninitView`](crate::UninitView).
+/// Elements of arrays are projected with integer literal indices, and distinct elements of the
+/// same array can be projected at the same time: `project_uninit_mut!(x => { buf => [0], buf => [1] })`.
+/// Fields of the same union overlap, so only one of them can be projected at a time. With the
+/// `derive` feature, this is also checked for paths that index into the fields.
+/// Fields that are themselves `MaybeUninit<_>` or `ManuallyDrop<_>` are continued into with a
+/// `*` segment, which goes through a pointer cast rather than `DerefMut`:
+/// `project_uninit_mut!(x => { cache => * => value, guard => * => len })`.
+/// Paths defined with [`field_path!`](crate::field_path) are used by writing `@NAME` at the start
+/// of a field path: `project_uninit_mut!(x => { @NAME, b })`.
+/// When the type of the struct can't be inferred from the field paths, as in generic code, it can
+/// be given after the slot with `as`: `project_uninit_mut!(x as MaybeUninit<Foo<T>> => a)`.
+/// A slot written with more than one token, like `self.slot`, must then be put in parentheses.
 ///
 /// ## Syntax
 /// ```
@@ -181,6 +304,81 @@ macro_rules! project_uninit {
 ///
 #[macro_export]
 macro_rules! project_uninit_mut {
+    // give the type of the slot
+    ($head:tt as $ty:ty => $($rest:tt)*) => {
+        $crate::project_uninit_mut!({
+            #[allow(unused_imports)]
+            use ::core::borrow::BorrowMut;
+            let _ref: &mut $ty = $head.borrow_mut();
+            _ref
+        } => $($rest)*)
+    };
+
+    // project mutliple exported fields
+    ($expr:expr => pub {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = $crate::__export_ptr!(ptr, $($props)=>+);
+                ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single exported field
+    ($expr:expr => pub $($props:tt)=>+) => {
+        $crate::project_uninit_mut!($expr => pub {$($props)=>+}).0
+    };
+
+    // project mutliple fields, checking that each is a field of the struct itself
+    ($expr:expr => strict {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields, and that they aren't
+            // reached through `Deref`
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+            #[allow(unused_unsafe)]
+            unsafe {
+                $( $crate::__strict_path!(ptr, $($props)=>+); )*
+            }
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single field, checking that it is a field of the struct itself
+    ($expr:expr => strict $($props:tt)=>+) => {
+        $crate::project_uninit_mut!($expr => strict {$($props)=>+}).0
+    };
+
     // project mutliple fields
     ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
         // generate an error message if a field is used more than once
@@ -196,23 +394,34 @@ macro_rules! project_uninit_mut {
             // it's only to assert that it is safe to access the fields
             #[allow(unused_unsafe)]
             let _x = unsafe { &mut *ptr };
-            let _y = ($(&mut _x.$($props).+,)*);
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
         }
         ($({
             let ret;
             #[allow(unused_unsafe)]
             unsafe {
-                let prop_ptr = ::core::ptr::addr_of_mut!((*ptr).$($props).+);
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
                 ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
             }
             ret
         },)*)
     }};
 
+    // project multiple fields, some of them through aliases
+    ($expr:expr => {$($body:tt)*}) => {
+        $crate::__resolve_paths!((project_uninit_mut ($expr =>) ()) [] [] $($body)*)
+    };
+
     // project a single field
     ($expr:expr => $($props:tt)=>+) => {
         $crate::project_uninit_mut!($expr => {$($props)=>+}).0
     };
+
+    // project a single field through an alias
+    ($expr:expr => @ $alias:ident $(=> $props:tt)*) => {
+        $crate::project_uninit_mut!($expr => {@ $alias $(=> $props)*}).0
+    };
 }
 
 /// **Unsafe:** Given a `*const` pointer to a struct, obtain `*const` pointers to one or more of its fields.
@@ -257,7 +466,7 @@ macro_rules! project_ptr {
     ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
         let ptr: *const _ = $expr;
         ($(
-            ::core::ptr::addr_of!((*ptr).$($props).+),
+            ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+)),
         )*)
     }};
 
@@ -313,7 +522,7 @@ macro_rules! project_ptr_mut {
     ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
         let ptr: *mut _ = $expr;
         ($(
-            ::core::ptr::addr_of_mut!((*ptr).$($props).+),
+            ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
         )*)
     }};
 
@@ -323,6 +532,338 @@ macro_rules! project_ptr_mut {
     };
 }
 
+/// **Unsafe:** Given a [`NonNull`](core::ptr::NonNull) pointer to a struct, obtain `NonNull`
+/// pointers to one or more of its fields, for reading.
+///
+/// This is like [`project_ptr!`](crate::project_ptr), but keeps the pointers non-null.
+/// The field pointers are derived without `addr_of_mut!`, so they should only be read through;
+/// use [`project_nonnull_mut!`](crate::project_nonnull_mut) for pointers to write through.
+///
+/// This does **not** statically check whether multiple pointers to the same data are returned.
+/// This must be used in an `unsafe` block or function.
+/// The pointer must be valid for the whole struct, as with `project_ptr!`.
+///
+/// ## Usage
+/// ```
+/// use core::ptr::NonNull;
+/// use project_uninit::project_nonnull;
+///
+/// struct Block { size: usize, next: Option<NonNull<Block>>, data: [u8; 16] }
+///
+/// let block = Block { size: 16, next: None, data: [3; 16] };
+/// let ptr = NonNull::from(&block);
+///
+/// unsafe {
+///     let size: NonNull<usize> = project_nonnull!(ptr => size);
+///     assert_eq!(*size.as_ref(), 16);
+///
+///     let (next, last) = project_nonnull!(ptr => { next, data => [15] });
+///     assert!(next.as_ref().is_none());
+///     assert_eq!(last.read(), 3);
+/// }
+/// ```
+#[macro_export]
+macro_rules! project_nonnull {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        let ptr: *const _ = ::core::ptr::NonNull::as_ptr($expr);
+        ($(
+            $crate::utils::nonnull_unchecked(
+                ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+)),
+            ),
+        )*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_nonnull!($expr => {$($props)=>+}).0
+    };
+}
+
+/// **Unsafe:** Given a [`NonNull`](core::ptr::NonNull) pointer to a struct, obtain `NonNull`
+/// pointers to one or more of its fields.
+///
+/// This is like [`project_ptr_mut!`](crate::project_ptr_mut), but keeps the pointers non-null.
+///
+/// This does **not** statically check whether multiple pointers to the same data are returned.
+/// This must be used in an `unsafe` block or function.
+/// The pointer must be valid for the whole struct, as with `project_ptr_mut!`.
+///
+/// ## Usage
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::ptr::NonNull;
+/// use project_uninit::project_nonnull_mut;
+///
+/// struct Block { size: usize, next: Option<NonNull<Block>>, data: [u8; 16] }
+///
+/// let mut slot = Box::new(MaybeUninit::<Block>::uninit());
+/// let ptr = NonNull::from(&mut *slot).cast::<Block>();
+///
+/// let block = unsafe {
+///     let (size, next, data) = project_nonnull_mut!(ptr => { size, next, data });
+///     size.write(16);
+///     next.write(None);
+///     data.write([0; 16]);
+///     slot.assume_init()
+/// };
+/// assert_eq!(block.size, 16);
+/// ```
+#[macro_export]
+macro_rules! project_nonnull_mut {
+    // project mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        let ptr: *mut _ = ::core::ptr::NonNull::as_ptr($expr);
+        ($(
+            $crate::utils::nonnull_unchecked(
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+            ),
+        )*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_nonnull_mut!($expr => {$($props)=>+}).0
+    };
+}
+
+/// **Unsafe:** Given a `*mut MaybeUninit<T>` pointer to a struct, obtain the addresses of one or
+/// more of its fields, as [`NonNull`](core::ptr::NonNull) pointers.
+///
+/// This is for self-referential structs, like intrusive lists or nodes with parent pointers,
+/// where a field stores a pointer to a sibling field, or to itself, before the struct is
+/// initialized. The addresses can be given to [`partial_init!`](crate::partial_init) along with
+/// the other values of the struct. The fields may not be initialized yet, so the pointers must
+/// not be read through until they are, and they only stay valid while the struct isn't moved,
+/// for example when it is boxed or pinned.
+///
+/// The addresses are derived from the given pointer, not from a borrow of the struct, so they
+/// stay valid as long as every later access to the struct is derived from that same pointer too,
+/// for example `partial_init!(unsafe { &mut *ptr } => ...)`. Any access through another
+/// reference to the struct, such as the `Box` the pointer was taken from, invalidates them.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// the same path may be given more than once.
+/// This must be used in an `unsafe` block or function, and the pointer must be valid for writes
+/// of the struct.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::ptr::NonNull;
+/// use project_uninit::{field_addrs, partial_init};
+///
+/// struct Link { prev: NonNull<Link>, next: NonNull<Link> }
+/// struct List { head: Link, len: usize }
+///
+/// let list = Box::into_raw(Box::new(MaybeUninit::<List>::uninit()));
+/// // an empty list's head links to itself
+/// let head = unsafe { field_addrs!(list => head) };
+/// // written through the same pointer, so `head` stays valid
+/// partial_init!(unsafe { &mut *list } => { head: Link { prev: head, next: head }, len: 0 });
+/// let list = unsafe { Box::from_raw(list).assume_init() };
+/// assert_eq!(list.head.next.as_ptr() as *const Link, &list.head as *const Link);
+/// ```
+#[macro_export]
+macro_rules! field_addrs {
+    // multiple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        let ptr: *mut _ = $crate::utils::uninit_inner_ptr($expr);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            let _x = &mut *ptr;
+            $( let _ = $crate::__field_mut!((_x) $($props)=>+); )*
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($(
+            $crate::utils::nonnull_unchecked(
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+            ),
+        )*)
+    }};
+
+    // a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::field_addrs!($expr => {$($props)=>+}).0
+    };
+}
+
+/// Obtain `*mut` pointers to the values in `UnsafeCell` fields of a struct wrapped in
+/// `MaybeUninit`, through a shared reference.
+///
+/// Each field path must end at a field of type `UnsafeCell<F>`, and gives a `*mut F` obtained
+/// with [`UnsafeCell::raw_get`](core::cell::UnsafeCell::raw_get), so no `&mut` to the field or
+/// the struct is ever created. This is meant for lock-free structures whose cells are written
+/// through shared references: the pointers may be written through while the struct is shared, as
+/// long as the writes are synchronized. They only stay valid while the struct isn't moved or
+/// dropped, and the cells may not be initialized, so they must not be read through until they
+/// are. The same path may be given more than once.
+///
+/// This accepts the same field paths as [`project_uninit!`](crate::project_uninit).
+///
+/// ## Example
+/// ```
+/// use core::cell::UnsafeCell;
+/// use core::mem::MaybeUninit;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use project_uninit::{partial_init, project_cells, project_uninit};
+///
+/// struct OneShot { ready: AtomicBool, value: UnsafeCell<u64> }
+///
+/// let mut slot = MaybeUninit::<OneShot>::uninit();
+/// partial_init!(slot => ready = AtomicBool::new(false));
+///
+/// let shared: &MaybeUninit<OneShot> = &slot;
+/// let ready = unsafe { project_uninit!(shared => ready).assume_init_ref() };
+/// let value: *mut u64 = project_cells!(shared => value);
+///
+/// // the producer writes the value, then publishes it
+/// unsafe { value.write(42) };
+/// ready.store(true, Ordering::Release);
+///
+/// // the consumer reads it once it's published
+/// assert!(ready.load(Ordering::Acquire));
+/// assert_eq!(unsafe { value.read() }, 42);
+/// ```
+#[macro_export]
+macro_rules! project_cells {
+    // multiple cells
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &::core::mem::MaybeUninit<_> = $expr.borrow();
+        let ptr = ::core::mem::MaybeUninit::as_ptr(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        #[allow(unused_unsafe)]
+        let cells = unsafe {
+            ($(
+                $crate::utils::cell_ptr(
+                    ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+)),
+                ),
+            )*)
+        };
+        cells
+    }};
+
+    // a single cell
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_cells!($expr => {$($props)=>+}).0
+    };
+}
+
+/// Obtain `&Cell<_>` references to fields of a value in a [`Cell`](core::cell::Cell).
+///
+/// Since a `Cell` can't be borrowed, its fields are usually changed by replacing the whole value.
+/// This projects `&Cell<T>` to a `&Cell<F>` for each field instead, so fields can be read with
+/// `get` and written with `set` on their own. Fields may overlap, and the same path may be given
+/// more than once, since the cells don't give out references to their values.
+///
+/// This accepts the same field paths as [`project_uninit!`](crate::project_uninit), including
+/// `strict` paths, and must be used in an `unsafe` block or function when accessing fields of
+/// unions.
+///
+/// ## Panics
+/// If a field isn't stored in the cell itself, which happens when a path goes through `Deref`,
+/// e.g. into a `Box`: setting the box would free the field. `strict` paths reject `Deref` at
+/// compile time instead.
+///
+/// ## Example
+/// ```
+/// use core::cell::Cell;
+/// use project_uninit::project_cell;
+///
+/// #[derive(Clone, Copy)]
+/// struct Stats { hits: u32, misses: u32, last: (u8, [u8; 2]) }
+///
+/// let stats = Cell::new(Stats { hits: 0, misses: 0, last: (0, [0; 2]) });
+/// let (hits, misses, key) = project_cell!(stats => { hits, misses, last => 1 => [0] });
+///
+/// hits.set(hits.get() + 2);
+/// misses.set(1);
+/// key.set(7);
+/// let stats = stats.get();
+/// assert_eq!((stats.hits, stats.misses, stats.last.1), (2, 1, [7, 0]));
+/// ```
+#[macro_export]
+macro_rules! project_cell {
+    // project multiple fields, checking that each is a field of the struct itself
+    ($expr:expr => strict {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let cell: &::core::cell::Cell<_> = $expr.borrow();
+        let ptr = ::core::cell::Cell::as_ptr(cell);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields, and that they aren't
+            // reached through `Deref`
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+            #[allow(unused_unsafe)]
+            unsafe {
+                $( $crate::__strict_path!(ptr, $($props)=>+); )*
+            }
+        }
+        $crate::__project_cell!(cell, ptr, $( [$($props)=>+] )*)
+    }};
+
+    // project a single field, checking that it is a field of the struct itself
+    ($expr:expr => strict $($props:tt)=>+) => {
+        $crate::project_cell!($expr => strict {$($props)=>+}).0
+    };
+
+    // project multiple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let cell: &::core::cell::Cell<_> = $expr.borrow();
+        let ptr = ::core::cell::Cell::as_ptr(cell);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            let _y = ($(&$crate::__field_place!((_x) $($props)=>+),)*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $crate::__project_cell!(cell, ptr, $( [$($props)=>+] )*)
+    }};
+
+    // project a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_cell!($expr => {$($props)=>+}).0
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __project_cell {
+    ($cell:ident, $ptr:ident, $( [$($props:tt)=>+] )*) => {
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*$ptr) $($props)=>+));
+                ret = $crate::utils::cell_field($cell, prop_ptr, $crate::__path_str!($($props)=>+));
+            }
+            ret
+        },)*)
+    };
+}
+
 ///```compile_fail
 /// use project_uninit::project_uninit_mut;
 /// use core::mem::MaybeUninit;
@@ -342,3 +883,100 @@ fn _test_multiple_per_mut_macro_call_fails() {}
 /// let aa = a;
 ///```
 fn _project_mut_with_existing_borrow_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_mut;
+/// struct Foo { buf: [u8; 4] }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (a, b) = project_uninit_mut!(x => { buf => [1], buf => [1] });
+///```
+fn _project_same_index_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_mut;
+/// struct Foo { buf: [u8; 4] }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (a, b) = project_uninit_mut!(x => { buf => [1], buf => [0x1] });
+///```
+fn _project_same_index_written_differently_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit;
+/// struct Foo { buf: Vec<u8> }
+/// let x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let a = project_uninit!(x => buf => [0]);
+///```
+fn _project_index_into_non_array_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit;
+/// struct Foo { buf: [u8; 4] }
+/// let x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let a = project_uninit!(x => buf => [4]);
+///```
+fn _project_index_out_of_bounds_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_mut;
+/// union Foo { bytes: [u8; 4], words: [u16; 2] }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (a, b) = unsafe { project_uninit_mut!(x => { bytes => [0], words => [1] }) };
+///```
+#[cfg(feature = "derive")]
+fn _project_indexed_fields_of_union_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_mut;
+/// use core::mem::MaybeUninit;
+/// struct Foo { cache: MaybeUninit<(u8, u8)> }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let (a, b) = project_uninit_mut!(x => { cache, cache => * => 0 });
+///```
+fn _project_flattened_field_and_parent_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_uninit_mut;
+/// use core::mem::MaybeUninit;
+/// union Foo { cache: MaybeUninit<(u8, u8)>, raw: u16 }
+/// let mut x = MaybeUninit::<Foo>::uninit();
+/// let a = project_uninit_mut!(x => cache => * => 0);
+///```
+fn _project_flattened_union_field_requires_unsafe() {}
+
+///```compile_fail,E0499
+/// use project_uninit::project_uninit_mut;
+/// use core::mem::MaybeUninit;
+/// union U { a: MaybeUninit<(u8, u8)>, b: MaybeUninit<(u8, u8)> }
+/// let mut x = MaybeUninit::<U>::uninit();
+/// let (a, b) = unsafe { project_uninit_mut!(x => { a => * => 0, b => * => 0 }) };
+///```
+#[cfg(feature = "derive")]
+fn _project_flattened_fields_of_union_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_cells;
+/// struct Foo { a: u32, b: core::cell::UnsafeCell<u32> }
+/// let x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let a = project_cells!(x => a);
+///```
+fn _project_cells_of_non_cell_fails() {}
+
+///```compile_fail
+/// use project_uninit::project_cell;
+/// #[derive(Clone, Copy)]
+/// union Foo { a: u32, b: f32 }
+/// let x = core::cell::Cell::new(Foo { a: 1 });
+/// let b = project_cell!(x => b);
+///```
+fn _project_cell_union_field_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::{project_cell, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Inner { value: u32 }
+/// #[derive(ProjectUninit)]
+/// struct Outer { boxed: Box<Inner> }
+/// let x = core::cell::Cell::new(Outer { boxed: Box::new(Inner { value: 1 }) });
+/// let value = project_cell!(x => strict boxed => value);
+///```
+fn _project_cell_strict_through_deref_fails() {}
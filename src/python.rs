@@ -0,0 +1,79 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use ::pyo3::exceptions::PyValueError;
+use ::pyo3::pyclass_init::PyClassInitializer;
+use ::pyo3::{Py, PyClass, PyErr, PyResult, Python};
+
+use crate::{InitTracker, UninitFields};
+
+/// Create a `#[pyclass]` object whose payload is initialized in place on the heap, one field at
+/// a time, instead of being built on the stack and moved into [`Py::new`].
+///
+/// The payload `T` is held by the class in a `Box<T>`, which is allocated uninitialized and
+/// passed to `init` through an [`InitTracker`]. Once every field is marked as initialized, the
+/// class is created from the box with `C::from` and moved into a new Python object, which only
+/// moves the pointer to the payload, however large it is.
+///
+/// If `init` leaves any field uninitialized, the fields that were initialized are dropped and a
+/// `ValueError` naming the missing fields is returned. If `init` panics, the fields that were
+/// initialized are dropped and the panic continues.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{partial_init, py_new_in_place, ProjectUninit};
+/// use project_uninit::pyo3::prelude::*;
+///
+/// #[derive(ProjectUninit)]
+/// struct Weights { layers: u32, data: [f32; 1 << 16] }
+///
+/// #[pyclass]
+/// struct Model { weights: Box<Weights> }
+///
+/// impl From<Box<Weights>> for Model {
+///     fn from(weights: Box<Weights>) -> Self {
+///         Self { weights }
+///     }
+/// }
+///
+/// Python::initialize();
+/// Python::attach(|py| {
+///     let model = py_new_in_place::<Model, _>(py, |t| unsafe {
+///         partial_init!(t.slot_mut() => { layers: 4, data: [0.5; 1 << 16] });
+///         t.set_init(0);
+///         t.set_init(1);
+///     })
+///     .unwrap();
+///     assert_eq!(model.borrow(py).weights.layers, 4);
+/// });
+/// # }
+/// ```
+pub fn py_new_in_place<C, T>(
+    py: Python<'_>,
+    init: impl FnOnce(&mut InitTracker<'_, T>),
+) -> PyResult<Py<C>>
+where
+    C: PyClass + From<Box<T>> + Into<PyClassInitializer<C>>,
+    T: UninitFields,
+{
+    let mut slot = Box::<T>::new_uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    init(&mut tracker);
+    if let Err(tracker) = tracker.finish() {
+        let mut missing = String::new();
+        for field in tracker.missing() {
+            if !missing.is_empty() {
+                missing.push_str(", ");
+            }
+            missing.push_str(field.name());
+        }
+        return Err(PyErr::new::<PyValueError, _>(alloc::format!(
+            "fields of `{}` were not initialized: {}",
+            core::any::type_name::<T>(),
+            missing
+        )));
+    }
+    let payload = unsafe { slot.assume_init() };
+    Py::new(py, C::from(payload))
+}
@@ -0,0 +1,280 @@
+/// **Unsafe:** Move the values of initialized fields out of a struct wrapped in `MaybeUninit`.
+///
+/// Each field is read with [`ptr::read`](core::ptr::read) and becomes logically uninitialized:
+/// it must not be read or dropped again until it is given a new value, e.g. with
+/// [`partial_init!`](crate::partial_init), and the struct must not be assumed to be initialized
+/// in the meantime.
+/// With the `poison` feature enabled in debug builds, the bytes of each field are overwritten
+/// with [`POISON_BYTE`](crate::POISON_BYTE) once its value is moved out.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut),
+/// and returns a single value or a tuple of values.
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, read_field};
+///
+/// struct Job { id: u64, input: Vec<u8>, output: Option<Vec<u8>> }
+///
+/// let mut job = MaybeUninit::<Job>::uninit();
+/// partial_init!(job => { id: 7, input: vec![1, 2, 3] });
+///
+/// // take the input early to process it before `output` is initialized
+/// let input = unsafe { read_field!(job => input) };
+/// let output = input.iter().map(|b| b * 2).collect();
+/// partial_init!(job => { input: input, output: Some(output) });
+///
+/// let job = unsafe { job.assume_init() };
+/// assert_eq!(job.output.unwrap(), [2, 4, 6]);
+/// ```
+#[macro_export]
+macro_rules! read_field {
+    // read mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            $crate::utils::take_field(prop_ptr)
+        },)*)
+    }};
+
+    // read a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::read_field!($expr => {$($props)=>+}).0
+    };
+}
+
+/// **Unsafe:** Replace the values of initialized fields of a struct wrapped in `MaybeUninit`,
+/// returning the old values.
+///
+/// Each field is replaced with [`ptr::replace`](core::ptr::replace), so the old value is moved
+/// out rather than dropped. This is useful for reusing a slot across iterations of a loop,
+/// keeping the old value of a field such as a buffer to recycle it.
+///
+/// This accepts the same syntax as [`partial_init!`](crate::partial_init) without `..rest`,
+/// and returns a single value or a tuple of values.
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, replace_field, swap_field};
+///
+/// struct Frame { index: u32, pixels: Vec<u8> }
+///
+/// let mut frame = MaybeUninit::<Frame>::uninit();
+/// partial_init!(frame => { index: 0, pixels: vec![0; 16] });
+///
+/// let mut spare = vec![1; 16];
+/// for i in 1..4 {
+///     // draw into the spare buffer, then swap it in
+///     spare.fill(i as u8);
+///     let old = unsafe { replace_field!(frame => index = i) };
+///     assert_eq!(old, i - 1);
+///     unsafe { swap_field!(frame => pixels, &mut spare) };
+/// }
+///
+/// let frame = unsafe { frame.assume_init() };
+/// assert_eq!((frame.index, frame.pixels[0], spare[0]), (3, 3, 2));
+/// ```
+#[macro_export]
+macro_rules! replace_field {
+    // replace mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ : $val:expr ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let value = $val;
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            ::core::ptr::replace(prop_ptr, value)
+        },)*)
+    }};
+
+    // replace a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::replace_field!($expr => { $($props)=>+ : $val }).0
+    };
+}
+
+/// **Unsafe:** Swap the values of initialized fields of a struct wrapped in `MaybeUninit` with
+/// the values behind mutable references.
+///
+/// Each field is swapped with [`ptr::swap`](core::ptr::swap), without dropping either value.
+///
+/// This accepts field paths like [`partial_init!`](crate::partial_init), each given a `&mut`
+/// reference to a value of the same type as the field:
+/// `swap_field!(x => { a: &mut a, b => 0: &mut b })`.
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized.
+///
+/// See [`replace_field!`](crate::replace_field) for an example.
+#[macro_export]
+macro_rules! swap_field {
+    // swap mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ : $other:expr ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $(
+            let other: &mut _ = $other;
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            ::core::ptr::swap(prop_ptr, other);
+        )*
+    }};
+
+    // swap a single field
+    ($expr:expr => $($props:tt)=>+ , $other:expr) => {
+        $crate::swap_field!($expr => { $($props)=>+ : $other })
+    };
+}
+
+/// **Unsafe:** Drop initialized fields of a struct wrapped in `MaybeUninit` in place, and return
+/// `&mut MaybeUninit<_>` references to them so they can be initialized again.
+///
+/// Each field is dropped with [`ptr::drop_in_place`](core::ptr::drop_in_place) and becomes
+/// logically uninitialized, as with [`read_field!`](crate::read_field), including being
+/// poisoned with the `poison` feature enabled in debug builds.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut),
+/// and returns a single reference or a tuple of references.
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be initialized.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{drop_field_in_place, partial_init};
+///
+/// struct Session { user: String, token: Option<String> }
+///
+/// let mut session = MaybeUninit::<Session>::uninit();
+/// partial_init!(session => { user: "alice".into(), token: Some("t0".into()) });
+///
+/// // log out, then log in as someone else
+/// let (user, token) = unsafe { drop_field_in_place!(session => { user, token }) };
+/// user.write("bob".into());
+/// token.write(None);
+///
+/// let session = unsafe { session.assume_init() };
+/// assert_eq!((session.user.as_str(), session.token), ("bob", None));
+/// ```
+#[macro_export]
+macro_rules! drop_field_in_place {
+    // drop mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            #[allow(unused_unsafe)]
+            let prop_ptr = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+            $crate::utils::drop_field(prop_ptr);
+            $crate::utils::uninit_from_mut_ptr(prop_ptr, lt)
+        },)*)
+    }};
+
+    // drop a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::drop_field_in_place!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::read_field;
+/// struct Foo { a: String }
+/// let mut x = core::mem::MaybeUninit::new(Foo { a: String::new() });
+/// let a = read_field!(x => a);
+///```
+fn _read_field_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::read_field;
+/// struct Foo { a: String, b: u32 }
+/// let mut x = core::mem::MaybeUninit::new(Foo { a: String::new(), b: 1 });
+/// let (a, a2) = unsafe { read_field!(x => { a, a }) };
+///```
+fn _read_same_field_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::replace_field;
+/// struct Foo { a: String }
+/// let mut x = core::mem::MaybeUninit::new(Foo { a: String::new() });
+/// let a = replace_field!(x => a = String::new());
+///```
+fn _replace_field_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::swap_field;
+/// struct Foo { a: String, b: String }
+/// let mut x = core::mem::MaybeUninit::new(Foo { a: String::new(), b: String::new() });
+/// let (mut a, mut b) = (String::new(), String::new());
+/// unsafe { swap_field!(x => { a: &mut a, a: &mut b }) };
+///```
+fn _swap_same_field_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::drop_field_in_place;
+/// struct Foo { a: String }
+/// let mut x = core::mem::MaybeUninit::new(Foo { a: String::new() });
+/// drop_field_in_place!(x => a).write(String::new());
+///```
+fn _drop_field_in_place_requires_unsafe() {}
@@ -0,0 +1,266 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::align_of;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{compiler_fence, Ordering};
+
+use crate::utils::Lifetime;
+
+/// Exclusive access to a `#[repr(C)]` block of memory-mapped registers, or any other struct at a
+/// fixed address that must only be accessed with volatile reads and writes, like a DMA
+/// descriptor.
+///
+/// The block is never dereferenced: fields are projected to [`Reg`] handles with
+/// [`project_regs!`](crate::project_regs), which statically rejects projecting the same register
+/// twice, and written in order with [`write_regs!`](crate::write_regs). Since both borrow the
+/// block mutably, a register can't be reached through two handles at once.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{project_regs, write_regs, RegisterBlock};
+///
+/// #[repr(C)]
+/// struct Timer { control: u32, reload: u32, value: u32, prescale: [u16; 2] }
+///
+/// // stands in for the timer's registers at a fixed address
+/// let mut mmio = Timer { control: 0, reload: 0, value: 0, prescale: [0; 2] };
+/// let mut timer = unsafe { RegisterBlock::new(&mut mmio as *mut Timer) };
+///
+/// write_regs!(timer => { prescale => [0]: 8, prescale => [1]: 1, reload: 1000 });
+/// let (mut control, value) = project_regs!(timer => { control, value });
+/// control.modify(|c| c | 1);
+/// assert_eq!(value.read(), 0);
+///
+/// assert_eq!((mmio.control, mmio.reload, mmio.prescale), (1, 1000, [8, 1]));
+/// ```
+pub struct RegisterBlock<T> {
+    ptr: NonNull<T>,
+}
+
+// the block is exclusive access to the registers, like a `&mut T`
+unsafe impl<T: Send> Send for RegisterBlock<T> {}
+
+impl<T> RegisterBlock<T> {
+    /// Take exclusive access to the register block at `ptr`.
+    ///
+    /// ## Safety
+    /// `ptr` must be non-null, properly aligned, and valid for volatile reads and writes of `T`
+    /// for as long as the block and any [`Reg`] projected from it exist, and it must not be
+    /// accessed other than through this block in that time.
+    /// Reading a register must give a valid value of its type, so fields in ordinary memory must
+    /// be written before they are read.
+    pub const unsafe fn new(ptr: *mut T) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+        }
+    }
+
+    /// Take exclusive access to the register block at the fixed address `addr`.
+    ///
+    /// ## Panics
+    /// If `addr` is null or not aligned for `T`.
+    ///
+    /// ## Safety
+    /// The same as for [`new`](Self::new).
+    pub unsafe fn from_addr(addr: usize) -> Self {
+        assert!(
+            addr != 0 && addr.is_multiple_of(align_of::<T>()),
+            "register block address {:#x} is null or misaligned",
+            addr
+        );
+        Self::new(ptr::with_exposed_provenance_mut(addr))
+    }
+
+    /// The address of the block.
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T> fmt::Debug for RegisterBlock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RegisterBlock").field(&self.ptr).finish()
+    }
+}
+
+/// A single register in a [`RegisterBlock`], projected with
+/// [`project_regs!`](crate::project_regs), that is only accessed with volatile reads and writes.
+///
+/// Volatile accesses are never merged, removed or reordered with each other by the compiler, but
+/// ordinary memory accesses can be moved across them. [`write_release`](Reg::write_release) and
+/// [`read_acquire`](Reg::read_acquire) add compiler fences for registers that hand ordinary memory
+/// to a device, like a DMA buffer. These only constrain the compiler: on a CPU that reorders
+/// accesses to device memory, a hardware barrier is still needed.
+pub struct Reg<'a, F> {
+    ptr: *mut F,
+    _lt: PhantomData<&'a mut F>,
+}
+
+// a `Reg` is exclusive access to the register, like a `&mut F`
+unsafe impl<F: Send> Send for Reg<'_, F> {}
+
+impl<F: Copy> Reg<'_, F> {
+    /// Read the register.
+    pub fn read(&self) -> F {
+        unsafe { ptr::read_volatile(self.ptr) }
+    }
+
+    /// Write `value` to the register.
+    pub fn write(&mut self, value: F) {
+        unsafe { ptr::write_volatile(self.ptr, value) }
+    }
+
+    /// Read the register, and write back the result of `f`.
+    pub fn modify(&mut self, f: impl FnOnce(F) -> F) {
+        let value = f(self.read());
+        self.write(value);
+    }
+
+    /// Write `value` to the register after every memory access before it, such as writes to a
+    /// buffer the register hands to a device.
+    pub fn write_release(&mut self, value: F) {
+        compiler_fence(Ordering::Release);
+        self.write(value);
+    }
+
+    /// Read the register before every memory access after it, such as reads from a buffer a
+    /// device reports it has filled.
+    pub fn read_acquire(&self) -> F {
+        let value = self.read();
+        compiler_fence(Ordering::Acquire);
+        value
+    }
+}
+
+impl<F> Reg<'_, F> {
+    /// The address of the register.
+    pub fn as_ptr(&self) -> *mut F {
+        self.ptr
+    }
+}
+
+impl<F> fmt::Debug for Reg<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Reg").field(&self.ptr).finish()
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn reg<'a, F>(ptr: *mut F, _lt: Lifetime<'a>) -> Reg<'a, F> {
+    Reg {
+        ptr,
+        _lt: PhantomData,
+    }
+}
+
+/// Obtain [`Reg`] handles to registers in a [`RegisterBlock`].
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// statically ensures that the same register isn't projected more than once. The handles borrow
+/// the block mutably.
+///
+/// See [`RegisterBlock`] for an example.
+#[macro_export]
+macro_rules! project_regs {
+    // project multiple registers
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut $crate::RegisterBlock<_> = $expr.borrow_mut();
+        let ptr = _ref.as_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                ret = $crate::utils::reg(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // project a single register
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::project_regs!($expr => {$($props)=>+}).0
+    };
+}
+
+/// Write registers in a [`RegisterBlock`] with volatile writes, one at a time, in the order
+/// they are given.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// each register may only be written once per invocation, as with
+/// [`write_volatile_field!`](crate::write_volatile_field). Unlike that macro, it is safe to
+/// use, since the block guarantees the registers can be written.
+///
+/// See [`RegisterBlock`] for an example.
+#[macro_export]
+macro_rules! write_regs {
+    // write multiple registers
+    ($expr:expr => {$( $($props:tt)=>+ : $val:expr ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut $crate::RegisterBlock<_> = $expr.borrow_mut();
+        let ptr = _ref.as_ptr();
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $(
+            let value = $val;
+            #[allow(unused_unsafe)]
+            unsafe {
+                ::core::ptr::write_volatile(
+                    ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                    value,
+                )
+            };
+        )*
+    }};
+
+    // write a single register
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::write_regs!($expr => { $($props)=>+ : $val })
+    };
+}
+
+///```compile_fail
+/// use project_uninit::{project_regs, RegisterBlock};
+/// #[repr(C)]
+/// struct Regs { a: u32, b: u32 }
+/// let mut mmio = Regs { a: 0, b: 0 };
+/// let mut block = unsafe { RegisterBlock::new(&mut mmio as *mut Regs) };
+/// let (a, a2) = project_regs!(block => { a, a });
+///```
+fn _project_same_register_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::{project_regs, write_regs, RegisterBlock};
+/// #[repr(C)]
+/// struct Regs { a: u32, b: u32 }
+/// let mut mmio = Regs { a: 0, b: 0 };
+/// let mut block = unsafe { RegisterBlock::new(&mut mmio as *mut Regs) };
+/// let mut a = project_regs!(block => a);
+/// write_regs!(block => a = 1);
+/// a.write(2);
+///```
+fn _register_aliased_through_block_fails() {}
@@ -0,0 +1,75 @@
+// Hooks that keep memory checkers informed about memory this crate de-initializes.
+//
+// Writes don't need a hook: instrumented stores already mark the stored bytes (and not any
+// padding) as initialized. But after a value is dropped in place, its bytes still look initialized
+// to the checker even though they are logically uninitialized again.
+
+#[cfg(feature = "msan")]
+extern "C" {
+    fn __msan_poison(addr: *const core::ffi::c_void, size: usize);
+}
+
+/// Tell any enabled memory checker that `len` bytes at `ptr` are now uninitialized.
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn mark_uninit(ptr: *const u8, len: usize) {
+    #[cfg(feature = "msan")]
+    unsafe {
+        __msan_poison(ptr as *const _, len)
+    };
+    #[cfg(feature = "valgrind")]
+    unsafe {
+        valgrind::client_request(0, valgrind::MAKE_MEM_UNDEFINED, [ptr.addr(), len, 0, 0, 0]);
+    }
+}
+
+// Memcheck client requests, as defined in valgrind.h and memcheck.h.
+// When the program isn't running under Valgrind, the request sequence is a no-op that returns
+// `default`.
+#[cfg(feature = "valgrind")]
+mod valgrind {
+    const fn tool_base(a: u8, b: u8) -> usize {
+        ((a as usize) << 24) | ((b as usize) << 16)
+    }
+
+    pub const MAKE_MEM_UNDEFINED: usize = tool_base(b'M', b'C') + 1;
+
+    #[cfg(target_arch = "x86_64")]
+    pub unsafe fn client_request(default: usize, request: usize, args: [usize; 5]) -> usize {
+        let args = [request, args[0], args[1], args[2], args[3], args[4]];
+        let mut result = default;
+        core::arch::asm!(
+            "rol rdi, 3",
+            "rol rdi, 13",
+            "rol rdi, 61",
+            "rol rdi, 51",
+            "xchg rbx, rbx",
+            inout("rdx") result,
+            in("rax") args.as_ptr(),
+            options(nostack),
+        );
+        result
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    pub unsafe fn client_request(default: usize, request: usize, args: [usize; 5]) -> usize {
+        let args = [request, args[0], args[1], args[2], args[3], args[4]];
+        let mut result = default;
+        core::arch::asm!(
+            "ror x12, x12, #3",
+            "ror x12, x12, #13",
+            "ror x12, x12, #51",
+            "ror x12, x12, #61",
+            "orr x10, x10, x10",
+            inout("x3") result,
+            in("x4") args.as_ptr(),
+            options(nostack),
+        );
+        result
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub unsafe fn client_request(default: usize, _request: usize, _args: [usize; 5]) -> usize {
+        default
+    }
+}
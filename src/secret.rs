@@ -0,0 +1,115 @@
+use core::mem::{size_of, MaybeUninit};
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+
+/// Partially initialize a struct wrapped in `MaybeUninit` with secret values, such as keys or
+/// nonces.
+///
+/// This accepts the same syntax as [`partial_init!`](crate::partial_init) and returns the same mutable references.
+/// Each field is written with a volatile store followed by a compiler fence, so the compiler
+/// can't elide, merge, or reorder the writes, or specialize them on the values being written.
+/// No code in this crate branches on the values.
+///
+/// Combine it with [`SecretGuard`] to wipe the target if initialization fails partway.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init_secret, SecretGuard};
+///
+/// struct Session { key: [u8; 32], nonce: [u8; 12], counter: u64 }
+///
+/// let mut slot = MaybeUninit::<Session>::uninit();
+/// let mut guard = SecretGuard::new(&mut slot);
+/// partial_init_secret!(guard.slot() => { key: [7; 32], nonce: [1; 12], counter: 0 });
+/// let session = unsafe { guard.finish() };
+/// assert_eq!(session.key[31], 7);
+/// ```
+#[macro_export]
+macro_rules! partial_init_secret {
+    // intialize multiple fields
+    ($expr:expr => {$($($props:tt)=>+ : $val:expr),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let prop_ref;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                $crate::utils::write_secret(prop_ptr, $val);
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                prop_ref = $crate::utils::deref_ptr_with_lt(prop_ptr, lt);
+            }
+            prop_ref
+        },)*)
+    }};
+
+    // initialize a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::partial_init_secret!($expr => { $($props)=>+: $val }).0
+    };
+}
+
+/// Overwrite every byte of `slot` with zero, using volatile stores that won't be optimized away.
+///
+/// Any value in the slot is not dropped.
+pub fn zeroize_uninit<T>(slot: &mut MaybeUninit<T>) {
+    let bytes = slot.as_mut_ptr() as *mut u8;
+    for i in 0..size_of::<T>() {
+        unsafe { ptr::write_volatile(bytes.add(i), 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+    crate::sanitize::mark_uninit(bytes, size_of::<T>());
+}
+
+/// Wipes a `MaybeUninit<_>` slot holding secret material with [`zeroize_uninit`] unless
+/// initialization is completed.
+///
+/// If the guard is dropped before [`finish`](SecretGuard::finish) is called, e.g. because an
+/// error was returned or a panic occurred partway through initialization, the fields written so
+/// far are zeroed rather than left behind in memory. They are not dropped.
+pub struct SecretGuard<'a, T> {
+    slot: &'a mut MaybeUninit<T>,
+}
+
+impl<'a, T> SecretGuard<'a, T> {
+    /// Guard `slot` until initialization is finished.
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        Self { slot }
+    }
+
+    /// A mutable reference to the guarded slot.
+    pub fn slot(&mut self) -> &mut MaybeUninit<T> {
+        self.slot
+    }
+
+    /// Disarm the guard and return a reference to the value.
+    ///
+    /// ## Safety
+    /// The slot must be fully initialized.
+    pub unsafe fn finish(self) -> &'a mut T {
+        let this = core::mem::ManuallyDrop::new(self);
+        let slot = ptr::read(&this.slot);
+        &mut *slot.as_mut_ptr()
+    }
+}
+
+impl<T> Drop for SecretGuard<'_, T> {
+    fn drop(&mut self) {
+        zeroize_uninit(self.slot);
+    }
+}
@@ -0,0 +1,178 @@
+/// The bytes of a single initialized field, along with its location in the containing struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldBytes<'a> {
+    /// The field path as written in the macro invocation, separated by `.` (e.g. `"inner.value2.0"`).
+    pub path: &'static str,
+    /// Offset of the field in bytes from the start of the containing struct.
+    pub offset: usize,
+    /// The raw bytes of the field.
+    pub bytes: &'a [u8],
+}
+
+/// A destination for the fields written by [`write_init_fields!`](crate::write_init_fields).
+///
+/// This is implemented for any `FnMut(FieldBytes<'_>) -> Result<(), E>`.
+pub trait FieldSink {
+    type Error;
+
+    /// Write a single field. Fields are written in the order they appear in the macro invocation.
+    fn write_field(&mut self, field: FieldBytes<'_>) -> Result<(), Self::Error>;
+}
+
+impl<F, E> FieldSink for F
+where
+    F: FnMut(FieldBytes<'_>) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn write_field(&mut self, field: FieldBytes<'_>) -> Result<(), E> {
+        self(field)
+    }
+}
+
+/// **Unsafe:** Write the bytes of only the specified fields of a struct wrapped in `MaybeUninit<_>`
+/// to a [`FieldSink`], without requiring the rest of the struct to be initialized.
+///
+/// Each field is passed to the sink as a [`FieldBytes`] containing the field's path, its offset in
+/// the struct, and its bytes. The macro evaluates to `Result<(), E>`, stopping at the first error
+/// returned by the sink.
+///
+/// This must be used in an `unsafe` block or function.
+/// Every specified field must be fully initialized, and must not contain any padding bytes.
+///
+/// ## Usage
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, write_init_fields, FieldBytes};
+///
+/// #[repr(C)]
+/// struct Header { magic: u32, version: u16, flags: u16, body_len: u64 }
+///
+/// let mut header = MaybeUninit::<Header>::uninit();
+/// partial_init!(header => { magic: 0xfeedbeef, flags: 3 });
+///
+/// let mut records = Vec::new();
+/// let result: Result<(), ()> = unsafe {
+///     write_init_fields!(|f: FieldBytes| Ok(records.push((f.path, f.offset, f.bytes.to_vec()))),
+///         header => { magic, flags }
+///     )
+/// };
+/// assert!(result.is_ok());
+/// assert_eq!(records, [
+///     ("magic", 0, 0xfeedbeef_u32.to_ne_bytes().to_vec()),
+///     ("flags", 6, 3_u16.to_ne_bytes().to_vec()),
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! write_init_fields {
+    // write mutliple fields
+    ($sink:expr, $expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &::core::mem::MaybeUninit<_> = $expr.borrow();
+        let ptr = ::core::mem::MaybeUninit::as_ptr(_ref);
+        let lt = $crate::utils::bind_ref_lt(_ref);
+        let sink = &mut $sink;
+        let mut result = ::core::result::Result::Ok(());
+        $(
+            if result.is_ok() {
+                let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+));
+                result = $crate::FieldSink::write_field(
+                    sink,
+                    $crate::utils::field_bytes(ptr, prop_ptr, $crate::__path_str!($($props)=>+), lt),
+                );
+            }
+        )*
+        result
+    }};
+
+    // write a single field
+    ($sink:expr, $expr:expr => $($props:tt)=>+) => {
+        $crate::write_init_fields!($sink, $expr => {$($props)=>+})
+    };
+}
+
+/// **Unsafe:** Write the bytes of fields of the slot of an [`InitTracker`](crate::InitTracker) to
+/// a [`FieldSink`], like [`write_init_fields!`], checking that each of them is initialized.
+///
+/// Only direct fields of the struct can be written, as with
+/// [`tracked_init!`](crate::tracked_init), and the macro evaluates to `Result<(), E>`, stopping
+/// at the first error returned by the sink.
+///
+/// This must be used in an `unsafe` block or function, since the fields must not contain any
+/// padding bytes.
+///
+/// ## Panics
+/// If a field isn't marked as initialized by the tracker.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{tracked_init, write_init_fields_tracked, FieldBytes, InitTracker};
+/// use project_uninit::ProjectUninit;
+///
+/// #[derive(ProjectUninit)]
+/// #[repr(C)]
+/// struct Header { magic: u32, version: u16, flags: u16 }
+///
+/// let mut slot = MaybeUninit::<Header>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// tracked_init!(tracker => { magic: 0xfeedbeef, flags: 3 });
+///
+/// let mut records = Vec::new();
+/// let result: Result<(), ()> = unsafe {
+///     write_init_fields_tracked!(|f: FieldBytes| Ok(records.push((f.path, f.offset))),
+///         tracker => { magic, flags }
+///     )
+/// };
+/// assert!(result.is_ok());
+/// assert_eq!(records, [("magic", 0), ("flags", 6)]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! write_init_fields_tracked {
+    ($sink:expr, $tracker:expr => {$($field:tt),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let tracker: &$crate::InitTracker<'_, _> = $tracker.borrow();
+        let sink = &mut $sink;
+        let mut result = ::core::result::Result::Ok(());
+        $(
+            if result.is_ok() {
+                let index = $crate::utils::tracked_field_index(tracker, ::core::stringify!($field));
+                ::core::assert!(
+                    tracker.is_init(index),
+                    "`{}` is not initialized",
+                    ::core::stringify!($field),
+                );
+                result = $crate::write_init_fields!(&mut *sink, tracker.slot() => $field);
+            }
+        )*
+        result
+    }};
+
+    ($sink:expr, $tracker:expr => $field:tt) => {
+        $crate::write_init_fields_tracked!($sink, $tracker => { $field })
+    };
+}
+
+///```compile_fail
+/// use project_uninit::write_init_fields;
+/// use core::mem::MaybeUninit;
+/// struct Foo { a: i32, b: u32 }
+/// let x = MaybeUninit::new(Foo { a: 1, b: 2 });
+/// let _: Result<(), ()> = write_init_fields!(|_| Ok(()), x => { a, b });
+///```
+fn _write_init_fields_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::{tracked_init, write_init_fields_tracked, InitTracker, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Foo { a: i32, b: u32 }
+/// let mut slot = core::mem::MaybeUninit::<Foo>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// tracked_init!(tracker => { a: 1, b: 2 });
+/// let _: Result<(), ()> = write_init_fields_tracked!(|_| Ok(()), tracker => { a, b });
+///```
+fn _write_init_fields_tracked_requires_unsafe() {}
@@ -0,0 +1,168 @@
+//! A byte-level shadow of which parts of tracked targets have been written, for use in tests.
+//!
+//! With the `shadow` feature enabled, every [`InitTracker`](crate::InitTracker) registers a shadow
+//! map with one entry per byte of its target. Writes performed by the init macros (such as
+//! [`partial_init!`](crate::partial_init)) mark the written bytes, and dropping a field in place
+//! clears them. [`InitTracker::set_init`](crate::InitTracker::set_init) and
+//! [`InitTracker::finish`](crate::InitTracker::finish) panic if a field they assume is initialized
+//! contains a byte that was never written.
+//!
+//! This catches fields that are only partly initialized, such as a tuple with one element
+//! written, which field-level tracking can't see. It is implemented in plain Rust, so it works on
+//! stable and under Miri.
+//!
+//! The init macros also panic if they write a field that needs to be dropped while some of its
+//! bytes are still marked as written, since the previous value was overwritten without being
+//! dropped and is leaked. This catches a field initialized twice by separate macro calls, which
+//! the compile-time check of a single call can't see. Besides the targets of `InitTracker`s, the
+//! slots given to [`init_with`](crate::init_with) and `boxed_init_with` are tracked while the
+//! closure runs, and any other slot can be tracked with [`watch`].
+//!
+//! Writes made without the init macros aren't observed, and must be recorded with [`mark_init`].
+//! Likewise, values moved out of a tracked target without this crate's macros must be recorded
+//! with [`mark_uninit`].
+//! Padding bytes of a field whose parts were written separately are never marked, so such fields
+//! should be written as a whole or recorded with [`mark_init`].
+
+use core::mem::MaybeUninit;
+use core::panic::Location;
+use std::sync::{Mutex, MutexGuard};
+use std::vec::Vec;
+
+struct Region {
+    base: usize,
+    init: Vec<bool>,
+}
+
+static REGIONS: Mutex<Vec<Region>> = Mutex::new(Vec::new());
+
+fn regions() -> MutexGuard<'static, Vec<Region>> {
+    REGIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn for_each_byte(ptr: *const u8, len: usize, mut f: impl FnMut(&mut bool)) {
+    let start = ptr.addr();
+    for region in regions().iter_mut() {
+        let region_end = region.base + region.init.len();
+        let (lo, hi) = (start.max(region.base), (start + len).min(region_end));
+        for byte in lo..hi {
+            f(&mut region.init[byte - region.base]);
+        }
+    }
+}
+
+/// Record that `len` bytes at `ptr` have been written, for any tracked target that contains them.
+pub fn mark_init(ptr: *const u8, len: usize) {
+    for_each_byte(ptr, len, |b| *b = true);
+}
+
+/// Record that the `len` bytes at `ptr` are no longer initialized, e.g. because their value was
+/// moved out, for any tracked target that contains them.
+pub fn mark_uninit(ptr: *const u8, len: usize) {
+    for_each_byte(ptr, len, |b| *b = false);
+}
+
+// Record a write of the field `path` of a `T` by an init macro, panicking if the field needs to
+// be dropped and was already written.
+#[track_caller]
+pub(crate) fn mark_written<T, F>(field: *const u8, path: &str) {
+    let mut overwritten = false;
+    for_each_byte(field, core::mem::size_of::<F>(), |b| {
+        overwritten |= *b;
+        *b = true;
+    });
+    if overwritten && core::mem::needs_drop::<F>() {
+        panic!(
+            "shadow check failed: field `{}` of `{}` was initialized again without dropping its previous value, which is leaked",
+            path,
+            core::any::type_name::<T>(),
+        );
+    }
+}
+
+/// Track which bytes of `slot` are written by the init macros until the returned guard is
+/// dropped, as the target of an [`InitTracker`](crate::InitTracker) is, so that initializing a
+/// field twice without dropping it panics.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, shadow};
+///
+/// let mut slot = MaybeUninit::<(String, u32)>::uninit();
+/// let watch = shadow::watch(&slot);
+/// partial_init!(slot => { 0: "a".into(), 1: 1 });
+/// // `u32` doesn't need to be dropped, so it can be written again
+/// partial_init!(slot => 1 = 2);
+/// let overwritten = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+///     partial_init!(slot => 0 = "b".into());
+/// }));
+/// assert!(overwritten.is_err());
+/// drop(watch);
+/// ```
+pub fn watch<T>(slot: &MaybeUninit<T>) -> Watch {
+    let base = slot.as_ptr() as *const u8;
+    register(base, core::mem::size_of::<T>());
+    Watch { base: base.addr() }
+}
+
+/// Stops tracking a slot passed to [`watch`] when dropped.
+#[derive(Debug)]
+#[must_use = "the slot is only tracked until the guard is dropped"]
+pub struct Watch {
+    base: usize,
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        unregister_addr(self.base);
+    }
+}
+
+pub(crate) fn register(base: *const u8, len: usize) {
+    regions().push(Region {
+        base: base.addr(),
+        init: std::vec![false; len],
+    });
+}
+
+pub(crate) fn unregister(base: *const u8) {
+    unregister_addr(base.addr());
+}
+
+fn unregister_addr(base: usize) {
+    let mut regions = regions();
+    if let Some(i) = regions.iter().rposition(|r| r.base == base) {
+        regions.remove(i);
+    }
+}
+
+/// Panic unless the `len` bytes of the field `name` of `type_name` at `ptr` have been written.
+/// `at` is where the field was marked as initialized, if known.
+pub(crate) fn check_init(
+    ptr: *const u8,
+    len: usize,
+    type_name: &str,
+    name: &str,
+    at: Option<&Location<'_>>,
+) {
+    let start = ptr.addr();
+    let first_uninit = regions()
+        .iter()
+        .rev()
+        .find(|r| r.base <= start && start + len <= r.base + r.init.len())
+        .and_then(|r| {
+            let offset = start - r.base;
+            r.init[offset..offset + len].iter().position(|b| !*b)
+        });
+    if let Some(byte) = first_uninit {
+        let marked_at = match at {
+            Some(at) => std::format!(", marked initialized at {}", at),
+            None => std::string::String::new(),
+        };
+        panic!(
+            "shadow check failed: field `{}` of `{}` is not fully initialized (byte {} of {} was never written{})",
+            name, type_name, byte, len, marked_at,
+        );
+    }
+}
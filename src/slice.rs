@@ -0,0 +1,300 @@
+use core::mem::{size_of, MaybeUninit};
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::utils::Lifetime;
+
+/// A part of a slice projected by `project_slice!` or `project_slice_mut!`: a pointer to it, and
+/// its size in bytes, for checking that mutable projections don't overlap.
+#[doc(hidden)]
+pub struct SliceSpan<P: ?Sized> {
+    ptr: *mut P,
+    size: usize,
+}
+
+/// An index or range into a slice of `MaybeUninit<T>`.
+#[doc(hidden)]
+pub trait UninitSliceIndex<T> {
+    type Output: ?Sized;
+
+    /// Panics if the index is out of bounds.
+    ///
+    /// ## Safety
+    /// `base` and `len` must describe a slice.
+    unsafe fn span(self, base: *mut MaybeUninit<T>, len: usize) -> SliceSpan<Self::Output>;
+}
+
+impl<T> UninitSliceIndex<T> for usize {
+    type Output = MaybeUninit<T>;
+
+    unsafe fn span(self, base: *mut MaybeUninit<T>, len: usize) -> SliceSpan<MaybeUninit<T>> {
+        assert!(
+            self < len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            self
+        );
+        SliceSpan {
+            ptr: base.add(self),
+            size: size_of::<T>(),
+        }
+    }
+}
+
+unsafe fn range_span<T>(
+    base: *mut MaybeUninit<T>,
+    len: usize,
+    start: usize,
+    end: usize,
+) -> SliceSpan<[MaybeUninit<T>]> {
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for slice of length {}",
+        start,
+        end,
+        len
+    );
+    SliceSpan {
+        ptr: core::ptr::slice_from_raw_parts_mut(base.add(start), end - start),
+        size: (end - start) * size_of::<T>(),
+    }
+}
+
+macro_rules! impl_range_index {
+    ($($range:ty => |$r:pat_param, $len:ident| ($start:expr, $end:expr),)*) => {$(
+        impl<T> UninitSliceIndex<T> for $range {
+            type Output = [MaybeUninit<T>];
+
+            unsafe fn span(
+                self,
+                base: *mut MaybeUninit<T>,
+                $len: usize,
+            ) -> SliceSpan<[MaybeUninit<T>]> {
+                let $r = self;
+                range_span(base, $len, $start, $end)
+            }
+        }
+    )*};
+}
+
+impl_range_index! {
+    Range<usize> => |r, len| (r.start, r.end),
+    RangeFrom<usize> => |r, len| (r.start, len),
+    RangeTo<usize> => |r, len| (0, r.end),
+    RangeFull => |_, len| (0, len),
+    RangeInclusive<usize> => |r, len| (*r.start(), inclusive_end(*r.end())),
+    RangeToInclusive<usize> => |r, len| (0, inclusive_end(r.end)),
+}
+
+fn inclusive_end(end: usize) -> usize {
+    end.checked_add(1)
+        .expect("attempted to index slice up to maximum usize")
+}
+
+/// ## Safety
+/// `base` and `len` must describe a slice.
+#[doc(hidden)]
+pub unsafe fn slice_span<T, I: UninitSliceIndex<T>>(
+    index: I,
+    base: *mut MaybeUninit<T>,
+    len: usize,
+) -> SliceSpan<I::Output> {
+    index.span(base, len)
+}
+
+#[doc(hidden)]
+pub fn element_ptr<T>(span: &SliceSpan<MaybeUninit<T>>) -> *mut T {
+    span.ptr as *mut T
+}
+
+#[doc(hidden)]
+pub fn field_span<F>(ptr: *mut F) -> SliceSpan<MaybeUninit<F>> {
+    SliceSpan {
+        ptr: ptr as *mut MaybeUninit<F>,
+        size: size_of::<F>(),
+    }
+}
+
+#[doc(hidden)]
+pub fn span_bytes<P: ?Sized>(span: &SliceSpan<P>) -> (usize, usize) {
+    ((span.ptr as *mut u8).addr(), span.size)
+}
+
+/// Panic if any two of the projected parts of a slice overlap.
+#[doc(hidden)]
+pub fn check_disjoint(spans: &[(usize, usize)], paths: &[&str]) {
+    for (i, &(a, a_len)) in spans.iter().enumerate() {
+        for (j, &(b, b_len)) in spans.iter().enumerate().take(i) {
+            if a < b + b_len && b < a + a_len {
+                panic!(
+                    "project_slice_mut! can't borrow `{}` and `{}` at the same time, since they \
+                     overlap",
+                    paths[j], paths[i]
+                );
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn deref_span<'a, P: ?Sized>(span: SliceSpan<P>, _lt: Lifetime<'a>) -> &'a P {
+    &*span.ptr
+}
+
+#[doc(hidden)]
+pub unsafe fn deref_span_mut<'a, P: ?Sized>(span: SliceSpan<P>, _lt: Lifetime<'a>) -> &'a mut P {
+    &mut *span.ptr
+}
+
+/// Given a slice of `MaybeUninit<T>`, obtain references to one or more of its elements,
+/// sub-slices, or fields of its elements.
+///
+/// Each projection starts with an index in brackets: an element index like `[i]`, which returns
+/// a `&MaybeUninit<T>`, or a range like `[a..b]`, which returns a `&[MaybeUninit<T>]`. Indices
+/// are evaluated at runtime and may be any expression, and the macro panics if one is out of
+/// bounds.
+/// An element index may be followed by a field path, like `[i] => inner => 0`, to return a
+/// `&MaybeUninit<_>` of that field of the element.
+///
+/// The target can be anything that borrows as a `[MaybeUninit<T>]`, such as a slice, an array
+/// or a `Vec`.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::project_slice;
+///
+/// struct Sample { time: u64, value: (f32, f32) }
+///
+/// let samples = [MaybeUninit::new(Sample { time: 4, value: (0.5, 1.0) })];
+/// let (first, value1) = project_slice!(samples => { [0..1], [0] => value => 1 });
+/// assert_eq!(first.len(), 1);
+/// assert_eq!(unsafe { value1.assume_init() }, 1.0);
+/// ```
+#[macro_export]
+macro_rules! project_slice {
+    // project multiple parts of the slice
+    ($expr:expr => {$( [$index:expr] $(=> $props:tt)* ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &[::core::mem::MaybeUninit<_>] = $expr.borrow();
+        let len = _ref.len();
+        let base = $crate::utils::as_mut_ptr(_ref.as_ptr());
+        let lt = $crate::utils::bind_ref_lt(_ref);
+        ($({
+            let span = $crate::project_slice!(@span base, len, [$index] $(=> $props)*);
+            #[allow(unused_unsafe)]
+            let part = unsafe { $crate::utils::deref_span(span, lt) };
+            part
+        },)*)
+    }};
+
+    // project a single part of the slice
+    ($expr:expr => [$index:expr] $(=> $props:tt)*) => {
+        $crate::project_slice!($expr => {[$index] $(=> $props)*}).0
+    };
+
+    // the span of an element or range
+    (@span $base:ident, $len:ident, [$index:expr]) => {{
+        let index = $index;
+        #[allow(unused_unsafe)]
+        let span = unsafe { $crate::utils::slice_span(index, $base, $len) };
+        span
+    }};
+    // the span of a field of an element
+    (@span $base:ident, $len:ident, [$index:expr] $(=> $props:tt)+) => {{
+        let index = $index;
+        #[allow(unused_unsafe)]
+        let span = unsafe { $crate::utils::slice_span(index, $base, $len) };
+        let ptr = $crate::utils::element_ptr(&span);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__field_mut!((_x) $($props)=>+);
+            $crate::__assert_arrays!((_x) $($props)=>+);
+        }
+        #[allow(unused_unsafe)]
+        let field = unsafe { ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)) };
+        $crate::utils::field_span(field)
+    }};
+}
+
+/// Given a mutable slice of `MaybeUninit<T>`, obtain mutable references to one or more of its
+/// elements, sub-slices, or fields of its elements.
+///
+/// This accepts the same syntax as [`project_slice!`](crate::project_slice).
+/// Since indices are only known at runtime, this panics if any of the projected parts overlap,
+/// including an element and one of its fields, or two overlapping ranges. Different fields of
+/// the same element may be projected together.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, project_slice_mut};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Particle { pos: [f32; 2], mass: f32 }
+///
+/// let mut particles: Vec<MaybeUninit<Particle>> = (0..4).map(|_| MaybeUninit::uninit()).collect();
+/// let i = 1;
+///
+/// let (head, pos, mass, tail) = project_slice_mut!(particles => {
+///     [..i],
+///     [i] => pos,
+///     [i] => mass,
+///     [i + 1..],
+/// });
+/// pos.write([1.0, 2.0]);
+/// mass.write(0.5);
+/// for p in head.iter_mut().chain(tail) {
+///     partial_init!(p => { pos: [0.0; 2], mass: 1.0 });
+/// }
+///
+/// let particle = project_slice_mut!(particles => [1]);
+/// assert_eq!(
+///     unsafe { particle.assume_init_read() },
+///     Particle { pos: [1.0, 2.0], mass: 0.5 },
+/// );
+/// ```
+#[macro_export]
+macro_rules! project_slice_mut {
+    // project multiple parts of the slice
+    ($expr:expr => {$( [$index:expr] $(=> $props:tt)* ),* $(,)?}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut [::core::mem::MaybeUninit<_>] = $expr.borrow_mut();
+        let len = _ref.len();
+        let base = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+        $crate::project_slice_mut!(@project base, len, lt, [] $([$index] $(=> $props)*),*)
+    }};
+
+    // project a single part of the slice
+    ($expr:expr => [$index:expr] $(=> $props:tt)*) => {
+        $crate::project_slice_mut!($expr => {[$index] $(=> $props)*}).0
+    };
+
+    // find the span of each part in order, then check that they don't overlap before turning them
+    // into references
+    (@project $base:ident, $len:ident, $lt:ident, [$(($done:ident, $path:expr))*]
+        [$index:expr] $(=> $props:tt)* $(, $($rest:tt)*)?) => {{
+        let span = $crate::project_slice!(@span $base, $len, [$index] $(=> $props)*);
+        $crate::project_slice_mut!(@project $base, $len, $lt, [
+            $(($done, $path))*
+            (span, ::core::concat!(
+                "[", ::core::stringify!($index), "]" $(, " => ", ::core::stringify!($props))*
+            ))
+        ] $($($rest)*)?)
+    }};
+    (@project $base:ident, $len:ident, $lt:ident, [$(($done:ident, $path:expr))*]) => {{
+        $crate::utils::check_disjoint(
+            &[$($crate::utils::span_bytes(&$done)),*],
+            &[$($path),*],
+        );
+        #[allow(unused_unsafe)]
+        let parts = unsafe { ($($crate::utils::deref_span_mut($done, $lt),)*) };
+        parts
+    }};
+}
@@ -0,0 +1,164 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use core::mem::MaybeUninit;
+
+/// Struct-of-arrays storage for a struct, usually generated by `#[derive(UninitSoA)]`.
+///
+/// The derive macro generates a `FooSoA` type for a struct `Foo`, with one
+/// `Box<[MaybeUninit<_>]>` column per field and a [`RowTracker`] recording which cells are
+/// initialized. `FooSoA` has the following methods, where `field` stands for the name of each
+/// field:
+///
+/// - `new(len)`: create storage for `len` rows with every cell uninitialized
+/// - `len()`, `is_empty()` and `rows()`, which returns the [`RowTracker`]
+/// - `set_field(row, value)`: initialize a single cell, dropping the previous value if there was one
+/// - `get_field(row)` and `get_field_mut(row)`: a reference to a cell if it is initialized
+/// - `clear_field(row)`: drop a single cell if it is initialized
+/// - `init_row(row, value)`: move each field of a `Foo` into its column
+/// - `take_row(row)`: move a complete row out as a `Foo`
+/// - `clear_row(row)`: drop every initialized cell of a row
+/// - `column_field()`: the column as a `&[MaybeUninit<_>]`
+///
+/// Dropping the storage drops every initialized cell.
+/// Fields of tuple structs are named by their index, as in `set_0` and `column_0`.
+/// Structs with at most 64 fields are supported.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "soa")] {
+/// use project_uninit::UninitSoA;
+///
+/// #[derive(UninitSoA, Debug, PartialEq)]
+/// struct Particle { pos: [f32; 2], mass: f32 }
+///
+/// let mut particles = Particle::soa(3);
+/// for row in 0..3 {
+///     particles.set_pos(row, [row as f32, 0.0]);
+/// }
+/// particles.set_mass(1, 2.5);
+///
+/// assert!(particles.rows().is_row_complete(1));
+/// assert_eq!(particles.rows().complete_rows().collect::<Vec<_>>(), [1]);
+/// assert_eq!(particles.get_mass(0), None);
+/// assert_eq!(
+///     particles.take_row(1),
+///     Some(Particle { pos: [1.0, 0.0], mass: 2.5 }),
+/// );
+/// # }
+/// ```
+pub trait UninitSoA: Sized {
+    /// The struct-of-arrays storage type.
+    type SoA;
+
+    /// Create storage for `len` rows with every cell uninitialized.
+    fn soa(len: usize) -> Self::SoA;
+}
+
+/// Records which cells of struct-of-arrays storage are initialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowTracker {
+    init: Box<[u64]>,
+    columns: usize,
+}
+
+impl RowTracker {
+    /// Create a tracker for `len` rows of `columns` cells each, with no cells initialized.
+    pub fn new(len: usize, columns: usize) -> Self {
+        assert!(columns <= 64, "RowTracker supports at most 64 columns");
+        Self {
+            init: vec![0; len].into_boxed_slice(),
+            columns,
+        }
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.init.len()
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.init.is_empty()
+    }
+
+    /// Whether the cell at `column` of `row` is initialized.
+    pub fn is_init(&self, row: usize, column: usize) -> bool {
+        self.init[row] & (1 << column) != 0
+    }
+
+    /// Whether every cell of `row` is initialized.
+    pub fn is_row_complete(&self, row: usize) -> bool {
+        self.init[row].count_ones() as usize == self.columns
+    }
+
+    /// Whether every cell of every row is initialized.
+    pub fn is_complete(&self) -> bool {
+        (0..self.len()).all(|row| self.is_row_complete(row))
+    }
+
+    /// The indices of the rows whose cells are all initialized.
+    pub fn complete_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&row| self.is_row_complete(row))
+    }
+
+    // The methods below are used by derive-generated code. Each takes the column with the given
+    // index, which must have `len()` cells.
+
+    #[doc(hidden)]
+    pub unsafe fn write<'a, T>(
+        &mut self,
+        cells: &'a mut [MaybeUninit<T>],
+        row: usize,
+        column: usize,
+        value: T,
+    ) -> &'a mut T {
+        self.clear(cells, row, column);
+        self.init[row] |= 1 << column;
+        cells[row].write(value)
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn get<'a, T>(
+        &self,
+        cells: &'a [MaybeUninit<T>],
+        row: usize,
+        column: usize,
+    ) -> Option<&'a T> {
+        match self.is_init(row, column) {
+            true => Some(&*cells[row].as_ptr()),
+            false => None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn get_mut<'a, T>(
+        &self,
+        cells: &'a mut [MaybeUninit<T>],
+        row: usize,
+        column: usize,
+    ) -> Option<&'a mut T> {
+        match self.is_init(row, column) {
+            true => Some(&mut *cells[row].as_mut_ptr()),
+            false => None,
+        }
+    }
+
+    /// The cell must be initialized.
+    #[doc(hidden)]
+    pub unsafe fn take<T>(&mut self, cells: &mut [MaybeUninit<T>], row: usize, column: usize) -> T {
+        debug_assert!(self.is_init(row, column));
+        self.init[row] &= !(1 << column);
+        let value = cells[row].as_ptr().read();
+        crate::poison::deinit(cells[row].as_mut_ptr() as *mut u8, core::mem::size_of::<T>());
+        value
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn clear<T>(&mut self, cells: &mut [MaybeUninit<T>], row: usize, column: usize) {
+        if self.is_init(row, column) {
+            self.init[row] &= !(1 << column);
+            core::ptr::drop_in_place(cells[row].as_mut_ptr());
+            crate::poison::deinit(cells[row].as_mut_ptr() as *mut u8, core::mem::size_of::<T>());
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// Initializes elements in the spare capacity of a `Vec` one at a time, growing its length as
+/// each element is completed.
+///
+/// Each element is initialized in place by a closure that is given the next spare slot, as
+/// returned by [`Vec::spare_capacity_mut`], and must return a reference to that same slot,
+/// initialized. The references returned by this crate's macros and functions that finish
+/// initializing a whole value, like [`partial_init!`](crate::partial_init) with `..rest`,
+/// [`InitTracker::finish`](crate::InitTracker::finish) or [`Out::write`](crate::Out::write),
+/// serve as that proof, so the length is never set to cover an element that isn't initialized.
+///
+/// If a closure panics or fails, the elements pushed before it stay in the `Vec`, and the fields
+/// it initialized are leaked.
+/// The `Vec` is never reallocated: pushing panics once the spare capacity runs out.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use project_uninit::{tracked_init, InitTracker, ProjectUninit, SpareInit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Particle { pos: [f32; 3], name: String }
+///
+/// let mut particles = Vec::<Particle>::with_capacity(4);
+/// let mut spare = SpareInit::new(&mut particles);
+/// for i in 0..3 {
+///     spare.push_with(|slot| {
+///         let mut tracker = InitTracker::new(slot);
+///         tracked_init!(tracker => { pos: [i as f32; 3], name: format!("p{}", i) });
+///         tracker.finish().ok().unwrap()
+///     });
+/// }
+/// assert_eq!(spare.remaining(), 1);
+/// assert_eq!(spare.finish().len(), 3);
+/// assert_eq!(particles[2].name, "p2");
+/// # }
+/// ```
+pub struct SpareInit<'a, T> {
+    vec: &'a mut Vec<T>,
+    start: usize,
+}
+
+impl<'a, T> SpareInit<'a, T> {
+    /// Start initializing elements after the current end of `vec`.
+    pub fn new(vec: &'a mut Vec<T>) -> Self {
+        let start = vec.len();
+        Self { vec, start }
+    }
+
+    /// The number of spare slots that can still be initialized.
+    pub fn remaining(&self) -> usize {
+        self.vec.capacity() - self.vec.len()
+    }
+
+    /// Initialize the next spare slot with `init` and add it to the end of the `Vec`.
+    ///
+    /// ## Panics
+    /// If there is no spare capacity left, or if `init` returns a reference to something other
+    /// than the slot it was given.
+    pub fn push_with(
+        &mut self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> &'s mut T,
+    ) -> &mut T {
+        match self.try_push_with(|slot| Ok::<_, core::convert::Infallible>(init(slot))) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Initialize the next spare slot with `init` and add it to the end of the `Vec`, unless
+    /// `init` fails.
+    ///
+    /// ## Panics
+    /// If there is no spare capacity left, or if `init` returns a reference to something other
+    /// than the slot it was given.
+    pub fn try_push_with<E>(
+        &mut self,
+        init: impl for<'s> FnOnce(&'s mut MaybeUninit<T>) -> Result<&'s mut T, E>,
+    ) -> Result<&mut T, E> {
+        let slot = self
+            .vec
+            .spare_capacity_mut()
+            .first_mut()
+            .expect("no spare capacity left in Vec");
+        let ptr = slot.as_mut_ptr();
+        let value: *const T = init(slot)?;
+        assert!(
+            core::ptr::eq(value, ptr),
+            "initializer returned a reference to something other than its slot"
+        );
+        let len = self.vec.len();
+        unsafe { self.vec.set_len(len + 1) };
+        Ok(&mut self.vec[len])
+    }
+
+    /// The elements that have been initialized so far.
+    pub fn initialized(&mut self) -> &mut [T] {
+        &mut self.vec[self.start..]
+    }
+
+    /// Stop initializing elements, returning the ones that were initialized.
+    pub fn finish(self) -> &'a mut [T] {
+        &mut self.vec[self.start..]
+    }
+}
+
+impl<T> fmt::Debug for SpareInit<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpareInit")
+            .field("initialized", &(self.vec.len() - self.start))
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
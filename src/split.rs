@@ -0,0 +1,304 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{size_of, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+use crate::utils::Lifetime;
+use crate::UninitFields;
+
+/// An owned handle to a possibly uninitialized field, split from a struct with
+/// [`split_uninit_mut!`](crate::split_uninit_mut).
+///
+/// Each handle refers to a different field, and can be sent to another thread if the field's type
+/// is `Send`, so disjoint fields can be initialized concurrently, for example in scoped threads.
+/// Writing the field consumes the handle and returns an [`InitPart`], which is passed back to
+/// [`UninitSplit::join`] as proof that the field was initialized.
+pub struct UninitPart<'a, F> {
+    ptr: *mut F,
+    _lt: PhantomData<&'a mut MaybeUninit<F>>,
+}
+
+// `UninitPart` is equivalent to a `&'a mut MaybeUninit<F>`
+unsafe impl<F: Send> Send for UninitPart<'_, F> {}
+unsafe impl<F: Sync> Sync for UninitPart<'_, F> {}
+
+impl<'a, F> UninitPart<'a, F> {
+    /// Initialize the field, returning proof that it was initialized.
+    /// This doesn't drop any value that was already there.
+    pub fn write(self, value: F) -> InitPart<'a, F> {
+        unsafe {
+            self.ptr.write(value);
+            self.assume_init()
+        }
+    }
+
+    /// Get a mutable reference to the possibly uninitialized field, e.g. to initialize it in
+    /// place with [`partial_init!`](crate::partial_init).
+    pub fn as_uninit_mut(&mut self) -> &mut MaybeUninit<F> {
+        unsafe { &mut *(self.ptr as *mut MaybeUninit<F>) }
+    }
+
+    /// Get a raw pointer to the field, e.g. to initialize it in place through an FFI call.
+    pub fn as_mut_ptr(&mut self) -> *mut F {
+        self.ptr
+    }
+
+    /// **Unsafe:** Assume the field was initialized through [`as_uninit_mut`](Self::as_uninit_mut)
+    /// or [`as_mut_ptr`](Self::as_mut_ptr), returning proof that it was initialized.
+    ///
+    /// ## Safety
+    /// The field must be initialized.
+    pub unsafe fn assume_init(self) -> InitPart<'a, F> {
+        InitPart {
+            ptr: self.ptr,
+            _lt: PhantomData,
+        }
+    }
+}
+
+impl<F> fmt::Debug for UninitPart<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitPart").finish_non_exhaustive()
+    }
+}
+
+/// A field split from a struct with [`split_uninit_mut!`](crate::split_uninit_mut) that has
+/// been initialized.
+///
+/// This dereferences to the field's value. Dropping it leaves the field initialized without
+/// dropping its value.
+pub struct InitPart<'a, F> {
+    ptr: *mut F,
+    _lt: PhantomData<&'a mut F>,
+}
+
+// `InitPart` is equivalent to a `&'a mut F`
+unsafe impl<F: Send> Send for InitPart<'_, F> {}
+unsafe impl<F: Sync> Sync for InitPart<'_, F> {}
+
+impl<'a, F> InitPart<'a, F> {
+    /// Get a mutable reference to the field for the rest of its lifetime, giving up the proof that
+    /// it was initialized.
+    pub fn into_mut(self) -> &'a mut F {
+        unsafe { &mut *self.ptr }
+    }
+}
+
+impl<F> Deref for InitPart<'_, F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<F> DerefMut for InitPart<'_, F> {
+    fn deref_mut(&mut self) -> &mut F {
+        unsafe { &mut *self.ptr }
+    }
+}
+
+impl<F: fmt::Debug> fmt::Debug for InitPart<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("InitPart").field(&**self).finish()
+    }
+}
+
+/// A tuple of [`InitPart`]s that can be passed to [`UninitSplit::join`].
+///
+/// This is implemented for tuples of up to 12 parts.
+pub trait JoinParts<'a, const N: usize> {
+    /// The address and size of each part, in order.
+    fn spans(&self) -> [(usize, usize); N];
+}
+
+macro_rules! impl_join_parts {
+    ($($n:literal => ($($part:ident . $index:tt),+),)*) => {$(
+        impl<'a, $($part),+> JoinParts<'a, $n> for ($(InitPart<'a, $part>,)+) {
+            fn spans(&self) -> [(usize, usize); $n] {
+                [$((self.$index.ptr.addr(), size_of::<$part>())),+]
+            }
+        }
+    )*};
+}
+
+impl_join_parts! {
+    1 => (A.0),
+    2 => (A.0, B.1),
+    3 => (A.0, B.1, C.2),
+    4 => (A.0, B.1, C.2, D.3),
+    5 => (A.0, B.1, C.2, D.3, E.4),
+    6 => (A.0, B.1, C.2, D.3, E.4, F.5),
+    7 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6),
+    8 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7),
+    9 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8),
+    10 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9),
+    11 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10),
+    12 => (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11),
+}
+
+/// A struct wrapped in `MaybeUninit` that was split into [`UninitPart`]s by
+/// [`split_uninit_mut!`](crate::split_uninit_mut), which can be joined back together once every
+/// part is initialized.
+pub struct UninitSplit<'a, T, const N: usize> {
+    ptr: *mut T,
+    spans: [(usize, usize); N],
+    _lt: PhantomData<&'a mut MaybeUninit<T>>,
+}
+
+impl<'a, T, const N: usize> UninitSplit<'a, T, N> {
+    /// Join the initialized parts, given in the order they were split, returning a mutable
+    /// reference to the initialized struct.
+    ///
+    /// ## Panics
+    /// If the parts weren't split from this struct, or if they don't cover every field of the
+    /// struct, in which case the initialized fields are leaked. A field split into several of its
+    /// own fields doesn't count as covered.
+    pub fn join(self, parts: impl JoinParts<'a, N>) -> &'a mut T
+    where
+        T: UninitFields,
+    {
+        assert!(
+            parts.spans() == self.spans,
+            "the parts passed to `UninitSplit::join` must be the ones split from it, in order"
+        );
+        let base = self.ptr.addr();
+        for field in T::FIELDS {
+            let start = base + field.offset();
+            let end = start + field.size();
+            assert!(
+                self.spans
+                    .iter()
+                    .any(|&(ptr, size)| ptr <= start && end <= ptr + size),
+                "field `{}` wasn't split from the struct, so it may not be initialized",
+                field.name()
+            );
+        }
+        unsafe { &mut *self.ptr }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for UninitSplit<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitSplit").field("parts", &N).finish()
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn uninit_split<'a, T, const N: usize>(
+    ptr: *mut T,
+    spans: [(usize, usize); N],
+    _lt: Lifetime<'a>,
+) -> UninitSplit<'a, T, N> {
+    UninitSplit {
+        ptr,
+        spans,
+        _lt: PhantomData,
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn uninit_part<'a, F>(ptr: *mut F, _lt: Lifetime<'a>) -> UninitPart<'a, F> {
+    UninitPart {
+        ptr,
+        _lt: PhantomData,
+    }
+}
+
+#[doc(hidden)]
+pub fn part_span<F>(ptr: *mut F) -> (usize, usize) {
+    (ptr.addr(), size_of::<F>())
+}
+
+/// Split a struct wrapped in `MaybeUninit` into owned handles to some of its fields, which can be
+/// initialized independently, even on different threads.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut) and
+/// statically ensures that the same field isn't split more than once.
+/// It returns an [`UninitSplit`] for the whole struct, along with a tuple of [`UninitPart`]s for
+/// the fields. Writing each part gives an [`InitPart`], and once every field of the struct is
+/// initialized, the `InitPart`s are passed to [`UninitSplit::join`] to get a `&mut` reference to
+/// the whole struct. Joining checks the fields against the metadata from
+/// [`UninitFields`](crate::UninitFields), so every top-level field must be split on its own.
+///
+/// This must be used in an `unsafe` block or function when accessing fields of unions.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{split_uninit_mut, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Stats { sum: u64, max: u32, histogram: Vec<usize> }
+///
+/// let data: Vec<u32> = (0..1000).collect();
+/// let mut stats = MaybeUninit::<Stats>::uninit();
+/// let (split, (sum, max, histogram)) = split_uninit_mut!(stats => { sum, max, histogram });
+///
+/// let stats = std::thread::scope(|s| {
+///     let sum = s.spawn(|| sum.write(data.iter().map(|&x| x as u64).sum()));
+///     let max = s.spawn(|| max.write(data.iter().copied().max().unwrap()));
+///     let histogram = histogram.write(data.iter().fold(vec![0; 10], |mut h, &x| {
+///         h[x as usize / 100] += 1;
+///         h
+///     }));
+///     split.join((sum.join().unwrap(), max.join().unwrap(), histogram))
+/// });
+/// assert_eq!((stats.sum, stats.max, stats.histogram[3]), (499500, 999, 100));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! split_uninit_mut {
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        #[allow(unused_unsafe)]
+        let split = unsafe {
+            $crate::utils::uninit_split(ptr, [$($crate::utils::part_span(
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+))
+            )),*], lt)
+        };
+        #[allow(unused_unsafe)]
+        let parts = unsafe {
+            ($($crate::utils::uninit_part(
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                lt,
+            ),)*)
+        };
+        (split, parts)
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::split_uninit_mut;
+/// struct Foo { a: u8, b: (u8, u8) }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (split, parts) = split_uninit_mut!(x => { a, b, b => 0 });
+///```
+fn _split_same_field_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::split_uninit_mut;
+/// struct Foo { a: std::rc::Rc<u8>, b: u8 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (split, (a, b)) = split_uninit_mut!(x => { a, b });
+/// std::thread::scope(|s| {
+///     s.spawn(move || a.write(std::rc::Rc::new(1)));
+/// });
+///```
+fn _split_non_send_field_fails() {}
@@ -0,0 +1,130 @@
+//! Helpers for testing code that initializes structs one field at a time.
+//!
+//! The proptest strategies require the `proptest` feature.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+#[cfg(feature = "proptest")]
+use proptest::{prelude::*, sample::subsequence};
+
+use crate::{FieldInfo, InitTracker, UninitFields};
+
+/// An order in which to initialize some or all of the fields of `T`, created with
+/// [`new`](InitPlan::new), or generated by `init_orders` or `partial_init_orders` with the
+/// `proptest` feature.
+pub struct InitPlan<T> {
+    order: Vec<usize>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: UninitFields> InitPlan<T> {
+    /// Create a plan that initializes the fields with the given indices, in order.
+    pub fn new(order: Vec<usize>) -> Self {
+        assert!(
+            order.iter().all(|&i| i < T::FIELDS.len()),
+            "field index out of range"
+        );
+        Self {
+            order,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The indices of the fields to initialize, in order.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// The fields to initialize, in order.
+    pub fn fields(&self) -> impl Iterator<Item = &'static FieldInfo> + '_ {
+        self.order.iter().map(|&i| &T::FIELDS[i])
+    }
+
+    /// Whether the plan initializes every field.
+    pub fn is_complete(&self) -> bool {
+        self.order.len() == T::FIELDS.len()
+    }
+
+    /// Initialize the fields of `slot` in the planned order by calling `init` with the index of
+    /// each field, keeping track of them with an [`InitTracker`].
+    ///
+    /// If the plan is complete, returns a reference to the initialized value.
+    /// Otherwise, the fields that were initialized are dropped and `None` is returned, so tests
+    /// can check that abandoning initialization partway through doesn't leak.
+    ///
+    /// ## Safety
+    /// Each call to `init` must initialize exactly the field at the given index.
+    pub unsafe fn run<'a>(
+        &self,
+        slot: &'a mut MaybeUninit<T>,
+        mut init: impl FnMut(&mut MaybeUninit<T>, usize),
+    ) -> Option<&'a mut T> {
+        let mut tracker = InitTracker::new(slot);
+        for &i in &self.order {
+            // a field initialized twice would leak its first value
+            tracker.drop_field(i);
+            init(tracker.slot_mut(), i);
+            tracker.set_init(i);
+        }
+        tracker.finish().ok()
+    }
+}
+
+impl<T> Clone for InitPlan<T> {
+    fn clone(&self) -> Self {
+        Self {
+            order: self.order.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: UninitFields> fmt::Debug for InitPlan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.fields().map(FieldInfo::name))
+            .finish()
+    }
+}
+
+/// A strategy generating every order in which all the fields of `T` can be initialized.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use proptest::proptest;
+/// use project_uninit::{partial_init, ProjectUninit};
+/// use project_uninit::test_support::init_orders;
+///
+/// #[derive(ProjectUninit, Debug, PartialEq)]
+/// struct Point { x: i32, y: i32, label: String }
+///
+/// proptest!(|(plan in init_orders::<Point>())| {
+///     let mut slot = MaybeUninit::uninit();
+///     let point = unsafe {
+///         plan.run(&mut slot, |slot, i| match i {
+///             0 => { partial_init!(slot => x = 1); }
+///             1 => { partial_init!(slot => y = 2); }
+///             _ => { partial_init!(slot => label = "p".into()); }
+///         })
+///     };
+///     assert_eq!(point, Some(&mut Point { x: 1, y: 2, label: "p".into() }));
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn init_orders<T: UninitFields>() -> impl Strategy<Value = InitPlan<T>> {
+    Just((0..T::FIELDS.len()).collect::<Vec<_>>())
+        .prop_shuffle()
+        .prop_map(InitPlan::new)
+}
+
+/// A strategy generating every order in which any subset of the fields of `T` can be
+/// initialized, including the empty and complete subsets.
+#[cfg(feature = "proptest")]
+pub fn partial_init_orders<T: UninitFields>() -> impl Strategy<Value = InitPlan<T>> {
+    let len = T::FIELDS.len();
+    subsequence((0..len).collect::<Vec<_>>(), 0..=len)
+        .prop_shuffle()
+        .prop_map(InitPlan::new)
+}
@@ -0,0 +1,175 @@
+use core::mem::MaybeUninit;
+
+use crate::{InitTracker, UninitFields};
+
+/// An error encountered while decoding TLV records into a struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlvError {
+    /// The input ended in the middle of a record.
+    Truncated,
+    /// A record's tag doesn't correspond to any field.
+    UnknownTag(u16),
+    /// A record's value couldn't be decoded as the type of its field.
+    InvalidValue { field: &'static str },
+    /// More than one record was found for the same field.
+    Duplicate { field: &'static str },
+    /// No record was found for a field.
+    Missing { field: &'static str },
+}
+
+/// A field type that can be decoded from the value bytes of a TLV record.
+///
+/// Integers and floats are decoded from exactly `size_of::<Self>()` little-endian bytes.
+pub trait TlvValue: Sized {
+    /// Decode a value, or return `None` if `value` is not a valid encoding.
+    fn decode_tlv(value: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_tlv_value_le {
+    ($($ty:ty),*) => {$(
+        impl TlvValue for $ty {
+            fn decode_tlv(value: &[u8]) -> Option<Self> {
+                let mut bytes = [0; core::mem::size_of::<$ty>()];
+                if value.len() != bytes.len() {
+                    return None;
+                }
+                bytes.copy_from_slice(value);
+                Some(<$ty>::from_le_bytes(bytes))
+            }
+        }
+    )*};
+}
+
+impl_tlv_value_le!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl TlvValue for bool {
+    fn decode_tlv(value: &[u8]) -> Option<Self> {
+        match value {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> TlvValue for [u8; N] {
+    fn decode_tlv(value: &[u8]) -> Option<Self> {
+        let mut bytes = [0; N];
+        if value.len() != N {
+            return None;
+        }
+        bytes.copy_from_slice(value);
+        Some(bytes)
+    }
+}
+
+/// A struct whose fields can be initialized from TLV records, usually generated by
+/// `#[derive(DecodeTlv)]`.
+///
+/// By default, each field's tag is its index. This can be overridden with `#[tlv(tag = N)]`.
+///
+/// ## Safety
+/// When `decode_field` returns `Ok(())`, the field at `index` must have been initialized.
+/// It must not write to any other field.
+pub unsafe trait DecodeTlv: UninitFields {
+    /// Find the index of the field with the given tag.
+    fn field_for_tag(tag: u16) -> Option<usize>;
+
+    /// Decode `value` and initialize the field at `index` with it.
+    fn decode_field(slot: &mut MaybeUninit<Self>, index: usize, value: &[u8]) -> Result<(), TlvError>;
+}
+
+/// Initializes the fields of a `MaybeUninit<T>` from TLV records as they arrive.
+///
+/// The value is only released by [`finish`](TlvDecoder::finish) once every field has been
+/// initialized. If the decoder is dropped first, any fields that were initialized are dropped.
+///
+/// Supports structs with at most 64 fields.
+pub struct TlvDecoder<'a, T: DecodeTlv> {
+    tracker: InitTracker<'a, T>,
+}
+
+impl<'a, T: DecodeTlv> TlvDecoder<'a, T> {
+    /// Create a decoder that initializes `slot`, which is assumed to be uninitialized.
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        Self {
+            tracker: InitTracker::new(slot),
+        }
+    }
+
+    /// Initialize the field with the given tag from the value of a single record.
+    pub fn record(&mut self, tag: u16, value: &[u8]) -> Result<(), TlvError> {
+        let index = T::field_for_tag(tag).ok_or(TlvError::UnknownTag(tag))?;
+        if self.tracker.is_init(index) {
+            return Err(TlvError::Duplicate {
+                field: T::FIELDS[index].name(),
+            });
+        }
+        unsafe {
+            T::decode_field(self.tracker.slot_mut(), index, value)?;
+            self.tracker.set_init(index);
+        }
+        Ok(())
+    }
+
+    /// Decode a sequence of records, each consisting of a little-endian `u16` tag,
+    /// a little-endian `u16` length, and `length` bytes of value.
+    pub fn decode(&mut self, mut bytes: &[u8]) -> Result<(), TlvError> {
+        while !bytes.is_empty() {
+            if bytes.len() < 4 {
+                return Err(TlvError::Truncated);
+            }
+            let tag = u16::from_le_bytes([bytes[0], bytes[1]]);
+            let len = u16::from_le_bytes([bytes[2], bytes[3]]) as usize;
+            let rest = &bytes[4..];
+            if rest.len() < len {
+                return Err(TlvError::Truncated);
+            }
+            self.record(tag, &rest[..len])?;
+            bytes = &rest[len..];
+        }
+        Ok(())
+    }
+
+    /// Check that every field has been initialized and return a reference to the value.
+    pub fn finish(self) -> Result<&'a mut T, TlvError> {
+        self.tracker.finish().map_err(|tracker| TlvError::Missing {
+            field: tracker.missing().next().unwrap().name(),
+        })
+    }
+}
+
+/// Initialize every field of `slot` from a sequence of TLV records.
+///
+/// See [`TlvDecoder::decode`] for the record format.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{decode_tlv, DecodeTlv, ProjectUninit, TlvError};
+///
+/// #[derive(ProjectUninit, DecodeTlv, Debug, PartialEq)]
+/// struct Config {
+///     baud: u32,
+///     #[tlv(tag = 7)]
+///     parity: bool,
+/// }
+///
+/// let mut slot = MaybeUninit::uninit();
+/// let config = decode_tlv(&mut slot, &[
+///     7, 0, 1, 0, 1,
+///     0, 0, 4, 0, 0x00, 0xc2, 0x01, 0x00,
+/// ]);
+/// assert_eq!(config, Ok(&mut Config { baud: 115200, parity: true }));
+///
+/// let mut slot = MaybeUninit::<Config>::uninit();
+/// assert_eq!(decode_tlv(&mut slot, &[7, 0, 1, 0, 1]), Err(TlvError::Missing { field: "baud" }));
+/// ```
+pub fn decode_tlv<'a, T: DecodeTlv>(
+    slot: &'a mut MaybeUninit<T>,
+    bytes: &[u8],
+) -> Result<&'a mut T, TlvError> {
+    let mut decoder = TlvDecoder::new(slot);
+    decoder.decode(bytes)?;
+    decoder.finish()
+}
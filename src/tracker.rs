@@ -0,0 +1,529 @@
+use core::{fmt, mem::MaybeUninit, panic::Location, ptr};
+
+use crate::{FieldInfo, UninitFields};
+
+/// Tracks at runtime which fields of a `MaybeUninit<T>` have been initialized.
+///
+/// When the tracker is dropped, every field that was marked as initialized is dropped in place,
+/// so a value abandoned partway through initialization doesn't leak.
+/// Call [`finish`](InitTracker::finish) once every field is initialized to obtain a reference to
+/// the value instead.
+/// Fields can be initialized and marked safely with [`tracked_init!`](crate::tracked_init),
+/// which marks each field as soon as it is written, so if producing a later value panics, the
+/// fields written before it are dropped as the tracker is dropped during unwinding.
+///
+/// Supports structs with at most 64 fields.
+///
+/// In debug builds with the `alloc` feature, the tracker also records where each field was marked
+/// as initialized, which is available from [`init_location`](InitTracker::init_location) and
+/// included in diagnostics.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Person { name: String, age: u32 }
+///
+/// let mut slot = MaybeUninit::<Person>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+///
+/// unsafe {
+///     partial_init!(tracker.slot_mut() => name = String::from("Alice"));
+///     tracker.set_init(0);
+/// }
+///
+/// assert_eq!(tracker.missing().next().unwrap().name(), "age");
+///
+/// // dropping the tracker here would drop `name` but leave `age` untouched
+///
+/// unsafe {
+///     partial_init!(tracker.slot_mut() => age = 22);
+///     tracker.set_init(1);
+/// }
+///
+/// let person = tracker.finish().unwrap();
+/// assert_eq!(person.name, "Alice");
+/// assert_eq!(person.age, 22);
+/// # }
+/// ```
+pub struct InitTracker<'a, T: UninitFields> {
+    slot: &'a mut MaybeUninit<T>,
+    init: u64,
+    // boxed, so that a tracker returned in an error stays small
+    #[cfg(all(debug_assertions, feature = "alloc"))]
+    locations: alloc::boxed::Box<[Option<&'static Location<'static>>; 64]>,
+}
+
+impl<'a, T: UninitFields> InitTracker<'a, T> {
+    /// Create a tracker for `slot`, with no fields marked as initialized.
+    ///
+    /// With the `poison` feature enabled in debug builds, every byte of `slot` is overwritten
+    /// with [`POISON_BYTE`](crate::POISON_BYTE), so fields must be initialized after the tracker
+    /// is created.
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        assert!(
+            T::FIELDS.len() <= 64,
+            "InitTracker supports structs with at most 64 fields"
+        );
+        crate::poison::poison_new(slot);
+        #[cfg(feature = "shadow")]
+        crate::shadow::register(slot.as_ptr() as *const u8, core::mem::size_of::<T>());
+        Self {
+            slot,
+            init: 0,
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            locations: alloc::boxed::Box::new([None; 64]),
+        }
+    }
+
+    /// Whether the field at `index` has been marked as initialized.
+    pub fn is_init(&self, index: usize) -> bool {
+        self.init & (1 << index) != 0
+    }
+
+    /// Where the field at `index` was last marked as initialized with
+    /// [`set_init`](InitTracker::set_init).
+    ///
+    /// Returns `None` if the field isn't initialized, or in release builds or without the `alloc`
+    /// feature, where locations aren't recorded.
+    #[allow(unused_variables)]
+    pub fn init_location(&self, index: usize) -> Option<&'static Location<'static>> {
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        if self.is_init(index) {
+            return self.locations[index];
+        }
+        None
+    }
+
+    /// Whether every field has been marked as initialized.
+    pub fn is_complete(&self) -> bool {
+        (0..T::FIELDS.len()).all(|i| self.is_init(i))
+    }
+
+    /// The fields that have been marked as initialized, in declaration order.
+    pub fn initialized(&self) -> impl Iterator<Item = &'static FieldInfo> + Clone + '_ {
+        T::FIELDS
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| self.is_init(*i))
+            .map(|(_, f)| f)
+    }
+
+    /// The fields that have not been marked as initialized, in declaration order.
+    pub fn missing(&self) -> impl Iterator<Item = &'static FieldInfo> + Clone + '_ {
+        T::FIELDS
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| !self.is_init(*i))
+            .map(|(_, f)| f)
+    }
+
+    /// A shared reference to the tracked slot.
+    pub fn slot(&self) -> &MaybeUninit<T> {
+        self.slot
+    }
+
+    /// A mutable reference to the tracked slot.
+    ///
+    /// ## Safety
+    /// Fields that are marked as initialized must not be de-initialized through this reference.
+    pub unsafe fn slot_mut(&mut self) -> &mut MaybeUninit<T> {
+        self.slot
+    }
+
+    /// Mark the field at `index` as initialized.
+    ///
+    /// ## Safety
+    /// The field must be initialized. If it was already marked as initialized, the previous value
+    /// must have been dropped or moved out.
+    #[track_caller]
+    pub unsafe fn set_init(&mut self, index: usize) {
+        assert!(index < T::FIELDS.len(), "field index out of range");
+        #[cfg(feature = "shadow")]
+        self.check_shadow(index, None);
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        {
+            self.locations[index] = Some(Location::caller());
+        }
+        #[cfg(feature = "coverage")]
+        {
+            if self.is_init(index) {
+                crate::coverage::record_field_overwrite::<T>(index);
+            }
+            crate::coverage::record_field_init::<T>(index);
+        }
+        self.init |= 1 << index;
+    }
+
+    #[cfg(feature = "shadow")]
+    fn check_shadow(&self, index: usize, at: Option<&'static Location<'static>>) {
+        let field = &T::FIELDS[index];
+        crate::shadow::check_init(
+            unsafe { (self.slot.as_ptr() as *const u8).add(field.offset()) },
+            field.size(),
+            core::any::type_name::<T>(),
+            field.name(),
+            at,
+        );
+    }
+
+    /// Drop the field at `index` in place if it is initialized, and mark it as uninitialized.
+    pub fn drop_field(&mut self, index: usize) {
+        if self.is_init(index) {
+            self.init &= !(1 << index);
+            unsafe { T::FIELDS[index].drop_in_place(self.slot.as_mut_ptr() as *mut u8) };
+        }
+    }
+
+    /// Mark the field at `index` as uninitialized without dropping it.
+    ///
+    /// If the field was initialized, its value is leaked unless it is moved out of the slot, as
+    /// [`take_field!`](crate::take_field) does.
+    pub fn forget_field(&mut self, index: usize) {
+        self.init &= !(1 << index);
+    }
+
+    /// Return a reference to the value if every field is initialized.
+    /// Otherwise, return the tracker unchanged.
+    pub fn finish(self) -> Result<&'a mut T, Self> {
+        if !self.is_complete() {
+            return Err(self);
+        }
+        #[cfg(feature = "shadow")]
+        {
+            (0..T::FIELDS.len()).for_each(|i| self.check_shadow(i, self.init_location(i)));
+            crate::shadow::unregister(self.slot.as_ptr() as *const u8);
+        }
+        #[cfg(feature = "coverage")]
+        crate::coverage::record_completed::<T>();
+        let slot = unsafe { ptr::read(&self.slot) };
+        core::mem::forget(self);
+        Ok(unsafe { &mut *slot.as_mut_ptr() })
+    }
+
+    /// Return a reference to the value if every field is initialized.
+    /// Otherwise, return an error listing the fields that aren't, which also gives the tracker
+    /// back.
+    ///
+    /// This works like [`finish`](InitTracker::finish), but the error can be printed, so a
+    /// forgotten field is reported by name instead of leading to undefined behavior with
+    /// `assume_init`.
+    ///
+    /// ## Example
+    /// ```
+    /// # #[cfg(feature = "derive")] {
+    /// use core::mem::MaybeUninit;
+    /// use project_uninit::{partial_init_tracked, InitTracker, ProjectUninit};
+    ///
+    /// #[derive(ProjectUninit, Debug)]
+    /// struct Config { path: String, verbose: bool, level: u8 }
+    ///
+    /// let mut slot = MaybeUninit::<Config>::uninit();
+    /// let mut tracker = InitTracker::new(&mut slot);
+    /// partial_init_tracked!(tracker => path = "/etc".into());
+    ///
+    /// let missing = tracker.try_assume_init().unwrap_err();
+    /// assert_eq!(missing.to_string(), "missing fields `verbose`, `level` of `Config`");
+    ///
+    /// let mut tracker = missing.into_tracker();
+    /// partial_init_tracked!(tracker => { verbose: false, level: 2 });
+    /// assert_eq!(tracker.try_assume_init().unwrap().level, 2);
+    /// # }
+    /// ```
+    pub fn try_assume_init(self) -> Result<&'a mut T, MissingFields<'a, T>> {
+        self.finish().map_err(|tracker| MissingFields { tracker })
+    }
+}
+
+/// The error returned by [`InitTracker::try_assume_init`] when some fields of the value aren't
+/// initialized.
+///
+/// It displays the names of the missing fields, and holds on to the tracker, so the fields that
+/// are initialized are dropped with it unless it is taken back with
+/// [`into_tracker`](MissingFields::into_tracker).
+pub struct MissingFields<'a, T: UninitFields> {
+    tracker: InitTracker<'a, T>,
+}
+
+impl<'a, T: UninitFields> MissingFields<'a, T> {
+    /// The names of the fields that aren't initialized, in declaration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + Clone + '_ {
+        self.tracker.missing().map(|f| f.name())
+    }
+
+    /// Take back the tracker, e.g. to initialize the missing fields.
+    pub fn into_tracker(self) -> InitTracker<'a, T> {
+        self.tracker
+    }
+}
+
+impl<T: UninitFields> fmt::Display for MissingFields<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.names().nth(1).is_some() {
+            "s"
+        } else {
+            ""
+        };
+        write!(f, "missing field{} ", plural)?;
+        for (i, name) in self.names().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name)?;
+        }
+        let type_name = core::any::type_name::<T>();
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        write!(f, " of `{}`", type_name)
+    }
+}
+
+impl<T: UninitFields> fmt::Debug for MissingFields<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Names<I>(I);
+        impl<I: Iterator<Item = &'static str> + Clone> fmt::Debug for Names<I> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.clone()).finish()
+            }
+        }
+        f.debug_struct("MissingFields")
+            .field("missing", &Names(self.names()))
+            .finish()
+    }
+}
+
+impl<T: UninitFields> Drop for InitTracker<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "coverage")]
+        if !self.is_complete() {
+            crate::coverage::record_dropped_incomplete::<T>();
+        }
+        for i in 0..T::FIELDS.len() {
+            self.drop_field(i);
+        }
+        #[cfg(feature = "shadow")]
+        crate::shadow::unregister(self.slot.as_ptr() as *const u8);
+    }
+}
+
+impl<T: UninitFields> fmt::Debug for InitTracker<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Names<I>(I);
+        impl<I: Iterator<Item = &'static FieldInfo> + Clone> fmt::Debug for Names<I> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.clone().map(|f| f.name())).finish()
+            }
+        }
+        f.debug_struct("InitTracker")
+            .field("initialized", &Names(self.initialized()))
+            .field("missing", &Names(self.missing()))
+            .finish()
+    }
+}
+
+/// Initialize fields of the slot of an [`InitTracker`] and mark each of them as initialized as
+/// soon as it is written.
+///
+/// Unlike [`partial_init!`](crate::partial_init) followed by
+/// [`set_init`](InitTracker::set_init), this doesn't need an `unsafe` block, and a field that is
+/// already initialized is dropped before its new value is written.
+/// Only direct fields of the struct can be initialized, since nested fields aren't tracked.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{tracked_init, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Connection { name: String, buffer: Vec<u8>, port: u16 }
+///
+/// fn parse_port(s: &str) -> u16 {
+///     s.parse().expect("invalid port")
+/// }
+///
+/// let result = std::panic::catch_unwind(|| {
+///     let mut slot = MaybeUninit::<Connection>::uninit();
+///     let mut tracker = InitTracker::new(&mut slot);
+///     tracked_init!(tracker => { name: "db".into(), buffer: vec![0; 4096] });
+///     // this panics, and `name` and `buffer` are dropped with the tracker
+///     tracked_init!(tracker => port = parse_port("http"));
+/// });
+/// assert!(result.is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tracked_init {
+    ($tracker:expr => {$($field:tt : $val:expr),* $(,)?}) => {{
+        let _ = $crate::tracked_init!(@refs $tracker => { $($field: $val),* });
+    }};
+
+    ($tracker:expr => $field:tt = $val:expr) => {
+        $crate::tracked_init!($tracker => { $field: $val })
+    };
+
+    // write the fields, and return mutable references to them
+    (@refs $tracker:expr => {$($field:tt : $val:expr),*}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($tracker, [ $( [ $field ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let tracker: &mut $crate::InitTracker<'_, _> = $tracker.borrow_mut();
+        // every field pointer is derived from this one, so using the tracker between the writes
+        // doesn't invalidate them
+        #[allow(unused_unsafe)]
+        let base = unsafe { tracker.slot_mut() }.as_mut_ptr();
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *base };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$field] )*);
+        }
+        $crate::tracked_init!(@name tracker, base [] $($field: $val),*)
+    }};
+
+    // name the pointer to each field, which is a different variable in each expansion
+    (@name $tracker:ident, $base:ident [$($done:tt)*] $field:tt : $val:expr $(, $($rest:tt)*)?) => {
+        $crate::tracked_init!(@name $tracker, $base [$($done)* ($field ($val) ptr)] $($($rest)*)?)
+    };
+    (@name $tracker:ident, $base:ident [$(($field:tt ($val:expr) $ptr:ident))*]) => {{
+        $(
+            let value = $val;
+            let index = $crate::utils::tracked_field_index($tracker, ::core::stringify!($field));
+            #[allow(unused_unsafe)]
+            let $ptr = unsafe { ::core::ptr::addr_of_mut!((*$base).$field) };
+            if $tracker.is_init(index) {
+                // cleared first, so a panicking `drop` can't drop the field twice
+                $tracker.forget_field(index);
+                #[allow(unused_unsafe)]
+                unsafe {
+                    $crate::utils::drop_field($ptr)
+                };
+            }
+            #[allow(unused_unsafe)]
+            unsafe {
+                ::core::ptr::write($ptr, value);
+                $crate::utils::init_hook($base, $ptr, ::core::stringify!($field));
+                $tracker.set_init(index);
+            }
+        )*
+        // the tracker is borrowed by the references once every field is written
+        let lt = $crate::utils::bind_mut_lt($tracker);
+        #[allow(unused_unsafe)]
+        let refs = unsafe { ($($crate::utils::deref_ptr_with_lt($ptr, lt),)*) };
+        refs
+    }};
+}
+
+/// Move the values of fields out of the slot of an [`InitTracker`], and mark each of them as
+/// uninitialized.
+///
+/// Each field is returned as `Some(value)` if it was initialized, or `None` otherwise, so a field
+/// is never read twice or dropped again with the tracker. This is useful for taking back a field
+/// from a value that is only partly built, e.g. to build it again differently.
+/// Only direct fields of the struct can be taken, as with [`tracked_init!`](crate::tracked_init).
+/// To move fields out of a `MaybeUninit` without a tracker, use
+/// [`read_field!`](crate::read_field).
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{take_field, tracked_init, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Request { url: String, body: Vec<u8>, retries: u8 }
+///
+/// let mut slot = MaybeUninit::<Request>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// tracked_init!(tracker => { url: "http://example.com".into(), body: b"ping".to_vec() });
+///
+/// // take the url back to switch it to https
+/// let url = take_field!(tracker => url).unwrap();
+/// assert_eq!(take_field!(tracker => retries), None);
+/// tracked_init!(tracker => { url: url.replace("http:", "https:"), retries: 3 });
+///
+/// let request = tracker.finish().ok().unwrap();
+/// assert_eq!(request.url, "https://example.com");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! take_field {
+    ($tracker:expr => {$($field:tt),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($tracker, [ $( [ $field ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let tracker: &mut $crate::InitTracker<'_, _> = $tracker.borrow_mut();
+        ($({
+            let index = $crate::utils::tracked_field_index(tracker, ::core::stringify!($field));
+            if tracker.is_init(index) {
+                tracker.forget_field(index);
+                #[allow(unused_unsafe)]
+                let value = unsafe { $crate::read_field!(tracker.slot_mut() => $field) };
+                ::core::option::Option::Some(value)
+            } else {
+                ::core::option::Option::None
+            }
+        },)*)
+    }};
+
+    ($tracker:expr => $field:tt) => {
+        $crate::take_field!($tracker => { $field }).0
+    };
+}
+
+/// Initialize fields of the slot of an [`InitTracker`], marking each of them as initialized, and
+/// return a tuple of mutable references to them, like [`partial_init!`](crate::partial_init).
+///
+/// This works like [`tracked_init!`](crate::tracked_init): a field that is already initialized is
+/// dropped before its new value is written, and only direct fields of the struct can be
+/// initialized. The references borrow the tracker, so it can't be used while they're alive.
+/// Once the fields are written, [`try_assume_init`](InitTracker::try_assume_init) reports any
+/// field that was forgotten.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init_tracked, InitTracker, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Index { words: Vec<String>, count: usize }
+///
+/// let mut slot = MaybeUninit::<Index>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// let (words, count) = partial_init_tracked!(tracker => { words: Vec::new(), count: 0 });
+/// for word in "a b c".split(' ') {
+///     words.push(word.into());
+///     *count += 1;
+/// }
+/// assert_eq!(tracker.try_assume_init().unwrap().count, 3);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! partial_init_tracked {
+    ($tracker:expr => {$($field:tt : $val:expr),* $(,)?}) => {
+        $crate::tracked_init!(@refs $tracker => { $($field: $val),* })
+    };
+
+    ($tracker:expr => $field:tt = $val:expr) => {
+        $crate::partial_init_tracked!($tracker => { $field: $val }).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::{partial_init_tracked, InitTracker, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Foo { a: String, b: u8 }
+/// let mut slot = core::mem::MaybeUninit::<Foo>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// let a = partial_init_tracked!(tracker => a = String::new());
+/// tracker.drop_field(0);
+/// a.push('!');
+///```
+fn _tracked_ref_outlives_tracker_borrow_fails() {}
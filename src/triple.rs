@@ -0,0 +1,211 @@
+use core::cell::UnsafeCell;
+use core::mem::{size_of, MaybeUninit};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::{InitTracker, UninitFields};
+
+// set in `TripleBuffer::state` when the middle slot holds a value the reader hasn't seen
+const FRESH: u8 = 0b100;
+const INDEX: u8 = 0b011;
+
+/// A lock-free triple buffer for handing values from one thread to another, with the values
+/// initialized in place in its slots.
+///
+/// The buffer has three slots: one owned by the [`TripleWriter`], one owned by the
+/// [`TripleReader`], and one in the middle. The writer initializes a value in its slot and
+/// publishes it by swapping it with the middle slot; the reader picks up the latest published
+/// value by swapping its slot with the middle one. Both sides are wait-free and never allocate,
+/// and a value is only ever dropped on the writer's side, when its slot is reused, or when the
+/// buffer is dropped, so the reader never runs destructors. This makes it suitable for handing
+/// state from a control thread to a real-time thread, such as an audio callback.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{partial_init, TripleBuffer};
+///
+/// struct Filter { cutoff: f32, resonance: f32 }
+///
+/// let mut buffer = TripleBuffer::<Filter>::new();
+/// let (mut writer, mut reader) = buffer.split();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || {
+///         let slot = writer.prepare();
+///         partial_init!(slot => { cutoff: 800.0, resonance: 0.7 });
+///         unsafe { writer.commit() };
+///     })
+///     .join()
+///     .unwrap();
+///
+///     s.spawn(move || {
+///         let filter = reader.read().unwrap();
+///         assert_eq!(filter.cutoff, 800.0);
+///     });
+/// });
+/// ```
+pub struct TripleBuffer<T> {
+    slots: [UnsafeCell<MaybeUninit<T>>; 3],
+    // the index of the middle slot, and whether it holds a value the reader hasn't seen
+    state: AtomicU8,
+    back: u8,
+    front: u8,
+    // which slots hold a value, only accessed by the writer
+    init: u8,
+    // whether the reader's slot holds a value
+    front_init: bool,
+}
+
+impl<T> TripleBuffer<T> {
+    /// Create a buffer with no values in it.
+    pub fn new() -> Self {
+        Self {
+            slots: [
+                UnsafeCell::new(MaybeUninit::uninit()),
+                UnsafeCell::new(MaybeUninit::uninit()),
+                UnsafeCell::new(MaybeUninit::uninit()),
+            ],
+            state: AtomicU8::new(1),
+            back: 0,
+            front: 2,
+            init: 0,
+            front_init: false,
+        }
+    }
+
+    /// Split the buffer into its writing and reading sides, which can be sent to different
+    /// threads.
+    pub fn split(&mut self) -> (TripleWriter<'_, T>, TripleReader<'_, T>) {
+        let Self {
+            slots,
+            state,
+            back,
+            front,
+            init,
+            front_init,
+        } = self;
+        (
+            TripleWriter {
+                slots,
+                state,
+                back,
+                init,
+            },
+            TripleReader {
+                slots,
+                state,
+                front,
+                front_init,
+            },
+        )
+    }
+}
+
+impl<T> Default for TripleBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TripleBuffer<T> {
+    fn drop(&mut self) {
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if self.init & (1 << i) != 0 {
+                unsafe { slot.get_mut().assume_init_drop() };
+                crate::poison::deinit(slot.get_mut().as_mut_ptr() as *mut u8, size_of::<T>());
+            }
+        }
+    }
+}
+
+/// The writing side of a [`TripleBuffer`].
+pub struct TripleWriter<'a, T> {
+    slots: &'a [UnsafeCell<MaybeUninit<T>>; 3],
+    state: &'a AtomicU8,
+    back: &'a mut u8,
+    init: &'a mut u8,
+}
+
+unsafe impl<T: Send> Send for TripleWriter<'_, T> {}
+
+impl<T> TripleWriter<'_, T> {
+    /// Drop the value in the writer's slot, if there is one, and return the empty slot to
+    /// initialize the next value in.
+    pub fn prepare(&mut self) -> &mut MaybeUninit<T> {
+        let back = *self.back;
+        let slot = unsafe { &mut *self.slots[back as usize].get() };
+        if *self.init & (1 << back) != 0 {
+            *self.init &= !(1 << back);
+            unsafe { slot.assume_init_drop() };
+            crate::poison::deinit(slot.as_mut_ptr() as *mut u8, size_of::<T>());
+        }
+        slot
+    }
+
+    /// Publish the value in the writer's slot to the reader, and take the middle slot as the
+    /// writer's slot.
+    ///
+    /// ## Safety
+    /// The slot returned by [`prepare`](TripleWriter::prepare) must be fully initialized.
+    pub unsafe fn commit(&mut self) {
+        *self.init |= 1 << *self.back;
+        let prev = self.state.swap(*self.back | FRESH, Ordering::AcqRel);
+        *self.back = prev & INDEX;
+    }
+
+    /// Publish `value` to the reader.
+    pub fn publish(&mut self, value: T) {
+        self.prepare().write(value);
+        unsafe { self.commit() };
+    }
+
+    /// Initialize a value in the writer's slot one field at a time with `init`, and publish it
+    /// if every field was initialized.
+    ///
+    /// Returns whether the value was published. If it wasn't, or if `init` panics, the fields
+    /// that were initialized are dropped.
+    pub fn publish_with(&mut self, init: impl FnOnce(&mut InitTracker<'_, T>)) -> bool
+    where
+        T: UninitFields,
+    {
+        let mut tracker = InitTracker::new(self.prepare());
+        init(&mut tracker);
+        let complete = tracker.finish().is_ok();
+        if complete {
+            unsafe { self.commit() };
+        }
+        complete
+    }
+}
+
+/// The reading side of a [`TripleBuffer`].
+pub struct TripleReader<'a, T> {
+    slots: &'a [UnsafeCell<MaybeUninit<T>>; 3],
+    state: &'a AtomicU8,
+    front: &'a mut u8,
+    front_init: &'a mut bool,
+}
+
+unsafe impl<T: Send> Send for TripleReader<'_, T> {}
+
+impl<T> TripleReader<'_, T> {
+    /// Whether a value has been published since the reader last picked one up.
+    pub fn has_update(&self) -> bool {
+        self.state.load(Ordering::Relaxed) & FRESH != 0
+    }
+
+    /// Pick up the latest published value, if there is a new one, and return the reader's value.
+    ///
+    /// Returns `None` if no value has been published yet.
+    pub fn read(&mut self) -> Option<&T> {
+        if self.has_update() {
+            let prev = self.state.swap(*self.front, Ordering::AcqRel);
+            *self.front = prev & INDEX;
+            *self.front_init = true;
+        }
+        if *self.front_init {
+            Some(unsafe { (*self.slots[*self.front as usize].get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+}
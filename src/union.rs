@@ -0,0 +1,185 @@
+/// Obtain a `&MaybeUninit<_>` reference to a field of a union wrapped in `MaybeUninit<_>`, or to
+/// a field reached through one or more unions.
+///
+/// The field path must be preceded by `unsafe`, which marks that the caller keeps track of which
+/// field of each union in the path is active: every field of a union starts at the same address,
+/// and only the field that was last written holds a meaningful value. Bytes of the union outside
+/// the active field, and every field that overlaps it without being the same field, must be
+/// assumed to be uninitialized, so a projected field other than the active one may only be
+/// written to, never read.
+///
+/// The path uses the same syntax as [`project_uninit!`](crate::project_uninit), and may go
+/// through structs, tuples and arrays before and after the union. Only one field can be projected
+/// per invocation, since fields of the same union overlap.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init_union, project_union};
+///
+/// #[derive(Clone, Copy)]
+/// union Value { int: i64, pair: (f32, f32) }
+/// struct Slot { tag: u8, value: Value }
+///
+/// let mut slot = MaybeUninit::<Slot>::uninit();
+/// partial_init_union!(unsafe slot => value => pair => 0 = 1.5);
+/// partial_init_union!(unsafe slot => value => pair => 1 = -1.5);
+///
+/// // `pair` is the active field, so it's initialized
+/// let pair = project_union!(unsafe slot => value => pair);
+/// assert_eq!(unsafe { pair.assume_init() }, (1.5, -1.5));
+/// ```
+#[macro_export]
+macro_rules! project_union {
+    (unsafe $expr:expr => $($props:tt)=>+) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::Borrow;
+        let _ref: &::core::mem::MaybeUninit<_> = $expr.borrow();
+        let ptr = ::core::mem::MaybeUninit::as_ptr(_ref);
+        let lt = $crate::utils::bind_ref_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the field
+            #[allow(unused_unsafe)]
+            unsafe {
+                let _x = &*ptr;
+                let _y = &$crate::__field_place!((_x) $($props)=>+);
+                $crate::__assert_arrays!((_x) $($props)=>+);
+            }
+        }
+        #[allow(unused_unsafe)]
+        let field = unsafe {
+            let prop_ptr = ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+));
+            $crate::utils::uninit_from_ptr(prop_ptr, lt)
+        };
+        field
+    }};
+}
+
+/// Obtain a `&mut MaybeUninit<_>` reference to a field of a union wrapped in `MaybeUninit<_>`, or
+/// to a field reached through one or more unions.
+///
+/// This accepts the same syntax as [`project_union!`](crate::project_union), including the
+/// `unsafe` before the field path, and follows the same rules for the active field of each union.
+/// Writing to the returned reference doesn't drop the value of the previously active field.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::project_union_mut;
+///
+/// union Bits { word: u32, bytes: [u8; 4] }
+///
+/// let mut bits = MaybeUninit::<Bits>::uninit();
+/// project_union_mut!(unsafe bits => word).write(u32::from_ne_bytes([1, 2, 3, 4]));
+/// assert_eq!(unsafe { bits.assume_init().bytes }, [1, 2, 3, 4]);
+/// ```
+#[macro_export]
+macro_rules! project_union_mut {
+    (unsafe $expr:expr => $($props:tt)=>+) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the field
+            #[allow(unused_unsafe)]
+            unsafe {
+                let _x = &mut *ptr;
+                let _y = $crate::__field_mut!((_x) $($props)=>+);
+                $crate::__assert_arrays!((_x) $($props)=>+);
+            }
+        }
+        #[allow(unused_unsafe)]
+        let field = unsafe {
+            let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+            $crate::utils::uninit_from_mut_ptr(prop_ptr, lt)
+        };
+        field
+    }};
+}
+
+/// Initialize a field of a union wrapped in `MaybeUninit`, or a field reached through one or
+/// more unions, making it the active field, and return a mutable reference to it.
+///
+/// This accepts the same syntax as a single field of [`partial_init!`](crate::partial_init),
+/// preceded by `unsafe` as in [`project_union!`](crate::project_union), and follows the same
+/// rules for the active field of each union.
+/// The value of the previously active field is overwritten without being dropped.
+/// When the path goes through a union into a field of one of its fields, like
+/// `value => pair => 0`, only that part of the union's field is initialized, and the rest of it
+/// must be initialized before the field is read.
+///
+/// See [`project_union!`](crate::project_union) for an example.
+#[macro_export]
+macro_rules! partial_init_union {
+    (unsafe $expr:expr => $($props:tt)=>+ = $val:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the field
+            #[allow(unused_unsafe)]
+            unsafe {
+                let _x = &mut *ptr;
+                let _y = $crate::__field_mut!((_x) $($props)=>+);
+                $crate::__assert_arrays!((_x) $($props)=>+);
+            }
+        }
+        let value = $val;
+        #[allow(unused_unsafe)]
+        let prop_ref = unsafe {
+            let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+            ::core::ptr::write(prop_ptr, value);
+            $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+            $crate::utils::deref_ptr_with_lt(prop_ptr, lt)
+        };
+        prop_ref
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::project_union_mut;
+/// union Bits { word: u32, bytes: [u8; 4] }
+/// let mut bits = core::mem::MaybeUninit::<Bits>::uninit();
+/// project_union_mut!(bits => word).write(1);
+///```
+fn _project_union_requires_unsafe_token() {}
+
+///```compile_fail
+/// use project_uninit::project_union_mut;
+/// union Bits { word: u32, bytes: [u8; 4] }
+/// let mut bits = core::mem::MaybeUninit::<Bits>::uninit();
+/// let word = project_union_mut!(unsafe bits => word);
+/// let bytes = project_union_mut!(unsafe bits => bytes);
+/// word.write(1);
+/// bytes.write([0; 4]);
+///```
+fn _project_union_mut_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::partial_init_union;
+/// union Bits { word: u32, bytes: [u8; 4] }
+/// let mut bits = core::mem::MaybeUninit::<Bits>::uninit();
+/// partial_init_union!(unsafe bits => bytes => [4] = 1);
+///```
+fn _partial_init_union_index_out_of_bounds_fails() {}
+
+///```compile_fail
+/// use project_uninit::{partial_init, ProjectUninit};
+/// #[derive(Clone, Copy)]
+/// union Bits { word: u32, bytes: [u8; 4] }
+/// #[derive(ProjectUninit)]
+/// struct Register { bits: Bits }
+/// let mut reg = core::mem::MaybeUninit::<Register>::uninit();
+/// unsafe { partial_init!(reg => strict bits => word = 1) };
+///```
+fn _strict_path_through_union_fails() {}
@@ -1,31 +1,774 @@
-use core::{marker::PhantomData, mem::MaybeUninit};
+use crate::{FieldBytes, UninitFields};
+use core::{
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+};
 
 /// Invariant lifetime used to constrain the lifetime of a projected field reference.
 #[derive(Clone, Copy)]
 pub struct Lifetime<'a>(PhantomData<*mut &'a ()>);
 
-pub fn bind_ref_lt<'a, T>(_: &'a T) -> Lifetime<'a> {
+pub const fn bind_ref_lt<'a, T: ?Sized>(_: &'a T) -> Lifetime<'a> {
     Lifetime(PhantomData)
 }
 
-pub fn bind_mut_lt<'a, T>(_: &'a mut T) -> Lifetime<'a> {
+pub const fn bind_mut_lt<'a, T: ?Sized>(_: &'a mut T) -> Lifetime<'a> {
     Lifetime(PhantomData)
 }
 
-pub unsafe fn uninit_from_ptr<'a, T>(
-    ptr: *const T,
-    _lt: Lifetime<'a>,
-) -> &'a MaybeUninit<T> {
+pub const unsafe fn uninit_from_ptr<'a, T>(ptr: *const T, _lt: Lifetime<'a>) -> &'a MaybeUninit<T> {
     &*(ptr as *const MaybeUninit<T>)
 }
 
-pub unsafe fn uninit_from_mut_ptr<'a, T>(
+pub const unsafe fn uninit_from_mut_ptr<'a, T>(
     ptr: *mut T,
     _lt: Lifetime<'a>,
 ) -> &'a mut MaybeUninit<T> {
     &mut *(ptr as *mut MaybeUninit<T>)
 }
 
-pub unsafe fn deref_ptr_with_lt<'a, T>(ptr: *mut T, _lt: Lifetime<'a>) -> &'a mut T {
+pub const unsafe fn deref_ptr_with_lt<'a, T>(ptr: *mut T, _lt: Lifetime<'a>) -> &'a mut T {
     &mut *ptr
 }
+
+pub const unsafe fn ref_ptr_with_lt<'a, T>(ptr: *mut T, _lt: Lifetime<'a>) -> &'a T {
+    &*ptr
+}
+
+pub const fn as_mut_ptr<T>(ptr: *const T) -> *mut T {
+    ptr as *mut T
+}
+
+pub const fn uninit_inner_ptr<T>(ptr: *mut MaybeUninit<T>) -> *mut T {
+    ptr as *mut T
+}
+
+pub unsafe fn export_field_ptrs<T: crate::ExportFields>(ptr: *mut T) -> T::Ptrs {
+    T::field_ptrs(ptr)
+}
+
+pub unsafe fn field_bytes<'a, T, F>(
+    base: *const T,
+    field: *const F,
+    path: &'static str,
+    _lt: Lifetime<'a>,
+) -> FieldBytes<'a> {
+    let offset = (field as *const u8).offset_from(base as *const u8) as usize;
+    let bytes = core::slice::from_raw_parts(field as *const u8, core::mem::size_of::<F>());
+    FieldBytes {
+        path,
+        offset,
+        bytes,
+    }
+}
+
+#[inline(always)]
+#[track_caller]
+#[allow(unused_variables)]
+pub fn init_hook<T, F>(base: *mut T, field: *mut F, path: &'static str) {
+    #[cfg(feature = "hooks")]
+    crate::hooks::fire::<T, F>(path);
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_written::<T, F>(field as *const u8, path);
+}
+
+#[cfg(feature = "test-support")]
+#[inline(always)]
+pub fn try_init_value<T, E: From<crate::InjectedFailure>>(value: Result<T, E>) -> Result<T, E> {
+    let value = value?;
+    crate::check_injected_failure()?;
+    Ok(value)
+}
+
+#[cfg(not(feature = "test-support"))]
+#[inline(always)]
+pub fn try_init_value<T, E>(value: Result<T, E>) -> Result<T, E> {
+    value
+}
+
+/// The fields named in `given` must be initialized, and each must be a field of `T`.
+/// Every other field is moved from `rest`, and the fields of `rest` named in `given` are dropped.
+pub unsafe fn init_rest<'a, T: UninitFields>(
+    slot: &'a mut MaybeUninit<T>,
+    rest: T,
+    given: &[&str],
+) -> &'a mut T {
+    let mut rest = core::mem::ManuallyDrop::new(rest);
+    let src = &mut *rest as *mut T as *mut u8;
+    let dst = slot.as_mut_ptr() as *mut u8;
+    for field in T::FIELDS {
+        if given.contains(&field.name()) {
+            field.drop_in_place(src);
+            continue;
+        }
+        let field_dst = dst.add(field.offset());
+        core::ptr::copy_nonoverlapping(src.add(field.offset()), field_dst, field.size());
+        #[cfg(feature = "hooks")]
+        crate::hooks::fire_sized::<T>(field.name(), field.size());
+        #[cfg(feature = "shadow")]
+        crate::shadow::mark_init(field_dst, field.size());
+    }
+    slot.assume_init_mut()
+}
+
+// Call `f` with a lifetime only known to be shorter than `'a`, so that references made with it
+// can't escape the call through its result.
+#[inline(always)]
+pub fn call_with<'a, R>(
+    lt: Lifetime<'a>,
+    f: impl for<'p> FnOnce(Lifetime<'p>, &'p Lifetime<'a>) -> R,
+) -> R {
+    f(Lifetime(PhantomData), &lt)
+}
+
+pub unsafe fn zero_field<F>(field: *mut F) {
+    core::ptr::write_bytes(field, 0, 1);
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_init(field as *const u8, core::mem::size_of::<F>());
+}
+
+/// The field must be initialized.
+pub unsafe fn replace_field<T, F>(base: *mut T, field: *mut F, value: F, path: &'static str) {
+    let old = core::ptr::replace(field, value);
+    // the old value is dropped below, so this isn't a double initialization
+    #[cfg(feature = "shadow")]
+    crate::shadow::mark_uninit(field as *const u8, core::mem::size_of::<F>());
+    init_hook(base, field, path);
+    drop(old);
+}
+
+/// `ptr` must not be null.
+pub const unsafe fn nonnull_unchecked<F>(ptr: *const F) -> core::ptr::NonNull<F> {
+    core::ptr::NonNull::new_unchecked(ptr as *mut F)
+}
+
+/// Get a pointer to the value in the `UnsafeCell` at `cell`, for `project_cells!`.
+pub const fn cell_ptr<F>(cell: *const core::cell::UnsafeCell<F>) -> *mut F {
+    core::cell::UnsafeCell::raw_get(cell)
+}
+
+/// Get the field at `field` of the value in `cell` as a `Cell`.
+///
+/// ## Panics
+/// If the field isn't stored in the cell, e.g. because its path went through `Deref`.
+pub unsafe fn cell_field<'a, T, F>(
+    cell: &'a core::cell::Cell<T>,
+    field: *const F,
+    path: &str,
+) -> &'a core::cell::Cell<F> {
+    let start = cell.as_ptr().addr();
+    let addr = field.addr();
+    assert!(
+        addr >= start && addr + core::mem::size_of::<F>() <= start + core::mem::size_of::<T>(),
+        "field `{}` is not stored in the cell",
+        path,
+    );
+    &*(field as *const core::cell::Cell<F>)
+}
+
+// Check that the source of `copy_from_init!` or `clone_from_init!` has the type of the slot.
+pub const fn source_of<'a, T>(_slot: &MaybeUninit<T>, src: &'a T) -> &'a T {
+    src
+}
+
+pub const fn copy_field<F: Copy>(field: &F) -> F {
+    *field
+}
+
+// Check that the source of `move_init!` has the type of the slot.
+pub fn move_source<T>(_slot: &MaybeUninit<T>, src: &mut MaybeUninit<T>) -> *mut T {
+    src.as_mut_ptr()
+}
+
+/// The field at `src` must be initialized, and becomes logically uninitialized.
+pub unsafe fn move_field<F>(src: *mut F, dst: *mut F) {
+    core::ptr::copy_nonoverlapping(src, dst, 1);
+    crate::poison::deinit(src as *mut u8, core::mem::size_of::<F>());
+}
+
+/// The field must be initialized, and becomes logically uninitialized.
+pub unsafe fn take_field<F>(field: *mut F) -> F {
+    let value = core::ptr::read(field);
+    crate::poison::deinit(field as *mut u8, core::mem::size_of::<F>());
+    value
+}
+
+/// The field must be initialized, and becomes logically uninitialized.
+pub unsafe fn drop_field<F>(field: *mut F) {
+    core::ptr::drop_in_place(field);
+    crate::poison::deinit(field as *mut u8, core::mem::size_of::<F>());
+}
+
+// Write `value` to `dst` such that the store can't be optimized away or made value-dependent.
+#[inline(never)]
+pub unsafe fn write_secret<F>(dst: *mut F, value: F) {
+    core::ptr::write_volatile(dst, value);
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+pub unsafe fn hash_bytes_of<H: core::hash::Hasher, F>(hasher: &mut H, field: &mut F) {
+    let bytes = core::slice::from_raw_parts(field as *const F as *const u8, core::mem::size_of::<F>());
+    hasher.write(bytes);
+}
+
+/// Drops the first `len` fields of `T` (in declaration order) unless `finish` is called.
+/// Used by derive-generated code that initializes fields one at a time and may return early.
+pub struct PrefixGuard<'a, T: UninitFields> {
+    slot: &'a mut MaybeUninit<T>,
+    len: usize,
+}
+
+impl<'a, T: UninitFields> PrefixGuard<'a, T> {
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        Self { slot, len: 0 }
+    }
+
+    /// Get the slot, to initialize the next field.
+    ///
+    /// ## Safety
+    /// The first `len` fields, which the guard drops, must not be de-initialized through the
+    /// returned reference.
+    pub unsafe fn slot(&mut self) -> &mut MaybeUninit<T> {
+        self.slot
+    }
+
+    /// Record that the next field has been initialized.
+    pub unsafe fn push(&mut self) {
+        self.len += 1;
+    }
+
+    /// All fields must have been initialized.
+    pub unsafe fn finish(self) -> &'a mut T {
+        debug_assert_eq!(self.len, T::FIELDS.len());
+        let this = core::mem::ManuallyDrop::new(self);
+        let slot = core::ptr::read(&this.slot);
+        &mut *slot.as_mut_ptr()
+    }
+}
+
+impl<T: UninitFields> Drop for PrefixGuard<'_, T> {
+    fn drop(&mut self) {
+        let base = self.slot.as_mut_ptr() as *mut u8;
+        for field in &T::FIELDS[..self.len] {
+            unsafe { field.drop_in_place(base) };
+        }
+    }
+}
+
+/// Get the view of the fields of the struct at `ptr`, for checking field paths in `strict` mode.
+pub unsafe fn strict_fields<'a, T: crate::UninitView + 'a>(ptr: *const T) -> T::Ref<'a> {
+    T::view(&*(ptr as *const MaybeUninit<T>))
+}
+
+pub fn strict_view<T: crate::UninitView>(slot: &MaybeUninit<T>) -> T::Ref<'_> {
+    T::view(slot)
+}
+
+/// Get the value at `ptr` as a `MaybeUninit`, for checking a field path that starts with `*` in
+/// `strict` mode.
+pub unsafe fn strict_field<'a, T>(ptr: *const T) -> &'a MaybeUninit<T> {
+    &*(ptr as *const MaybeUninit<T>)
+}
+
+/// A `#[repr(transparent)]` wrapper that a `*` segment of a field path continues into.
+///
+/// ## Safety
+/// `Self` must have the same layout as `Inner`, and a pointer to it must be usable as a pointer
+/// to `Inner`.
+pub unsafe trait Wrapper {
+    type Inner;
+}
+
+unsafe impl<T> Wrapper for MaybeUninit<T> {
+    type Inner = T;
+}
+
+unsafe impl<T> Wrapper for ManuallyDrop<T> {
+    type Inner = T;
+}
+
+/// Unwrap a wrapper field for checking a `*` segment of a field path in `strict` mode.
+pub fn strict_wrapped<W: Wrapper>(slot: &MaybeUninit<W>) -> &MaybeUninit<W::Inner> {
+    unsafe { &*(slot as *const MaybeUninit<W> as *const MaybeUninit<W::Inner>) }
+}
+
+/// Get a pointer to the value in a wrapper field, for a `*` segment of a field path.
+pub const fn wrapped_ptr<W: Wrapper>(ptr: *const W) -> *mut W::Inner {
+    ptr as *mut W::Inner
+}
+
+/// Stand in for the value in a wrapper field, for a `*` segment of a field path in an `if false`
+/// block. The field is borrowed mutably, so that wrappers of fields that can't be borrowed
+/// together, like fields of the same union, are rejected. This never returns.
+pub fn wrapped_check<W: Wrapper>(_field: &mut W) -> &mut W::Inner {
+    unreachable!()
+}
+
+/// Like `wrapped_check`, for shared projections.
+pub fn wrapped_check_ref<W: Wrapper>(_field: &W) -> &W::Inner {
+    unreachable!()
+}
+
+/// The struct must treat the field as structurally pinned.
+pub const unsafe fn pin_field<F>(
+    field: &mut MaybeUninit<F>,
+) -> core::pin::Pin<&mut MaybeUninit<F>> {
+    core::pin::Pin::new_unchecked(field)
+}
+
+pub const fn unpin_field<F: Unpin>(field: &mut MaybeUninit<F>) -> &mut MaybeUninit<F> {
+    field
+}
+
+pub fn tracked_field_index<T: UninitFields>(_: &crate::InitTracker<'_, T>, name: &str) -> usize {
+    match T::field_index(name) {
+        Some(index) => index,
+        None => panic!("`{}` is not a field of the tracked struct", name),
+    }
+}
+
+/// Pointers to the fields and tag of a variant of an enum implementing `UninitEnum`, and the
+/// value of its tag.
+pub struct VariantPtrs<P, D> {
+    pub fields: P,
+    pub tag: *mut D,
+    pub value: D,
+}
+
+pub unsafe fn variant_ptrs<T: crate::UninitEnum>(ptr: *mut T) -> T::Variants {
+    T::variant_ptrs(ptr)
+}
+
+/// The struct being built by a builder generated by `#[derive(InitBuilder)]`, along with the
+/// states of its fields, which the builder takes as type parameters.
+pub struct BuilderSlot<T: UninitFields, S>(MaybeUninit<T>, PhantomData<S>);
+
+impl<T: UninitFields, S> BuilderSlot<T, S> {
+    /// `S` must be the states of a builder that starts with every field unset.
+    pub unsafe fn new() -> Self {
+        let mut slot = MaybeUninit::uninit();
+        crate::poison::poison_new(&mut slot);
+        Self(slot, PhantomData)
+    }
+
+    /// `S2` must be the states of the fields after the fields that were written since the last
+    /// change of state.
+    pub unsafe fn into_state<S2>(self) -> BuilderSlot<T, S2> {
+        BuilderSlot(self.0, PhantomData)
+    }
+
+    /// Fields must only be written if the builder marks them as set.
+    pub unsafe fn slot(&mut self) -> &mut MaybeUninit<T> {
+        &mut self.0
+    }
+
+    /// `set` must contain whether each field is initialized.
+    pub unsafe fn drop_fields(&mut self, set: &[bool]) {
+        let base = self.0.as_mut_ptr() as *mut u8;
+        for (field, _) in T::FIELDS.iter().zip(set).filter(|(_, &set)| set) {
+            field.drop_in_place(base);
+        }
+    }
+
+    /// All fields must have been initialized.
+    pub unsafe fn assume_init(self) -> T {
+        self.0.assume_init()
+    }
+}
+
+/// The slot of a guard generated by `#[derive(PartialGuard)]`, along with whether each of its
+/// fields is initialized.
+pub struct GuardSlot<'a, T, const N: usize> {
+    slot: &'a mut MaybeUninit<T>,
+    init: [bool; N],
+}
+
+impl<'a, T, const N: usize> GuardSlot<'a, T, N> {
+    pub fn new(slot: &'a mut MaybeUninit<T>) -> Self {
+        crate::poison::poison_new(slot);
+        Self {
+            slot,
+            init: [false; N],
+        }
+    }
+
+    pub fn is_init(&self, index: usize) -> bool {
+        self.init[index]
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.init.iter().all(|&init| init)
+    }
+
+    /// Mark the field as uninitialized, and return whether it was initialized. If it was, it's
+    /// leaked unless the caller drops or moves it.
+    pub fn clear(&mut self, index: usize) -> bool {
+        core::mem::replace(&mut self.init[index], false)
+    }
+
+    /// The field must be initialized.
+    pub unsafe fn mark_init(&mut self, index: usize) {
+        self.init[index] = true;
+    }
+
+    /// `place` must initialize the field and return a reference to it.
+    pub unsafe fn init_field<F>(
+        &mut self,
+        index: usize,
+        place: impl FnOnce(&mut MaybeUninit<T>) -> &mut F,
+    ) -> &mut F {
+        let field = place(self.slot);
+        self.init[index] = true;
+        field
+    }
+
+    pub fn slot(&self) -> &MaybeUninit<T> {
+        self.slot
+    }
+
+    /// The fields marked as initialized must stay initialized.
+    pub unsafe fn slot_mut(&mut self) -> &mut MaybeUninit<T> {
+        self.slot
+    }
+
+    pub fn into_init(self) -> &'a mut T {
+        assert!(self.is_complete(), "the guarded struct isn't fully initialized");
+        unsafe { self.slot.assume_init_mut() }
+    }
+}
+
+///```compile_fail,E0616
+/// use core::mem::MaybeUninit;
+/// use project_uninit::PartialGuard;
+/// #[derive(PartialGuard)]
+/// struct Foo { a: String }
+/// let mut slot = MaybeUninit::<Foo>::uninit();
+/// let mut guard = FooPartialGuard::new(&mut slot);
+/// // marking a field as initialized without initializing it needs `unsafe`
+/// guard.__slot.init[0] = true;
+///```
+#[cfg(feature = "derive")]
+fn _guard_flags_are_private_fails() {}
+
+#[cfg(feature = "bumpalo")]
+#[allow(clippy::mut_from_ref)]
+pub fn alloc_uninit_in<T>(bump: &::bumpalo::Bump) -> &mut MaybeUninit<T> {
+    let ptr = bump.alloc_layout(core::alloc::Layout::new::<T>());
+    unsafe { &mut *(ptr.as_ptr() as *mut MaybeUninit<T>) }
+}
+
+pub use crate::slice::{
+    check_disjoint, deref_span, deref_span_mut, element_ptr, field_span, slice_span, span_bytes,
+    SliceSpan, UninitSliceIndex,
+};
+
+pub use crate::column::{dangling_field, element_base, uninit_column, UninitElements};
+pub use crate::dst::uninit_place;
+pub use crate::option::{write_err, write_ok, write_some};
+pub use crate::pin::AlwaysUnpin;
+pub use crate::poison::poison_new;
+pub use crate::register::reg;
+pub use crate::split::{part_span, uninit_part, uninit_split};
+pub use crate::via::call_via;
+
+#[cfg(feature = "serde")]
+pub use crate::de::{deserialize_struct, DeserializeFields};
+
+#[cfg(feature = "gpu")]
+pub use crate::gpu::{gpu_layout, write_unaligned};
+
+#[cfg(feature = "std")]
+pub use crate::io::read_exact_field;
+
+#[cfg(feature = "alloc")]
+pub use crate::boxed::{box_emplace, emplace_slot, EmplaceProof};
+
+#[cfg(feature = "soa")]
+pub use alloc::boxed::Box;
+
+#[cfg(feature = "soa")]
+pub fn new_column<T>(len: usize) -> Box<[MaybeUninit<T>]> {
+    (0..len).map(|_| MaybeUninit::uninit()).collect()
+}
+
+/// The columns of struct-of-arrays storage generated by `#[derive(UninitSoA)]`, as a tuple of
+/// `Box<[MaybeUninit<_>]>`, along with the `RowTracker` recording which of their cells are
+/// initialized.
+#[cfg(feature = "soa")]
+pub struct SoaColumns<C> {
+    columns: C,
+    rows: crate::RowTracker,
+}
+
+#[cfg(feature = "soa")]
+impl<C> SoaColumns<C> {
+    /// Each of the `count` columns must have `len` cells.
+    pub unsafe fn new(columns: C, len: usize, count: usize) -> Self {
+        Self {
+            columns,
+            rows: crate::RowTracker::new(len, count),
+        }
+    }
+
+    pub fn rows(&self) -> &crate::RowTracker {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &C {
+        &self.columns
+    }
+
+    /// The columns must keep their lengths, and the tracker must keep recording which of their
+    /// cells are initialized.
+    pub unsafe fn parts(&mut self) -> (&mut C, &mut crate::RowTracker) {
+        (&mut self.columns, &mut self.rows)
+    }
+}
+
+///```compile_fail,E0616
+/// use project_uninit::UninitSoA;
+/// #[derive(UninitSoA)]
+/// struct Particle { mass: f32 }
+/// let mut particles = Particle::soa(1);
+/// // replacing a column without updating the tracker needs `unsafe`
+/// particles.__columns.columns.0 = vec![core::mem::MaybeUninit::uninit()].into();
+///```
+#[cfg(feature = "soa")]
+fn _soa_columns_are_private_fails() {}
+
+/// Checks that `I` is an index into an array, at compile time.
+pub struct ArrayIndex<const I: usize>;
+
+impl<const I: usize> ArrayIndex<I> {
+    pub const fn check<T, const N: usize>(_: *const [T; N]) {
+        IndexBounds::<I, N>::IN_BOUNDS
+    }
+}
+
+struct IndexBounds<const I: usize, const N: usize>;
+
+impl<const I: usize, const N: usize> IndexBounds<I, N> {
+    const IN_BOUNDS: () = assert!(I < N, "array index in field path is out of bounds");
+}
+
+// Whether two lists of field paths without duplicates contain the same paths, in any order.
+pub const fn same_paths(a: &[&str], b: &[&str]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < b.len() {
+        let mut j = 0;
+        while j < a.len() && !str_eq(a[j], b[i]) {
+            j += 1;
+        }
+        if j == a.len() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Check that an array index in a field path is a plain decimal integer, so that indices that
+/// refer to the same element are always written the same way.
+pub const fn index_literal(literal: &str, index: usize) -> usize {
+    let bytes = literal.as_bytes();
+    let mut plain = !bytes.is_empty() && (bytes[0] != b'0' || bytes.len() == 1);
+    let mut i = 0;
+    while i < bytes.len() {
+        plain &= bytes[i].is_ascii_digit();
+        i += 1;
+    }
+    assert!(
+        plain,
+        "array indices in field paths must be plain decimal integers, like `[3]`"
+    );
+    index
+}
+
+// Join a field path into a string literal, e.g. `inner => value2 => 0` becomes "inner.value2.0"
+// and `buf => [3]` becomes "buf[3]". `*` segments are left out, so `cache => * => value` becomes
+// "cache.value"
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __path_str {
+    (@segment [$($index:tt)*]) => {
+        ::core::stringify!([$($index)*])
+    };
+    (@segment *) => {
+        ""
+    };
+    (@segment $field:tt) => {
+        ::core::concat!(".", ::core::stringify!($field))
+    };
+    ([$($index:tt)*] $(=> $rest:tt)*) => {
+        ::core::concat!(::core::stringify!([$($index)*]) $(, $crate::__path_str!(@segment $rest))*)
+    };
+    ($first:tt $(=> $rest:tt)*) => {
+        ::core::concat!(::core::stringify!($first) $(, $crate::__path_str!(@segment $rest))*)
+    };
+}
+
+// Build the place expression for a field path starting from `$base`, e.g. `(*ptr) inner => 0`
+// becomes `(*ptr).inner.0` and `(*ptr) buf => [3]` becomes `(*ptr).buf[3]`.
+// A `*` segment continues into the value of a `MaybeUninit` or `ManuallyDrop` field. From a
+// dereferenced pointer like `(*ptr)` this goes through a pointer cast; from a reference like
+// `(_x)`, which is only done in `if false` blocks and starts from `(*_x)`, it goes through a
+// function that never returns, so the checks there don't need `unsafe`. Writing `mut` before
+// the reference borrows each wrapper mutably on the way, for places that will be borrowed
+// mutably.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __field_place {
+    (@ $mode:tt [$($place:tt)*]) => {
+        $($place)*
+    };
+    (@ $mode:tt [$($place:tt)*] [$index:literal] $(=> $rest:tt)*) => {
+        $crate::__field_place!(@ $mode [$($place)* [{
+            const INDEX: usize = $crate::utils::index_literal(::core::stringify!($index), $index);
+            INDEX
+        }]] $($rest)=>*)
+    };
+    (@ $mode:tt [$($place:tt)*] [$($index:tt)*] $(=> $rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "array indices in field paths must be integer literals, found `",
+            ::core::stringify!($($index)*),
+            "`",
+        ))
+    };
+    (@ ptr [$($place:tt)*] * $(=> $rest:tt)*) => {
+        $crate::__field_place!(@ ptr [
+            (*$crate::utils::wrapped_ptr(::core::ptr::addr_of!($($place)*)))
+        ] $($rest)=>*)
+    };
+    (@ ref [$($place:tt)*] * $(=> $rest:tt)*) => {
+        $crate::__field_place!(@ ref [
+            (*$crate::utils::wrapped_check_ref(&$($place)*))
+        ] $($rest)=>*)
+    };
+    (@ mut [$($place:tt)*] * $(=> $rest:tt)*) => {
+        $crate::__field_place!(@ mut [
+            (*$crate::utils::wrapped_check(&mut $($place)*))
+        ] $($rest)=>*)
+    };
+    (@ $mode:tt [$($place:tt)*] $field:tt $(=> $rest:tt)*) => {
+        $crate::__field_place!(@ $mode [$($place)* . $field] $($rest)=>*)
+    };
+    ((* $ptr:tt) $($path:tt)=>+) => {
+        $crate::__field_place!(@ ptr [(*$ptr)] $($path)=>+)
+    };
+    (mut ($($base:tt)*) $($path:tt)=>+) => {
+        $crate::__field_place!(@ mut [(*$($base)*)] $($path)=>+)
+    };
+    (($($base:tt)*) $($path:tt)=>+) => {
+        $crate::__field_place!(@ ref [(*$($base)*)] $($path)=>+)
+    };
+}
+
+// Borrow the place for a field path starting from `$base` mutably, to check in an `if false`
+// block that it can be accessed. Paths with array indices only get a pointer, since the borrow
+// checker can't tell elements apart; those are checked by `__assert_unique!` instead. So do paths
+// through a `*` segment, since each one borrows its wrapper as a whole.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __field_mut {
+    (@ [$($path:tt)=>+] $base:tt [$($index:tt)*] $(=> $rest:tt)*) => {
+        ::core::ptr::addr_of_mut!($crate::__field_place!(mut $base $($path)=>+))
+    };
+    (@ [$($path:tt)=>+] $base:tt * $(=> $rest:tt)*) => {
+        ::core::ptr::addr_of_mut!($crate::__field_place!(mut $base $($path)=>+))
+    };
+    (@ $path:tt $base:tt $field:tt $(=> $rest:tt)*) => {
+        $crate::__field_mut!(@ $path $base $($rest)=>*)
+    };
+    (@ [$($path:tt)=>+] $base:tt) => {
+        &mut $crate::__field_place!(mut $base $($path)=>+)
+    };
+    ($base:tt $($path:tt)=>+) => {
+        $crate::__field_mut!(@ [$($path)=>+] $base $($path)=>+)
+    };
+}
+
+// Borrow the places for every field path in an invocation mutably at once, in an `if false`
+// block. With the `derive` feature this is replaced by a proc macro that also borrows the part of
+// each indexed or flattened path before its first index or `*`, so that fields of the same union
+// are rejected even when the paths index or continue into them. `$krate` is `$crate`, for the proc macro.
+#[cfg(not(feature = "derive"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_mut {
+    ($krate:tt, $base:tt $( [$($path:tt)=>+] )*) => {
+        ($($crate::__field_mut!($base $($path)=>+),)*)
+    };
+}
+
+// Assert that every value indexed in a field path starting from the reference `$base` is an array,
+// rather than a type that is indexed through `Index`, and that the index is in bounds. Only used
+// in `if false` blocks.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_arrays {
+    (@ [$($place:tt)*]) => {};
+    (@ [$($place:tt)*] [$($index:tt)*] $(=> $rest:tt)*) => {
+        $crate::utils::ArrayIndex::<$($index)*>::check(::core::ptr::addr_of!($($place)*));
+        $crate::__assert_arrays!(@ [$($place)* [$($index)*]] $($rest)=>*);
+    };
+    (@ [$($place:tt)*] * $(=> $rest:tt)*) => {
+        $crate::__assert_arrays!(
+            @ [(*$crate::utils::wrapped_check_ref(&$($place)*))] $($rest)=>*
+        );
+    };
+    (@ [$($place:tt)*] $field:tt $(=> $rest:tt)*) => {
+        $crate::__assert_arrays!(@ [$($place)* . $field] $($rest)=>*);
+    };
+    (($($base:tt)*) $($path:tt)=>+) => {
+        $crate::__assert_arrays!(@ [(*$($base)*)] $($path)=>+);
+    };
+}
+
+/// Implemented by `#[derive(ProjectUninit)]`, to check that `init_all!` and `box_emplace!`
+/// initialize every field of the struct, at type-check time.
+pub trait AllFields {
+    /// A struct with a field of a distinct marker type for each field of `Self`, by the same name.
+    type Fields;
+    /// A tuple with an [`Unset`] for each field.
+    type Unset;
+    /// A tuple with a [`Set`] for each field.
+    type Complete;
+}
+
+pub struct Unset;
+pub struct Set;
+
+/// Implemented by the marker type of a field, to mark it as [`Set`] in the tuple `S`.
+pub trait SetField<S> {
+    type Out;
+}
+
+/// Checks that `check` can mark every field of `T` as set. `check` is never called.
+pub fn check_all_fields<T: AllFields>(
+    _: &MaybeUninit<T>,
+    _check: impl FnOnce(T::Fields, T::Unset) -> T::Complete,
+) {
+}
+
+/// Mark the field of the marker `_field` as set. Only called in closures that are never called.
+pub fn set_field<S, F: SetField<S>>(_state: S, _field: F) -> F::Out {
+    unreachable!()
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,148 @@
+/// An enum whose variants can be initialized in place with [`init_variant!`](crate::init_variant), usually
+/// implemented with `#[derive(UninitEnum)]`.
+///
+/// The derive requires the enum to have an integer representation such as `#[repr(u8)]`, which
+/// guarantees that each variant is laid out like a `#[repr(C)]` struct starting with its
+/// discriminant, followed by its fields.
+///
+/// ## Safety
+/// `Variants` must be a struct with a public field named after each variant of the enum, of type
+/// `utils::VariantPtrs<P, D>`. `P` must be a struct with a public `*mut` pointer to each field of
+/// the variant, with the same name or index, and `tag` and `value` must be a pointer to the
+/// discriminant and the value that selects the variant.
+pub unsafe trait UninitEnum: Sized {
+    /// A struct with pointers to the fields of each variant.
+    type Variants;
+
+    /// Get pointers to the fields of every variant of the enum at `this`, without reading it.
+    ///
+    /// ## Safety
+    /// `this` must point to an allocation that is large enough for `Self`, but it doesn't need
+    /// to be initialized.
+    unsafe fn variant_ptrs(this: *mut Self) -> Self::Variants;
+}
+
+/// Initialize a variant of an enum wrapped in `MaybeUninit` in place, by writing each of its
+/// fields and then its discriminant, returning a mutable reference to the initialized enum.
+///
+/// The enum must implement [`UninitEnum`]. Every field of the variant must be given a value,
+/// which is checked at compile time. Tuple variants are initialized with the same syntax, using
+/// the indices of their fields: `init_variant!(slot => Shape::Pair { 0: a, 1: b })`.
+/// As with [`partial_init!`](crate::partial_init), a previous value in the slot is not dropped.
+///
+/// `#[derive(UninitEnum)]` also generates a function for each variant that takes its fields as
+/// arguments and calls this macro, named after the variant in snake case:
+/// `Shape::init_variant_pair(&mut slot, a, b)` initializes `slot` as `Shape::Pair(a, b)`.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{init_variant, UninitEnum};
+///
+/// #[derive(UninitEnum, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Message {
+///     Quit,
+///     Move { x: i32, y: i32 },
+///     Write(String),
+///     Block([u8; 4096]) = 10,
+/// }
+///
+/// let mut slot = MaybeUninit::<Message>::uninit();
+/// let message = init_variant!(slot => Message::Move { x: 3, y: -4 });
+/// assert_eq!(*message, Message::Move { x: 3, y: -4 });
+///
+/// let mut block = Box::new(MaybeUninit::<Message>::uninit());
+/// init_variant!(block => Message::Block { 0: [0; 4096] });
+/// let block = unsafe { block.assume_init() };
+/// assert!(matches!(*block, Message::Block(_)));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! init_variant {
+    ($slot:expr => $($path:ident)::+ $({$($field:tt : $val:expr),* $(,)?})?) => {
+        $crate::init_variant!(@variant ($slot) [$($path)::+] $($path)::+; {$($($field: $val),*)?})
+    };
+
+    // find the name of the variant at the end of the path
+    (@variant ($slot:expr) [$($path:ident)::+] $first:ident :: $($rest:ident)::+; $fields:tt) => {
+        $crate::init_variant!(@variant ($slot) [$($path)::+] $($rest)::+; $fields)
+    };
+
+    (@variant ($slot:expr) [$($path:ident)::+] $variant:ident; {$($field:tt : $val:expr),*}) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $slot.borrow_mut();
+        if false {
+            // this will never be executed
+            // it's only to assert that the path names a variant of the enum in the slot, and
+            // that every field of the variant is given a value
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
+            {
+                *_ref = ::core::mem::MaybeUninit::new($($path)::+ {
+                    $($field: ::core::unreachable!()),*
+                });
+            }
+        }
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        let ret;
+        #[allow(unused_unsafe)]
+        unsafe {
+            let variant = $crate::utils::variant_ptrs(ptr).$variant;
+            $(
+                ::core::ptr::write(variant.fields.$field, $val);
+                $crate::utils::init_hook(
+                    ptr,
+                    variant.fields.$field,
+                    ::core::concat!(::core::stringify!($variant), ".", ::core::stringify!($field)),
+                );
+            )*
+            ::core::ptr::write(variant.tag, variant.value);
+            $crate::utils::init_hook(ptr, variant.tag, ::core::stringify!($variant));
+            ret = $crate::utils::deref_ptr_with_lt(ptr, lt);
+        }
+        ret
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::{init_variant, UninitEnum};
+/// #[derive(UninitEnum)]
+/// #[repr(u8)]
+/// enum Foo { A { a: u32, b: u32 }, B }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// init_variant!(x => Foo::A { a: 1 });
+///```
+fn _init_variant_missing_field_fails() {}
+
+///```compile_fail
+/// use project_uninit::{init_variant, UninitEnum};
+/// #[derive(UninitEnum)]
+/// #[repr(u8)]
+/// enum Foo { A(u32), B }
+/// #[derive(UninitEnum)]
+/// #[repr(u8)]
+/// enum Bar { A(u32), B }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// init_variant!(x => Bar::A { 0: 1 });
+///```
+fn _init_variant_of_other_enum_fails() {}
+
+///```compile_fail
+/// use project_uninit::UninitEnum;
+/// #[derive(UninitEnum)]
+/// #[repr(u8)]
+/// #[allow(non_camel_case_types)]
+/// enum Foo { FooBar, Foo_Bar }
+///```
+fn _variant_constructor_collision_fails() {}
+
+///```compile_fail
+/// use project_uninit::UninitEnum;
+/// #[derive(UninitEnum)]
+/// enum Foo { A(u32), B }
+///```
+fn _uninit_enum_without_repr_fails() {}
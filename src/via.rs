@@ -0,0 +1,181 @@
+use core::convert::Infallible;
+
+/// The return value of a function that initializes a value through a pointer, such as a C
+/// function with an out-parameter, which tells [`init_via!`](crate::init_via) whether the value
+/// was initialized.
+///
+/// Integers are treated as status codes, where `0` is success and any other value is returned as
+/// the error, which matches most C APIs. For a function that returns something else, like a
+/// negative value on failure, map the result to a `bool` or a `Result<(), E>` in the closure
+/// passed to `init_via!`.
+pub trait ReturnCode {
+    /// The error returned by `init_via!` when the value wasn't initialized.
+    type Error;
+
+    /// `Ok(())` if the value was initialized.
+    fn check(self) -> Result<(), Self::Error>;
+}
+
+macro_rules! impl_return_code_int {
+    ($($ty:ty),*) => {$(
+        impl ReturnCode for $ty {
+            type Error = $ty;
+
+            fn check(self) -> Result<(), $ty> {
+                match self {
+                    0 => Ok(()),
+                    code => Err(code),
+                }
+            }
+        }
+    )*};
+}
+
+impl_return_code_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// `true` means the value was initialized.
+impl ReturnCode for bool {
+    type Error = ();
+
+    fn check(self) -> Result<(), ()> {
+        if self {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl<E> ReturnCode for Result<(), E> {
+    type Error = E;
+
+    fn check(self) -> Result<(), E> {
+        self
+    }
+}
+
+/// For functions that always initialize the value.
+impl ReturnCode for () {
+    type Error = Infallible;
+
+    fn check(self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn call_via<F, R: ReturnCode>(
+    ptr: *mut F,
+    call: impl FnOnce(*mut F) -> R,
+) -> Result<(), R::Error> {
+    call(ptr).check()
+}
+
+/// **Unsafe:** Initialize a field of a struct wrapped in `MaybeUninit` by passing a pointer to it
+/// to a function that writes through it, such as a C function with an out-parameter, and return
+/// a mutable reference to the field if the function succeeded.
+///
+/// The function is given as a closure taking a `*mut F` to the field, and returns a
+/// [`ReturnCode`] that is checked to decide whether the field was initialized:
+/// `init_via!(slot => field, |ptr| some_c_function(ptr))` returns `Ok(&mut field)` if
+/// `some_c_function` returned `0`, and `Err(code)` otherwise.
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut).
+///
+/// With `=> tracked field`, the struct is the slot of an [`InitTracker`](crate::InitTracker)
+/// instead, and the field is marked as initialized if the function succeeded. As with
+/// [`tracked_init!`](crate::tracked_init), a field that is already initialized is dropped before
+/// the function is called, and only direct fields of the struct can be initialized.
+///
+/// This must be used in an `unsafe` block or function, which also covers the call in the
+/// closure. Whenever the closure returns a successful code, the field must be initialized.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use std::os::raw::c_int;
+/// use project_uninit::{init_via, tracked_init, InitTracker, ProjectUninit};
+///
+/// #[repr(C)]
+/// struct Version { major: u16, minor: u16 }
+///
+/// // a C function like `int get_version(struct version *out)`
+/// unsafe extern "C" fn get_version(out: *mut Version) -> c_int {
+///     out.write(Version { major: 2, minor: 7 });
+///     0
+/// }
+///
+/// #[derive(ProjectUninit)]
+/// struct Library { name: String, version: Version }
+///
+/// let mut slot = MaybeUninit::<Library>::uninit();
+/// let mut tracker = InitTracker::new(&mut slot);
+/// tracked_init!(tracker => name = "zlib".into());
+/// let version = unsafe { init_via!(tracker => tracked version, |ptr| get_version(ptr)) };
+/// assert_eq!(version.unwrap().minor, 7);
+///
+/// let library = tracker.try_assume_init().unwrap();
+/// assert_eq!((library.version.major, library.version.minor), (2, 7));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! init_via {
+    // initialize a field of the slot of an `InitTracker`
+    ($tracker:expr => tracked $field:tt, $call:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let tracker: &mut $crate::InitTracker<'_, _> = $tracker.borrow_mut();
+        let index = $crate::utils::tracked_field_index(tracker, ::core::stringify!($field));
+        tracker.drop_field(index);
+        #[allow(unused_unsafe)]
+        let (ptr, prop_ptr) = unsafe {
+            let slot = tracker.slot_mut();
+            (slot.as_mut_ptr(), $crate::project_uninit_mut!(slot => $field).as_mut_ptr())
+        };
+        match $crate::utils::call_via(prop_ptr, $call) {
+            ::core::result::Result::Ok(()) => {
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($field));
+                tracker.set_init(index);
+                let lt = $crate::utils::bind_mut_lt(tracker);
+                ::core::result::Result::Ok($crate::utils::deref_ptr_with_lt(prop_ptr, lt))
+            }
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    }};
+
+    ($expr:expr => $($props:tt)=>+, $call:expr) => {{
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = _ref.as_mut_ptr();
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the field
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__field_mut!((_x) $($props)=>+);
+            $crate::__assert_arrays!((_x) $($props)=>+);
+        }
+        #[allow(unused_unsafe)]
+        let prop_ptr = unsafe {
+            ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+))
+        };
+        match $crate::utils::call_via(prop_ptr, $call) {
+            ::core::result::Result::Ok(()) => {
+                $crate::utils::init_hook(ptr, prop_ptr, $crate::__path_str!($($props)=>+));
+                ::core::result::Result::Ok($crate::utils::deref_ptr_with_lt(prop_ptr, lt))
+            }
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    }};
+}
+
+///```compile_fail
+/// use project_uninit::init_via;
+/// struct Foo { a: u32 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let a = init_via!(x => a, |ptr: *mut u32| -> i32 { 0 });
+///```
+fn _init_via_requires_unsafe() {}
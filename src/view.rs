@@ -0,0 +1,215 @@
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+
+/// A struct with typed views of its possibly uninitialized fields, generated by
+/// `#[derive(ProjectUninit)]`.
+///
+/// For a struct `Foo`, the derive generates `FooUninitRef<'a>` and `FooUninitMut<'a>` next to
+/// it, with the same fields as `Foo`, each wrapped in `&'a MaybeUninit<_>` or
+/// `&'a mut MaybeUninit<_>`. Each field of the views has the same visibility as the field of
+/// `Foo` it refers to. Views aren't generated for `#[repr(packed)]` structs, whose fields may
+/// not be aligned.
+///
+/// The views are usually created with [`uninit_fields`](UninitViewExt::uninit_fields) and
+/// [`uninit_fields_mut`](UninitViewExt::uninit_fields_mut), which project every field at once
+/// with ordinary field access, as an alternative to
+/// [`project_uninit_mut!`](crate::project_uninit_mut). It is also implemented for tuples of up
+/// to 12 elements, and for arrays, whose view is an array of `MaybeUninit` elements.
+///
+/// Field paths written after `strict` in [`project_uninit!`](crate::project_uninit),
+/// [`project_uninit_mut!`](crate::project_uninit_mut) and [`partial_init!`](crate::partial_init)
+/// must only go through fields of types that implement `UninitView`. Without it, a path like
+/// `boxed => value` compiles by going through the `DerefMut` impl of an uninitialized `Box`.
+/// Unions don't implement `UninitView`, so strict paths can't go through them; use
+/// [`project_union!`](crate::project_union) and [`partial_init_union!`](crate::partial_init_union)
+/// for fields of unions.
+///
+/// ## Example
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{ProjectUninit, UninitViewExt};
+///
+/// #[derive(ProjectUninit)]
+/// struct Point { x: f32, y: f32, label: &'static str }
+///
+/// let mut point = MaybeUninit::<Point>::uninit();
+/// let fields = point.uninit_fields_mut();
+/// fields.x.write(1.0);
+/// fields.y.write(2.0);
+/// fields.label.write("origin");
+///
+/// let point = unsafe { point.assume_init() };
+/// assert_eq!((point.x, point.y, point.label), (1.0, 2.0, "origin"));
+/// # }
+/// ```
+///
+/// ## Strict paths
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, ProjectUninit};
+///
+/// #[derive(ProjectUninit)]
+/// struct Inner { value: u32 }
+///
+/// #[derive(ProjectUninit)]
+/// struct Outer { inner: Inner, boxed: Box<Inner>, pair: (u8, u16) }
+///
+/// let mut outer = MaybeUninit::<Outer>::uninit();
+/// partial_init!(outer => strict { inner => value: 1, pair => 0: 2, pair => 1: 3 });
+/// // `partial_init!(outer => strict boxed => value = 4)` doesn't compile
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't have typed views of its fields",
+    note = "strict field paths can only go through tuples, arrays and structs that derive `ProjectUninit`, not unions"
+)]
+pub trait UninitView: Sized {
+    /// A struct with a `&MaybeUninit` reference to each field.
+    type Ref<'a>
+    where
+        Self: 'a;
+
+    /// A struct with a `&mut MaybeUninit` reference to each field.
+    type Mut<'a>
+    where
+        Self: 'a;
+
+    /// Get references to the fields of `slot`.
+    fn view(slot: &MaybeUninit<Self>) -> Self::Ref<'_>;
+
+    /// Get mutable references to the fields of `slot`.
+    fn view_mut(slot: &mut MaybeUninit<Self>) -> Self::Mut<'_>;
+}
+
+/// Methods on `MaybeUninit` for viewing the fields of a struct implementing [`UninitView`].
+pub trait UninitViewExt<T: UninitView> {
+    /// Get references to every field at once.
+    fn uninit_fields(&self) -> T::Ref<'_>;
+
+    /// Get mutable references to every field at once.
+    fn uninit_fields_mut(&mut self) -> T::Mut<'_>;
+}
+
+impl<T: UninitView> UninitViewExt<T> for MaybeUninit<T> {
+    fn uninit_fields(&self) -> T::Ref<'_> {
+        T::view(self)
+    }
+
+    fn uninit_fields_mut(&mut self) -> T::Mut<'_> {
+        T::view_mut(self)
+    }
+}
+
+macro_rules! tuple_view {
+    ($($ty:ident . $index:tt),+) => {
+        impl<$($ty),+> UninitView for ($($ty,)+) {
+            type Ref<'a> = ($(&'a MaybeUninit<$ty>,)+) where Self: 'a;
+            type Mut<'a> = ($(&'a mut MaybeUninit<$ty>,)+) where Self: 'a;
+
+            fn view(slot: &MaybeUninit<Self>) -> Self::Ref<'_> {
+                let ptr = slot.as_ptr() as *mut Self;
+                unsafe { ($(&*(addr_of_mut!((*ptr).$index) as *const MaybeUninit<$ty>),)+) }
+            }
+
+            fn view_mut(slot: &mut MaybeUninit<Self>) -> Self::Mut<'_> {
+                let ptr = slot.as_mut_ptr();
+                unsafe { ($(&mut *(addr_of_mut!((*ptr).$index) as *mut MaybeUninit<$ty>),)+) }
+            }
+        }
+    };
+}
+
+tuple_view!(A.0);
+tuple_view!(A.0, B.1);
+tuple_view!(A.0, B.1, C.2);
+tuple_view!(A.0, B.1, C.2, D.3);
+tuple_view!(A.0, B.1, C.2, D.3, E.4);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10);
+tuple_view!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11);
+
+impl<T, const N: usize> UninitView for [T; N] {
+    type Ref<'a> = &'a [MaybeUninit<T>; N] where Self: 'a;
+    type Mut<'a> = &'a mut [MaybeUninit<T>; N] where Self: 'a;
+
+    fn view(slot: &MaybeUninit<Self>) -> Self::Ref<'_> {
+        unsafe { &*(slot.as_ptr() as *const [MaybeUninit<T>; N]) }
+    }
+
+    fn view_mut(slot: &mut MaybeUninit<Self>) -> Self::Mut<'_> {
+        unsafe { &mut *(slot.as_mut_ptr() as *mut [MaybeUninit<T>; N]) }
+    }
+}
+
+// Assert that a field path only goes through fields of structs implementing `UninitView`, so
+// that none of its fields are reached through `Deref`. Only used in `if false` blocks.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __strict_path {
+    (@ ($field:expr) [$index:expr] $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ (&$crate::utils::strict_view($field)[$index]) $($rest)=>*)
+    };
+    (@ ($field:expr) * $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ ($crate::utils::strict_wrapped($field)) $($rest)=>*)
+    };
+    (@ ($field:expr) $next:tt $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ ($crate::utils::strict_view($field).$next) $($rest)=>*)
+    };
+    (@ ($field:expr)) => {
+        let _ = $field;
+    };
+    ($ptr:expr, [$index:expr] $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ (&$crate::utils::strict_fields($ptr)[$index]) $($rest)=>*)
+    };
+    ($ptr:expr, * $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ ($crate::utils::strict_field($ptr)) * $(=> $rest)*)
+    };
+    ($ptr:expr, $first:tt $(=> $rest:tt)*) => {
+        $crate::__strict_path!(@ ($crate::utils::strict_fields($ptr).$first) $($rest)=>*)
+    };
+}
+
+///```compile_fail
+/// use project_uninit::{ProjectUninit, UninitViewExt};
+/// #[derive(ProjectUninit)]
+/// struct Foo { a: u32 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let first = x.uninit_fields_mut();
+/// let second = x.uninit_fields_mut();
+/// first.a.write(1);
+/// second.a.write(2);
+///```
+fn _view_mut_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::{partial_init, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Inner { value: u32 }
+/// #[derive(ProjectUninit)]
+/// struct Outer { boxed: Box<Inner> }
+/// let mut x = core::mem::MaybeUninit::<Outer>::uninit();
+/// partial_init!(x => strict boxed => value = 1);
+///```
+fn _strict_path_through_deref_fails() {}
+
+///```compile_fail
+/// use project_uninit::{project_uninit, ProjectUninit};
+/// #[derive(ProjectUninit)]
+/// struct Inner { value: u32 }
+/// struct Wrapper(Inner);
+/// impl core::ops::Deref for Wrapper {
+///     type Target = Inner;
+///     fn deref(&self) -> &Inner { &self.0 }
+/// }
+/// #[derive(ProjectUninit)]
+/// struct Outer { wrapper: Wrapper }
+/// let x = core::mem::MaybeUninit::<Outer>::uninit();
+/// let value = project_uninit!(x => strict wrapper => value);
+///```
+fn _strict_project_through_deref_fails() {}
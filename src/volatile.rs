@@ -0,0 +1,131 @@
+/// **Unsafe:** Given a `*mut` pointer to a struct, write to one or more of its fields with
+/// [`write_volatile`](core::ptr::write_volatile).
+///
+/// This is meant for memory-mapped register blocks and DMA descriptors, where each field must be
+/// written individually and in order, without the compiler merging, reordering or removing the
+/// stores.
+/// Values are evaluated and written one at a time, in the order they are given.
+/// Each field may only be written once per invocation.
+///
+/// This must be used in an `unsafe` block or function.
+/// `ptr` must be valid for writes and properly aligned for every written field.
+/// Old values are overwritten without being dropped.
+///
+/// ## Example
+/// ```
+/// use project_uninit::{read_volatile_field, write_volatile_field};
+///
+/// #[repr(C)]
+/// struct Uart { data: u32, status: u32, control: [u32; 2] }
+///
+/// // stands in for a register block at a fixed address
+/// let mut regs = Uart { data: 0, status: 1, control: [0; 2] };
+/// let uart: *mut Uart = &mut regs;
+///
+/// unsafe {
+///     write_volatile_field!(uart => { control => [0]: 0x3, control => [1]: 115_200 });
+///     if read_volatile_field!(uart => status) & 1 != 0 {
+///         write_volatile_field!(uart => data = b'A' as u32);
+///     }
+/// }
+/// assert_eq!(regs.data, 65);
+/// assert_eq!(regs.control, [3, 115_200]);
+/// ```
+#[macro_export]
+macro_rules! write_volatile_field {
+    // write multiple fields
+    ($expr:expr => {$( $($props:tt)=>+ : $val:expr ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        let ptr: *mut _ = $expr;
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        $(
+            let value = $val;
+            ::core::ptr::write_volatile(
+                ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+)),
+                value,
+            );
+        )*
+    }};
+
+    // write a single field
+    ($expr:expr => $($props:tt)=>+ = $val:expr) => {
+        $crate::write_volatile_field!($expr => { $($props)=>+ : $val })
+    };
+}
+
+/// **Unsafe:** Given a `*const` pointer to a struct, read one or more of its fields with
+/// [`read_volatile`](core::ptr::read_volatile).
+///
+/// Fields are read in the order they are given, and returned as a single value or a tuple of
+/// values.
+/// Each field may only be read once per invocation.
+///
+/// This must be used in an `unsafe` block or function.
+/// `ptr` must be valid for reads and properly aligned for every read field, and the fields must
+/// be initialized.
+/// The fields are copied bitwise, so they should be `Copy`.
+///
+/// See [`write_volatile_field!`](crate::write_volatile_field) for an example.
+#[macro_export]
+macro_rules! read_volatile_field {
+    // read multiple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        let ptr: *const _ = $expr;
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &*ptr };
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($(
+            ::core::ptr::read_volatile(
+                ::core::ptr::addr_of!($crate::__field_place!((*ptr) $($props)=>+)),
+            ),
+        )*)
+    }};
+
+    // read a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::read_volatile_field!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::write_volatile_field;
+/// struct Regs { a: u32 }
+/// let mut regs = Regs { a: 0 };
+/// let ptr: *mut Regs = &mut regs;
+/// write_volatile_field!(ptr => a = 1);
+///```
+fn _write_volatile_requires_unsafe() {}
+
+///```compile_fail
+/// use project_uninit::write_volatile_field;
+/// struct Regs { a: u32, b: u32 }
+/// let mut regs = Regs { a: 0, b: 0 };
+/// let ptr: *mut Regs = &mut regs;
+/// unsafe { write_volatile_field!(ptr => { a: 1, b: 2, a: 3 }) };
+///```
+fn _write_volatile_same_field_twice_fails() {}
+
+///```compile_fail
+/// use project_uninit::write_volatile_field;
+/// struct Regs { buf: [u32; 4] }
+/// let mut regs = Regs { buf: [0; 4] };
+/// let ptr: *mut Regs = &mut regs;
+/// unsafe { write_volatile_field!(ptr => buf => [4] = 1) };
+///```
+fn _write_volatile_index_out_of_bounds_fails() {}
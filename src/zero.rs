@@ -0,0 +1,73 @@
+/// Set every byte of one or more fields of a struct wrapped in `MaybeUninit<_>` to zero, and
+/// obtain `&mut MaybeUninit<_>` references to them.
+///
+/// Each field is zeroed in place with [`ptr::write_bytes`](core::ptr::write_bytes), so large
+/// buffers are never built as values and then moved into place. The references are
+/// `MaybeUninit` because not every type is valid when all of its bytes are zero; for types where
+/// it is, such as integers and arrays of them, `assume_init_mut` can be called on them.
+/// Any value in a field is not dropped.
+///
+/// This accepts the same field paths as [`project_uninit_mut!`](crate::project_uninit_mut), and
+/// likewise statically ensures that multiple references to the same field are not returned.
+///
+/// ## Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use project_uninit::{partial_init, zero_fields};
+///
+/// struct Frame { id: u32, pixels: [u8; 4096], palette: [u32; 16] }
+///
+/// let mut frame = MaybeUninit::<Frame>::uninit();
+/// let (pixels, palette) = zero_fields!(frame => { pixels, palette });
+/// unsafe { palette.assume_init_mut()[1] = 0xffffff };
+/// let _ = pixels;
+/// partial_init!(frame => id = 1);
+///
+/// let frame = unsafe { frame.assume_init() };
+/// assert_eq!((frame.pixels[100], frame.palette[1]), (0, 0xffffff));
+/// ```
+#[macro_export]
+macro_rules! zero_fields {
+    // zero mutliple fields
+    ($expr:expr => {$( $($props:tt)=>+ ),* $(,)?}) => {{
+        // generate an error message if a field is used more than once
+        $crate::__assert_unique!($expr, [ $( [ $($props).+ ] )* ]);
+        #[allow(unused_imports)]
+        use ::core::borrow::BorrowMut;
+        let _ref: &mut ::core::mem::MaybeUninit<_> = $expr.borrow_mut();
+        let ptr = ::core::mem::MaybeUninit::as_mut_ptr(_ref);
+        let lt = $crate::utils::bind_mut_lt(_ref);
+
+        if false {
+            // this will never be executed
+            // it's only to assert that it is safe to access the fields
+            #[allow(unused_unsafe)]
+            let _x = unsafe { &mut *ptr };
+            let _y = $crate::__fields_mut!($crate, (_x) $( [$($props)=>+] )*);
+            $( $crate::__assert_arrays!((_x) $($props)=>+); )*
+        }
+        ($({
+            let ret;
+            #[allow(unused_unsafe)]
+            unsafe {
+                let prop_ptr = ::core::ptr::addr_of_mut!($crate::__field_place!((*ptr) $($props)=>+));
+                $crate::utils::zero_field(prop_ptr);
+                ret = $crate::utils::uninit_from_mut_ptr(prop_ptr, lt);
+            }
+            ret
+        },)*)
+    }};
+
+    // zero a single field
+    ($expr:expr => $($props:tt)=>+) => {
+        $crate::zero_fields!($expr => {$($props)=>+}).0
+    };
+}
+
+///```compile_fail
+/// use project_uninit::zero_fields;
+/// struct Foo { a: [u8; 4], b: u32 }
+/// let mut x = core::mem::MaybeUninit::<Foo>::uninit();
+/// let (a, a2) = zero_fields!(x => { a, a });
+///```
+fn _zero_same_field_twice_fails() {}
@@ -0,0 +1,72 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{field_path, partial_init, project_uninit, project_uninit_mut};
+
+#[derive(Debug, PartialEq)]
+struct Inner {
+    value1: u8,
+    value2: (i32, [u16; 2]),
+}
+
+#[derive(Debug, PartialEq)]
+struct Outer {
+    name: &'static str,
+    inner: Inner,
+}
+
+field_path!(
+    /// The pair inside `Outer`.
+    PAIR = inner => value2
+);
+field_path!(FIRST = inner => value2 => 1 => [0]);
+
+mod nested {
+    use super::*;
+
+    pub fn init_value1(x: &mut MaybeUninit<Outer>) {
+        field_path!(VALUE1 = inner => value1);
+        partial_init!(x => @VALUE1 = 9);
+    }
+}
+
+#[test]
+fn aliases_in_each_macro() {
+    let mut x = MaybeUninit::<Outer>::uninit();
+    let (name, n) = partial_init!(x => { name: "x", @PAIR => 0: 5 });
+    assert_eq!((*name, *n), ("x", 5));
+    partial_init!(x => @FIRST = 1);
+    let second = project_uninit_mut!(x => @PAIR => 1 => [1]);
+    second.write(2);
+    nested::init_value1(&mut x);
+
+    let (pair, first) = project_uninit!(x => { @PAIR, @FIRST });
+    assert_eq!(unsafe { pair.assume_init_ref() }, &(5, [1, 2]));
+    assert_eq!(unsafe { first.assume_init() }, 1);
+
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Outer {
+            name: "x",
+            inner: Inner {
+                value1: 9,
+                value2: (5, [1, 2])
+            }
+        }
+    );
+}
+
+#[test]
+fn let_bindings_through_aliases() {
+    let mut x = MaybeUninit::<Outer>::uninit();
+    partial_init!(x => {
+        @PAIR => 0: 4 => let n,
+        @FIRST: 1 => let first,
+        @PAIR => 1 => [1]: 2,
+    });
+    *n += 1;
+    *first += 1;
+    assert_eq!(
+        unsafe { project_uninit!(x => @PAIR).assume_init_ref() },
+        &(5, [2, 2])
+    );
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "alloc")]
+
+use std::rc::Rc;
+use std::sync::{Arc, Weak};
+
+use project_uninit::{
+    alloc_init, arc_emplace_cyclic, arc_uninit, boxed_uninit, partial_init, project_uninit_mut,
+    rc_uninit, UninitAlloc,
+};
+
+struct Big {
+    id: u32,
+    data: [u64; 1 << 12],
+    owner: Rc<()>,
+}
+
+#[test]
+fn box_is_a_target() {
+    let owner = Rc::new(());
+    let mut slot = boxed_uninit::<Big>();
+    partial_init!(slot => { id: 1, owner: owner.clone() });
+    let data = project_uninit_mut!(slot => data);
+    data.write([7; 1 << 12]);
+    let big: Box<Big> = unsafe { UninitAlloc::assume_init(slot) };
+    assert_eq!((big.id, big.data[1000]), (1, 7));
+    assert_eq!(Rc::strong_count(&owner), 2);
+    drop(big);
+    assert_eq!(Rc::strong_count(&owner), 1);
+}
+
+#[test]
+fn rc_and_arc_slots() {
+    let mut rc = rc_uninit::<(u8, String)>();
+    partial_init!(rc.slot_mut() => { 0: 1, 1: "rc".into() });
+    let rc = unsafe { UninitAlloc::assume_init(rc) };
+    assert_eq!(*rc, (1, "rc".to_string()));
+
+    let mut arc = arc_uninit::<(u8, String)>();
+    partial_init!(arc.slot_mut() => { 0: 2, 1: "arc".into() });
+    let arc = unsafe { UninitAlloc::assume_init(arc) };
+    assert_eq!(*arc, (2, "arc".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "shared with another Rc")]
+fn shared_rc_slot_panics() {
+    let mut rc = rc_uninit::<u32>();
+    let _other = rc.clone();
+    rc.slot_mut();
+}
+
+#[test]
+fn alloc_init_each_pointer() {
+    let owner = Rc::new(());
+    let big: Box<Big> =
+        alloc_init!(Box, Big => { id: 1, data: [1; 1 << 12], owner: owner.clone() });
+    let rc: Rc<Big> = alloc_init!(Rc, Big => { id: 2, data: [2; 1 << 12], owner: owner.clone() });
+    let arc: Arc<Big> = alloc_init!(std::sync::Arc, Big => {
+        id: 3,
+        data: [3; 1 << 12],
+        owner: owner.clone(),
+    });
+    assert_eq!((big.id, big.data[5], rc.id, rc.data[5]), (1, 1, 2, 2));
+    assert_eq!((arc.id, arc.data[5]), (3, 3));
+    assert_eq!(Rc::strong_count(&owner), 4);
+    drop((big, rc, arc));
+    assert_eq!(Rc::strong_count(&owner), 1);
+}
+
+#[test]
+fn zero_sized() {
+    let mut slot = boxed_uninit::<((), [u8; 0])>();
+    partial_init!(slot => { 0: (), 1: [] });
+    let value: Box<((), [u8; 0])> = unsafe { UninitAlloc::assume_init(slot) };
+    assert_eq!(*value, ((), []));
+}
+
+struct Ring {
+    id: u32,
+    next: Weak<Ring>,
+    prev: Weak<Ring>,
+}
+
+#[test]
+fn arc_cyclic_points_to_itself() {
+    let ring = arc_emplace_cyclic(|this: &Weak<Ring>, slot| {
+        assert!(this.upgrade().is_none());
+        partial_init!(slot => { id: 1, next: this.clone(), prev: this.clone() });
+        unsafe { slot.assume_init_mut() }
+    });
+    let next = ring.next.upgrade().unwrap();
+    assert!(Arc::ptr_eq(&next, &ring));
+    assert_eq!(
+        (next.id, Arc::strong_count(&ring), Arc::weak_count(&ring)),
+        (1, 2, 2)
+    );
+    drop(next);
+    let prev = ring.prev.clone();
+    drop(ring);
+    assert!(prev.upgrade().is_none());
+}
+
+// `arc_emplace_cyclic` relies on `Arc::new_cyclic` allocating before calling its closure, at the
+// address of the `Weak` pointer it gives it, and keeping what's written there
+#[test]
+fn arc_cyclic_slot_is_the_allocation() {
+    let mut addr = core::ptr::null();
+    let ring = arc_emplace_cyclic(|this: &Weak<Ring>, slot| {
+        assert_eq!(slot.as_ptr(), this.as_ptr());
+        addr = slot.as_ptr();
+        partial_init!(slot => { id: 7, next: Weak::new(), prev: this.clone() });
+        unsafe { slot.assume_init_mut() }
+    });
+    assert_eq!(Arc::as_ptr(&ring), addr);
+    assert!(Arc::ptr_eq(&ring.prev.upgrade().unwrap(), &ring));
+    assert_eq!(ring.id, 7);
+}
+
+#[test]
+fn arc_cyclic_panic_is_never_upgraded() {
+    let mut weak = Weak::new();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        arc_emplace_cyclic(|this: &Weak<Ring>, slot| {
+            weak = this.clone();
+            partial_init!(slot => next = this.clone());
+            panic!("before prev")
+        })
+    }));
+    assert!(result.is_err());
+    // the `next` field is leaked, along with its weak reference
+    assert_eq!((weak.strong_count(), weak.weak_count()), (0, 0));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+#[should_panic(expected = "initializer returned a reference to something other than its slot")]
+fn arc_cyclic_wrong_slot_panics() {
+    let other = Box::leak(Box::new(0u32));
+    arc_emplace_cyclic(move |_, _| other);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "arbitrary")]
+
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use project_uninit::arbitrary::{Arbitrary, Error, Result, Unstructured};
+use project_uninit::{arbitrary_in_place, ArbitraryInPlace, ProjectUninit};
+
+#[derive(ProjectUninit, ArbitraryInPlace, Debug, PartialEq)]
+struct Outer<T> {
+    a: u8,
+    #[uninit(in_place)]
+    inner: [Inner; 2],
+    b: T,
+}
+
+#[derive(ProjectUninit, ArbitraryInPlace, Debug, PartialEq)]
+struct Inner(u16, bool);
+
+#[test]
+fn generate_in_place() {
+    let mut slot = MaybeUninit::uninit();
+    let mut u = Unstructured::new(&[1, 2, 0, 1, 3, 4, 0, 5]);
+    let value: &mut Outer<u8> = arbitrary_in_place(&mut slot, &mut u).unwrap();
+    assert_eq!(
+        *value,
+        Outer {
+            a: 1,
+            inner: [Inner(2, true), Inner(0x0403, false)],
+            b: 5,
+        }
+    );
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl<'a> Arbitrary<'a> for Counted {
+    fn arbitrary(_: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Counted)
+    }
+}
+
+struct Fails;
+
+impl<'a> Arbitrary<'a> for Fails {
+    fn arbitrary(_: &mut Unstructured<'a>) -> Result<Self> {
+        Err(Error::IncorrectFormat)
+    }
+}
+
+#[derive(ProjectUninit, ArbitraryInPlace)]
+struct PartlyFails {
+    _a: Counted,
+    _b: Counted,
+    _c: Fails,
+}
+
+#[test]
+fn initialized_fields_dropped_on_error() {
+    let mut slot = MaybeUninit::<PartlyFails>::uninit();
+    let mut u = Unstructured::new(&[]);
+    assert!(arbitrary_in_place(&mut slot, &mut u).is_err());
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,62 @@
+use core::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+use project_uninit::{init_array, init_array_with, partial_init};
+
+#[test]
+fn init_array_in_box() {
+    let mut slot = Box::new(MaybeUninit::<[usize; 4096]>::uninit());
+    let array = init_array(&mut slot, |i| i * 2);
+    assert_eq!(array[4095], 8190);
+}
+
+#[test]
+fn panic_drops_initialized_elements() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<[Rc<()>; 8]>::uninit();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        init_array(&mut slot, |i| {
+            assert!(i < 5, "out of values");
+            rc.clone()
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn init_array_with_field() {
+    struct Pool {
+        slots: [Rc<usize>; 4],
+        next: usize,
+    }
+
+    let mut pool = Box::new(MaybeUninit::<Pool>::uninit());
+    let slots = init_array_with!(pool => slots, |i| Rc::new(i * 10));
+    assert_eq!(*slots[3], 30);
+    partial_init!(pool => next = 0);
+    let pool = unsafe { pool.assume_init() };
+    assert_eq!(pool.slots.iter().map(|s| **s).sum::<usize>(), 60);
+}
+
+#[test]
+fn init_array_with_panic_drops_prefix() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<([Rc<()>; 8], u8)>::uninit();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        init_array_with!(slot => 0, |i| {
+            assert!(i < 3, "out of values");
+            rc.clone()
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let mut whole = Box::new(MaybeUninit::<[Rc<()>; 8]>::uninit());
+    let array = init_array_with!(whole, |_| rc.clone());
+    assert_eq!(array.len(), 8);
+    assert_eq!(Rc::strong_count(&rc), 9);
+    unsafe { whole.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,64 @@
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit, project_uninit_mut};
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    hits: u32,
+}
+
+fn entry<K: Default, V>(value: V) -> Entry<K, V> {
+    let mut slot = MaybeUninit::uninit();
+    let hits = partial_init!(slot as MaybeUninit<Entry<K, V>> => hits = 0);
+    *hits += 1;
+    partial_init!(slot as MaybeUninit<Entry<K, V>> => { key: K::default(), value: value });
+    unsafe { slot.assume_init() }
+}
+
+#[test]
+fn generic_slot() {
+    let e: Entry<String, [u8; 2]> = entry([1, 2]);
+    assert_eq!((&*e.key, e.value, e.hits), ("", [1, 2], 1));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn closure_slot() {
+    let e = project_uninit::boxed_init_with(|slot| {
+        partial_init!(slot as MaybeUninit<Entry<u8, &str>> => {
+            key: 1,
+            value: "a",
+            hits: 2 => let hits,
+        });
+        *hits += 1;
+        unsafe { slot.assume_init_mut() }
+    });
+    assert_eq!((e.key, e.value, e.hits), (1, "a", 3));
+}
+
+struct Table<T> {
+    slots: [MaybeUninit<Entry<u16, T>>; 2],
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn parenthesized_slot() {
+    let mut table = Table::<char> {
+        slots: [MaybeUninit::uninit(), MaybeUninit::uninit()],
+        marker: PhantomData,
+    };
+    let (key, value) = project_uninit_mut!((table.slots[1]) as MaybeUninit<Entry<u16, char>> => {
+        key,
+        value,
+    });
+    key.write(7);
+    value.write('x');
+    partial_init!((table.slots[1]) as MaybeUninit<_> => hits = 0);
+
+    let key = project_uninit!((table.slots[1]) as MaybeUninit<Entry<u16, char>> => key);
+    assert_eq!(unsafe { key.assume_init() }, 7);
+    let entry = unsafe { table.slots[1].assume_init_ref() };
+    assert_eq!((entry.value, entry.hits), ('x', 0));
+}
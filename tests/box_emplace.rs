@@ -0,0 +1,122 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{box_emplace, partial_init, ProjectUninit};
+
+#[derive(ProjectUninit)]
+struct Config {
+    name: String,
+    table: [u32; 1 << 19],
+    flags: (bool, bool),
+    owner: Rc<()>,
+}
+
+#[test]
+fn huge_struct_without_stack_copy() {
+    let owner = Rc::new(());
+    let config: Box<Config> = box_emplace!(Config, |slot| {
+        let name = format!("cfg-{}", 7);
+        partial_init!(slot => { name: name, owner: owner.clone() });
+        for _ in 0..2 {
+            partial_init!(slot => flags => 0 = false);
+        }
+        partial_init!(slot => flags = (true, false));
+        project_uninit::init_array_with!(slot => table, |i| i as u32);
+    });
+    assert_eq!(config.name, "cfg-7");
+    assert_eq!(config.flags, (true, false));
+    assert_eq!(config.table[(1 << 19) - 1], (1 << 19) - 1);
+    assert_eq!(Rc::strong_count(&owner), 2);
+    drop(config);
+    assert_eq!(Rc::strong_count(&owner), 1);
+}
+
+#[derive(ProjectUninit, Debug, PartialEq)]
+struct Pair(u8, String);
+
+#[test]
+fn tuple_struct_and_tail_statement() {
+    let pair = box_emplace!(|slot: &mut MaybeUninit<Pair>| {
+        partial_init!(slot => 1 = "b".into());
+        let (first,) = partial_init!(slot => { 0: 1 });
+        *first += 1;
+        partial_init!(slot => 0 = 3)
+    });
+    assert_eq!(*pair, Pair(3, "b".into()));
+}
+
+#[test]
+#[should_panic(expected = "box_emplace! statement was given something other than the slot")]
+fn shadowed_slot_panics() {
+    let mut other = MaybeUninit::<Pair>::uninit();
+    let _ = box_emplace!(Pair, |slot| {
+        partial_init!(slot => 0 = 1);
+        let slot = &mut other;
+        partial_init!(slot => 1 = String::new());
+    });
+}
+
+fn parse_flags(s: &str) -> (bool, bool) {
+    match s {
+        "-v" => (true, false),
+        "-q" => (false, true),
+        _ => panic!("unknown flag {}", s),
+    }
+}
+
+#[test]
+fn panic_leaks_initialized_fields() {
+    let owner = Rc::new(());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = box_emplace!(Config, |slot| {
+            partial_init!(slot => { name: String::new(), owner: owner.clone() });
+            partial_init!(slot => flags = parse_flags("-x"));
+            init_array_with!(slot => table, |_| 0);
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&owner), 2);
+}
+
+#[derive(ProjectUninit, Default)]
+struct Options {
+    name: &'static str,
+    retries: u32,
+    verbose: bool,
+}
+
+#[test]
+fn uncounted_forms_still_run() {
+    let options = box_emplace!(Options, |slot| {
+        partial_init!(slot => { name: "a", ..Default::default() });
+        partial_init!(slot => {
+            retries: 3,
+            verbose: true,
+            name: |(retries, _)| => ["a", "b"][*retries as usize % 2],
+        });
+    });
+    assert_eq!(
+        (options.name, options.retries, options.verbose),
+        ("b", 3, true)
+    );
+}
+
+mod shadowed_macro {
+    use project_uninit::box_emplace;
+
+    // does nothing, so counting it as initializing a field would be unsound
+    #[allow(unused_macros)]
+    macro_rules! partial_init {
+        ($($args:tt)*) => {};
+    }
+
+    #[test]
+    fn counted_statements_use_this_crate() {
+        let pair = box_emplace!(super::Pair, |slot| {
+            partial_init!(slot => { 0: 1, 1: "a".into() });
+        });
+        assert_eq!(*pair, super::Pair(1, "a".into()));
+    }
+}
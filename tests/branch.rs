@@ -0,0 +1,59 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::partial_init_branch;
+
+#[derive(Debug, PartialEq)]
+struct Shape {
+    kind: &'static str,
+    size: (u32, u32),
+    area: u32,
+}
+
+fn build(n: u32) -> Shape {
+    let mut x = MaybeUninit::<Shape>::uninit();
+    let (kind, area, _) = partial_init_branch!(x => match (n) {
+        0 | 1 => { kind: "point", area: 0, size: (n, n) },
+        n if n % 2 == 0 => {
+            size: (n, n / 2),
+            kind: "rect",
+            area: |(size, _)| => size.0 * size.1,
+        },
+        _ => { size: (n, n), area: n * n, kind: "square" },
+    });
+    assert!(!kind.is_empty());
+    *area += 1;
+    unsafe { x.assume_init() }
+}
+
+#[test]
+fn match_branches() {
+    assert_eq!(
+        build(1),
+        Shape {
+            kind: "point",
+            size: (1, 1),
+            area: 1,
+        }
+    );
+    assert_eq!(build(4).area, 9);
+    assert_eq!(build(3).kind, "square");
+}
+
+#[test]
+fn if_branches() {
+    for i in 0..3 {
+        let mut x = MaybeUninit::<(u8, [u8; 2])>::uninit();
+        let (first, second) = partial_init_branch!(x => if (i == 0) {
+            0: 0,
+            1: [0; 2],
+        } else if (i == 1) {
+            1: [1; 2],
+            0: |(second,)| => second[0],
+        } else {
+            1: [2; 2],
+            0: 2,
+        });
+        assert_eq!(*first, i);
+        assert_eq!(second.len(), 2);
+    }
+}
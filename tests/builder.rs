@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+
+use std::rc::Rc;
+
+use project_uninit::{InitBuilder, ProjectUninit};
+
+#[derive(ProjectUninit, InitBuilder, Debug, PartialEq)]
+struct Config {
+    name: String,
+    retries: u32,
+    data: Rc<()>,
+}
+
+#[derive(ProjectUninit, InitBuilder)]
+struct Borrowed<'a, T: Clone, const N: usize> {
+    items: [T; N],
+    label: &'a str,
+}
+
+#[test]
+fn fields_can_be_set_in_any_order() {
+    let rc = Rc::new(());
+    let config = ConfigInit::new()
+        .set_retries(3)
+        .set_data(rc.clone())
+        .set_name("db".into())
+        .finish();
+    assert_eq!(config.name, "db");
+    assert_eq!(config.retries, 3);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(config);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn dropping_a_partial_builder_drops_set_fields() {
+    let rc = Rc::new(());
+    let builder = ConfigInit::default().set_data(rc.clone()).set_retries(1);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(builder);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn generic_builder() {
+    let label = String::from("pair");
+    let value = BorrowedInit::<'_, u8, 2>::new()
+        .set_label(&label)
+        .set_items([1, 2])
+        .finish();
+    assert_eq!((value.items, value.label), ([1, 2], "pair"));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "bumpalo")]
+
+use project_uninit::alloc_init_in;
+use project_uninit::bumpalo::Bump;
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Add,
+    Lit(i64),
+}
+
+#[derive(Debug)]
+struct Expr<'a> {
+    kind: Kind,
+    span: (u32, u32),
+    operands: &'a [&'a Expr<'a>],
+}
+
+fn lit<'a>(bump: &'a Bump, value: i64, at: u32) -> &'a Expr<'a> {
+    alloc_init_in!(bump, Expr => { kind: Kind::Lit(value), span: (at, at + 1), operands: &[] })
+}
+
+fn eval(expr: &Expr<'_>) -> i64 {
+    match expr.kind {
+        Kind::Lit(value) => value,
+        Kind::Add => expr.operands.iter().map(|e| eval(e)).sum(),
+    }
+}
+
+#[test]
+fn builds_tree_in_arena() {
+    let bump = Bump::new();
+    let operands = bump.alloc_slice_copy(&[lit(&bump, 2, 0), lit(&bump, 3, 4)]);
+    let sum = alloc_init_in!(&bump, Expr => {
+        span: (0, 5),
+        operands: operands,
+        kind: Kind::Add,
+    });
+    sum.span.1 = 6;
+    assert_eq!(eval(sum), 5);
+    assert_eq!(sum.span, (0, 6));
+    assert_eq!(sum.operands[1].kind, Kind::Lit(3));
+}
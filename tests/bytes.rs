@@ -0,0 +1,82 @@
+#![cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit, project_uninit_mut, ByteLenError};
+
+#[cfg(feature = "bytemuck")]
+mod pod {
+    use super::*;
+    use project_uninit::bytemuck::{Pod, Zeroable};
+    use project_uninit::{init_pod, pod_bytes};
+
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    #[repr(C)]
+    pub struct Record {
+        pub id: u32,
+        pub flags: [u8; 4],
+    }
+
+    pub struct Parsed {
+        pub record: Record,
+        pub name: String,
+    }
+
+    #[test]
+    fn init_fields_from_bytes() {
+        let input = [1, 0, 0, 0, 0xa, 0xb, 0xc, 0xd];
+        let mut parsed = MaybeUninit::<Parsed>::uninit();
+        let record = init_pod(project_uninit_mut!(parsed => record), &input).unwrap();
+        assert_eq!(record.id, u32::from_ne_bytes([1, 0, 0, 0]));
+        partial_init!(parsed => name = "first".into());
+
+        let flags = unsafe { pod_bytes(project_uninit!(parsed => record => flags)) };
+        assert_eq!(flags, [0xa, 0xb, 0xc, 0xd]);
+        let whole = unsafe { pod_bytes(project_uninit!(parsed => record)) };
+        assert_eq!(whole, input);
+        let parsed = unsafe { parsed.assume_init() };
+        assert_eq!(parsed.name, "first");
+    }
+
+    #[test]
+    fn wrong_length() {
+        let mut slot = MaybeUninit::<Record>::uninit();
+        assert_eq!(
+            init_pod(&mut slot, &[0; 7]).err(),
+            Some(ByteLenError { expected: 8, found: 7 })
+        );
+        // unaligned input is fine
+        let input = [0u8; 9];
+        assert!(init_pod(&mut slot, &input[1..]).is_ok());
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod from_bytes {
+    use super::*;
+    use project_uninit::zerocopy::{FromBytes, Immutable, IntoBytes};
+    use project_uninit::{init_from_bytes, value_bytes};
+
+    #[derive(FromBytes, IntoBytes, Immutable)]
+    #[repr(C)]
+    pub struct Record {
+        pub id: u32,
+        pub flags: [u8; 4],
+    }
+
+    #[test]
+    fn init_fields_from_bytes() {
+        let input = [2, 0, 0, 0, 1, 2, 3, 4];
+        let mut pair = MaybeUninit::<(Record, bool)>::uninit();
+        let record = init_from_bytes(project_uninit_mut!(pair => 0), &input).unwrap();
+        assert_eq!(record.flags, [1, 2, 3, 4]);
+        partial_init!(pair => 1 = true);
+
+        let bytes = unsafe { value_bytes(project_uninit!(pair => 0)) };
+        assert_eq!(bytes, input);
+        assert_eq!(
+            init_from_bytes(project_uninit_mut!(pair => 0 => id), &input).err(),
+            Some(ByteLenError { expected: 4, found: 8 })
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use core::mem::MaybeUninit;
+
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use project_uninit::{partial_init, AtomicInitCell, UninitCell};
+
+struct Config {
+    name: &'static str,
+    buffers: [[u8; 256]; 4],
+}
+
+static CONFIG: UninitCell<Config> = UninitCell::new();
+static COUNTERS: UninitCell<[u32; 8]> = UninitCell::new();
+
+#[test]
+fn take_static_once() {
+    assert!(!CONFIG.is_taken());
+    let slot: &'static mut MaybeUninit<Config> = CONFIG.take().unwrap();
+    assert!(CONFIG.take().is_none());
+    assert!(CONFIG.is_taken());
+
+    partial_init!(slot => { name: "boot", buffers: [[0; 256]; 4] });
+    let config = unsafe { slot.assume_init_mut() };
+    config.buffers[3][255] = 1;
+    assert_eq!(config.name, "boot");
+    assert_eq!(format!("{:?}", CONFIG), "UninitCell { taken: true, .. }");
+}
+
+#[test]
+fn init_with_claims_slot() {
+    let counters = COUNTERS.init_with(|slot| slot.write([1; 8])).unwrap();
+    counters[0] += 1;
+    assert_eq!(counters[..2], [2, 1]);
+    assert!(COUNTERS.init_with(|_| unreachable!()).is_none());
+}
+
+#[test]
+fn take_from_many_threads() {
+    let cell = UninitCell::<String>::default();
+    let claimed = std::thread::scope(|s| {
+        let threads: Vec<_> = (0..8)
+            .map(|_| s.spawn(|| cell.take().map(|slot| slot.write("mine".into())).is_some()))
+            .collect();
+        threads
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .filter(|&c| c)
+            .count()
+    });
+    assert_eq!(claimed, 1);
+}
+
+#[test]
+#[should_panic(expected = "initializer returned a reference to something other than its slot")]
+fn init_with_checks_slot() {
+    static OTHER: UninitCell<u8> = UninitCell::new();
+    let mut other = MaybeUninit::new(0);
+    OTHER.init_with(|_| unsafe { &mut *(other.as_mut_ptr()) });
+}
+
+#[test]
+fn publish_to_readers() {
+    static TABLE: AtomicInitCell<([u64; 64], usize)> = AtomicInitCell::new();
+    let seen = AtomicUsize::new(0);
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| loop {
+                if let Some((table, len)) = TABLE.get() {
+                    // every write made before publishing is visible
+                    assert!(table.iter().all(|&v| v == 7));
+                    seen.fetch_add(*len, Ordering::Relaxed);
+                    break;
+                }
+                std::hint::spin_loop();
+            });
+        }
+        s.spawn(|| {
+            let mut slot = TABLE.claim().unwrap();
+            assert!(TABLE.claim().is_none());
+            partial_init!(slot => { 0: [7; 64], 1: 1 });
+            let published = unsafe { slot.publish() };
+            assert_eq!(published.1, 1);
+        });
+    });
+    assert_eq!(seen.load(Ordering::Relaxed), 4);
+    assert!(TABLE.is_published());
+    assert!(TABLE.claim().is_none());
+}
+
+#[test]
+fn dropped_publisher_releases_claim() {
+    let cell = AtomicInitCell::<(u8, String)>::new();
+    {
+        let mut slot = cell.claim().unwrap();
+        partial_init!(slot => 0 = 1);
+        assert_eq!(format!("{:?}", cell), "AtomicInitCell(<unpublished>)");
+    }
+    assert!(cell.get().is_none());
+
+    let value = cell
+        .init_with(|slot| slot.write((2, "two".into())))
+        .unwrap();
+    assert_eq!(value.1, "two");
+    assert!(cell.init_with(|_| unreachable!()).is_none());
+    assert_eq!(format!("{:?}", cell), "AtomicInitCell((2, \"two\"))");
+    assert_eq!(cell.into_inner(), Some((2, "two".into())));
+}
+
+#[test]
+fn published_value_is_dropped_with_cell() {
+    let rc = Rc::new(());
+    let mut cell = AtomicInitCell::default();
+    cell.init_with(|slot| slot.write(rc.clone()));
+    *cell.get_mut().unwrap() = rc.clone();
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(cell);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let unpublished = AtomicInitCell::<Rc<()>>::new();
+    assert!(unpublished.into_inner().is_none());
+}
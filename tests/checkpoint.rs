@@ -0,0 +1,109 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{partial_init, CheckpointError, FieldBytes, InitTracker, ProjectUninit};
+
+#[derive(ProjectUninit)]
+#[repr(C)]
+struct Grid {
+    width: u32,
+    height: u32,
+    cells: [f32; 16],
+}
+
+fn save(tracker: &InitTracker<'_, Grid>) -> Vec<(String, usize, Vec<u8>)> {
+    let mut saved = Vec::new();
+    let result: Result<(), ()> = unsafe {
+        tracker.checkpoint(&mut |f: FieldBytes| {
+            saved.push((f.path.to_string(), f.offset, f.bytes.to_vec()));
+            Ok(())
+        })
+    };
+    result.unwrap();
+    saved
+}
+
+#[test]
+fn resume_from_checkpoint() {
+    let saved = {
+        let mut slot = MaybeUninit::<Grid>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+        unsafe {
+            partial_init!(tracker.slot_mut() => { width: 4, cells: [1.5; 16] });
+            tracker.set_init(0);
+            tracker.set_init(2);
+        }
+        save(&tracker)
+    };
+    assert_eq!(
+        saved
+            .iter()
+            .map(|(name, offset, bytes)| (name.as_str(), *offset, bytes.len()))
+            .collect::<Vec<_>>(),
+        [("width", 0, 4), ("cells", 8, 64)]
+    );
+
+    let mut slot = MaybeUninit::<Grid>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    for (name, _, bytes) in &saved {
+        unsafe { tracker.restore_field(name, bytes) }.unwrap();
+    }
+    assert_eq!(
+        tracker.missing().map(|f| f.name()).collect::<Vec<_>>(),
+        ["height"]
+    );
+    unsafe {
+        partial_init!(tracker.slot_mut() => height = 4);
+        tracker.set_init(1);
+    }
+    let grid = tracker.finish().unwrap();
+    assert_eq!((grid.width, grid.height, grid.cells[15]), (4, 4, 1.5));
+}
+
+#[test]
+fn restore_errors() {
+    let mut slot = MaybeUninit::<Grid>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    assert_eq!(
+        unsafe { tracker.restore_field("depth", &[0; 4]) },
+        Err(CheckpointError::UnknownField)
+    );
+    assert_eq!(
+        unsafe { tracker.restore_field("height", &[0; 8]) },
+        Err(CheckpointError::SizeMismatch {
+            index: 1,
+            expected: 4,
+            found: 8
+        })
+    );
+    assert!(!tracker.is_init(1));
+}
+
+#[derive(ProjectUninit)]
+struct Owned {
+    id: u32,
+    tag: Rc<()>,
+}
+
+#[test]
+fn restore_drops_previous_value() {
+    let tag = Rc::new(());
+    let mut slot = MaybeUninit::<Owned>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { id: 1, tag: tag.clone() });
+        tracker.set_init(0);
+        tracker.set_init(1);
+        let fresh = core::mem::ManuallyDrop::new(tag.clone());
+        let bytes = core::slice::from_raw_parts(
+            &*fresh as *const Rc<()> as *const u8,
+            core::mem::size_of::<Rc<()>>(),
+        );
+        tracker.restore_field("tag", bytes).unwrap();
+    }
+    assert_eq!(Rc::strong_count(&tag), 2);
+    drop(tracker);
+    assert_eq!(Rc::strong_count(&tag), 1);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+use project_uninit::{emplace_row, partial_init, ColumnStorage, ProjectUninit, RawColumn};
+
+#[derive(ProjectUninit)]
+struct Sprite {
+    texture: Rc<()>,
+    layer: u8,
+}
+
+// A column of 16-byte slots with a free list, like a simple archetype table
+struct Table {
+    slots: Vec<[u64; 2]>,
+    free: Vec<usize>,
+}
+
+unsafe impl ColumnStorage for Table {
+    fn slot_ptr(&mut self, row: usize) -> *mut u8 {
+        self.slots[row].as_mut_ptr() as *mut u8
+    }
+
+    fn abandon_row(&mut self, row: usize) {
+        self.free.push(row);
+    }
+}
+
+#[test]
+fn emplace_in_raw_column() {
+    let rc = Rc::new(());
+    let mut storage: [MaybeUninit<Sprite>; 3] = [
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+    ];
+    let stride = core::mem::size_of::<Sprite>();
+    let mut column = unsafe { RawColumn::new(storage.as_mut_ptr() as *mut u8, stride, 3) };
+    assert_eq!((column.len(), column.stride()), (3, stride));
+
+    let sprite = emplace_row::<Sprite, _>(&mut column, 2, |t| unsafe {
+        partial_init!(t.slot_mut() => { texture: rc.clone(), layer: 4 });
+        t.set_init(0);
+        t.set_init(1);
+    })
+    .unwrap();
+    assert_eq!(sprite.layer, 4);
+    unsafe { storage[2].assume_init_drop() };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn incomplete_row_is_abandoned() {
+    let rc = Rc::new(());
+    let mut table = Table {
+        slots: vec![[0; 2]; 2],
+        free: Vec::new(),
+    };
+    let result = emplace_row::<Sprite, _>(&mut table, 1, |t| unsafe {
+        partial_init!(t.slot_mut() => texture = rc.clone());
+        t.set_init(0);
+    });
+    assert!(result.is_none());
+    assert_eq!(Rc::strong_count(&rc), 1);
+    assert_eq!(table.free, [1]);
+}
+
+#[test]
+fn panicking_row_is_abandoned() {
+    let rc = Rc::new(());
+    let mut table = Table {
+        slots: vec![[0; 2]; 2],
+        free: Vec::new(),
+    };
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        emplace_row::<Sprite, _>(&mut table, 0, |t| unsafe {
+            partial_init!(t.slot_mut() => texture = rc.clone());
+            t.set_init(0);
+            panic!("failed to load layer");
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+    assert_eq!(table.free, [0]);
+}
@@ -0,0 +1,68 @@
+use core::mem::MaybeUninit;
+use project_uninit::{const_partial_init, const_project_uninit, const_project_uninit_mut};
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq)]
+struct Shape {
+    name: &'static str,
+    corners: [Point; 2],
+    closed: (bool, u8),
+}
+
+static SQUARE: Shape = {
+    let mut shape = MaybeUninit::<Shape>::uninit();
+    let (name, x0, _, _, _) = const_partial_init!(shape => {
+        name: "square",
+        corners => [0] => x: 0,
+        corners => [0] => y: 0,
+        corners => [1]: Point { x: 2, y: 2 },
+        closed => 0: true,
+    });
+    assert!(name.len() == 6 && *x0 == 0);
+    *const_partial_init!(shape => closed => 1 = 3) += 1;
+    unsafe { shape.assume_init() }
+};
+
+#[test]
+fn static_from_partial_init() {
+    assert_eq!(
+        SQUARE,
+        Shape {
+            name: "square",
+            corners: [Point { x: 0, y: 0 }, Point { x: 2, y: 2 }],
+            closed: (true, 4),
+        }
+    );
+}
+
+const fn init_point(slot: &mut MaybeUninit<Point>, x: i32) -> &mut Point {
+    let (px, py) = const_project_uninit_mut!(*slot => { x, y });
+    px.write(x);
+    py.write(-x);
+    unsafe { slot.assume_init_mut() }
+}
+
+const fn point_y(slot: &MaybeUninit<Point>) -> i32 {
+    unsafe { const_project_uninit!(*slot => y).assume_init() }
+}
+
+const NEG: i32 = {
+    let mut slot = MaybeUninit::uninit();
+    init_point(&mut slot, 5);
+    point_y(&slot)
+};
+
+#[test]
+fn const_fn_projection() {
+    assert_eq!(NEG, -5);
+
+    // the macros also work outside of const contexts
+    let mut slot = MaybeUninit::uninit();
+    assert_eq!(*init_point(&mut slot, 1), Point { x: 1, y: -1 });
+    assert_eq!(point_y(&slot), -1);
+}
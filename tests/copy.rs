@@ -0,0 +1,81 @@
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{clone_from_init, copy_from_init, move_init, partial_init};
+
+struct Config {
+    id: u32,
+    limits: (u16, [u8; 3]),
+    shared: Rc<str>,
+    name: String,
+}
+
+fn template() -> Config {
+    Config {
+        id: 1,
+        limits: (10, [1, 2, 3]),
+        shared: "shared".into(),
+        name: "template".into(),
+    }
+}
+
+#[test]
+fn copy_nested_fields() {
+    let template = template();
+    let mut x = MaybeUninit::<Config>::uninit();
+    let (id, max) = copy_from_init!(x => { id, limits => 1 => [2] } from &template);
+    *id += 1;
+    assert_eq!(*max, 3);
+    partial_init!(x => {
+        limits => 0: 20,
+        limits => 1 => [0]: 0,
+        limits => 1 => [1]: 0,
+        shared: template.shared.clone(),
+        name: "copy".into(),
+    });
+
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.id, x.limits), (2, (20, [0, 0, 3])));
+    assert_eq!(template.id, 1);
+}
+
+#[test]
+fn clone_fields() {
+    let template = template();
+    let mut x = MaybeUninit::<Config>::uninit();
+    clone_from_init!(x => { shared, name, limits } from template);
+    copy_from_init!(x => id from template);
+    assert_eq!(Rc::strong_count(&template.shared), 2);
+
+    let x = unsafe { x.assume_init() };
+    assert_eq!(x.name, "template");
+    assert_eq!(x.limits, (10, [1, 2, 3]));
+    assert!(Rc::ptr_eq(&x.shared, &template.shared));
+}
+
+#[test]
+fn move_fields() {
+    let shared: Rc<str> = "shared".into();
+    let mut src = Box::new(MaybeUninit::<Config>::uninit());
+    partial_init!(src => {
+        id: 3,
+        limits: (1, [4, 5, 6]),
+        shared: shared.clone(),
+        name: "moved".into(),
+    });
+
+    let mut x = MaybeUninit::<Config>::uninit();
+    let (name, last) = unsafe { move_init!(x => { name, limits => 1 => [2] } from src) };
+    name.push('!');
+    assert_eq!(*last, 6);
+    unsafe { move_init!(x => { id, shared, limits => 0, limits => 1 => [0] } from &mut *src) };
+    unsafe { move_init!(x => limits => 1 => [1] from src) };
+    assert_eq!(Rc::strong_count(&shared), 2);
+    drop(src);
+
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.id, x.limits), (3, (1, [4, 5, 6])));
+    assert_eq!(x.name, "moved!");
+    drop(x);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "coverage", feature = "derive"))]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{coverage, partial_init, InitTracker, ProjectUninit};
+
+#[derive(ProjectUninit)]
+struct Foo {
+    a: u8,
+    b: u8,
+    c: u8,
+}
+
+fn init(fields: &[usize]) -> bool {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    for &i in fields {
+        unsafe {
+            match i {
+                0 => drop(partial_init!(tracker.slot_mut() => a = 0)),
+                1 => drop(partial_init!(tracker.slot_mut() => b = 1)),
+                _ => drop(partial_init!(tracker.slot_mut() => c = 2)),
+            }
+            tracker.set_init(i);
+        }
+    }
+    let complete = tracker.finish().is_ok();
+    complete
+}
+
+#[test]
+fn report_counts_fields_and_outcomes() {
+    assert!(!init(&[0]));
+    assert!(init(&[2, 0, 1]));
+    assert!(!init(&[0, 2]));
+
+    let report = coverage::report();
+    let foo = report.get(core::any::type_name::<Foo>()).unwrap();
+    assert_eq!(foo.completed, 1);
+    assert_eq!(foo.dropped_incomplete, 2);
+    let counts: Vec<_> = foo.fields.iter().map(|f| (f.name, f.init_count)).collect();
+    assert_eq!(counts, [("a", 3), ("b", 1), ("c", 2)]);
+    assert_eq!(foo.never_initialized().count(), 0);
+    assert_eq!(foo.overwritten().count(), 0);
+
+    let text = report.to_string();
+    assert!(text.contains("Foo: 1 completed, 2 dropped incomplete"));
+    assert!(text.contains("    a: initialized 3 times"));
+}
+
+#[derive(ProjectUninit)]
+struct Bar {
+    x: u32,
+    y: u32,
+}
+
+#[test]
+fn overwrites_without_drop_are_counted() {
+    let mut slot = MaybeUninit::<Bar>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { x: 1, y: 2 });
+        tracker.set_init(0);
+        tracker.set_init(1);
+        partial_init!(tracker.slot_mut() => x = 3);
+        tracker.set_init(0);
+        tracker.drop_field(1);
+        partial_init!(tracker.slot_mut() => y = 4);
+        tracker.set_init(1);
+    }
+    drop(tracker);
+
+    let report = coverage::report();
+    let bar = report.get(core::any::type_name::<Bar>()).unwrap();
+    let counts: Vec<_> = bar
+        .fields
+        .iter()
+        .map(|f| (f.init_count, f.overwrite_count))
+        .collect();
+    assert_eq!(counts, [(2, 1), (2, 0)]);
+    // every field was initialized when the tracker was dropped
+    assert_eq!((bar.completed, bar.dropped_incomplete), (0, 0));
+    assert_eq!(bar.overwritten().collect::<Vec<_>>(), ["x"]);
+    assert!(report
+        .to_string()
+        .contains("    x: initialized 2 times (1 overwrites)"));
+}
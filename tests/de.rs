@@ -0,0 +1,120 @@
+#![cfg(feature = "serde")]
+
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use project_uninit::serde::{Deserialize, Deserializer};
+use project_uninit::{deserialize_uninit, DeserializeUninit, ProjectUninit};
+
+#[derive(ProjectUninit, DeserializeUninit, Debug, PartialEq)]
+struct Outer<T> {
+    a: u8,
+    #[uninit(in_place)]
+    inner: Inner,
+    r#type: T,
+}
+
+#[derive(ProjectUninit, DeserializeUninit, Debug, PartialEq)]
+struct Inner(u16, #[uninit(in_place)] [bool; 3]);
+
+fn from_json<'de, T: DeserializeUninit<'de>>(
+    slot: &mut MaybeUninit<T>,
+    json: &'de str,
+) -> serde_json::Result<()> {
+    let mut de = serde_json::Deserializer::from_str(json);
+    deserialize_uninit(slot, &mut de)?;
+    de.end()
+}
+
+#[test]
+fn deserialize_map() {
+    let mut slot = MaybeUninit::<Outer<String>>::uninit();
+    from_json(
+        &mut slot,
+        r#"{ "type": "x", "unknown": [1, 2], "inner": [5, [true, false, true]], "a": 1 }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        unsafe { slot.assume_init() },
+        Outer {
+            a: 1,
+            inner: Inner(5, [true, false, true]),
+            r#type: String::from("x"),
+        }
+    );
+}
+
+#[test]
+fn deserialize_seq() {
+    let mut slot = MaybeUninit::<Outer<u32>>::uninit();
+    from_json(&mut slot, "[1, [2, [false, false, true]], 3]").unwrap();
+    let value = unsafe { slot.assume_init() };
+    assert_eq!(value.inner, Inner(2, [false, false, true]));
+    assert_eq!(value.r#type, 3);
+}
+
+#[test]
+fn large_array() {
+    #[derive(ProjectUninit, DeserializeUninit)]
+    struct Table {
+        #[uninit(in_place)]
+        entries: [u16; 100],
+    }
+
+    let json = format!("{{ \"entries\": {:?} }}", [9; 100]);
+    let mut slot = MaybeUninit::<Table>::uninit();
+    from_json(&mut slot, &json).unwrap();
+    assert_eq!(unsafe { slot.assume_init() }.entries, [9; 100]);
+}
+
+#[test]
+fn errors() {
+    let mut slot = MaybeUninit::<Outer<u8>>::uninit();
+    let err = from_json(&mut slot, r#"{ "a": 1, "inner": [1, [true, true, true]] }"#);
+    assert!(err
+        .unwrap_err()
+        .to_string()
+        .contains("missing field `type`"));
+
+    let err = from_json(&mut slot, r#"{ "a": 1, "a": 2 }"#);
+    assert!(err.unwrap_err().to_string().contains("duplicate field `a`"));
+
+    let err = from_json(&mut slot, "[1, [2, [true]], 3]");
+    assert!(err.unwrap_err().to_string().contains("invalid length 1"));
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl<'de> Deserialize<'de> for Counted {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <()>::deserialize(deserializer)?;
+        Ok(Counted)
+    }
+}
+
+#[test]
+fn drop_on_error() {
+    #[derive(ProjectUninit, DeserializeUninit)]
+    struct Foo {
+        a: Counted,
+        #[uninit(in_place)]
+        b: [Counted; 3],
+        c: u8,
+    }
+
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    assert!(from_json(&mut slot, r#"{ "a": null, "b": [null, null, 1] }"#).is_err());
+    assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+
+    DROPS.store(0, Ordering::Relaxed);
+    assert!(from_json(&mut slot, r#"{ "b": [null, null, null], "c": -1 }"#).is_err());
+    assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,56 @@
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use project_uninit::{project_uninit_dst, UninitDst};
+
+struct Header {
+    id: u32,
+    flags: (u8, u8),
+}
+
+struct Record {
+    header: Header,
+    values: [u64],
+}
+
+struct Label {
+    len: usize,
+    text: str,
+}
+
+#[test]
+fn project_prefix_and_slice_tail() {
+    let mut buf = [MaybeUninit::<u64>::uninit(); 5];
+    let ptr = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr() as *mut u64, 3) as *mut Record;
+    let mut slot = unsafe { UninitDst::from_raw(ptr) };
+
+    let (id, flags0, values) =
+        project_uninit_dst!(slot => { header => id, header => flags => 0, values });
+    id.write(7);
+    flags0.write(1);
+    assert_eq!(values.len(), 3);
+    for (i, value) in values.iter_mut().enumerate() {
+        value.write(i as u64 * 10);
+    }
+    project_uninit_dst!(slot => header => flags => 1).write(2);
+
+    let record = unsafe { slot.assume_init() };
+    assert_eq!(record.header.id, 7);
+    assert_eq!(record.header.flags, (1, 2));
+    assert_eq!(&record.values, &[0, 10, 20]);
+}
+
+#[test]
+fn project_str_tail() {
+    let mut buf = [MaybeUninit::<usize>::uninit(); 3];
+    let ptr = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, 5) as *mut Label;
+    let mut slot = unsafe { UninitDst::from_raw(ptr) };
+
+    let (len, text) = project_uninit_dst!(slot => { len, text });
+    len.write(text.len());
+    for (byte, &b) in text.iter_mut().zip(b"hello") {
+        byte.write(b);
+    }
+    let label = unsafe { slot.assume_init() };
+    assert_eq!((label.len, &label.text), (5, "hello"));
+}
@@ -0,0 +1,107 @@
+use core::alloc::Layout;
+use core::cell::{Cell, RefCell};
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use project_uninit::{partial_init, Emplace, FnArena};
+
+struct Node {
+    id: u32,
+    name: String,
+    weights: [f32; 16],
+}
+
+fn node<A: Emplace>(arena: &A, id: u32) -> A::Handle<'_, Node> {
+    arena.emplace_with(|slot: &mut MaybeUninit<Node>| {
+        partial_init!(slot => { id: id, name: format!("n{}", id), weights: [0.5; 16] });
+        unsafe { slot.assume_init_mut() }
+    })
+}
+
+/// Owns the chunks it hands out, like a typed arena.
+#[derive(Default)]
+struct Chunks {
+    chunks: RefCell<Vec<Box<[MaybeUninit<u64>]>>>,
+}
+
+impl Chunks {
+    fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if layout.align() > 8 {
+            return None;
+        }
+        let words = layout.size().div_ceil(8);
+        let mut chunk = vec![MaybeUninit::uninit(); words.max(1)].into_boxed_slice();
+        let ptr = NonNull::new(chunk.as_mut_ptr() as *mut u8);
+        self.chunks.borrow_mut().push(chunk);
+        ptr
+    }
+}
+
+#[test]
+fn emplace_in_adapter() {
+    let chunks = Chunks::default();
+    let arena = unsafe { FnArena::new(|layout| chunks.alloc(layout)) };
+    let a = node(&arena, 1);
+    let b = node(&arena, 2);
+    a.weights[0] = 2.0;
+    assert_eq!((a.id, &*a.name, a.weights[0]), (1, "n1", 2.0));
+    assert_eq!((b.id, &*b.name, b.weights[0]), (2, "n2", 0.5));
+    assert_eq!(chunks.chunks.borrow().len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "arena failed to allocate 0 bytes aligned to 16")]
+fn adapter_allocation_failure_panics() {
+    #[repr(align(16))]
+    struct Wide;
+
+    let chunks = Chunks::default();
+    let arena = unsafe { FnArena::new(|layout| chunks.alloc(layout)) };
+    arena.emplace_with(|slot: &mut MaybeUninit<Wide>| slot.write(Wide));
+}
+
+#[test]
+#[should_panic(expected = "initializer returned a reference to something other than its slot")]
+fn wrong_slot_panics() {
+    let chunks = Chunks::default();
+    let arena = unsafe { FnArena::new(|layout| chunks.alloc(layout)) };
+    let other = Box::leak(Box::new(0u32));
+    arena.emplace_with(move |_: &mut MaybeUninit<u32>| other);
+}
+
+#[test]
+fn adapter_calls_allocator_once_per_value() {
+    let calls = Cell::new(0);
+    let chunks = Chunks::default();
+    let arena = unsafe {
+        FnArena::new(|layout| {
+            calls.set(calls.get() + 1);
+            chunks.alloc(layout)
+        })
+    };
+    let x = arena.emplace_with(|slot| slot.write(7u8));
+    let y = arena.emplace_with(|slot| slot.write([1u16; 3]));
+    assert_eq!((*x, *y), (7, [1; 3]));
+    assert_eq!(calls.get(), 2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn emplace_in_heap() {
+    let node: Box<Node> = node(&project_uninit::Heap, 3);
+    assert_eq!((node.id, &*node.name), (3, "n3"));
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn emplace_in_bump() {
+    let bump = project_uninit::bumpalo::Bump::new();
+    let a = node(&bump, 4);
+    let b = node(&bump, 5);
+    assert_eq!((a.id, &*a.name, b.id, &*b.name), (4, "n4", 5, "n5"));
+    // the arena doesn't drop its values
+    unsafe {
+        core::ptr::drop_in_place(a);
+        core::ptr::drop_in_place(b);
+    }
+}
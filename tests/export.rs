@@ -0,0 +1,100 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit, project_uninit_mut};
+
+mod geometry {
+    use core::mem::MaybeUninit;
+
+    use project_uninit::ExportFields;
+
+    #[derive(ExportFields, Debug, PartialEq)]
+    pub struct Point {
+        pub x: f32,
+        #[uninit(export)]
+        y: f32,
+    }
+
+    #[derive(ExportFields)]
+    pub struct Segment {
+        #[uninit(export)]
+        start: Point,
+        #[uninit(export)]
+        end: Point,
+        length: f32,
+    }
+
+    #[derive(ExportFields)]
+    pub struct Pair<T>(pub T, #[uninit(export)] T, u8);
+
+    impl Point {
+        pub fn y(&self) -> f32 {
+            self.y
+        }
+    }
+
+    impl Segment {
+        pub fn finish(slot: &mut MaybeUninit<Segment>) -> &Segment {
+            unsafe {
+                let s = slot.as_mut_ptr();
+                let (start, end) = (&(*s).start, &(*s).end);
+                let length = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+                (*s).length = length;
+                slot.assume_init_ref()
+            }
+        }
+
+        pub fn length(&self) -> f32 {
+            self.length
+        }
+    }
+
+    impl<T: Copy> Pair<T> {
+        pub fn finish(slot: &mut MaybeUninit<Pair<T>>) -> &Pair<T> {
+            unsafe {
+                (*slot.as_mut_ptr()).2 = 0;
+                slot.assume_init_ref()
+            }
+        }
+
+        pub fn second(&self) -> T {
+            self.1
+        }
+    }
+}
+
+use geometry::{Pair, Point, Segment};
+
+#[test]
+fn initialize_exported_fields() {
+    let mut p = MaybeUninit::<Point>::uninit();
+    let (x, y) = partial_init!(p => pub { x: 1.0, y: 2.0 });
+    *y += 1.0;
+    assert_eq!(*x, 1.0);
+    let y = project_uninit!(p => pub y);
+    assert_eq!(unsafe { y.assume_init() }, 3.0);
+    let p = unsafe { p.assume_init() };
+    assert_eq!((p.x, p.y()), (1.0, 3.0));
+}
+
+#[test]
+fn nested_exported_fields() {
+    let mut s = MaybeUninit::<Segment>::uninit();
+    partial_init!(s => pub {
+        start => x: 0.0,
+        start => y: 0.0,
+        end => x: 3.0,
+    });
+    let end_y = project_uninit_mut!(s => pub end => y);
+    end_y.write(4.0);
+    assert_eq!(Segment::finish(&mut s).length(), 5.0);
+}
+
+#[test]
+fn tuple_struct_fields() {
+    let mut pair = MaybeUninit::<Pair<u64>>::uninit();
+    partial_init!(pair => pub { 0: 1, 1: 2 });
+    let pair = Pair::finish(&mut pair);
+    assert_eq!((pair.0, pair.second()), (1, 2));
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "ffi")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{FfiError, FfiFields, FfiInit};
+
+// What an older plugin was built with
+#[derive(FfiFields)]
+#[repr(C)]
+struct PluginInfoV1 {
+    api: u16,
+    flags: u64,
+}
+
+// What the host is built with
+#[derive(FfiFields)]
+#[repr(C)]
+struct PluginInfoV2 {
+    api: u16,
+    flags: u64,
+    #[uninit(since = 2)]
+    priority: i32,
+}
+
+fn host_init(init: &mut FfiInit<'_>) -> Result<(), FfiError> {
+    init.write("api", 3u16)?;
+    init.write("flags", 0b101u64)?;
+    init.write("priority", -1i32)?;
+    Ok(())
+}
+
+#[test]
+fn versions_and_offsets() {
+    let v1 = &PluginInfoV1::LAYOUT;
+    let v2 = &PluginInfoV2::LAYOUT;
+    assert_eq!((v1.version(), v2.version()), (0, 2));
+    assert_eq!(v2.size(), core::mem::size_of::<PluginInfoV2>());
+    let fields: Vec<_> = v2
+        .fields()
+        .iter()
+        .map(|f| (f.name(), f.offset(), f.size(), f.since()))
+        .collect();
+    assert_eq!(fields, [("api", 0, 2, 0), ("flags", 8, 8, 0), ("priority", 16, 4, 2)]);
+}
+
+#[test]
+fn newer_host_initializes_older_plugin() {
+    let mut info = MaybeUninit::<PluginInfoV1>::uninit();
+    let mut init = unsafe { FfiInit::new(info.as_mut_ptr() as *mut u8, &PluginInfoV1::LAYOUT) };
+    host_init(&mut init).unwrap();
+    assert_eq!(unsafe { init.read::<u64>("flags") }, Some(0b101));
+    assert_eq!(unsafe { init.read::<i32>("priority") }, None);
+    init.finish().unwrap();
+    let info = unsafe { info.assume_init() };
+    assert_eq!((info.api, info.flags), (3, 0b101));
+}
+
+#[test]
+fn missing_and_mismatched_fields() {
+    let mut info = MaybeUninit::<PluginInfoV2>::uninit();
+    let mut init = unsafe { FfiInit::new(info.as_mut_ptr() as *mut u8, &PluginInfoV2::LAYOUT) };
+    assert_eq!(
+        init.write("api", 3u32),
+        Err(FfiError::SizeMismatch {
+            index: 0,
+            expected: 2,
+            found: 4
+        })
+    );
+    init.write("api", 3u16).unwrap();
+    assert_eq!(
+        init.missing().map(|f| f.name()).collect::<Vec<_>>(),
+        ["flags", "priority"]
+    );
+    assert_eq!(init.finish(), Err(FfiError::Missing { index: 1 }));
+}
+
+#[derive(FfiFields)]
+#[repr(C, align(16))]
+struct Aligned {
+    tag: u8,
+    value: u32,
+}
+
+#[derive(FfiFields)]
+#[repr(C, packed(2))]
+struct Packed {
+    tag: u8,
+    value: u32,
+}
+
+#[test]
+fn align_and_packed_reprs() {
+    let aligned = &Aligned::LAYOUT;
+    assert_eq!((aligned.size(), aligned.align()), (16, 16));
+    let packed = &Packed::LAYOUT;
+    assert_eq!((packed.size(), packed.align()), (6, 2));
+    assert_eq!(packed.field("value").unwrap().1.offset(), 2);
+
+    let mut value = MaybeUninit::<Packed>::uninit();
+    let mut init = unsafe { FfiInit::new(value.as_mut_ptr() as *mut u8, packed) };
+    init.write("tag", 1u8).unwrap();
+    init.write("value", 0xdead_beefu32).unwrap();
+    init.finish().unwrap();
+    let value = unsafe { value.assume_init() };
+    assert_eq!({ value.value }, 0xdead_beef);
+}
+
+#[test]
+fn compatible_layouts() {
+    PluginInfoV2::LAYOUT
+        .check_compatible(&PluginInfoV1::LAYOUT)
+        .unwrap();
+    assert_eq!(
+        Aligned::LAYOUT.check_compatible(&Packed::LAYOUT),
+        Err(FfiError::OffsetMismatch {
+            index: 1,
+            expected: 4,
+            found: 2
+        })
+    );
+}
@@ -0,0 +1,49 @@
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use project_uninit::{field_addrs, partial_init};
+
+struct Child {
+    parent: NonNull<Tree>,
+    value: u32,
+}
+
+struct Tree {
+    children: [Child; 2],
+    name: &'static str,
+}
+
+#[test]
+fn children_point_to_parent() {
+    let tree = Box::into_raw(Box::new(MaybeUninit::<Tree>::uninit()));
+    let parent = NonNull::new(tree).unwrap().cast::<Tree>();
+    let (first, second) = unsafe { field_addrs!(tree => { children => [0], children => [1] }) };
+    assert_eq!(
+        second.as_ptr() as usize - first.as_ptr() as usize,
+        core::mem::size_of::<Child>()
+    );
+    partial_init!(unsafe { &mut *tree } => {
+        children: [Child { parent, value: 1 }, Child { parent, value: 2 }],
+        name: "root",
+    });
+    assert_eq!(unsafe { (*first.as_ptr()).value }, 1);
+
+    let tree = unsafe { Box::from_raw(tree).assume_init() };
+    assert_eq!(
+        first.as_ptr() as *const Child,
+        &tree.children[0] as *const Child
+    );
+    for child in &tree.children {
+        assert_eq!(child.parent.as_ptr() as *const Tree, &*tree as *const Tree);
+    }
+    assert_eq!(tree.children[1].value, 2);
+}
+
+#[test]
+fn same_field_twice() {
+    let mut pair = MaybeUninit::<(u8, u16)>::uninit();
+    let ptr: *mut MaybeUninit<(u8, u16)> = &mut pair;
+    let (a, b, a2) = unsafe { field_addrs!(ptr => { 0, 1, 0 }) };
+    assert_eq!(a, a2);
+    assert_ne!(a.cast::<u8>(), b.cast::<u8>());
+}
@@ -0,0 +1,88 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit, project_uninit_mut};
+
+struct Inner {
+    value: u32,
+    buf: [u8; 4],
+}
+
+struct Outer {
+    id: u8,
+    cache: MaybeUninit<Inner>,
+}
+
+#[test]
+fn project_through_maybe_uninit_field() {
+    let mut x = MaybeUninit::<Outer>::uninit();
+    let (id, value, first, last) = project_uninit_mut!(x => {
+        id,
+        cache => * => value,
+        cache => * => buf => [0],
+        cache => * => buf => [3],
+    });
+    id.write(1);
+    value.write(7);
+    first.write(0xaa);
+    last.write(0xbb);
+    partial_init!(x => cache => * => buf => { [1]: 1, [2]: 2 });
+
+    let (value, buf) = project_uninit!(x => { cache => * => value, cache => * => buf });
+    assert_eq!(unsafe { value.assume_init() }, 7);
+    assert_eq!(unsafe { buf.assume_init() }, [0xaa, 1, 2, 0xbb]);
+
+    let cache: &mut MaybeUninit<Inner> = project_uninit_mut!(x => cache => *);
+    let inner = unsafe { cache.assume_init_mut() };
+    assert_eq!(inner.value, 7);
+}
+
+#[test]
+fn flatten_at_start_of_path() {
+    let mut x = MaybeUninit::<MaybeUninit<Inner>>::uninit();
+    partial_init!(x => { * => value: 3, * => buf: [4; 4] });
+    let inner = unsafe { x.assume_init().assume_init() };
+    assert_eq!((inner.value, inner.buf), (3, [4; 4]));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn strict_path_through_maybe_uninit_field() {
+    use project_uninit::ProjectUninit;
+
+    #[derive(ProjectUninit)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(ProjectUninit)]
+    struct Lazy {
+        point: MaybeUninit<Point>,
+    }
+
+    let mut lazy = MaybeUninit::<Lazy>::uninit();
+    partial_init!(lazy => strict { point => * => x: 1.0, point => * => y: 2.0 });
+    let point = unsafe { lazy.assume_init().point.assume_init() };
+    assert_eq!((point.x, point.y), (1.0, 2.0));
+}
+
+#[test]
+fn project_through_manually_drop_field() {
+    use core::mem::ManuallyDrop;
+
+    struct Guarded {
+        guard: ManuallyDrop<(String, Vec<u8>)>,
+        len: usize,
+    }
+
+    let mut x = MaybeUninit::<Guarded>::uninit();
+    let (name, bytes) = project_uninit_mut!(x => { guard => * => 0, guard => * => 1 });
+    name.write("guarded".into());
+    bytes.write(vec![1, 2]);
+    partial_init!(x => len = 2);
+
+    let mut x = unsafe { x.assume_init() };
+    assert_eq!(x.guard.0, "guarded");
+    assert_eq!(x.guard.1.len(), x.len);
+    unsafe { ManuallyDrop::drop(&mut x.guard) };
+}
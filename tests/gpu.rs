@@ -0,0 +1,76 @@
+#![cfg(feature = "gpu")]
+
+use project_uninit::{GpuLayout, StagingWriter};
+
+#[derive(GpuLayout, Clone, Copy)]
+#[gpu(align = 16)]
+struct Uniforms {
+    view: [[f32; 4]; 4],
+    #[gpu(offset = 80)]
+    time: f32,
+    frame: u32,
+}
+
+#[derive(GpuLayout, Clone, Copy)]
+struct Instance<T: Copy> {
+    scale: T,
+    #[gpu(align = 8)]
+    id: u16,
+}
+
+fn field_offsets<T: GpuLayout>() -> Vec<(&'static str, usize)> {
+    T::FIELDS.iter().map(|f| (f.name(), f.offset())).collect()
+}
+
+#[test]
+fn layout_follows_attributes() {
+    assert_eq!(
+        field_offsets::<Uniforms>(),
+        [("view", 0), ("time", 80), ("frame", 84)]
+    );
+    assert_eq!(Uniforms::SIZE, 96);
+
+    assert_eq!(field_offsets::<Instance<u8>>(), [("scale", 0), ("id", 8)]);
+    assert_eq!(Instance::<u8>::SIZE, 16);
+    assert_eq!(Instance::<[f32; 3]>::SIZE, 24);
+}
+
+#[test]
+fn write_leaves_padding_untouched() {
+    let mut mapping = [0xffu8; 96];
+    let mut writer = unsafe { StagingWriter::new(mapping.as_mut_ptr(), mapping.len()) };
+    let uniforms = Uniforms {
+        view: [[0.0; 4]; 4],
+        time: 1.5,
+        frame: 7,
+    };
+    writer.write(0, &uniforms);
+    assert!(mapping[..64].iter().all(|&b| b == 0));
+    assert!(mapping[64..80].iter().all(|&b| b == 0xff));
+    assert_eq!(mapping[80..84], 1.5f32.to_ne_bytes());
+    assert_eq!(mapping[84..88], 7u32.to_ne_bytes());
+    assert!(mapping[88..].iter().all(|&b| b == 0xff));
+}
+
+#[test]
+fn unaligned_destination() {
+    let mut mapping = [0u8; 1 + 16 * 2];
+    assert_eq!(Instance::<u32>::SIZE, 16);
+    let mut writer = unsafe { StagingWriter::new(mapping.as_mut_ptr().add(1), 32) };
+    writer.write_slice(0, &[Instance { scale: 2u32, id: 3 }, Instance { scale: 4, id: 5 }]);
+    assert_eq!(mapping[1..5], 2u32.to_ne_bytes());
+    assert_eq!(mapping[25..27], 5u16.to_ne_bytes());
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn out_of_bounds_write_panics() {
+    let mut mapping = [0u8; 90];
+    let mut writer = unsafe { StagingWriter::new(mapping.as_mut_ptr(), mapping.len()) };
+    writer.write_field::<Uniforms, u32>(0, "frame", 1);
+    writer.write(0, &Uniforms {
+        view: [[0.0; 4]; 4],
+        time: 0.0,
+        frame: 0,
+    });
+}
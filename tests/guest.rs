@@ -0,0 +1,50 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, GuestMemory, GuestMemoryError};
+
+#[repr(C)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+#[repr(C, align(8))]
+struct Memory([u8; 64]);
+
+#[test]
+fn bounds_and_alignment() {
+    let mut bytes = Memory([0; 64]);
+    let mut memory = GuestMemory::new(&mut bytes.0);
+    assert_eq!(
+        unsafe { memory.slot::<Vec2>(60) }.unwrap_err(),
+        GuestMemoryError::OutOfBounds {
+            offset: 60,
+            size: 8,
+            len: 64
+        }
+    );
+    assert_eq!(
+        unsafe { memory.slot::<Vec2>(2) }.unwrap_err(),
+        GuestMemoryError::Misaligned {
+            offset: 2,
+            align: 4
+        }
+    );
+    assert!(unsafe { memory.slot::<Vec2>(usize::MAX) }.is_err());
+    assert!(unsafe { memory.slots::<Vec2>(8, usize::MAX) }.is_err());
+    assert!(unsafe { memory.slot::<Vec2>(56) }.is_ok());
+}
+
+#[test]
+fn initialize_in_guest_memory() {
+    let mut bytes = Memory([0; 64]);
+    let mut memory = GuestMemory::new(&mut bytes.0);
+    let slots = unsafe { memory.slots::<Vec2>(16, 3) }.unwrap();
+    for (i, slot) in slots.iter_mut().enumerate() {
+        partial_init!(slot => { x: i as f32, y: -(i as f32) });
+    }
+    let slots = unsafe { &*(slots as *const [MaybeUninit<Vec2>] as *const [Vec2]) };
+    assert_eq!(slots[2].x, 2.0);
+    assert_eq!(bytes.0[24..28], 1.0f32.to_ne_bytes());
+    assert_eq!(bytes.0[36..40], (-2.0f32).to_ne_bytes());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "hooks")]
+
+use core::mem::MaybeUninit;
+use std::sync::Mutex;
+
+use project_uninit::{partial_init, set_init_hook, InitEvent};
+
+static EVENTS: Mutex<Vec<InitEvent>> = Mutex::new(Vec::new());
+
+struct Foo {
+    a: u16,
+    b: (u8, [u32; 4]),
+}
+
+#[test]
+fn hook_called_for_each_write() {
+    set_init_hook(Some(|event| EVENTS.lock().unwrap().push(event)));
+
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: 1, b => 1: [0; 4] });
+    partial_init!(x => b => 0 = 2);
+    partial_init!(x => b => 1 => [2] = 7);
+
+    set_init_hook(None);
+    partial_init!(x => a = 3);
+
+    let type_name = core::any::type_name::<Foo>();
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            InitEvent { type_name, path: "a", size: 2 },
+            InitEvent { type_name, path: "b.1", size: 16 },
+            InitEvent { type_name, path: "b.0", size: 1 },
+            InitEvent { type_name, path: "b.1[2]", size: 4 },
+        ]
+    );
+}
@@ -0,0 +1,118 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{
+    partial_init, project_ptr_mut, project_uninit, project_uninit_mut, read_field, reinit,
+    try_partial_init,
+};
+
+#[derive(Debug, PartialEq)]
+struct Bindgen {
+    r#type: u32,
+    r#fn: (u8, u8),
+    ptr: u16,
+    ret: i64,
+    lt: [u8; 2],
+    _ref: bool,
+    prop_ref: char,
+    value: u8,
+    field: u8,
+    this: u8,
+}
+
+#[test]
+fn raw_and_colliding_field_names() {
+    let (ptr, ret, lt, _ref, value, field, this, prop_ref) = (1, 2, 3, true, 4, 5, 6, 'x');
+    let mut x = MaybeUninit::<Bindgen>::uninit();
+    let (ty, f0) = partial_init!(x => { r#type: 7, r#fn => 0: ptr as u8 });
+    assert_eq!((*ty, *f0), (7, 1));
+    partial_init!(x => r#fn => 1 = ret as u8);
+    partial_init!(x => {
+        ptr: ptr,
+        ret: ret,
+        lt => [0]: lt,
+        lt => [1]: |(_, _, lt0)| => *lt0 + 1,
+        _ref: _ref,
+        prop_ref: prop_ref,
+        value: value,
+        field: field,
+        this: this,
+    });
+    let (ty, lt) = project_uninit!(x => { r#type, lt });
+    assert_eq!(unsafe { (ty.assume_init(), lt.assume_init()) }, (7, [3, 4]));
+    let r#fn = project_uninit_mut!(x => r#fn => 1);
+    assert_eq!(unsafe { r#fn.assume_init() }, 2);
+    let ptr = unsafe { project_ptr_mut!(x.as_mut_ptr() => ptr) };
+    assert_eq!(unsafe { *ptr }, 1);
+    let ret: Result<_, ()> = try_partial_init!(x => r#type = Ok(ret as u32));
+    assert_eq!(*ret.unwrap(), 2);
+    unsafe { reinit!(x => { r#type: 8, _ref: false }) };
+    let r#type = unsafe { read_field!(x => r#type) };
+    assert_eq!(r#type, 8);
+    partial_init!(x => r#type = r#type);
+
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Bindgen {
+            r#type: 8,
+            r#fn: (1, 2),
+            ptr: 1,
+            ret: 2,
+            lt: [3, 4],
+            _ref: false,
+            prop_ref: 'x',
+            value: 4,
+            field: 5,
+            this: 6,
+        }
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derived {
+    use core::mem::MaybeUninit;
+
+    use project_uninit::{
+        init_all, partial_init, project_uninit_mut, tracked_init, ExportFields, InitBuilder,
+        InitTracker, ProjectUninit, UninitViewExt,
+    };
+
+    #[derive(ProjectUninit, InitBuilder, ExportFields, Default, Debug, PartialEq)]
+    pub struct Raw {
+        pub r#type: u32,
+        pub r#fn: (u8, u8),
+        pub ptr: u16,
+        pub this: u8,
+        pub slot: u8,
+    }
+
+    #[test]
+    fn raw_fields_with_derives() {
+        let mut x = MaybeUninit::<Raw>::uninit();
+        partial_init!(x => strict { r#type: 1, slot: 2 });
+        partial_init!(x => pub r#fn = (2, 3));
+        let raw = partial_init!(x => { ptr: 4, ..Default::default() });
+        assert_eq!((raw.r#fn, raw.slot), ((0, 0), 0));
+
+        let view = x.uninit_fields_mut();
+        view.r#type.write(5);
+        assert_eq!(unsafe { project_uninit_mut!(x => r#type).assume_init() }, 5);
+
+        let mut tracker = InitTracker::new(&mut x);
+        tracked_init!(tracker => { r#type: 6, this: 7 });
+        tracked_init!(tracker => { r#fn: (8, 9), ptr: 10, slot: 11 });
+        let raw = tracker.finish().ok().unwrap();
+        assert_eq!((raw.r#type, raw.r#fn, raw.slot), (6, (8, 9), 11));
+
+        let raw = init_all!(x => { r#fn: (0, 0), r#type: 1, ptr: 2, this: 3, slot: 4 });
+        assert_eq!(raw.r#type, 1);
+
+        let raw = RawInit::new()
+            .set_type(1)
+            .set_fn((2, 3))
+            .set_ptr(4)
+            .set_this(5)
+            .set_slot(6)
+            .finish();
+        assert_eq!(raw.r#fn, (2, 3));
+    }
+}
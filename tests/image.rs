@@ -0,0 +1,82 @@
+#![cfg(feature = "derive")]
+
+use core::convert::TryInto;
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, reinit, ImageError, InitTracker, ProjectUninit};
+
+#[derive(ProjectUninit)]
+#[repr(C)]
+struct Block {
+    version: u8,
+    channels: (u16, u16, u16),
+    gain: f32,
+}
+
+fn stored() -> [u8; 12] {
+    let mut image = [0xaa; 12];
+    image[0] = 2;
+    for (i, channel) in [10u16, 20, 30].iter().enumerate() {
+        image[2 + i * 2..4 + i * 2].copy_from_slice(&channel.to_ne_bytes());
+    }
+    image[8..12].copy_from_slice(&0.5f32.to_ne_bytes());
+    image
+}
+
+#[test]
+fn patch_and_write_back() {
+    let mut slot = MaybeUninit::<Block>::uninit();
+    let mut block = InitTracker::new(&mut slot);
+    unsafe { block.load_image(&stored(), |_, _| true) }.unwrap();
+    assert!(block.is_complete());
+    let mut out = [0xff; 12];
+    unsafe {
+        reinit!(block.slot_mut() => { channels => 1: 25, gain: 1.0 });
+        block.write_image(&mut out).unwrap();
+    }
+    assert_eq!(out[0..2], [2, 0]);
+    assert_eq!(out[4..6], 25u16.to_ne_bytes());
+    assert_eq!(out[8..12], 1.0f32.to_ne_bytes());
+    assert_eq!(block.finish().unwrap().channels, (10, 25, 30));
+}
+
+#[test]
+fn rejected_fields_stay_uninit() {
+    let mut slot = MaybeUninit::<Block>::uninit();
+    let mut block = InitTracker::new(&mut slot);
+    unsafe {
+        block
+            .load_image(&stored(), |field, bytes| {
+                field.name() != "gain" || f32::from_ne_bytes(bytes.try_into().unwrap()) > 1.0
+            })
+            .unwrap();
+    }
+    assert_eq!(
+        block.missing().map(|f| f.name()).collect::<Vec<_>>(),
+        ["gain"]
+    );
+    let mut out = [0; 12];
+    assert_eq!(
+        unsafe { block.write_image(&mut out) },
+        Err(ImageError::Incomplete { index: 2 })
+    );
+    unsafe {
+        partial_init!(block.slot_mut() => gain = 2.0);
+        block.set_init(2);
+    }
+    assert_eq!(unsafe { block.write_image(&mut out) }, Ok(()));
+    assert_eq!(out[8..12], 2.0f32.to_ne_bytes());
+}
+
+#[test]
+fn size_mismatch() {
+    let mut slot = MaybeUninit::<Block>::uninit();
+    let mut block = InitTracker::new(&mut slot);
+    assert_eq!(
+        unsafe { block.load_image(&[0; 8], |_, _| true) },
+        Err(ImageError::SizeMismatch {
+            expected: 12,
+            found: 8
+        })
+    );
+}
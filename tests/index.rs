@@ -0,0 +1,48 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit, project_uninit_mut};
+
+struct Buffer {
+    len: usize,
+    buf: [u8; 4],
+    pairs: [(u16, u32); 2],
+}
+
+#[test]
+fn project_array_elements() {
+    let mut x = MaybeUninit::<Buffer>::uninit();
+    let (b0, b3, p1) = project_uninit_mut!(x => { buf => [0], buf => [3], pairs => [1] => 1 });
+    b0.write(1);
+    b3.write(4);
+    p1.write(7);
+    project_uninit_mut!(x => buf => [1]).write(2);
+    let (b0, b1, p1) = project_uninit!(x => { buf => [0], buf => [1], pairs => [1] => 1 });
+    assert_eq!(
+        unsafe { (b0.assume_init(), b1.assume_init(), p1.assume_init()) },
+        (1, 2, 7)
+    );
+}
+
+#[test]
+fn partial_init_array_elements() {
+    let mut x = MaybeUninit::<Buffer>::uninit();
+    partial_init!(x => {
+        len: 4,
+        buf => [0]: 10,
+        buf => [1]: 11,
+        buf => [2]: 12,
+        buf => [3]: 13,
+        pairs => [0]: (1, 2),
+    });
+    let (a, b) = partial_init!(x => { pairs => [1] => 0: 3, pairs => [1] => 1: 4 });
+    assert_eq!((*a, *b), (3, 4));
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.len, x.buf, x.pairs), (4, [10, 11, 12, 13], [(1, 2), (3, 4)]));
+}
+
+#[test]
+fn index_at_root() {
+    let mut x = MaybeUninit::<[u32; 2]>::uninit();
+    partial_init!(x => { [1]: 5, [0]: 4 });
+    assert_eq!(unsafe { x.assume_init() }, [4, 5]);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{init_all, ProjectUninit};
+
+#[derive(ProjectUninit, Debug, PartialEq)]
+struct Record {
+    r#type: u8,
+    name: String,
+    tags: [&'static str; 2],
+}
+
+#[derive(ProjectUninit)]
+struct Pair(Rc<()>, u32);
+
+#[derive(ProjectUninit, Debug, PartialEq)]
+struct Empty {}
+
+#[test]
+fn returns_initialized_value() {
+    let mut slot = MaybeUninit::<Record>::uninit();
+    let record = init_all!(slot => { tags: ["a", "b"], r#type: 1, name: "x".into() });
+    assert_eq!(
+        record,
+        Record {
+            r#type: 1,
+            name: "x".into(),
+            tags: ["a", "b"]
+        }
+    );
+
+    let mut boxed = Box::new(MaybeUninit::<Empty>::uninit());
+    assert_eq!(init_all!(boxed => {}), Empty {});
+}
+
+#[test]
+fn slot_can_be_reused() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Pair>::uninit();
+    let first = init_all!(&mut slot => { 0: rc.clone(), 1: 1 });
+    let second = init_all!(slot => { 1: 2, 0: rc.clone() });
+    assert_eq!((first.1, second.1), (1, 2));
+    assert_eq!(Rc::strong_count(&rc), 3);
+    drop((first, second));
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{init_in_place, ProjectUninit};
+
+#[derive(ProjectUninit)]
+struct Config {
+    name: String,
+    retries: u32,
+    callback: fn(u32) -> u32,
+    limits: (usize, usize),
+}
+
+impl Config {
+    #[init_in_place]
+    pub fn new(name: &str, retries: u32) -> Self {
+        let slot = name.len();
+        Self {
+            name: name.into(),
+            retries,
+            callback: |x| x + 1,
+            limits: (slot, slot * 2),
+        }
+    }
+
+    #[init_in_place]
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::new("default", 3)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Pair<T>(T, Vec<T>);
+
+impl<T: Clone> Pair<T> {
+    #[init_in_place]
+    fn repeat(value: T, n: usize) -> Self {
+        Self(value.clone(), vec![value; n])
+    }
+}
+
+#[test]
+fn in_place_constructor_matches_original() {
+    let mut slot = MaybeUninit::uninit();
+    let config = Config::new_in_place(&mut slot, "abc", 2);
+    let expected = Config::new("abc", 2);
+    assert_eq!((config.callback)(1), (expected.callback)(1));
+    assert_eq!(
+        (&config.name, config.retries, config.limits),
+        (&expected.name, expected.retries, expected.limits)
+    );
+    assert_eq!(config.limits, (3, 6));
+    unsafe { slot.assume_init_drop() };
+}
+
+#[test]
+fn in_place_constructor_with_rest() {
+    let mut slot = MaybeUninit::uninit();
+    let config = Config::named_in_place(&mut slot, "custom");
+    assert_eq!(config.name, Config::named("custom").name);
+    assert_eq!((config.retries, config.limits), (3, (7, 14)));
+    unsafe { slot.assume_init_drop() };
+}
+
+#[test]
+fn in_place_tuple_constructor() {
+    let mut slot = MaybeUninit::uninit();
+    let pair = Pair::repeat_in_place(&mut slot, 'x', 2);
+    assert_eq!(*pair, Pair::repeat('x', 2));
+    unsafe { slot.assume_init_drop() };
+}
@@ -0,0 +1,82 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{init_with, partial_init};
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn init_point(slot: &mut MaybeUninit<Point>) -> &mut Point {
+    partial_init!(slot => { x: 1, y: 2 });
+    unsafe { slot.assume_init_mut() }
+}
+
+#[test]
+fn init_with_returns_the_initialized_value() {
+    assert_eq!(init_with(init_point), Point { x: 1, y: 2 });
+}
+
+#[test]
+#[should_panic(expected = "initializer returned a reference to something other than its slot")]
+fn init_with_rejects_other_references() {
+    init_with(|_: &mut MaybeUninit<Point>| Box::leak(Box::new(Point { x: 0, y: 0 })));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed_init_with_initializes_on_the_heap() {
+    let point = project_uninit::boxed_init_with(init_point);
+    assert_eq!(*point, Point { x: 1, y: 2 });
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn init_with_tracker_proof() {
+    use project_uninit::{tracked_init, InitTracker, ProjectUninit};
+
+    #[derive(ProjectUninit, Debug, PartialEq)]
+    struct Named {
+        name: String,
+        id: u32,
+    }
+
+    let named = init_with::<Named>(|slot| {
+        let mut tracker = InitTracker::new(slot);
+        tracked_init!(tracker => { id: 3, name: "n".into() });
+        tracker.finish().ok().unwrap()
+    });
+    assert_eq!(
+        named,
+        Named {
+            name: "n".into(),
+            id: 3
+        }
+    );
+}
+
+#[cfg(all(feature = "alloc", feature = "derive"))]
+#[test]
+fn boxed_init_with_skipped_field_has_no_proof() {
+    use project_uninit::{tracked_init, InitTracker, ProjectUninit};
+
+    #[derive(ProjectUninit)]
+    struct Frame {
+        pixels: Vec<u32>,
+        width: usize,
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        project_uninit::boxed_init_with::<Frame>(|slot| {
+            let mut tracker = InitTracker::new(slot);
+            tracked_init!(tracker => width = 4);
+            // `pixels` was skipped, so the tracker can't produce the proof
+            match tracker.finish() {
+                Ok(frame) => frame,
+                Err(_) => panic!("missing field"),
+            }
+        })
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "test-support")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{
+    check_injected_failure, clear_injected_failure, exhaust_failure_points, fail_at_write,
+    try_partial_init, InjectedFailure,
+};
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Parse,
+    Injected,
+}
+
+impl From<InjectedFailure> for Error {
+    fn from(_: InjectedFailure) -> Self {
+        Error::Injected
+    }
+}
+
+struct Foo {
+    a: Rc<()>,
+    b: (u8, Rc<()>),
+}
+
+#[test]
+fn try_partial_init_writes_all_fields() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    let (a, b1) = try_partial_init!(x => {
+        a: Ok::<_, Error>(rc.clone()),
+        b => 1: Ok(rc.clone()),
+    })
+    .unwrap();
+    assert!(Rc::ptr_eq(a, b1));
+    let b0 = try_partial_init!(x => b => 0 = Ok::<_, Error>(3)).unwrap();
+    assert_eq!(*b0, 3);
+    drop(unsafe { x.assume_init() });
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn try_partial_init_stops_at_first_error() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    let mut evaluated = false;
+    let result = try_partial_init!(x => {
+        a: Ok(rc.clone()),
+        b => 0: Err(Error::Parse),
+        b => 1: {
+            evaluated = true;
+            Ok(rc.clone())
+        },
+    });
+    assert_eq!(result.unwrap_err(), Error::Parse);
+    assert!(!evaluated);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn injected_failures_hit_every_write() {
+    let rc = Rc::new(());
+    let mut errors = Vec::new();
+    let failures = exhaust_failure_points(|| {
+        let mut x = MaybeUninit::<Foo>::uninit();
+        match try_partial_init!(x => { a: Ok::<_, Error>(rc.clone()), b => 1: Ok(rc.clone()) }) {
+            Ok(_) => {
+                // second call with the remaining field
+                match try_partial_init!(x => b => 0 = Ok::<_, Error>(0)) {
+                    Ok(_) => drop(unsafe { x.assume_init() }),
+                    Err(e) => {
+                        errors.push(e);
+                        unsafe { core::ptr::drop_in_place(x.as_mut_ptr()) };
+                    }
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+        assert_eq!(Rc::strong_count(&rc), 1);
+    });
+    assert_eq!(failures, 3);
+    assert_eq!(errors, [Error::Injected, Error::Injected, Error::Injected]);
+}
+
+#[test]
+fn injected_failure_is_one_shot() {
+    fail_at_write(1);
+    assert_eq!(check_injected_failure(), Ok(()));
+    assert_eq!(check_injected_failure(), Err(InjectedFailure));
+    assert_eq!(check_injected_failure(), Ok(()));
+
+    fail_at_write(0);
+    clear_injected_failure();
+    assert_eq!(check_injected_failure(), Ok(()));
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "std")]
+
+use core::mem::MaybeUninit;
+use std::io::{self, Read};
+
+use project_uninit::{partial_init, project_uninit_mut, read_exact_into, ReadBuf};
+
+#[repr(C)]
+struct Header {
+    magic: [u8; 4],
+    sizes: [[u8; 2]; 2],
+    kind: u8,
+}
+
+/// Returns at most `chunk` bytes from each read, and fails once with `Interrupted`.
+struct Chunked<'a> {
+    data: &'a [u8],
+    chunk: usize,
+    interrupted: bool,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.interrupted {
+            self.interrupted = true;
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn read_header_fields() {
+    let mut input = Chunked {
+        data: b"RIFF\x01\x02\x03\x04",
+        chunk: 3,
+        interrupted: false,
+    };
+    let mut header = MaybeUninit::<Header>::uninit();
+    let magic = read_exact_into!(header => magic, &mut input).unwrap();
+    magic.make_ascii_lowercase();
+    read_exact_into!(header => sizes => [1], &mut input).unwrap();
+    read_exact_into!(header => sizes => [0], &mut &[5u8, 6][..]).unwrap();
+    partial_init!(header => kind = 1);
+
+    let header = unsafe { header.assume_init() };
+    assert_eq!(&header.magic, b"riff");
+    assert_eq!(header.sizes, [[5, 6], [1, 2]]);
+}
+
+#[test]
+fn read_exact_into_reports_eof() {
+    let mut header = MaybeUninit::<Header>::uninit();
+    let err = read_exact_into!(header => magic, &mut &b"RI"[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_buf_tracks_filled_and_init() {
+    let mut storage = [MaybeUninit::<u8>::uninit(); 8];
+    let mut buf = ReadBuf::new(&mut storage);
+    assert_eq!((buf.len(), buf.init_len(), buf.capacity()), (0, 0, 8));
+
+    let mut input: &[u8] = b"abc";
+    assert_eq!(buf.read_from(&mut input).unwrap(), 3);
+    assert_eq!(buf.filled(), b"abc");
+    // the whole buffer is zeroed before the first read, and not again afterwards
+    assert_eq!(buf.init_len(), 8);
+    assert_eq!(buf.read_from(&mut input).unwrap(), 0);
+
+    let err = buf.read_exact_from(&mut &b"de"[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(buf.filled(), b"abcde");
+
+    buf.read_exact_from(&mut &b"fghij"[..]).unwrap();
+    assert!(buf.is_full());
+    assert_eq!(buf.read_from(&mut &b"k"[..]).unwrap(), 0);
+    buf.filled_mut()[0] = b'A';
+    assert_eq!(buf.into_filled(), b"Abcdefgh");
+}
+
+#[test]
+fn read_buf_from_projected_array() {
+    struct Packet {
+        len: u8,
+        payload: [u8; 16],
+    }
+
+    let mut packet = MaybeUninit::<Packet>::uninit();
+    let (len, payload) = project_uninit_mut!(packet => { len, payload });
+    let mut buf = ReadBuf::from_array(payload);
+    buf.read_from(&mut &b"ping"[..]).unwrap();
+    len.write(buf.len() as u8);
+    assert_eq!(
+        format!("{:?}", buf),
+        "ReadBuf { filled: 4, init: 16, capacity: 16 }"
+    );
+
+    let packet = unsafe { packet.assume_init() };
+    assert_eq!(&packet.payload[..packet.len as usize], b"ping");
+    assert_eq!(packet.payload[4..], [0; 12]);
+}
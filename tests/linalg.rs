@@ -0,0 +1,26 @@
+#![cfg(feature = "nalgebra")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::nalgebra::{SMatrix, SVector};
+use project_uninit::{boxed_matrix, init_matrix};
+
+#[test]
+fn matrix_elements_are_column_major() {
+    let mut slot = MaybeUninit::<SMatrix<u32, 2, 3>>::uninit();
+    let mut order = Vec::new();
+    let m = init_matrix(&mut slot, |r, c| {
+        order.push((r, c));
+        (10 * r + c) as u32
+    });
+    assert_eq!(*m, SMatrix::<u32, 2, 3>::new(0, 1, 2, 10, 11, 12));
+    assert_eq!(order, [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+}
+
+#[test]
+fn large_boxed_matrix() {
+    let m: Box<SMatrix<f64, 1024, 1024>> = boxed_matrix(|r, c| if r == c { 2.0 } else { 0.0 });
+    assert_eq!(m.trace(), 2048.0);
+    let v: Box<SVector<i64, 100_000>> = boxed_matrix(|r, _| r as i64);
+    assert_eq!(v[99_999], 99_999);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "derive")]
+
+use core::mem::{align_of, size_of, MaybeUninit};
+
+use project_uninit::{partial_init, UninitMirror, UninitMirrorExt};
+
+#[derive(UninitMirror, Debug, PartialEq)]
+#[repr(C)]
+struct Record {
+    id: u8,
+    name: String,
+    scores: [u32; 3],
+}
+
+#[derive(UninitMirror, Debug, PartialEq)]
+#[repr(C, align(16))]
+struct Pair<T>(T, u16);
+
+#[derive(UninitMirror, Debug, PartialEq)]
+#[repr(transparent)]
+struct Meters(f64);
+
+#[test]
+fn same_layout() {
+    assert_eq!(size_of::<RecordUninitMirror>(), size_of::<Record>());
+    assert_eq!(align_of::<PairUninitMirror<u8>>(), 16);
+    assert_eq!(size_of::<PairUninitMirror<u64>>(), size_of::<Pair<u64>>());
+    assert_eq!(size_of::<MetersUninitMirror>(), size_of::<Meters>());
+}
+
+#[test]
+fn init_through_mirror() {
+    let mut slot = MaybeUninit::<Record>::uninit();
+    let mirror = slot.as_mirror_mut();
+    mirror.id.write(3);
+    mirror.name.write("three".into());
+    // fields of the mirror can still be initialized with the macros
+    partial_init!(mirror.scores => [0] = 1);
+    partial_init!(mirror.scores => { [1]: 2, [2]: 3 });
+
+    let record = unsafe { slot.assume_init() };
+    assert_eq!(
+        record,
+        Record {
+            id: 3,
+            name: "three".into(),
+            scores: [1, 2, 3],
+        }
+    );
+}
+
+#[test]
+fn destructure_mirror() {
+    let mut slot = MaybeUninit::<Pair<&str>>::uninit();
+    let PairUninitMirror(first, second) = slot.as_mirror_mut();
+    first.write("a");
+    second.write(2);
+    assert_eq!(unsafe { slot.as_mirror().1.assume_init() }, 2);
+    assert_eq!(unsafe { slot.assume_init() }, Pair("a", 2));
+}
+
+#[test]
+fn mirror_by_value() {
+    const EMPTY: MetersUninitMirror = MetersUninitMirror::uninit();
+    let mut mirror = EMPTY;
+    mirror.0.write(1.5);
+    assert_eq!(
+        unsafe { mirror.as_uninit().assume_init_ref() },
+        &Meters(1.5)
+    );
+    assert_eq!(unsafe { mirror.assume_init() }, Meters(1.5));
+
+    let mut mirror = RecordUninitMirror::uninit();
+    partial_init!(mirror.as_uninit_mut() => {
+        id: 1,
+        name: String::new(),
+        scores: [0; 3],
+    });
+    assert_eq!(unsafe { mirror.assume_init() }.name, "");
+}
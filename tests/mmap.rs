@@ -0,0 +1,56 @@
+#![cfg(all(feature = "mmap", unix))]
+
+use std::rc::Rc;
+
+use project_uninit::mmap::{HugePages, MapOptions, MappedUninit};
+use project_uninit::{init_array, partial_init};
+
+struct Header {
+    name: Rc<str>,
+    len: usize,
+}
+
+#[test]
+fn initialize_mapped_struct() {
+    let name: Rc<str> = "mapped".into();
+    let mut header = MappedUninit::<Header>::new(MapOptions::default()).unwrap();
+    partial_init!(header => { name: name.clone(), len: 6 });
+    let header = unsafe { header.assume_init() };
+    assert_eq!((&*header.name, header.len), ("mapped", 6));
+    assert_eq!(Rc::strong_count(&name), 2);
+    drop(header);
+    assert_eq!(Rc::strong_count(&name), 1);
+}
+
+#[test]
+fn populated_transparent_huge_pages() {
+    let options = MapOptions {
+        huge_pages: HugePages::Transparent,
+        populate: true,
+    };
+    let mut buf = MappedUninit::<[u32; 1 << 21]>::new(options).unwrap();
+    init_array(&mut buf, |i| i as u32);
+    let mut buf = unsafe { buf.assume_init() };
+    buf[5] = 0;
+    assert_eq!(buf.iter().map(|&x| x as u64).sum::<u64>(), (1u64 << 20) * ((1 << 21) - 1) - 5);
+}
+
+#[test]
+fn zero_sized() {
+    let unit = MappedUninit::<()>::new(MapOptions::default()).unwrap();
+    drop(unit);
+}
+
+// the huge page pool is usually empty, so this only checks a small value unmaps cleanly if it is
+// mapped
+#[test]
+fn explicit_huge_pages_small_value() {
+    let options = MapOptions {
+        huge_pages: HugePages::Explicit,
+        populate: false,
+    };
+    if let Ok(mut header) = MappedUninit::<(u64, u64)>::new(options) {
+        header.write((1, 2));
+        assert_eq!(unsafe { header.assume_init() }.1, 2);
+    }
+}
@@ -0,0 +1,35 @@
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use project_uninit::{project_nonnull, project_nonnull_mut};
+
+#[derive(Debug, PartialEq)]
+struct Node {
+    value: (u32, i8),
+    links: [Option<NonNull<Node>>; 2],
+}
+
+#[test]
+fn project_nonnull_fields() {
+    let mut slot = MaybeUninit::<Node>::uninit();
+    let ptr = NonNull::from(&mut slot).cast::<Node>();
+
+    unsafe {
+        let value1: NonNull<i8> = project_nonnull_mut!(ptr => value => 1);
+        value1.write(-1);
+        let (value0, link0, link1) =
+            project_nonnull_mut!(ptr => { value => 0, links => [0], links => [1] });
+        value0.write(7);
+        link0.write(None);
+        link1.write(Some(ptr));
+    }
+    let node = unsafe { slot.assume_init_ref() };
+    assert_eq!(node.value, (7, -1));
+    assert_eq!(node.links[1], Some(NonNull::from(node)));
+
+    let ptr = NonNull::from(node);
+    let (value, link1) = unsafe { project_nonnull!(ptr => { value, links => [1] }) };
+    assert_eq!(value.as_ptr() as *const _, &node.value as *const _);
+    assert_eq!(unsafe { link1.read() }, Some(ptr));
+    assert_eq!(unsafe { *project_nonnull!(ptr => value => 0).as_ref() }, 7);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "derive")]
+
+use core::mem::{offset_of, MaybeUninit};
+
+use project_uninit::{
+    partial_init, project_by_offset, project_by_offset_mut, ProjectUninit, UninitFields,
+};
+
+#[derive(ProjectUninit)]
+struct Record {
+    id: u64,
+    r#type: u8,
+    name: &'static str,
+}
+
+#[derive(ProjectUninit)]
+struct Pair<T>(T, u16);
+
+// `OFFSET_AB` would have been generated for both `ab` and `AB`
+#[allow(non_snake_case)]
+mod mixed {
+    use project_uninit::ProjectUninit;
+
+    #[derive(ProjectUninit)]
+    pub struct Mixed {
+        pub ab: u8,
+        pub AB: u32,
+        pub a_b: u16,
+    }
+}
+use mixed::Mixed;
+
+#[test]
+fn offset_fns_match_fields() {
+    assert_eq!(Record::offset_of_id(), offset_of!(Record, id));
+    assert_eq!(Record::offset_of_type(), offset_of!(Record, r#type));
+    assert_eq!(Record::offset_of_name(), offset_of!(Record, name));
+    assert_eq!(Pair::<u32>::offset_of_1(), offset_of!(Pair<u32>, 1));
+    let offsets: Vec<_> = Record::FIELDS.iter().map(|f| f.offset()).collect();
+    assert_eq!(
+        offsets,
+        [
+            Record::offset_of_id(),
+            Record::offset_of_type(),
+            Record::offset_of_name()
+        ]
+    );
+}
+
+#[test]
+fn offset_fns_keep_field_case() {
+    assert_eq!(Mixed::offset_of_ab(), offset_of!(Mixed, ab));
+    assert_eq!(Mixed::offset_of_AB(), offset_of!(Mixed, AB));
+    assert_eq!(Mixed::offset_of_a_b(), offset_of!(Mixed, a_b));
+}
+
+#[test]
+fn project_with_offset_fns() {
+    let mut record = MaybeUninit::<Record>::uninit();
+    partial_init!(record => { id: 7, r#type: 2 });
+    unsafe {
+        project_by_offset_mut::<_, &str>(&mut record, Record::offset_of_name()).write("seven");
+        assert_eq!(
+            project_by_offset::<_, u64>(&record, Record::offset_of_id()).assume_init(),
+            7
+        );
+        let record = record.assume_init();
+        assert_eq!((record.r#type, record.name), (2, "seven"));
+    }
+}
+
+#[test]
+fn field_offset_from_pointers() {
+    use project_uninit::field_offset;
+
+    let record = MaybeUninit::<Record>::uninit();
+    let base = record.as_ptr();
+    let name = unsafe { core::ptr::addr_of!((*base).name) };
+    assert_eq!(field_offset(base, name), Record::offset_of_name());
+    assert_eq!(field_offset(base, base), 0);
+}
@@ -0,0 +1,33 @@
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{init_err, init_ok, init_some};
+
+#[test]
+fn init_some_returns_payload() {
+    let mut slot = MaybeUninit::<Option<(u8, String)>>::uninit();
+    let payload = init_some!(slot => (1, "a".into()));
+    payload.1.push('b');
+    assert_eq!(unsafe { slot.assume_init() }, Some((1, "ab".into())));
+}
+
+#[test]
+fn init_some_with_niche() {
+    let mut slot = Box::new(MaybeUninit::<Option<Box<u32>>>::uninit());
+    **init_some!(slot => Box::new(4)) *= 2;
+    assert_eq!(*unsafe { slot.assume_init() }, Some(Box::new(8)));
+}
+
+#[test]
+fn init_result_variants() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Result<Rc<()>, Rc<()>>>::uninit();
+    let slot_ref = &mut slot;
+    assert!(Rc::ptr_eq(init_ok!(slot_ref => rc.clone()), &rc));
+    unsafe { slot.assume_init_drop() };
+    assert!(Rc::ptr_eq(init_err!(slot => rc.clone()), &rc));
+    assert!(unsafe { slot.assume_init_ref() }.is_err());
+    assert_eq!(Rc::strong_count(&rc), 2);
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,50 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{project_out, Out};
+
+#[derive(Debug, PartialEq)]
+struct Packet {
+    kind: u8,
+    body: (String, [u16; 3]),
+}
+
+fn fill_body(mut out: Out<'_, String>) {
+    out.reborrow().write("draft".into());
+    // the first value is leaked rather than dropped
+    out.write("final".into());
+}
+
+#[test]
+fn write_fields_through_out() {
+    let mut x = MaybeUninit::<Packet>::uninit();
+    let (kind, body, first, rest) = project_out!(x => {
+        kind,
+        body => 0,
+        body => 1 => [0],
+        body => 1 => [1],
+    });
+    *kind.write(1) += 1;
+    fill_body(body);
+    first.write(10);
+    rest.write(11);
+    project_out!(&mut x => body => 1 => [2]).write(12);
+
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Packet {
+            kind: 2,
+            body: ("final".into(), [10, 11, 12])
+        }
+    );
+}
+
+#[test]
+fn raw_pointer_writes() {
+    let mut x = MaybeUninit::<(u32, u64)>::uninit();
+    let (mut a, b) = project_out!(x => { 0, 1 });
+    unsafe { a.as_mut_ptr().write(5) };
+    b.write(6);
+    assert_eq!(unsafe { x.assume_init() }, (5, 6));
+    let mut y = MaybeUninit::<u8>::uninit();
+    assert_eq!(format!("{:?}", Out::from(&mut y)), "Out { .. }");
+}
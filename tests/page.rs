@@ -0,0 +1,103 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, slot_from_bytes, PageError, PageView};
+
+#[repr(C)]
+struct Header {
+    lsn: u64,
+    count: u16,
+    free_start: u16,
+}
+
+#[repr(C, align(8))]
+struct Page(MaybeUninit<[u8; 64]>);
+
+#[test]
+fn carve_header_and_slots() {
+    let mut page = Page(MaybeUninit::uninit());
+    let view = PageView::new(&mut page.0);
+    let (header, rest) = view.split_first::<Header>().unwrap();
+    assert_eq!(rest.offset(), 16);
+    let (slots, rest) = rest.split_first_n::<u16>(2).unwrap();
+    let (free, entry) = rest.split_last::<u32>().unwrap();
+    assert_eq!((free.offset(), free.len()), (20, 40));
+
+    partial_init!(header => { lsn: 7, count: 2, free_start: 20 });
+    slots[0].write(60);
+    slots[1].write(60);
+    entry.write(0xdead_beef);
+
+    // only read the bytes that were written
+    let read = |at: usize, len: usize| unsafe {
+        core::slice::from_raw_parts((page.0.as_ptr() as *const u8).add(at), len)
+    };
+    assert_eq!(read(0, 8), 7u64.to_ne_bytes());
+    assert_eq!(read(16, 2), 60u16.to_ne_bytes());
+    assert_eq!(read(60, 4), 0xdead_beefu32.to_ne_bytes());
+}
+
+#[test]
+fn bounds_and_alignment_errors() {
+    let mut page = Page(MaybeUninit::uninit());
+    let mut view = PageView::new(&mut page.0);
+    assert_eq!(
+        view.get::<u32>(62).unwrap_err(),
+        PageError::OutOfBounds {
+            offset: 62,
+            size: 4,
+            end: 64
+        }
+    );
+    assert_eq!(
+        view.get::<u32>(2).unwrap_err(),
+        PageError::Misaligned {
+            offset: 2,
+            align: 4
+        }
+    );
+    assert!(view.get::<u32>(60).is_ok());
+
+    let (_, rest) = view.split_at(3).unwrap();
+    let rest = rest.align_to(4).unwrap();
+    assert_eq!(rest.offset(), 4);
+    assert!(rest.split_first_n::<u32>(16).is_err());
+
+    let mut page = Page(MaybeUninit::uninit());
+    let view = PageView::new(&mut page.0);
+    let (rest, _) = view.split_last::<u8>().unwrap();
+    assert_eq!(
+        rest.split_last::<u32>().unwrap_err(),
+        PageError::Misaligned {
+            offset: 59,
+            align: 4
+        }
+    );
+}
+
+#[test]
+fn slot_from_scratch_bytes() {
+    let mut scratch = [MaybeUninit::<u8>::uninit(); 32];
+    let offset = scratch.as_ptr().align_offset(8);
+    let scratch = &mut scratch[offset..offset + 24];
+
+    let (header, rest) = slot_from_bytes::<Header>(scratch).unwrap();
+    partial_init!(header => { lsn: 9, count: 0, free_start: 16 });
+    assert_eq!(unsafe { header.assume_init_ref() }.lsn, 9);
+    assert_eq!(rest.len(), 24 - core::mem::size_of::<Header>());
+
+    assert_eq!(
+        slot_from_bytes::<Header>(rest).unwrap_err(),
+        PageError::OutOfBounds {
+            offset: 0,
+            size: 16,
+            end: 8
+        }
+    );
+    assert_eq!(
+        slot_from_bytes::<u32>(&mut rest[1..]).unwrap_err(),
+        PageError::Misaligned {
+            offset: 0,
+            align: 4
+        }
+    );
+}
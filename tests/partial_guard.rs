@@ -0,0 +1,104 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+use project_uninit::PartialGuard;
+
+#[derive(PartialGuard, Debug)]
+struct Session {
+    user: Rc<String>,
+    id: u64,
+    cache: Vec<Rc<String>>,
+}
+
+#[derive(PartialGuard)]
+#[repr(C, align(8))]
+struct Pair<'a, T>(&'a str, T);
+
+#[test]
+fn dropping_the_guard_drops_initialized_fields() {
+    let user = Rc::new(String::from("alice"));
+    let mut slot = MaybeUninit::<Session>::uninit();
+    let mut guard = SessionPartialGuard::new(&mut slot);
+    guard.set_user(user.clone());
+    guard.set_cache(vec![user.clone(), user.clone()]);
+    assert_eq!(Rc::strong_count(&user), 4);
+    assert!(!guard.is_complete());
+    assert_eq!(guard.get_id(), None);
+
+    // setting a field again drops its previous value
+    guard.set_cache(vec![user.clone()]);
+    assert_eq!(Rc::strong_count(&user), 3);
+
+    drop(guard);
+    assert_eq!(Rc::strong_count(&user), 1);
+}
+
+#[test]
+fn panic_drops_initialized_fields() {
+    let user = Rc::new(String::from("bob"));
+    let mut slot = MaybeUninit::<Session>::uninit();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut guard = SessionPartialGuard::new(&mut slot);
+        guard.set_user(user.clone());
+        guard.set_id("not a number".parse().unwrap());
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&user), 1);
+}
+
+#[test]
+fn finish_once_complete() {
+    let user = Rc::new(String::from("carol"));
+    let mut slot = MaybeUninit::<Session>::uninit();
+    let mut guard = SessionPartialGuard::new(&mut slot);
+    guard.set_user(user.clone());
+    guard.set_id(7);
+    let mut guard = guard.finish().unwrap_err();
+    assert_eq!(
+        format!("{:?}", guard),
+        "SessionPartialGuard { user: true, id: true, cache: false }"
+    );
+
+    unsafe {
+        guard.uninit_cache().write(Vec::with_capacity(4));
+        guard.mark_cache_init();
+    }
+    guard.get_cache_mut().unwrap().push(user.clone());
+    *guard.get_id_mut().unwrap() += 1;
+
+    let session = guard.finish().unwrap();
+    assert_eq!((session.id, session.cache.len()), (8, 1));
+    assert_eq!(Rc::strong_count(&user), 3);
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&user), 1);
+}
+
+#[test]
+fn take_fields_back() {
+    let user = Rc::new(String::from("dave"));
+    let mut slot = MaybeUninit::<Session>::uninit();
+    let mut guard = SessionPartialGuard::new(&mut slot);
+    guard.set_user(user.clone());
+    let taken = guard.take_user().unwrap();
+    assert!(guard.take_user().is_none());
+    drop(guard);
+    assert_eq!(Rc::strong_count(&user), 2);
+    drop(taken);
+    assert_eq!(Rc::strong_count(&user), 1);
+}
+
+#[test]
+fn generic_tuple_struct() {
+    let label = String::from("pair");
+    let mut slot = MaybeUninit::<Pair<'_, Rc<()>>>::uninit();
+    let mut guard = PairPartialGuard::new(&mut slot);
+    guard.set_0(&label);
+    let rc = guard.set_1(Rc::new(())).clone();
+    assert_eq!(guard.get_0(), Some(&"pair"));
+    let pair = guard.finish().unwrap();
+    assert_eq!(pair.0, "pair");
+    assert_eq!(Rc::strong_count(&rc), 2);
+}
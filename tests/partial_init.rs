@@ -1,6 +1,6 @@
 use core::mem::MaybeUninit;
 
-use project_uninit::partial_init;
+use project_uninit::{partial_init, partial_init_hashed, partial_init_unaligned, reinit};
 
 #[derive(Debug, PartialEq, Eq)]
 struct Foo {
@@ -72,3 +72,215 @@ fn init_escaping_reference() {
     *a = 100;
     assert_eq!(unsafe { x.assume_init() }, (100, 20));
 }
+
+#[test]
+fn partial_init_grouped_paths() {
+    let mut x = MaybeUninit::<Foo>::uninit();
+
+    let (b0, b10, b11) = partial_init!(x => b => { 0: -1, 1 => { 0: 2, 1: -3 } });
+    assert_eq!((*b0, *b10, *b11), (-1, 2, -3));
+
+    let (a, b2) = partial_init!(x => {
+        a: 4,
+        b => { 2: "grouped", },
+    });
+    *a += 1;
+    assert_eq!(*b2, "grouped");
+
+    let (b1, b0) = partial_init!(x => { b => { 1: (6, 7) }, b => 0: 8 });
+    assert_eq!((*b1, *b0), ((6, 7), 8));
+
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Foo {
+            a: 5,
+            b: (8, (6, 7), "grouped"),
+        }
+    );
+}
+
+#[test]
+fn partial_init_named_bindings() {
+    let mut x = MaybeUninit::<Foo>::uninit();
+
+    partial_init!(x => {
+        b => 2: "named" => let b2,
+        a: 1 => let a,
+        b => { 0: 3 => let b0, 1: (4, 5) },
+    });
+    *a += 1;
+    assert_eq!((*b2, *b0), ("named", 3));
+
+    partial_init!(x => {
+        b => 1 => 0: 6,
+        b => 1 => 1: |(b10,)| => *b10 as i8 + 1 => let b11,
+    });
+    assert_eq!(*b11, 7);
+
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Foo {
+            a: 2,
+            b: (3, (6, 7), "named"),
+        }
+    );
+}
+
+#[derive(Default)]
+struct RecordingHasher(Vec<u8>);
+
+impl core::hash::Hasher for RecordingHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        0
+    }
+}
+
+#[test]
+fn partial_init_hashed_writes_field_bytes() {
+    let mut x = MaybeUninit::<(u32, [u8; 3], u16)>::uninit();
+    let mut hasher = RecordingHasher::default();
+
+    let (a, c) = unsafe { partial_init_hashed!(hasher, x => { 2: 0x0102, 0: 0x03040506 }) };
+    assert_eq!((*a, *c), (0x0102, 0x03040506));
+    let b = unsafe { partial_init_hashed!(&mut hasher, x => 1 = [7, 8, 9]) };
+    assert_eq!(*b, [7, 8, 9]);
+
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&0x0102u16.to_ne_bytes());
+    expected.extend_from_slice(&0x03040506u32.to_ne_bytes());
+    expected.extend_from_slice(&[7, 8, 9]);
+    assert_eq!(hasher.0, expected);
+}
+
+#[test]
+fn reinit_drops_old_values() {
+    use std::rc::Rc;
+
+    struct Live {
+        a: Rc<()>,
+        b: (u8, Rc<()>),
+    }
+
+    let old = Rc::new(());
+    let new = Rc::new(());
+    let mut x = MaybeUninit::new(Live {
+        a: old.clone(),
+        b: (1, old.clone()),
+    });
+    let (a, b0, b1) = unsafe {
+        reinit!(x => {
+            a: new.clone(),
+            b => 0: 2,
+            b => 1: new.clone(),
+        })
+    };
+    assert!(Rc::ptr_eq(a, b1));
+    assert_eq!(*b0, 2);
+    assert_eq!(Rc::strong_count(&old), 1);
+    assert_eq!(Rc::strong_count(&new), 3);
+
+    unsafe { reinit!(x => a = old.clone()) };
+    assert_eq!(Rc::strong_count(&new), 2);
+    drop(unsafe { x.assume_init() });
+    assert_eq!(Rc::strong_count(&old), 1);
+    assert_eq!(Rc::strong_count(&new), 1);
+}
+
+#[test]
+fn closures_see_earlier_fields() {
+    let mut x = MaybeUninit::<Foo>::uninit();
+    let (text, len, first, sum, last) = partial_init!(x => {
+        b => 2: "hello",
+        a: |(text,)| => text.len(),
+        b => 1 => 0: |(_, len)| => *len as u8 * 2,
+        b => 0: |(text, len, first): (&&str, &usize, &u8)| => {
+            text.len() as i32 + *len as i32 + *first as i32
+        },
+        b => 1 => 1: -1,
+    });
+    assert_eq!((*text, *len, *first, *sum, *last), ("hello", 5, 10, 20, -1));
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Foo {
+            a: 5,
+            b: (20, (10, -1), "hello"),
+        }
+    );
+}
+
+#[test]
+fn plain_closure_is_a_value() {
+    let mut x = MaybeUninit::<(fn(u8) -> u8, u8)>::uninit();
+    let (f, _) = partial_init!(x => { 0: |v| v + 1, 1: |(f,)| => f(1) });
+    assert_eq!(f(2), 3);
+    assert_eq!(unsafe { x.assume_init() }.1, 2);
+}
+
+macro_rules! wide_struct_test {
+    ($($field:ident)*) => {
+        #[test]
+        fn partial_init_wide_struct() {
+            struct Wide { $($field: usize),* }
+
+            let mut x = MaybeUninit::<Wide>::uninit();
+            let mut n = 0;
+            partial_init!(x => { $($field: { n += 1; n }),* });
+            let x = unsafe { x.assume_init() };
+            assert_eq!(0 $(+ x.$field)*, n * (n + 1) / 2);
+        }
+    };
+}
+
+wide_struct_test!(
+    f00 f01 f02 f03 f04 f05 f06 f07 f08 f09 f10 f11 f12 f13 f14 f15
+    f16 f17 f18 f19 f20 f21 f22 f23 f24 f25 f26 f27 f28 f29 f30 f31
+    f32 f33 f34 f35 f36 f37 f38 f39 f40 f41 f42 f43 f44 f45 f46 f47
+    f48 f49 f50 f51 f52 f53 f54 f55 f56 f57 f58 f59 f60 f61 f62 f63
+);
+
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    value: u64,
+    pair: (u8, u32),
+    buf: [u16; 3],
+}
+
+#[test]
+fn partial_init_unaligned_packed() {
+    let mut x = MaybeUninit::<Packed>::uninit();
+    let (tag, value, pair1) = partial_init_unaligned!(x => {
+        tag: 7,
+        value: u64::MAX - 1,
+        pair => 1: 0xdead_beef,
+    });
+    assert_eq!(
+        unsafe { (tag.read(), value.read_unaligned(), pair1.read_unaligned()) },
+        (7, u64::MAX - 1, 0xdead_beef)
+    );
+
+    partial_init_unaligned!(x => { pair => 0: 1, buf => [0]: 2, buf => [2]: 4 });
+    let buf1 = partial_init_unaligned!(x => buf => [1] = 3);
+    assert_eq!(unsafe { buf1.read_unaligned() }, 3);
+
+    let x = unsafe { x.assume_init() };
+    assert_eq!({ x.value }, u64::MAX - 1);
+    assert_eq!({ x.pair }, (1, 0xdead_beef));
+    assert_eq!({ x.buf }, [2, 3, 4]);
+}
+
+#[cfg(not(feature = "test-support"))]
+#[test]
+fn try_partial_init_plain_error() {
+    let mut x = MaybeUninit::<(u8, u16)>::uninit();
+    let r: Result<_, core::num::ParseIntError> =
+        project_uninit::try_partial_init!(x => { 0: "7".parse(), 1: "300".parse() });
+    let (a, b) = r.unwrap();
+    assert_eq!((*a, *b), (7, 300));
+    let err = project_uninit::try_partial_init!(x => 0 = "300".parse::<u8>());
+    assert!(err.is_err());
+}
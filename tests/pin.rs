@@ -0,0 +1,50 @@
+use core::marker::PhantomPinned;
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+
+use project_uninit::project_uninit_pin;
+
+struct Waiter {
+    next: *const Waiter,
+    woken: bool,
+    _pin: PhantomPinned,
+}
+
+struct Queue {
+    head: Waiter,
+    len: usize,
+    ids: [u32; 2],
+}
+
+#[test]
+fn project_pinned_and_unpinned_fields() {
+    let mut slot = Box::pin(MaybeUninit::<Queue>::uninit());
+    let (head, len) = unsafe { project_uninit_pin!(slot.as_mut() => { head, unpin len }) };
+    len.write(1);
+    let head_ptr = head.as_ptr();
+    let (next, woken) = project_uninit_pin!(head => { unpin next, unpin woken });
+    next.write(head_ptr);
+    woken.write(false);
+    let (id0, id1) = project_uninit_pin!(slot.as_mut() => { unpin ids => [0], unpin ids => [1] });
+    id0.write(3);
+    id1.write(4);
+
+    let queue = unsafe { slot.as_mut().map_unchecked_mut(|s| s.assume_init_mut()) };
+    assert_eq!(queue.len, 1);
+    assert_eq!(queue.ids, [3, 4]);
+    assert!(!queue.head.woken);
+    assert!(core::ptr::eq(queue.head.next, &queue.head));
+}
+
+#[test]
+fn project_single_fields() {
+    let mut slot = Box::pin(MaybeUninit::<Queue>::uninit());
+    project_uninit_pin!(slot.as_mut() => unpin len).write(5);
+    project_uninit_pin!(slot.as_mut() => unpin head => woken).write(true);
+    let head: Pin<&mut MaybeUninit<Waiter>> = unsafe { project_uninit_pin!(slot.as_mut() => head) };
+    assert_eq!(head.as_ptr(), unsafe {
+        core::ptr::addr_of!((*slot.as_ptr()).head)
+    });
+    let len = project_uninit_pin!(slot.as_mut() => unpin len);
+    assert_eq!(unsafe { len.assume_init_read() }, 5);
+}
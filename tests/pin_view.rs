@@ -0,0 +1,58 @@
+#![cfg(feature = "derive")]
+
+use core::marker::PhantomPinned;
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+
+use project_uninit::{assume_init_pin, ProjectUninit, UninitPinView, UninitPinViewExt};
+
+struct Waker {
+    woken: bool,
+    _pin: PhantomPinned,
+}
+
+#[derive(ProjectUninit)]
+struct Task<T> {
+    #[uninit(pin)]
+    waker: Waker,
+    #[uninit(pin)]
+    output: T,
+    id: u32,
+}
+
+#[derive(ProjectUninit)]
+struct Pair(#[uninit(pin)] u8, String);
+
+fn assert_unpin<T: Unpin>() {}
+
+#[test]
+fn pinned_fields_are_projected_pinned() {
+    let mut slot = Box::pin(MaybeUninit::<Task<Vec<u8>>>::uninit());
+    let fields = slot.as_mut().uninit_fields_pin();
+    let _: Pin<&mut MaybeUninit<Waker>> = fields.waker;
+    let output: Pin<&mut MaybeUninit<Vec<u8>>> = fields.output;
+    Pin::get_mut(output).write(vec![1, 2]);
+    fields.id.write(3);
+    unsafe {
+        fields.waker.get_unchecked_mut().write(Waker {
+            woken: false,
+            _pin: PhantomPinned,
+        });
+    }
+
+    let task: Pin<&mut Task<Vec<u8>>> = unsafe { assume_init_pin(slot.as_mut()) };
+    assert_eq!((task.id, task.output.as_slice()), (3, &[1, 2][..]));
+    assert!(!task.waker.woken);
+    unsafe { slot.as_mut().get_unchecked_mut().assume_init_drop() };
+}
+
+#[test]
+fn tuple_struct_pin_view() {
+    let mut slot = MaybeUninit::<Pair>::uninit();
+    let fields = Pair::view_pin(Pin::new(&mut slot));
+    Pin::get_mut(fields.0).write(1);
+    fields.1.write("one".into());
+    let pair = unsafe { slot.assume_init() };
+    assert_eq!((pair.0, pair.1.as_str()), (1, "one"));
+    assert_unpin::<Pair>();
+}
@@ -0,0 +1,86 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{fill_poison, partial_init, uninit_poisoned, POISON_BYTE};
+
+#[test]
+fn uninit_poisoned_fills_pattern() {
+    let mut x = uninit_poisoned::<(u8, [u8; 3], u64)>();
+    partial_init!(x => 0 = 1);
+    let (a, b, c) = unsafe { x.assume_init() };
+    assert_eq!(a, 1);
+    assert_eq!(b, [POISON_BYTE; 3]);
+    assert_eq!(c, u64::from_ne_bytes([POISON_BYTE; 8]));
+}
+
+#[test]
+fn fill_poison_overwrites_slot() {
+    let mut x = MaybeUninit::new([1u16, 2, 3]);
+    fill_poison(&mut x);
+    assert_eq!(unsafe { x.assume_init() }, [0xa5a5; 3]);
+}
+
+#[cfg(all(feature = "poison", feature = "derive", debug_assertions))]
+#[test]
+fn dropped_fields_are_poisoned() {
+    use project_uninit::{InitTracker, ProjectUninit};
+
+    #[derive(ProjectUninit)]
+    struct Foo {
+        a: u32,
+        b: u32,
+    }
+
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { a: 1, b: 2 });
+        tracker.set_init(0);
+        tracker.set_init(1);
+    }
+    tracker.drop_field(1);
+    let b = unsafe { *project_uninit::project_ptr!(tracker.slot().as_ptr() => b) };
+    assert_eq!(b, 0xa5a5a5a5);
+}
+
+#[cfg(all(feature = "poison", feature = "derive", debug_assertions))]
+#[test]
+fn tracked_slots_are_poisoned() {
+    use project_uninit::{InitTracker, PartialGuard, ProjectUninit};
+
+    #[derive(ProjectUninit, PartialGuard)]
+    struct Foo {
+        a: u32,
+        b: u32,
+    }
+
+    let mut slot = MaybeUninit::new(Foo { a: 1, b: 2 });
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe { partial_init!(tracker.slot_mut() => a = 3) };
+    let b = unsafe { *project_uninit::project_ptr!(tracker.slot().as_ptr() => b) };
+    assert_eq!(b, 0xa5a5a5a5);
+    drop(tracker);
+
+    let mut slot = MaybeUninit::new(Foo { a: 1, b: 2 });
+    let mut guard = FooPartialGuard::new(&mut slot);
+    guard.set_b(4);
+    drop(guard);
+    let a = unsafe { *project_uninit::project_ptr!(slot.as_ptr() => a) };
+    assert_eq!(a, 0xa5a5a5a5);
+}
+
+#[cfg(all(feature = "poison", debug_assertions))]
+#[test]
+fn created_slots_are_poisoned() {
+    let pair = project_uninit::init_with::<(u8, u16)>(|slot| {
+        partial_init!(slot => 0 = 1);
+        // oops, forgot to initialize the second field
+        unsafe { slot.assume_init_mut() }
+    });
+    assert_eq!(pair, (1, 0xa5a5));
+
+    #[cfg(feature = "alloc")]
+    {
+        let boxed = project_uninit::boxed_uninit::<[u8; 64]>();
+        assert_eq!(unsafe { boxed.assume_init_read() }, [POISON_BYTE; 64]);
+    }
+}
@@ -0,0 +1,77 @@
+use core::cell::Cell;
+
+use project_uninit::project_cell;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span {
+    start: u32,
+    end: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Token {
+    span: Span,
+    kind: u8,
+}
+
+#[test]
+fn overlapping_fields() {
+    let token = Cell::new(Token {
+        span: Span { start: 1, end: 4 },
+        kind: 0,
+    });
+    let (span, end, again) = project_cell!(token => { span, span => end, span => end });
+    end.set(6);
+    assert_eq!(again.get(), 6);
+    assert_eq!(span.get(), Span { start: 1, end: 6 });
+    span.set(Span { start: 0, end: 0 });
+    assert_eq!(end.get(), 0);
+
+    project_cell!(token => kind).set(3);
+    assert_eq!(token.get().kind, 3);
+}
+
+#[test]
+fn project_cell_from_array_of_cells() {
+    let mut pairs = [(1u8, 2u16), (3, 4)];
+    let cells = Cell::from_mut(&mut pairs).as_array_of_cells();
+    for pair in cells {
+        let (a, b) = project_cell!(pair => { 0, 1 });
+        b.set(b.get() + a.get() as u16);
+    }
+    assert_eq!(cells[1].get(), (3, 7));
+}
+
+#[test]
+#[should_panic(expected = "field `boxed.0` is not stored in the cell")]
+fn field_behind_deref_panics() {
+    struct Boxed {
+        boxed: Box<(u8, u8)>,
+    }
+    let cell = Cell::new(Boxed {
+        boxed: Box::new((1, 2)),
+    });
+    project_cell!(cell => boxed => 0);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn strict_paths() {
+    use project_uninit::ProjectUninit;
+
+    #[derive(ProjectUninit)]
+    struct Counter {
+        value: u64,
+        window: [u16; 4],
+    }
+
+    let counter = Cell::new(Counter {
+        value: 1,
+        window: [0; 4],
+    });
+    let (value, last) = project_cell!(counter => strict { value, window => [3] });
+    value.set(value.get() * 10);
+    last.set(9);
+    let counter = counter.into_inner();
+    assert_eq!((counter.value, counter.window), (10, [0, 0, 0, 9]));
+}
@@ -0,0 +1,60 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use project_uninit::{partial_init, project_cells, project_uninit};
+
+struct Ring {
+    head: AtomicUsize,
+    slots: [UnsafeCell<u32>; 4],
+}
+
+// each slot is written by the one thread that claimed its index
+unsafe impl Sync for Ring {}
+
+#[test]
+fn write_cells_from_many_threads() {
+    let mut ring = MaybeUninit::<Ring>::uninit();
+    partial_init!(ring => head = AtomicUsize::new(0));
+    let ring = &ring;
+    let head = unsafe { project_uninit!(ring => head).assume_init_ref() };
+
+    std::thread::scope(|s| {
+        for value in 0..4 {
+            s.spawn(move || {
+                let index = head.fetch_add(1, Ordering::Relaxed);
+                let (a, b, c, d) = project_cells!(ring => {
+                    slots => [0],
+                    slots => [1],
+                    slots => [2],
+                    slots => [3],
+                });
+                unsafe { [a, b, c, d][index].write(value * 10) };
+            });
+        }
+    });
+
+    let slots = unsafe { &(*ring.as_ptr()).slots };
+    let mut values: Vec<u32> = slots.iter().map(|v| unsafe { *v.get() }).collect();
+    values.sort_unstable();
+    assert_eq!(values, [0, 10, 20, 30]);
+}
+
+#[test]
+fn nested_and_repeated_cells() {
+    struct Node {
+        pair: (UnsafeCell<u8>, UnsafeCell<[u16; 2]>),
+    }
+
+    let slot = MaybeUninit::<Node>::uninit();
+    let (first, again, second) = project_cells!(slot => { pair => 0, pair => 0, pair => 1 });
+    assert_eq!(first, again);
+    unsafe {
+        first.write(1);
+        second.write([2, 3]);
+        *again += 1;
+    }
+    let node = unsafe { slot.assume_init() };
+    assert_eq!(node.pair.0.into_inner(), 2);
+    assert_eq!(node.pair.1.into_inner(), [2, 3]);
+}
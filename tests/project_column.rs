@@ -0,0 +1,80 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{project_column_mut, UninitColumn};
+
+#[derive(Debug, PartialEq)]
+struct Entity {
+    name: String,
+    transform: ([f32; 2], f32),
+    flags: u8,
+}
+
+#[test]
+fn init_columns_of_slice() {
+    let mut entities: Vec<MaybeUninit<Entity>> = (0..3).map(|_| MaybeUninit::uninit()).collect();
+    let (mut names, mut pos, mut angle, mut flags) = project_column_mut!(entities => {
+        name,
+        transform => 0,
+        transform => 1,
+        flags,
+    });
+    assert_eq!(
+        (names.len(), names.stride()),
+        (3, core::mem::size_of::<Entity>())
+    );
+
+    names.clone_from_slice(&["a".to_string(), "b".into(), "c".into()]);
+    pos.fill_with(|i| [i as f32; 2]);
+    angle.fill(0.5);
+    for (i, flag) in flags.iter_mut().enumerate() {
+        flag.write(1 << i);
+    }
+    *flags.write(2, 0) += 7;
+    unsafe { pos.get_mut(0).unwrap().assume_init_mut()[1] = -1.0 };
+    assert!(pos.get_mut(3).is_none());
+
+    let entities: Vec<Entity> = entities
+        .into_iter()
+        .map(|e| unsafe { e.assume_init() })
+        .collect();
+    assert_eq!(
+        entities[0],
+        Entity {
+            name: "a".into(),
+            transform: ([0.0, -1.0], 0.5),
+            flags: 1,
+        }
+    );
+    assert_eq!(entities[2].flags, 7);
+    assert_eq!(entities[2].transform.0, [2.0; 2]);
+}
+
+#[test]
+fn init_nested_column_of_array() {
+    let mut grid = MaybeUninit::<[([u16; 3], bool); 4]>::uninit();
+    let mut cells: UninitColumn<'_, u16> = project_column_mut!(grid => 0 => [1]);
+    cells.copy_from_slice(&[1, 2, 3, 4]);
+    let (mut first, mut last, mut set) = project_column_mut!(grid => { 0 => [0], 0 => [2], 1 });
+    first.fill(0);
+    last.fill(9);
+    set.fill(true);
+
+    let grid = unsafe { grid.assume_init() };
+    assert_eq!(grid[3], ([0, 4, 9], true));
+}
+
+#[test]
+fn empty_column() {
+    let mut empty: [MaybeUninit<Entity>; 0] = [];
+    let mut flags = project_column_mut!(empty => flags);
+    flags.fill(1);
+    assert!(flags.is_empty());
+    assert_eq!(flags.iter_mut().count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "source slice length does not match column length")]
+fn copy_from_slice_checks_length() {
+    let mut pairs = [MaybeUninit::<(u8, u8)>::uninit(); 2];
+    project_column_mut!(pairs => 0).copy_from_slice(&[1, 2, 3]);
+}
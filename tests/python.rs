@@ -0,0 +1,77 @@
+#![cfg(all(feature = "pyo3", feature = "derive"))]
+
+use std::rc::Rc;
+
+use project_uninit::pyo3::exceptions::PyValueError;
+use project_uninit::pyo3::prelude::*;
+use project_uninit::{
+    init_array, partial_init, project_uninit_mut, py_new_in_place, ProjectUninit,
+};
+
+#[derive(ProjectUninit)]
+struct Frame {
+    id: u64,
+    pixels: [u8; 1 << 20],
+}
+
+#[pyclass]
+struct PyFrame {
+    frame: Box<Frame>,
+}
+
+impl From<Box<Frame>> for PyFrame {
+    fn from(frame: Box<Frame>) -> Self {
+        Self { frame }
+    }
+}
+
+#[derive(ProjectUninit)]
+struct Counted {
+    a: Rc<()>,
+    b: Rc<()>,
+}
+
+#[pyclass(unsendable)]
+struct PyCounted {
+    _counted: Box<Counted>,
+}
+
+impl From<Box<Counted>> for PyCounted {
+    fn from(counted: Box<Counted>) -> Self {
+        Self { _counted: counted }
+    }
+}
+
+#[test]
+fn initializes_payload_in_place() {
+    Python::initialize();
+    Python::attach(|py| {
+        let frame = py_new_in_place::<PyFrame, _>(py, |t| unsafe {
+            partial_init!(t.slot_mut() => id = 9);
+            t.set_init(0);
+            init_array(project_uninit_mut!(t.slot_mut() => pixels), |_| 0xff);
+            t.set_init(1);
+        })
+        .unwrap();
+        let frame = frame.borrow(py);
+        assert_eq!(frame.frame.id, 9);
+        assert!(frame.frame.pixels.iter().all(|&p| p == 0xff));
+    });
+}
+
+#[test]
+fn incomplete_payload_is_an_error() {
+    Python::initialize();
+    let rc = Rc::new(());
+    Python::attach(|py| {
+        let err = py_new_in_place::<PyCounted, _>(py, |t| unsafe {
+            partial_init!(t.slot_mut() => b = rc.clone());
+            t.set_init(1);
+        })
+        .err()
+        .unwrap();
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert!(err.to_string().ends_with("were not initialized: a"));
+    });
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,87 @@
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{drop_field_in_place, partial_init, read_field, replace_field, swap_field};
+
+struct Foo {
+    a: Rc<()>,
+    b: (u8, [Rc<()>; 2]),
+}
+
+#[test]
+fn read_single_field() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: rc.clone(), b: (1, [rc.clone(), rc.clone()]) });
+    assert_eq!(Rc::strong_count(&rc), 4);
+
+    let a = unsafe { read_field!(x => a) };
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(a);
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    partial_init!(x => a = rc.clone());
+    drop(unsafe { x.assume_init() });
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn read_multiple_fields() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: rc.clone(), b => 0: 5, b => 1 => [0]: rc.clone() });
+
+    let (a, n, first) = unsafe { read_field!(&mut x => { a, b => 0, b => 1 => [0] }) };
+    assert_eq!(n, 5);
+    assert!(Rc::ptr_eq(&a, &first));
+    drop((a, first));
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[cfg(all(feature = "poison", debug_assertions))]
+#[test]
+fn read_field_poisons_bytes() {
+    let mut x = MaybeUninit::<(u32, u32)>::uninit();
+    partial_init!(x => { 0: 1, 1: 2 });
+    assert_eq!(unsafe { read_field!(x => 1) }, 2);
+    let bytes = unsafe { *(x.as_ptr() as *const [u8; 8]) };
+    assert_eq!(bytes[4..], [project_uninit::POISON_BYTE; 4]);
+}
+
+#[test]
+fn replace_and_swap_fields() {
+    let rc = Rc::new(());
+    let other = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: rc.clone(), b: (1, [rc.clone(), rc.clone()]) });
+
+    let (a, n) = unsafe { replace_field!(x => { a: other.clone(), b => 0: 2 }) };
+    assert!(Rc::ptr_eq(&a, &rc));
+    assert_eq!(n, 1);
+    drop(a);
+
+    let mut spare = other.clone();
+    unsafe { swap_field!(x => b => 1 => [1], &mut spare) };
+    assert!(Rc::ptr_eq(&spare, &rc));
+    assert_eq!(Rc::strong_count(&other), 3);
+
+    drop(spare);
+    drop(unsafe { x.assume_init() });
+    assert_eq!(Rc::strong_count(&rc), 1);
+    assert_eq!(Rc::strong_count(&other), 1);
+}
+
+#[test]
+fn drop_and_reinit_fields() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: rc.clone(), b: (1, [rc.clone(), rc.clone()]) });
+
+    let (a, first) = unsafe { drop_field_in_place!(x => { a, b => 1 => [0] }) };
+    assert_eq!(Rc::strong_count(&rc), 2);
+    a.write(rc.clone());
+    first.write(rc.clone());
+
+    drop(unsafe { x.assume_init() });
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
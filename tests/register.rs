@@ -0,0 +1,64 @@
+use project_uninit::{project_regs, write_regs, Reg, RegisterBlock};
+
+#[repr(C)]
+#[derive(Default)]
+struct Dma {
+    source: usize,
+    len: u32,
+    control: u32,
+    status: [u8; 4],
+}
+
+fn start(block: &mut RegisterBlock<Dma>, buf: &[u8]) {
+    write_regs!(block => { source: buf.as_ptr() as usize, len: buf.len() as u32 });
+    let mut control: Reg<'_, u32> = project_regs!(block => control);
+    control.write_release(1);
+}
+
+#[test]
+fn write_and_project_registers() {
+    let mut mmio = Dma::default();
+    let buf = [1u8; 16];
+    let mut block = unsafe { RegisterBlock::new(&mut mmio as *mut Dma) };
+    assert_eq!(block.as_ptr(), &mut mmio as *mut Dma);
+    start(&mut block, &buf);
+
+    let (len, mut status, mut control) = project_regs!(block => { len, status => [3], control });
+    assert_eq!(len.read(), 16);
+    status.write(0x80);
+    control.modify(|c| c << 4);
+    assert_eq!(status.read_acquire(), 0x80);
+    assert_eq!(len.as_ptr(), &mut mmio.len as *mut u32);
+
+    assert_eq!(mmio.source, buf.as_ptr() as usize);
+    assert_eq!((mmio.control, mmio.status), (0x10, [0, 0, 0, 0x80]));
+}
+
+#[test]
+fn block_from_addr() {
+    let mut mmio = Dma::default();
+    let addr = (&mut mmio as *mut Dma).expose_provenance();
+    let mut block = unsafe { RegisterBlock::<Dma>::from_addr(addr) };
+    write_regs!(block => control = 7);
+    assert_eq!(mmio.control, 7);
+}
+
+#[test]
+#[should_panic(expected = "is null or misaligned")]
+fn block_from_misaligned_addr() {
+    let _ = unsafe { RegisterBlock::<Dma>::from_addr(0x4000_0002) };
+}
+
+#[test]
+fn registers_can_move_to_other_threads() {
+    let mut mmio = Dma::default();
+    let mut block = unsafe { RegisterBlock::new(&mut mmio as *mut Dma) };
+    let mut len = project_regs!(block => len);
+    std::thread::scope(|s| {
+        s.spawn(|| len.write(3));
+    });
+    std::thread::spawn(move || write_regs!(block => control = 2))
+        .join()
+        .unwrap();
+    assert_eq!((mmio.len, mmio.control), (3, 2));
+}
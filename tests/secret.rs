@@ -0,0 +1,51 @@
+use core::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use project_uninit::{partial_init_secret, zeroize_uninit, SecretGuard};
+
+#[derive(Debug, PartialEq)]
+struct Keys {
+    enc: [u8; 16],
+    mac: [u8; 16],
+}
+
+struct Pair {
+    ids: (u64, u64),
+}
+
+#[test]
+fn secret_fields_are_written() {
+    let mut slot = MaybeUninit::<Keys>::uninit();
+    let (enc, mac) = partial_init_secret!(slot => { enc: [1; 16], mac: [2; 16] });
+    assert_eq!((enc[0], mac[15]), (1, 2));
+    assert_eq!(unsafe { slot.assume_init_ref() }.enc[..2], [1, 1]);
+
+    let mut pair = MaybeUninit::<Pair>::uninit();
+    let id1 = partial_init_secret!(pair => ids => 1 = 9);
+    assert_eq!(*id1, 9);
+}
+
+#[test]
+fn guard_wipes_on_failure() {
+    let mut slot = MaybeUninit::<Keys>::uninit();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut guard = SecretGuard::new(&mut slot);
+        partial_init_secret!(guard.slot() => enc = [0xaa; 16]);
+        panic!("key derivation failed");
+    }));
+    assert!(result.is_err());
+    let bytes = unsafe { &*(slot.as_ptr() as *const [u8; 32]) };
+    assert_eq!(*bytes, [0; 32]);
+}
+
+#[test]
+fn finished_guard_keeps_value() {
+    let mut slot = MaybeUninit::<Keys>::uninit();
+    let mut guard = SecretGuard::new(&mut slot);
+    partial_init_secret!(guard.slot() => { enc: [3; 16], mac: [4; 16] });
+    let keys = unsafe { guard.finish() };
+    assert_eq!(*keys, Keys { enc: [3; 16], mac: [4; 16] });
+
+    zeroize_uninit(&mut slot);
+    assert_eq!(unsafe { slot.assume_init() }, Keys { enc: [0; 16], mac: [0; 16] });
+}
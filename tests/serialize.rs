@@ -0,0 +1,116 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, write_init_fields, FieldBytes};
+
+#[repr(C)]
+struct Foo {
+    a: u32,
+    b: (u16, u16),
+    c: u64,
+}
+
+#[test]
+fn write_subset_of_fields() {
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => { a: 7, b => 1: 9 });
+
+    let mut records = Vec::new();
+    let result: Result<(), ()> = unsafe {
+        write_init_fields!(
+            |f: FieldBytes| {
+                records.push((f.path, f.offset, f.bytes.to_vec()));
+                Ok(())
+            },
+            x => { a, b => 1 }
+        )
+    };
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        records,
+        [
+            ("a", 0, 7u32.to_ne_bytes().to_vec()),
+            ("b.1", 6, 9u16.to_ne_bytes().to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn write_stops_at_first_error() {
+    let x = MaybeUninit::new(Foo {
+        a: 1,
+        b: (2, 3),
+        c: 4,
+    });
+
+    let mut paths = Vec::new();
+    let result = unsafe {
+        write_init_fields!(
+            |f: FieldBytes| {
+                paths.push(f.path);
+                if f.path == "b" {
+                    Err(f.offset)
+                } else {
+                    Ok(())
+                }
+            },
+            x => { a, b, c }
+        )
+    };
+
+    assert_eq!(result, Err(4));
+    assert_eq!(paths, ["a", "b"]);
+}
+
+#[cfg(feature = "derive")]
+mod tracked {
+    use core::mem::MaybeUninit;
+
+    use project_uninit::{
+        tracked_init, write_init_fields_tracked, FieldBytes, InitTracker, ProjectUninit,
+    };
+
+    #[derive(ProjectUninit)]
+    #[repr(C)]
+    struct Bar {
+        a: u32,
+        b: u16,
+        c: u16,
+    }
+
+    #[test]
+    fn write_tracked_fields() {
+        let mut slot = MaybeUninit::<Bar>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+        tracked_init!(tracker => { c: 5, a: 3 });
+
+        let mut records = Vec::new();
+        let result: Result<(), ()> = unsafe {
+            write_init_fields_tracked!(
+                |f: FieldBytes| {
+                    records.push((f.path, f.offset, f.bytes.to_vec()));
+                    Ok(())
+                },
+                tracker => { c, a }
+            )
+        };
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            records,
+            [
+                ("c", 6, 5u16.to_ne_bytes().to_vec()),
+                ("a", 0, 3u32.to_ne_bytes().to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`b` is not initialized")]
+    fn write_uninitialized_tracked_field_panics() {
+        let mut slot = MaybeUninit::<Bar>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+        tracked_init!(tracker => a = 1);
+        let _: Result<(), ()> =
+            unsafe { write_init_fields_tracked!(|_: FieldBytes| Ok(()), tracker => { a, b }) };
+    }
+}
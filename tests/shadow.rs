@@ -0,0 +1,132 @@
+#![cfg(all(feature = "shadow", feature = "derive"))]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit_mut, shadow, InitTracker, ProjectUninit};
+
+#[derive(ProjectUninit, Debug, PartialEq)]
+struct Foo {
+    a: u32,
+    b: (u16, u16),
+}
+
+#[test]
+fn fully_written_fields_pass() {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { a: 1, b => 0: 2, b => 1: 3 });
+        tracker.set_init(0);
+        tracker.set_init(1);
+    }
+    assert_eq!(tracker.finish().unwrap(), &Foo { a: 1, b: (2, 3) });
+}
+
+#[test]
+#[should_panic(expected = "field `b` of `shadow::Foo` is not fully initialized (byte 2 of 4")]
+fn half_written_tuple_fails() {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => b => 0 = 2);
+        tracker.set_init(1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "field `a` of `shadow::Foo` is not fully initialized (byte 0 of 4")]
+fn dropped_field_is_uninit() {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => a = 1);
+        tracker.set_init(0);
+        tracker.drop_field(0);
+        tracker.set_init(0);
+    }
+}
+
+#[test]
+fn manual_writes_can_be_recorded() {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        let (a, b) = project_uninit_mut!(tracker.slot_mut() => { a, b });
+        *a = MaybeUninit::new(5);
+        *b = MaybeUninit::new((6, 7));
+        shadow::mark_init(a.as_ptr() as *const u8, 4);
+        shadow::mark_init(b.as_ptr() as *const u8, 4);
+        tracker.set_init(0);
+        tracker.set_init(1);
+    }
+    assert_eq!(tracker.finish().unwrap(), &Foo { a: 5, b: (6, 7) });
+}
+
+#[derive(ProjectUninit)]
+struct Table {
+    len: u32,
+    rows: [u16; 64],
+}
+
+#[test]
+fn init_array_writes_are_recorded() {
+    let mut slot = MaybeUninit::<Table>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => len = 64);
+        project_uninit::init_array(project_uninit_mut!(tracker.slot_mut() => rows), |i| {
+            i as u16
+        });
+        tracker.set_init(0);
+        tracker.set_init(1);
+    }
+    assert_eq!(tracker.finish().unwrap().rows[63], 63);
+}
+
+#[derive(ProjectUninit)]
+struct Named {
+    name: String,
+    id: u32,
+}
+
+#[test]
+#[should_panic(
+    expected = "field `name` of `shadow::Named` was initialized again without dropping its previous value"
+)]
+fn double_init_of_drop_field_fails() {
+    let mut slot = MaybeUninit::<Named>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { name: "a".into(), id: 1 });
+        partial_init!(tracker.slot_mut() => name = "b".into());
+    }
+}
+
+#[test]
+fn reinit_after_drop_or_replace_passes() {
+    let mut slot = MaybeUninit::<Named>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { name: "a".into(), id: 1 });
+        tracker.set_init(0);
+        tracker.set_init(1);
+        // fields that don't need to be dropped can be written again
+        partial_init!(tracker.slot_mut() => id = 2);
+        tracker.drop_field(0);
+        partial_init!(tracker.slot_mut() => name = "b".into());
+        tracker.set_init(0);
+        let old = project_uninit::replace_field!(tracker.slot_mut() => name = "c".into());
+        assert_eq!(old, "b");
+    }
+    assert_eq!(tracker.finish().unwrap().name, "c");
+}
+
+#[test]
+#[should_panic(expected = "field `0` of `(alloc::string::String, u8)` was initialized again")]
+fn double_init_in_init_with_fails() {
+    project_uninit::init_with::<(String, u8)>(|slot| {
+        partial_init!(slot => { 0: "a".into(), 1: 1 });
+        partial_init!(slot => 0 = "b".into());
+        unsafe { slot.assume_init_mut() }
+    });
+}
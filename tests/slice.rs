@@ -0,0 +1,100 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_slice, project_slice_mut};
+
+#[derive(Debug, PartialEq)]
+struct Foo {
+    a: u32,
+    b: (u8, [i16; 2]),
+}
+
+fn uninit_slice(len: usize) -> Vec<MaybeUninit<Foo>> {
+    (0..len).map(|_| MaybeUninit::uninit()).collect()
+}
+
+#[test]
+fn project_elements_ranges_and_fields() {
+    let mut foos = uninit_slice(5);
+    let slice: &mut [MaybeUninit<Foo>] = &mut foos;
+    let mut next = 0;
+    let mut index = || {
+        next += 1;
+        next
+    };
+
+    let (first, a, b0, b1, rest) = project_slice_mut!(slice => {
+        [0],
+        [index()] => a,
+        [1] => b => 0,
+        [1] => b => 1 => [1],
+        [2..=4],
+    });
+    partial_init!(first => { a: 0, b: (0, [0; 2]) });
+    a.write(1);
+    b0.write(2);
+    b1.write(3);
+    for (i, foo) in rest.iter_mut().enumerate() {
+        partial_init!(foo => { a: i as u32 + 2, b: (0, [0; 2]) });
+    }
+    assert_eq!(next, 1);
+    project_slice_mut!(slice => [1] => b => 1 => [0]).write(4);
+
+    let (a1, b) = project_slice!(slice => { [1] => a, [1] => b });
+    assert_eq!(
+        unsafe { (a1.assume_init(), b.assume_init_read()) },
+        (1, (2, [4, 3]))
+    );
+    assert_eq!(project_slice!(foos => [3..]).len(), 2);
+
+    let foos: Vec<Foo> = foos
+        .into_iter()
+        .map(|f| unsafe { f.assume_init() })
+        .collect();
+    assert_eq!(
+        foos.iter().map(|f| f.a).collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn shared_projections_may_overlap() {
+    let foos = [MaybeUninit::new(Foo {
+        a: 7,
+        b: (1, [2, 3]),
+    })];
+    let (all, foo, a) = project_slice!(foos => { [..], [0], [0] => a });
+    assert_eq!(all.len(), 1);
+    assert_eq!(
+        unsafe { (foo.assume_init_ref().a, a.assume_init()) },
+        (7, 7)
+    );
+}
+
+#[test]
+#[should_panic(expected = "can't borrow `[0] => b` and `[i] => b => 0` at the same time")]
+fn overlapping_field_panics() {
+    let mut foos = uninit_slice(2);
+    let i = 0;
+    let _ = project_slice_mut!(foos => { [0] => b, [1], [i] => b => 0 });
+}
+
+#[test]
+#[should_panic(expected = "overlap")]
+fn overlapping_ranges_panic() {
+    let mut foos = uninit_slice(4);
+    let _ = project_slice_mut!(foos => { [..2], [1..] });
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn index_out_of_bounds_panics() {
+    let foos = uninit_slice(2);
+    let _ = project_slice!(foos => [2] => a);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds for slice of length 2")]
+fn range_out_of_bounds_panics() {
+    let mut foos = uninit_slice(2);
+    let _ = project_slice_mut!(foos => [1..3]);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "soa")]
+
+use std::rc::Rc;
+
+use project_uninit::UninitSoA;
+
+#[derive(UninitSoA, Debug, PartialEq)]
+struct Entity {
+    id: u32,
+    name: Rc<str>,
+}
+
+#[derive(UninitSoA)]
+pub struct Pair<T: Clone> {
+    first: T,
+    second: T,
+}
+
+#[derive(UninitSoA)]
+struct DropRow {
+    a: Rc<()>,
+}
+
+impl Drop for DropRow {
+    fn drop(&mut self) {
+        panic!("init_row should not drop its argument");
+    }
+}
+
+#[test]
+fn set_get_and_take() {
+    let mut entities = Entity::soa(4);
+    assert_eq!(entities.len(), 4);
+    entities.set_id(0, 10);
+    entities.set_name(0, "zero".into());
+    entities.set_id(2, 12);
+    *entities.get_id_mut(2).unwrap() += 1;
+
+    assert_eq!(entities.get_id(2), Some(&13));
+    assert_eq!(entities.get_name(2), None);
+    assert!(entities.rows().is_init(2, 0));
+    assert!(!entities.rows().is_row_complete(2));
+    assert_eq!(entities.rows().complete_rows().collect::<Vec<_>>(), [0]);
+    assert!(!entities.rows().is_complete());
+
+    assert_eq!(entities.take_row(2), None);
+    assert_eq!(
+        entities.take_row(0),
+        Some(Entity {
+            id: 10,
+            name: "zero".into()
+        })
+    );
+    assert!(!entities.rows().is_init(0, 0));
+    assert_eq!(entities.column_id().len(), 4);
+}
+
+#[test]
+fn cells_are_dropped() {
+    let rc: Rc<str> = "shared".into();
+    let mut entities = Entity::soa(3);
+    entities.init_row(0, Entity { id: 0, name: rc.clone() });
+    entities.set_name(1, rc.clone());
+    entities.set_name(1, rc.clone());
+    entities.set_name(2, rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 4);
+
+    entities.clear_name(2);
+    assert_eq!(Rc::strong_count(&rc), 3);
+    entities.clear_row(0);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(entities);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn generic_struct() {
+    let mut pairs = Pair::<String>::soa(2);
+    pairs.set_first(1, "a".into());
+    pairs.set_second(1, "b".into());
+    let pair = pairs.take_row(1).unwrap();
+    assert_eq!((pair.first, pair.second), ("a".into(), "b".into()));
+    assert!(pairs.rows().complete_rows().next().is_none());
+}
+
+#[test]
+fn init_row_does_not_drop_source() {
+    let rc = Rc::new(());
+    let mut rows = DropRow::soa(1);
+    rows.init_row(0, DropRow { a: rc.clone() });
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(rows);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "alloc")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, Out, SpareInit};
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    label: String,
+}
+
+#[test]
+fn push_into_spare_capacity() {
+    let mut points = vec![Point {
+        x: 0,
+        label: "origin".into(),
+    }];
+    points.reserve_exact(3);
+    let capacity = points.capacity();
+
+    let mut spare = SpareInit::new(&mut points);
+    let remaining = spare.remaining();
+    let p = spare.push_with(|slot| {
+        Out::new(slot).write(Point {
+            x: 1,
+            label: "a".into(),
+        })
+    });
+    p.x += 1;
+    spare.push_with(|slot| {
+        partial_init!(slot => { x: 3, label: "b".into() });
+        unsafe { slot.assume_init_mut() }
+    });
+    assert_eq!(
+        spare.try_push_with(|_: &mut MaybeUninit<Point>| Err("failed")),
+        Err("failed")
+    );
+    assert_eq!(spare.remaining(), remaining - 2);
+    assert_eq!(spare.initialized().len(), 2);
+    assert_eq!(spare.finish()[1].label, "b");
+
+    assert_eq!(points.capacity(), capacity);
+    assert_eq!(points.iter().map(|p| p.x).collect::<Vec<_>>(), [0, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "other than its slot")]
+fn push_rejects_other_reference() {
+    let other = Box::new(Point {
+        x: 0,
+        label: String::new(),
+    });
+    let other: &'static mut Point = Box::leak(other);
+    let mut points = Vec::with_capacity(1);
+    SpareInit::new(&mut points).push_with(move |_| other);
+}
+
+#[test]
+#[should_panic(expected = "no spare capacity")]
+fn push_without_capacity() {
+    let mut points: Vec<Point> = Vec::new();
+    SpareInit::new(&mut points).push_with(|slot| {
+        Out::new(slot).write(Point {
+            x: 0,
+            label: String::new(),
+        })
+    });
+}
@@ -0,0 +1,89 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::split_uninit_mut;
+
+#[derive(Debug, PartialEq)]
+struct Frame {
+    pixels: Vec<u8>,
+    size: (u32, u32),
+    checksum: u64,
+}
+
+#[test]
+fn parts_are_initialized_on_other_threads() {
+    let mut frame = MaybeUninit::<Frame>::uninit();
+    let (_, (pixels, width, height, mut checksum)) =
+        split_uninit_mut!(frame => { pixels, size => 0, size => 1, checksum });
+    std::thread::scope(|s| {
+        s.spawn(move || pixels.write(vec![1, 2, 3]));
+        s.spawn(move || {
+            let mut width = width.write(2);
+            *width += 1;
+        });
+        s.spawn(move || height.write(1));
+    });
+    unsafe { checksum.as_mut_ptr().write(6) };
+    let checksum = unsafe { checksum.assume_init() };
+    assert_eq!(*checksum, 6);
+
+    assert_eq!(
+        unsafe { frame.assume_init() },
+        Frame {
+            pixels: vec![1, 2, 3],
+            size: (3, 1),
+            checksum: 6,
+        }
+    );
+}
+
+#[cfg(feature = "derive")]
+mod join {
+    use core::mem::MaybeUninit;
+
+    use project_uninit::{split_uninit_mut, ProjectUninit};
+
+    #[derive(ProjectUninit, Debug, PartialEq)]
+    struct Pair {
+        left: String,
+        right: (u8, u8),
+    }
+
+    #[test]
+    fn join_returns_the_initialized_struct() {
+        let mut pair = MaybeUninit::<Pair>::uninit();
+        let (split, (left, right)) = split_uninit_mut!(pair => { left, right });
+        let (left, right) = std::thread::scope(|s| {
+            let left = s.spawn(|| left.write("left".into()));
+            let right = s.spawn(|| right.write((1, 2)));
+            (left.join().unwrap(), right.join().unwrap())
+        });
+        let pair = split.join((left, right));
+        pair.right.0 = 3;
+        assert_eq!(
+            *pair,
+            Pair {
+                left: "left".into(),
+                right: (3, 2)
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "field `right` wasn't split from the struct")]
+    fn join_requires_whole_fields() {
+        let mut pair = MaybeUninit::<Pair>::uninit();
+        let (split, (left, r0, r1)) = split_uninit_mut!(pair => { left, right => 0, right => 1 });
+        split.join((left.write(String::new()), r0.write(1), r1.write(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be the ones split from it")]
+    fn join_rejects_parts_of_another_split() {
+        let mut a = MaybeUninit::<Pair>::uninit();
+        let mut b = MaybeUninit::<Pair>::uninit();
+        let (split_a, (left_a, right_a)) = split_uninit_mut!(a => { left, right });
+        let (_, (left_b, _)) = split_uninit_mut!(b => { left, right });
+        let _ = left_a.write(String::new());
+        split_a.join((left_b.write(String::new()), right_a.write((1, 2))));
+    }
+}
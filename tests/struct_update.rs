@@ -0,0 +1,50 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{partial_init, ProjectUninit};
+
+#[derive(ProjectUninit, Debug, Default, PartialEq)]
+struct Settings {
+    name: String,
+    level: u8,
+    tags: Vec<&'static str>,
+}
+
+#[derive(ProjectUninit, Clone)]
+struct Shared(Rc<()>, Rc<()>, u32);
+
+#[test]
+fn rest_from_default() {
+    let mut slot = MaybeUninit::<Settings>::uninit();
+    let settings = partial_init!(slot => { level: 3, ..Default::default() });
+    settings.tags.push("a");
+    assert_eq!(
+        unsafe { slot.assume_init() },
+        Settings {
+            name: String::new(),
+            level: 3,
+            tags: vec!["a"]
+        }
+    );
+
+    let mut slot = MaybeUninit::<Settings>::uninit();
+    partial_init!(slot => { ..Settings { name: "all".into(), level: 1, tags: vec![] } });
+    assert_eq!(unsafe { slot.assume_init_ref() }.name, "all");
+}
+
+#[test]
+fn given_fields_of_rest_are_dropped() {
+    let a = Rc::new(());
+    let b = Rc::new(());
+    let fallback = Shared(a.clone(), b.clone(), 1);
+
+    let mut slot = MaybeUninit::<Shared>::uninit();
+    let shared = partial_init!(&mut slot => { 2: 5, 1: b.clone(), ..fallback.clone() });
+    assert_eq!(shared.2, 5);
+    assert_eq!((Rc::strong_count(&a), Rc::strong_count(&b)), (3, 3));
+    drop(fallback);
+    drop(unsafe { slot.assume_init() });
+    assert_eq!((Rc::strong_count(&a), Rc::strong_count(&b)), (1, 1));
+}
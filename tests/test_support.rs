@@ -0,0 +1,47 @@
+#![cfg(feature = "proptest")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use proptest::proptest;
+use project_uninit::test_support::{init_orders, partial_init_orders};
+use project_uninit::{partial_init, ProjectUninit};
+
+#[derive(ProjectUninit, Debug, PartialEq)]
+struct Foo {
+    a: Rc<u32>,
+    b: (u8, u8),
+    c: Rc<u32>,
+    d: [u16; 3],
+}
+
+fn init_field(slot: &mut MaybeUninit<Foo>, rc: &Rc<u32>, index: usize) {
+    match index {
+        0 => drop(partial_init!(slot => a = rc.clone())),
+        1 => drop(partial_init!(slot => b = (1, 2))),
+        2 => drop(partial_init!(slot => c = rc.clone())),
+        3 => drop(partial_init!(slot => d = [3, 4, 5])),
+        _ => unreachable!(),
+    }
+}
+
+proptest! {
+    #[test]
+    fn every_order_produces_same_value(plan in init_orders::<Foo>()) {
+        let rc = Rc::new(10);
+        let mut slot = MaybeUninit::uninit();
+        let foo = unsafe { plan.run(&mut slot, |slot, i| init_field(slot, &rc, i)) };
+        assert_eq!(foo, Some(&mut Foo { a: rc.clone(), b: (1, 2), c: rc.clone(), d: [3, 4, 5] }));
+    }
+
+    #[test]
+    fn partial_orders_do_not_leak(plan in partial_init_orders::<Foo>()) {
+        let rc = Rc::new(10);
+        let mut slot = MaybeUninit::uninit();
+        let foo = unsafe { plan.run(&mut slot, |slot, i| init_field(slot, &rc, i)) };
+        assert_eq!(foo.is_some(), plan.is_complete());
+        if foo.is_none() {
+            assert_eq!(Rc::strong_count(&rc), 1);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "tlv")]
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::mem::MaybeUninit;
+
+use project_uninit::{decode_tlv, DecodeTlv, ProjectUninit, TlvDecoder, TlvError, TlvValue};
+
+#[derive(ProjectUninit, DecodeTlv, Debug, PartialEq)]
+struct Packet {
+    id: u16,
+    #[tlv(tag = 0x10)]
+    key: [u8; 4],
+    #[tlv(tag = 0x20)]
+    temperature: f32,
+}
+
+#[test]
+fn decode_records_in_any_order() {
+    let mut slot = MaybeUninit::uninit();
+    let mut decoder = TlvDecoder::new(&mut slot);
+    decoder.record(0x20, &1.5f32.to_le_bytes()).unwrap();
+    decoder.record(0, &[0x34, 0x12]).unwrap();
+    decoder.record(0x10, b"abcd").unwrap();
+    assert_eq!(
+        decoder.finish(),
+        Ok(&mut Packet {
+            id: 0x1234,
+            key: *b"abcd",
+            temperature: 1.5,
+        })
+    );
+}
+
+#[test]
+fn decode_errors() {
+    let mut slot = MaybeUninit::<Packet>::uninit();
+    let mut decoder = TlvDecoder::new(&mut slot);
+    assert_eq!(decoder.record(3, &[]), Err(TlvError::UnknownTag(3)));
+    assert_eq!(
+        decoder.record(0, &[1]),
+        Err(TlvError::InvalidValue { field: "id" })
+    );
+    assert_eq!(decoder.record(0, &[1, 2]), Ok(()));
+    assert_eq!(
+        decoder.record(0, &[1, 2]),
+        Err(TlvError::Duplicate { field: "id" })
+    );
+    assert_eq!(decoder.decode(&[0x10, 0, 4, 0, 1]), Err(TlvError::Truncated));
+    assert_eq!(
+        decoder.finish().map(|_| ()),
+        Err(TlvError::Missing { field: "key" })
+    );
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl TlvValue for Counted {
+    fn decode_tlv(value: &[u8]) -> Option<Self> {
+        u8::decode_tlv(value).map(|_| Counted)
+    }
+}
+
+#[derive(ProjectUninit, DecodeTlv)]
+struct Pair(Counted, Counted, u32);
+
+#[test]
+fn incomplete_fields_are_dropped() {
+    let mut slot = MaybeUninit::<Pair>::uninit();
+    assert_eq!(
+        decode_tlv(&mut slot, &[1, 0, 1, 0, 9, 2, 0, 4, 0, 1, 0, 0, 0]).map(|_| ()),
+        Err(TlvError::Missing { field: "0" })
+    );
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+}
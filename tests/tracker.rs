@@ -0,0 +1,179 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{
+    partial_init, partial_init_tracked, take_field, tracked_init, InitTracker, ProjectUninit,
+};
+
+#[derive(ProjectUninit, Debug)]
+struct Foo {
+    a: Rc<()>,
+    b: u32,
+    c: Rc<()>,
+}
+
+#[test]
+fn drop_initialized_fields() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => c = rc.clone());
+        tracker.set_init(2);
+    }
+    assert!(tracker.is_init(2));
+    assert!(!tracker.is_complete());
+    assert_eq!(
+        format!("{:?}", tracker),
+        r#"InitTracker { initialized: ["c"], missing: ["a", "b"] }"#
+    );
+    assert_eq!(Rc::strong_count(&rc), 2);
+
+    let tracker = tracker.finish().unwrap_err();
+    drop(tracker);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn drop_field_and_finish() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    unsafe {
+        partial_init!(tracker.slot_mut() => { a: rc.clone(), b: 5, c: rc.clone() });
+        tracker.set_init(0);
+        tracker.set_init(1);
+        tracker.set_init(2);
+    }
+    tracker.drop_field(0);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    let tracker = tracker.finish().unwrap_err();
+    assert_eq!(tracker.missing().map(|f| f.name()).collect::<Vec<_>>(), ["a"]);
+    drop(tracker);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "alloc"))]
+fn records_init_locations() {
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    let line = line!() + 3;
+    unsafe {
+        partial_init!(tracker.slot_mut() => b = 1);
+        tracker.set_init(1);
+    }
+    let location = tracker.init_location(1).unwrap();
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+    assert!(tracker.init_location(0).is_none());
+
+    tracker.drop_field(1);
+    assert!(tracker.init_location(1).is_none());
+}
+
+#[test]
+fn tracked_init_marks_fields() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    tracked_init!(tracker => { a: rc.clone(), b: 1 });
+    assert!(tracker.is_init(0) && tracker.is_init(1) && !tracker.is_init(2));
+
+    // overwriting a field drops its previous value
+    tracked_init!(&mut tracker => a = rc.clone());
+    tracked_init!(tracker => c = rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    let foo = tracker.finish().unwrap();
+    assert_eq!(foo.b, 1);
+    unsafe { core::ptr::drop_in_place(foo) };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn tracked_init_drops_fields_on_panic() {
+    fn fail() -> Rc<()> {
+        panic!("failed to produce c")
+    }
+
+    let rc = Rc::new(());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut slot = MaybeUninit::<Foo>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+        tracked_init!(tracker => {
+            a: rc.clone(),
+            b: 2,
+            c: fail(),
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn take_field_moves_out() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    tracked_init!(tracker => { a: rc.clone(), c: rc.clone() });
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    let (a, b) = take_field!(tracker => { a, b });
+    assert!(Rc::ptr_eq(&a.unwrap(), &rc));
+    assert_eq!(b, None);
+    assert!(!tracker.is_init(0));
+    assert_eq!(take_field!(tracker => a), None);
+    assert_eq!(Rc::strong_count(&rc), 2);
+
+    drop(tracker);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn partial_init_tracked_returns_references() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    let (b, a) = partial_init_tracked!(tracker => { b: 1, a: rc.clone() });
+    *b += 1;
+    assert_eq!(Rc::strong_count(a), 2);
+    assert!(tracker.is_init(0) && tracker.is_init(1));
+
+    // writing a field again drops its previous value
+    let a = partial_init_tracked!(tracker => a = Rc::new(()));
+    assert_eq!(Rc::strong_count(a), 1);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    partial_init_tracked!(tracker => c = rc.clone());
+    let foo = tracker.try_assume_init().unwrap();
+    assert_eq!(foo.b, 2);
+}
+
+#[test]
+fn try_assume_init_lists_missing_fields() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::<Foo>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    partial_init_tracked!(tracker => a = rc.clone());
+
+    let missing = tracker.try_assume_init().unwrap_err();
+    assert_eq!(missing.names().collect::<Vec<_>>(), ["b", "c"]);
+    assert_eq!(missing.to_string(), "missing fields `b`, `c` of `Foo`");
+    assert_eq!(
+        format!("{:?}", missing),
+        r#"MissingFields { missing: ["b", "c"] }"#
+    );
+
+    let mut tracker = missing.into_tracker();
+    partial_init_tracked!(tracker => b = 1);
+    let missing = tracker.try_assume_init().unwrap_err();
+    assert_eq!(missing.to_string(), "missing field `c` of `Foo`");
+
+    // the initialized fields are dropped with the error
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(missing);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "derive")]
+
+use std::rc::Rc;
+
+use project_uninit::{partial_init, ProjectUninit, TripleBuffer};
+
+#[derive(ProjectUninit)]
+struct State {
+    gain: f32,
+    tag: Rc<u32>,
+}
+
+#[test]
+fn reader_sees_latest_value() {
+    let mut buffer = TripleBuffer::<u32>::new();
+    let (mut writer, mut reader) = buffer.split();
+    assert!(!reader.has_update());
+    assert_eq!(reader.read(), None);
+    writer.publish(1);
+    writer.publish(2);
+    assert!(reader.has_update());
+    assert_eq!(reader.read(), Some(&2));
+    assert_eq!(reader.read(), Some(&2));
+    writer.publish(3);
+    assert_eq!(reader.read(), Some(&3));
+}
+
+#[test]
+fn values_are_dropped_once() {
+    let tag = Rc::new(0);
+    {
+        let mut buffer = TripleBuffer::<State>::new();
+        let (mut writer, mut reader) = buffer.split();
+        for i in 0..10 {
+            let published = writer.publish_with(|t| unsafe {
+                partial_init!(t.slot_mut() => { gain: i as f32, tag: tag.clone() });
+                t.set_init(0);
+                t.set_init(1);
+            });
+            assert!(published);
+            if i % 3 == 0 {
+                assert_eq!(reader.read().unwrap().gain, i as f32);
+            }
+        }
+        assert!(Rc::strong_count(&tag) <= 4);
+    }
+    assert_eq!(Rc::strong_count(&tag), 1);
+}
+
+#[test]
+fn incomplete_values_are_not_published() {
+    let tag = Rc::new(0);
+    let mut buffer = TripleBuffer::<State>::new();
+    let (mut writer, mut reader) = buffer.split();
+    let published = writer.publish_with(|t| unsafe {
+        partial_init!(t.slot_mut() => tag = tag.clone());
+        t.set_init(1);
+    });
+    assert!(!published);
+    assert_eq!(Rc::strong_count(&tag), 1);
+    assert!(reader.read().is_none());
+}
+
+#[test]
+fn handoff_across_threads() {
+    let mut buffer = TripleBuffer::<[u64; 8]>::new();
+    let (mut writer, mut reader) = buffer.split();
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            for i in 1..=10_000u64 {
+                writer.prepare().write([i; 8]);
+                unsafe { writer.commit() };
+            }
+        });
+        s.spawn(move || {
+            let mut last = 0;
+            while last < 10_000 {
+                if let Some(block) = reader.read() {
+                    assert!(block.iter().all(|&x| x == block[0]));
+                    assert!(block[0] >= last);
+                    last = block[0];
+                }
+            }
+        });
+    });
+}
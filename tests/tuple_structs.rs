@@ -0,0 +1,125 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{
+    partial_init_tracked, project_uninit_mut, tracked_init, InitBuilder, InitTracker, PartialGuard,
+    ProjectUninit, UninitFields, UninitMirror, UninitMirrorExt, UninitViewExt,
+};
+
+#[derive(ProjectUninit, InitBuilder, PartialGuard, Debug, PartialEq)]
+struct Pair(u32, Rc<String>);
+
+#[derive(ProjectUninit, InitBuilder, PartialGuard, UninitMirror, Debug, PartialEq)]
+#[repr(transparent)]
+struct Meters(f64);
+
+#[derive(ProjectUninit, InitBuilder, PartialGuard, UninitMirror, Debug, PartialEq)]
+#[repr(C)]
+struct Marker;
+
+#[test]
+fn tuple_struct_fields() {
+    assert_eq!(
+        Pair::FIELDS.iter().map(|f| f.name()).collect::<Vec<_>>(),
+        ["0", "1"]
+    );
+    let mut x = MaybeUninit::<Pair>::uninit();
+    let (a, b) = project_uninit_mut!(x => { 0, 1 });
+    a.write(1);
+    b.write(Rc::new("one".into()));
+    let view = x.uninit_fields();
+    assert_eq!(unsafe { view.0.assume_init() }, 1);
+    assert_eq!(unsafe { x.assume_init() }, Pair(1, Rc::new("one".into())));
+}
+
+#[test]
+fn tuple_struct_tracker() {
+    let rc = Rc::new("shared".to_string());
+    let mut slot = MaybeUninit::<Pair>::uninit();
+    let mut tracker = InitTracker::new(&mut slot);
+    tracked_init!(tracker => 1 = rc.clone());
+    let missing = tracker.try_assume_init().unwrap_err();
+    assert_eq!(missing.to_string(), "missing field `0` of `Pair`");
+
+    let mut tracker = missing.into_tracker();
+    let first = partial_init_tracked!(tracker => 0 = 2);
+    *first += 1;
+    assert_eq!(tracker.try_assume_init().unwrap().0, 3);
+    assert_eq!(Rc::strong_count(&rc), 2);
+}
+
+#[test]
+fn tuple_struct_builder() {
+    let pair = PairInit::new().set_1(Rc::new("b".into())).set_0(7).finish();
+    assert_eq!(pair, Pair(7, Rc::new("b".into())));
+    assert_eq!(MetersInit::new().set_0(1.5).finish(), Meters(1.5));
+    assert_eq!(MarkerInit::new().finish(), Marker);
+}
+
+#[test]
+fn tuple_struct_guard() {
+    let rc = Rc::new(String::new());
+    let mut slot = MaybeUninit::<Pair>::uninit();
+    let mut guard = PairPartialGuard::new(&mut slot);
+    guard.set_1(rc.clone());
+    assert_eq!(guard.get_0(), None);
+    drop(guard);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let mut slot = MaybeUninit::<Marker>::uninit();
+    let guard = MarkerPartialGuard::new(&mut slot);
+    assert!(guard.is_complete());
+    assert_eq!(*guard.finish().unwrap(), Marker);
+}
+
+#[test]
+fn newtype_and_unit_mirror() {
+    let mut slot = MaybeUninit::<Meters>::uninit();
+    slot.as_mirror_mut().0.write(2.0);
+    assert_eq!(unsafe { slot.assume_init() }, Meters(2.0));
+
+    let mirror = MarkerUninitMirror::uninit();
+    assert_eq!(unsafe { mirror.assume_init() }, Marker);
+}
+
+#[test]
+fn unit_struct_tracker() {
+    assert!(Marker::FIELDS.is_empty());
+    let mut slot = MaybeUninit::<Marker>::uninit();
+    let tracker = InitTracker::new(&mut slot);
+    assert!(tracker.is_complete());
+    assert_eq!(*tracker.try_assume_init().unwrap(), Marker);
+}
+
+#[cfg(feature = "soa")]
+mod soa {
+    use project_uninit::UninitSoA;
+
+    #[derive(UninitSoA, Debug, PartialEq)]
+    struct Rgb(u8, u8, u8);
+
+    #[derive(UninitSoA, Debug, PartialEq)]
+    struct Empty;
+
+    #[test]
+    fn tuple_struct_soa() {
+        let mut pixels = Rgb::soa(2);
+        pixels.init_row(0, Rgb(1, 2, 3));
+        pixels.set_0(1, 4);
+        pixels.set_2(1, 6);
+        assert_eq!(pixels.take_row(1), None);
+        pixels.set_1(1, 5);
+        assert_eq!(pixels.column_2().len(), 2);
+        assert_eq!(pixels.get_1(0), Some(&2));
+        assert_eq!(pixels.take_row(1), Some(Rgb(4, 5, 6)));
+    }
+
+    #[test]
+    fn unit_struct_soa() {
+        let mut empty = Empty::soa(1);
+        assert!(empty.rows().is_row_complete(0));
+        assert_eq!(empty.take_row(0), Some(Empty));
+    }
+}
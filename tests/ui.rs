@@ -0,0 +1,7 @@
+#![cfg(feature = "derive")]
+
+// With `derive`, duplicate field paths are reported at the offending path; these pin where.
+#[test]
+fn duplicate_path_spans() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,19 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::partial_init;
+
+struct Foo {
+    a: u8,
+    b: (u16, u32),
+    c: i64,
+}
+
+fn main() {
+    let mut x = MaybeUninit::<Foo>::uninit();
+    partial_init!(x => {
+        a: 1,
+        b: (2, 3),
+        c: 4,
+        a: 5,
+    });
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, project_uninit_mut, uninit_impl};
+
+#[derive(Debug, PartialEq)]
+struct Node {
+    name: String,
+    weight: u32,
+    links: (usize, [usize; 2]),
+}
+
+#[uninit_impl]
+impl Node {
+    fn init(self: &mut MaybeUninit<Self>, name: &str) -> &mut u32 {
+        let this = name.len();
+        self.name = name.into();
+        self.links.0 = this;
+        self.links.1 = [0; 2];
+        partial_init!(self => weight = 1)
+    }
+
+    fn relink(self: &mut MaybeUninit<Self>, links: [usize; 2]) {
+        let slot = project_uninit_mut!(self => links => 1);
+        *slot = MaybeUninit::new(links);
+    }
+
+    fn named(name: &str) -> Self {
+        let mut node = MaybeUninit::uninit();
+        *Self::init(&mut node, name) += 1;
+        unsafe { node.assume_init() }
+    }
+}
+
+#[test]
+fn uninit_methods_initialize_self() {
+    let mut node = MaybeUninit::<Node>::uninit();
+    let weight = Node::init(&mut node, "root");
+    *weight = 5;
+    Node::relink(&mut node, [1, 2]);
+    assert_eq!(
+        unsafe { node.assume_init() },
+        Node {
+            name: "root".into(),
+            weight: 5,
+            links: (4, [1, 2]),
+        }
+    );
+
+    assert_eq!(Node::named("leaf").weight, 2);
+}
@@ -0,0 +1,49 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, partial_init_union, project_union, project_union_mut};
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+union Payload {
+    raw: [u8; 8],
+    words: (u32, u32),
+    value: u64,
+}
+
+struct Message {
+    kind: u8,
+    payload: Payload,
+}
+
+#[test]
+fn switch_active_field() {
+    let mut msg = MaybeUninit::<Message>::uninit();
+    partial_init!(msg => kind = 1);
+
+    let value = partial_init_union!(unsafe msg => payload => value = u64::MAX);
+    assert_eq!(*value, u64::MAX);
+
+    let raw = partial_init_union!(unsafe msg => payload => raw => [0] = 7);
+    *raw += 1;
+    let raw = project_union!(unsafe msg => payload => raw);
+    assert_eq!(
+        unsafe { raw.assume_init() },
+        [8, 255, 255, 255, 255, 255, 255, 255]
+    );
+
+    project_union_mut!(unsafe msg => payload => words).write((3, 4));
+    let words = project_union!(unsafe msg => payload => words => 1);
+    assert_eq!(unsafe { words.assume_init() }, 4);
+
+    let msg = unsafe { msg.assume_init() };
+    assert_eq!(msg.kind, 1);
+    assert_eq!(unsafe { msg.payload.words }, (3, 4));
+}
+
+#[test]
+fn union_at_root() {
+    let mut payload = Box::new(MaybeUninit::<Payload>::uninit());
+    partial_init_union!(unsafe payload => words => 0 = 5);
+    partial_init_union!(unsafe payload => words => 1 = 6);
+    assert_eq!(unsafe { payload.assume_init().words }, (5, 6));
+}
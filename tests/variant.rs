@@ -0,0 +1,123 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{init_variant, UninitEnum};
+
+#[derive(UninitEnum, Debug, PartialEq)]
+#[repr(u8)]
+enum Shape {
+    Empty,
+    Circle { radius: f32 },
+    Rect(u16, u32) = 5,
+    Named { name: String, sides: u8 },
+}
+
+#[derive(UninitEnum)]
+#[repr(i32)]
+enum Generic<'a, T> {
+    Borrowed(&'a T) = -2,
+    Owned(T),
+    Nothing,
+}
+
+#[test]
+fn init_each_variant() {
+    let mut slot = MaybeUninit::<Shape>::uninit();
+    assert_eq!(*init_variant!(slot => Shape::Empty), Shape::Empty);
+    assert_eq!(
+        *init_variant!(slot => Shape::Circle { radius: 1.5 }),
+        Shape::Circle { radius: 1.5 }
+    );
+    assert_eq!(*init_variant!(slot => Shape::Rect { 0: 3, 1: 4 }), Shape::Rect(3, 4));
+    let named = init_variant!(slot => Shape::Named { sides: 6, name: "hex".into() });
+    assert_eq!(
+        *named,
+        Shape::Named {
+            name: "hex".into(),
+            sides: 6
+        }
+    );
+    unsafe { slot.assume_init_drop() };
+}
+
+#[test]
+fn init_through_a_reference() {
+    let mut slot = Box::new(MaybeUninit::<Shape>::uninit());
+    let slot_ref: &mut MaybeUninit<Shape> = &mut slot;
+    init_variant!(slot_ref => Shape::Rect { 0: 1, 1: 2 });
+    assert_eq!(unsafe { slot.assume_init() }, Box::new(Shape::Rect(1, 2)));
+}
+
+#[test]
+fn generic_enum_with_negative_discriminant() {
+    let rc = Rc::new(5);
+    let mut slot = MaybeUninit::<Generic<'_, Rc<i32>>>::uninit();
+    match init_variant!(slot => Generic::Borrowed { 0: &rc }) {
+        Generic::Borrowed(value) => assert_eq!(***value, 5),
+        _ => panic!("wrong variant"),
+    }
+    match init_variant!(slot => Generic::Owned { 0: rc.clone() }) {
+        Generic::Owned(value) => assert_eq!(**value, 5),
+        _ => panic!("wrong variant"),
+    }
+    assert_eq!(Rc::strong_count(&rc), 2);
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&rc), 1);
+    assert!(matches!(
+        init_variant!(slot => Generic::Nothing),
+        Generic::Nothing
+    ));
+}
+
+#[test]
+fn generated_variant_constructors() {
+    let mut slot = MaybeUninit::<Shape>::uninit();
+    assert_eq!(*Shape::init_variant_empty(&mut slot), Shape::Empty);
+    assert_eq!(
+        *Shape::init_variant_rect(&mut slot, 7, 8),
+        Shape::Rect(7, 8)
+    );
+    let named = Shape::init_variant_named(&mut slot, "tri".into(), 3);
+    assert_eq!(
+        *named,
+        Shape::Named {
+            name: "tri".into(),
+            sides: 3
+        }
+    );
+    unsafe { slot.assume_init_drop() };
+
+    let rc = Rc::new(1);
+    let mut slot = MaybeUninit::<Generic<'_, Rc<i32>>>::uninit();
+    match Generic::init_variant_owned(&mut slot, rc.clone()) {
+        Generic::Owned(value) => assert_eq!(**value, 1),
+        _ => panic!("wrong variant"),
+    }
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[derive(UninitEnum, Debug, PartialEq)]
+#[repr(u16)]
+enum Request {
+    HTTPGet(u8),
+    Put2Fast,
+    #[allow(non_camel_case_types)]
+    delete,
+}
+
+#[test]
+fn variant_constructor_names() {
+    let mut slot = MaybeUninit::<Request>::uninit();
+    assert_eq!(
+        *Request::init_variant_http_get(&mut slot, 1),
+        Request::HTTPGet(1)
+    );
+    assert_eq!(
+        *Request::init_variant_put2_fast(&mut slot),
+        Request::Put2Fast
+    );
+    assert_eq!(*Request::init_variant_delete(&mut slot), Request::delete);
+}
@@ -0,0 +1,118 @@
+use core::mem::MaybeUninit;
+use std::os::raw::c_int;
+
+use project_uninit::init_via;
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Stat {
+    size: u64,
+    mode: u32,
+}
+
+struct File {
+    name: String,
+    stat: Stat,
+    times: [u64; 2],
+}
+
+unsafe extern "C" fn fake_stat(size: u64, out: *mut Stat) -> c_int {
+    if size == 0 {
+        return -2;
+    }
+    out.write(Stat { size, mode: 0o644 });
+    0
+}
+
+#[test]
+fn init_via_out_parameter() {
+    let mut file = MaybeUninit::<File>::uninit();
+    let stat = unsafe { init_via!(file => stat, |ptr| fake_stat(12, ptr)) }.unwrap();
+    stat.mode |= 0o100;
+    let time = unsafe { init_via!(file => times => [1], |ptr: *mut u64| ptr.write(7)) };
+    assert_eq!(*time.unwrap(), 7);
+    let ok = unsafe {
+        init_via!(file => times => [0], |ptr: *mut u64| {
+            ptr.write(3);
+            true
+        })
+    };
+    assert_eq!(ok, Ok(&mut 3));
+    unsafe { init_via!(file => name, |ptr: *mut String| ptr.write("a.txt".into())) }.unwrap();
+
+    let file = unsafe { file.assume_init() };
+    assert_eq!(file.name, "a.txt");
+    assert_eq!(
+        file.stat,
+        Stat {
+            size: 12,
+            mode: 0o744
+        }
+    );
+    assert_eq!(file.times, [3, 7]);
+}
+
+#[test]
+fn init_via_returns_error_code() {
+    let mut file = MaybeUninit::<File>::uninit();
+    let stat = unsafe { init_via!(file => stat, |ptr| fake_stat(0, ptr)) };
+    assert_eq!(stat, Err(-2));
+    let time = unsafe { init_via!(file => times => [0], |_| Err::<(), _>("unsupported")) };
+    assert_eq!(time, Err("unsupported"));
+}
+
+#[cfg(feature = "derive")]
+mod tracked {
+    use core::mem::MaybeUninit;
+    use std::rc::Rc;
+
+    use project_uninit::{init_via, tracked_init, InitTracker, ProjectUninit};
+
+    #[derive(ProjectUninit, Debug)]
+    struct Handle {
+        owner: Rc<()>,
+        fd: i32,
+    }
+
+    #[test]
+    fn init_via_tracked_marks_field() {
+        let rc = Rc::new(());
+        let mut slot = MaybeUninit::<Handle>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+
+        let fd = unsafe { init_via!(tracker => tracked fd, |_| 22) };
+        assert_eq!(fd, Err(22));
+        assert!(!tracker.is_init(1));
+
+        let fd = unsafe { init_via!(tracker => tracked fd, |ptr: *mut i32| ptr.write(3)) };
+        *fd.unwrap() += 1;
+        assert!(tracker.is_init(1));
+
+        tracked_init!(tracker => owner = rc.clone());
+        // the previous value is dropped before the field is initialized again
+        let owner = unsafe {
+            init_via!(tracker => tracked owner, |ptr: *mut Rc<()>| ptr.write(Rc::new(())))
+        };
+        assert_eq!(Rc::strong_count(owner.unwrap()), 1);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let handle = tracker.try_assume_init().unwrap();
+        assert_eq!(handle.fd, 4);
+    }
+
+    #[test]
+    fn init_via_tracked_failure_keeps_other_fields() {
+        let rc = Rc::new(());
+        let mut slot = MaybeUninit::<Handle>::uninit();
+        let mut tracker = InitTracker::new(&mut slot);
+        tracked_init!(tracker => owner = rc.clone());
+
+        let fd = unsafe { init_via!(tracker => tracked fd, |_| false) };
+        assert_eq!(fd, Err(()));
+        let missing = tracker.try_assume_init().unwrap_err();
+        assert_eq!(missing.names().collect::<Vec<_>>(), ["fd"]);
+
+        drop(missing);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "derive")]
+
+use core::mem::MaybeUninit;
+use std::rc::Rc;
+
+use project_uninit::{partial_init, ProjectUninit, UninitViewExt};
+
+#[derive(ProjectUninit)]
+struct Named {
+    a: u32,
+    b: Rc<()>,
+    c: (u8, u16),
+}
+
+#[derive(ProjectUninit)]
+struct Tuple(u64, &'static str);
+
+#[derive(ProjectUninit)]
+struct Generic<'a, T: Clone> {
+    value: T,
+    name: &'a str,
+}
+
+#[derive(ProjectUninit)]
+#[repr(C, packed)]
+struct Packed {
+    _a: u8,
+    _b: u32,
+}
+
+#[test]
+fn view_mut_initializes_every_field() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Named>::uninit();
+    let fields = x.uninit_fields_mut();
+    fields.a.write(1);
+    fields.b.write(rc.clone());
+    fields.c.write((2, 3));
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.a, x.c), (1, (2, 3)));
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(x);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn view_points_into_the_struct() {
+    let mut x = MaybeUninit::<Named>::uninit();
+    partial_init!(x => { a: 5, c: (6, 7) });
+    let fields = x.uninit_fields();
+    assert_eq!(unsafe { fields.a.assume_init() }, 5);
+    assert_eq!(unsafe { fields.c.assume_init() }, (6, 7));
+    assert_eq!(
+        fields.a as *const _ as usize,
+        unsafe { core::ptr::addr_of!((*x.as_ptr()).a) } as usize
+    );
+}
+
+#[test]
+fn tuple_struct_view() {
+    let mut x = MaybeUninit::<Tuple>::uninit();
+    let fields = x.uninit_fields_mut();
+    fields.0.write(9);
+    fields.1.write("nine");
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.0, x.1), (9, "nine"));
+}
+
+#[test]
+fn generic_struct_view() {
+    let name = String::from("n");
+    let mut x = MaybeUninit::<Generic<'_, Vec<u8>>>::uninit();
+    let fields = x.uninit_fields_mut();
+    fields.value.write(vec![1, 2]);
+    fields.name.write(&name);
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.value, x.name), (vec![1, 2], "n"));
+}
+
+#[test]
+fn packed_struct_still_has_fields() {
+    use project_uninit::UninitFields;
+    assert_eq!(Packed::FIELDS.len(), 2);
+}
+
+#[derive(ProjectUninit)]
+struct Outer {
+    inner: Named,
+    pair: (u8, Tuple),
+}
+
+#[test]
+fn strict_partial_init() {
+    let rc = Rc::new(());
+    let mut x = MaybeUninit::<Outer>::uninit();
+    let (a, c) = partial_init!(x => strict { inner => a: 1, inner => c: (2, 3) });
+    assert_eq!((*a, *c), (1, (2, 3)));
+    partial_init!(x => strict inner => b = rc.clone());
+    partial_init!(x => strict { pair => 0: 4, pair => 1 => 0: 5, pair => 1 => 1: "five" });
+    let x = unsafe { x.assume_init() };
+    assert_eq!((x.inner.a, x.pair.0, x.pair.1 .0, x.pair.1 .1), (1, 4, 5, "five"));
+    assert_eq!(Rc::strong_count(&rc), 2);
+}
+
+#[test]
+fn strict_projection() {
+    use project_uninit::{project_uninit, project_uninit_mut};
+
+    let mut x = MaybeUninit::<Outer>::uninit();
+    let (a, n) = project_uninit_mut!(x => strict { inner => a, pair => 1 => 0 });
+    a.write(7);
+    n.write(8);
+    project_uninit_mut!(x => strict pair => 0).write(9);
+    let (a, n) = project_uninit!(x => strict { inner => a, pair => 1 => 0 });
+    assert_eq!(unsafe { (a.assume_init(), n.assume_init()) }, (7, 8));
+    assert_eq!(unsafe { project_uninit!(x => strict pair => 0).assume_init() }, 9);
+}
+
+#[derive(ProjectUninit)]
+struct Table {
+    rows: [Named; 2],
+}
+
+#[test]
+fn strict_array_elements() {
+    let mut x = MaybeUninit::<Table>::uninit();
+    let (a0, a1) = partial_init!(x => strict { rows => [0] => a: 1, rows => [1] => a: 2 });
+    assert_eq!((*a0, *a1), (1, 2));
+}
@@ -0,0 +1,48 @@
+use project_uninit::{read_volatile_field, write_volatile_field};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Descriptor {
+    addr: u64,
+    len: u16,
+    flags: (u8, u8),
+    next: [u32; 3],
+}
+
+#[test]
+fn write_and_read_fields() {
+    let mut desc = Descriptor {
+        addr: 0,
+        len: 0,
+        flags: (0, 0),
+        next: [0; 3],
+    };
+    let ptr: *mut Descriptor = &mut desc;
+
+    let mut order = Vec::new();
+    unsafe {
+        write_volatile_field!(ptr => {
+            addr: { order.push("addr"); 0x1000 },
+            len: { order.push("len"); 512 },
+            flags => 1: 0x80,
+            next => [2]: 7,
+        });
+        write_volatile_field!(ptr => flags => 0 = 1);
+    }
+    assert_eq!(order, ["addr", "len"]);
+    assert_eq!(
+        desc,
+        Descriptor {
+            addr: 0x1000,
+            len: 512,
+            flags: (1, 0x80),
+            next: [0, 0, 7],
+        }
+    );
+
+    let ptr: *const Descriptor = &desc;
+    let (len, flags, next) =
+        unsafe { read_volatile_field!(ptr => { len, flags => 1, next => [2] }) };
+    assert_eq!((len, flags, next), (512, 0x80, 7));
+    assert_eq!(unsafe { read_volatile_field!(ptr => addr) }, 0x1000);
+}
@@ -0,0 +1,32 @@
+use core::mem::MaybeUninit;
+
+use project_uninit::{partial_init, zero_fields};
+
+#[derive(Debug, PartialEq)]
+struct Buffers {
+    name: &'static str,
+    data: [u64; 64],
+    pair: (u16, [u8; 3]),
+}
+
+#[test]
+fn zeroes_selected_fields() {
+    let mut x = MaybeUninit::<Buffers>::uninit();
+    let (data, first) = zero_fields!(x => { data, pair => 0 });
+    unsafe { data.assume_init_mut()[3] = 7 };
+    assert_eq!(unsafe { first.assume_init() }, 0);
+    let last = zero_fields!(&mut x => pair => 1 => [2]);
+    last.write(9);
+    partial_init!(x => { name: "zeroed", pair => 1 => [0]: 1, pair => 1 => [1]: 2 });
+
+    let mut data = [0; 64];
+    data[3] = 7;
+    assert_eq!(
+        unsafe { x.assume_init() },
+        Buffers {
+            name: "zeroed",
+            data,
+            pair: (0, [1, 2, 9])
+        }
+    );
+}